@@ -0,0 +1,75 @@
+//! Native fast path vs the VRL mapping for logs, traces and metrics.
+//!
+//! Run with `cargo bench --bench transform`.
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use otlp2records::{
+    apply_log_transform, apply_metric_transform, apply_trace_transform, decode_logs,
+    decode_metrics, decode_traces, exp_histogram_schema, gauge_schema, histogram_schema,
+    logs_schema, sum_schema, summary_schema, traces_schema, transform_logs, transform_metrics,
+    transform_traces, values_to_arrow, InputFormat,
+};
+
+const LOGS: &[u8] = include_bytes!("../testdata/logs_large.pb");
+const TRACES: &[u8] = include_bytes!("../testdata/traces_large.pb");
+const METRICS: &[u8] = include_bytes!("../testdata/metrics_mixed.pb");
+
+fn bench_logs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("logs");
+    group.throughput(Throughput::Bytes(LOGS.len() as u64));
+
+    group.bench_function("native", |b| {
+        b.iter(|| transform_logs(LOGS, InputFormat::Protobuf).unwrap())
+    });
+    group.bench_function("vrl", |b| {
+        b.iter(|| {
+            let values = decode_logs(LOGS, InputFormat::Protobuf).unwrap();
+            let transformed = apply_log_transform(values).unwrap();
+            values_to_arrow(&transformed, &logs_schema()).unwrap()
+        })
+    });
+    group.finish();
+}
+
+fn bench_traces(c: &mut Criterion) {
+    let mut group = c.benchmark_group("traces");
+    group.throughput(Throughput::Bytes(TRACES.len() as u64));
+
+    group.bench_function("native", |b| {
+        b.iter(|| transform_traces(TRACES, InputFormat::Protobuf).unwrap())
+    });
+    group.bench_function("vrl", |b| {
+        b.iter(|| {
+            let values = decode_traces(TRACES, InputFormat::Protobuf).unwrap();
+            let transformed = apply_trace_transform(values).unwrap();
+            values_to_arrow(&transformed, &traces_schema()).unwrap()
+        })
+    });
+    group.finish();
+}
+
+fn bench_metrics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("metrics");
+    group.throughput(Throughput::Bytes(METRICS.len() as u64));
+
+    group.bench_function("native", |b| {
+        b.iter(|| transform_metrics(METRICS, InputFormat::Protobuf).unwrap())
+    });
+    group.bench_function("vrl", |b| {
+        b.iter(|| {
+            let decoded = decode_metrics(METRICS, InputFormat::Protobuf).unwrap();
+            let transformed = apply_metric_transform(decoded.values).unwrap();
+            [
+                values_to_arrow(&transformed.gauge, &gauge_schema()).unwrap(),
+                values_to_arrow(&transformed.sum, &sum_schema()).unwrap(),
+                values_to_arrow(&transformed.histogram, &histogram_schema()).unwrap(),
+                values_to_arrow(&transformed.exp_histogram, &exp_histogram_schema()).unwrap(),
+                values_to_arrow(&transformed.summary, &summary_schema()).unwrap(),
+            ]
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_logs, bench_traces, bench_metrics);
+criterion_main!(benches);
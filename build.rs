@@ -8,6 +8,13 @@
 
 use std::{env, fs, path::Path};
 
+// The annotation parser is shared with the library's runtime schema API.
+#[allow(dead_code)]
+#[path = "src/schemas/annotation.rs"]
+mod annotation;
+
+use annotation::{AnnotatedSchema, AnnotationType};
+
 fn main() {
     compile_vrl_scripts();
 
@@ -74,19 +81,6 @@ extern "C" {
     }
 }
 
-/// Schema field parsed from VRL annotations
-struct SchemaField {
-    name: String,
-    field_type: String,
-    required: bool,
-}
-
-/// Schema parsed from VRL file header
-struct Schema {
-    name: String,
-    fields: Vec<SchemaField>,
-}
-
 /// VRL scripts to compile and embed
 const VRL_SCRIPTS: &[(&str, &str)] = &[
     ("OTLP_LOGS", "otlp_logs.vrl"),
@@ -95,6 +89,8 @@ const VRL_SCRIPTS: &[(&str, &str)] = &[
     ("OTLP_SUM", "otlp_sum.vrl"),
     ("OTLP_HISTOGRAM", "otlp_histogram.vrl"),
     ("OTLP_EXP_HISTOGRAM", "otlp_exp_histogram.vrl"),
+    ("OTLP_SUMMARY", "otlp_summary.vrl"),
+    ("OTLP_PROFILES", "otlp_profiles.vrl"),
 ];
 
 fn compile_vrl_scripts() {
@@ -222,7 +218,7 @@ fn compile_vrl_scripts() {
     );
 }
 
-fn generate_schema_defs(const_name: &str, schema: &Schema) -> String {
+fn generate_schema_defs(const_name: &str, schema: &AnnotatedSchema) -> String {
     let mut out = String::new();
 
     out.push_str(&format!(
@@ -232,7 +228,7 @@ fn generate_schema_defs(const_name: &str, schema: &Schema) -> String {
 
     for field in &schema.fields {
         out.push_str(&format!(
-            "    crate::schemas::SchemaField {{ name: \"{}\", field_type: \"{}\", required: {} }},\n",
+            "    crate::schemas::SchemaField {{ name: ::std::borrow::Cow::Borrowed(\"{}\"), field_type: ::std::borrow::Cow::Borrowed(\"{}\"), required: {} }},\n",
             field.name, field.field_type, field.required
         ));
     }
@@ -240,116 +236,33 @@ fn generate_schema_defs(const_name: &str, schema: &Schema) -> String {
     out.push_str("];\n\n");
 
     out.push_str(&format!(
-        "/// Schema definition for {}\n#[allow(dead_code)]\npub static {}_SCHEMA_DEF: crate::schemas::SchemaDef = crate::schemas::SchemaDef {{ name: \"{}\", fields: {}_FIELDS }};\n\n",
+        "/// Schema definition for {}\n#[allow(dead_code)]\npub const {}_SCHEMA_DEF: crate::schemas::SchemaDef = crate::schemas::SchemaDef {{ name: ::std::borrow::Cow::Borrowed(\"{}\"), fields: ::std::borrow::Cow::Borrowed({}_FIELDS) }};\n\n",
         schema.name, const_name, schema.name, const_name
     ));
 
     out
 }
 
-fn parse_schema_from_vrl(source: &str) -> Option<Schema> {
-    let mut in_schema_block = false;
-    let mut schema_name = None;
-    let mut fields = Vec::new();
-
-    for line in source.lines() {
-        let line = line.trim();
-
-        // Start of schema block
-        if line.starts_with("# @schema ") {
-            in_schema_block = true;
-            schema_name = Some(line.trim_start_matches("# @schema ").trim().to_string());
-            continue;
-        }
-
-        // End of schema block
-        if line == "# @end" {
-            break;
-        }
-
-        if !in_schema_block {
-            continue;
-        }
-
-        // Skip @description and empty lines
-        if line.starts_with("# @") || line == "#" || line.is_empty() {
-            continue;
-        }
-
-        // Field definition: # field_name: type, required?, "description"?
-        if line.starts_with("# ") && line.contains(':') {
-            if let Some(field) = parse_field_line(&line[2..]) {
-                fields.push(field);
-            } else {
-                println!(
-                    "cargo:warning=Failed to parse schema field: {}",
-                    line.trim_start_matches("# ")
-                );
-            }
-        }
-    }
-
-    schema_name.map(|name| Schema { name, fields })
-}
-
-fn parse_field_line(line: &str) -> Option<SchemaField> {
-    // Format: field_name: type, required?, "description"?
-    let mut parts = line.splitn(2, ':');
-    let name = parts.next()?.trim().to_string();
-    let rest = parts.next()?.trim();
-
-    // Strip description in quotes if present
-    let rest = if let Some(quote_start) = rest.find('"') {
-        rest[..quote_start].trim()
-    } else {
-        rest
-    };
-
-    // Parse type and required flag
-    let mut field_type = String::new();
-    let mut required = false;
-    for part in rest.split(',') {
-        let part = part.trim();
-        if part == "required" {
-            required = true;
-        } else if !part.is_empty() && field_type.is_empty() {
-            field_type = part.to_string();
-        }
-    }
-
-    if field_type.is_empty() {
-        return None;
+fn parse_schema_from_vrl(source: &str) -> Option<AnnotatedSchema> {
+    let schema = annotation::parse_schema_block(source)?;
+    for line in &schema.invalid_lines {
+        println!("cargo:warning=Failed to parse schema field: {line}");
     }
-
-    Some(SchemaField {
-        name,
-        field_type,
-        required,
-    })
+    Some(schema)
 }
 
-/// Map schema type to Arrow DataType
+/// Render the Arrow DataType expression for a schema type
 fn map_to_arrow_type(field_type: &str) -> String {
-    match field_type {
-        "timestamp" => {
-            "arrow::datatypes::DataType::Timestamp(arrow::datatypes::TimeUnit::Microsecond, None)"
-                .to_string()
-        }
-        "int64" => "arrow::datatypes::DataType::Int64".to_string(),
-        "int32" => "arrow::datatypes::DataType::Int32".to_string(),
-        "float64" => "arrow::datatypes::DataType::Float64".to_string(),
-        "bool" => "arrow::datatypes::DataType::Boolean".to_string(),
-        "string" => "arrow::datatypes::DataType::Utf8".to_string(),
-        "json" => "arrow::datatypes::DataType::Utf8".to_string(), // JSON stored as string
-        other => {
-            println!("cargo:warning=Unknown schema type '{other}', defaulting to Utf8");
-            "arrow::datatypes::DataType::Utf8".to_string()
-        }
-    }
+    let annotation_type = AnnotationType::parse(field_type).unwrap_or_else(|| {
+        println!("cargo:warning=Unknown schema type '{field_type}', defaulting to Utf8");
+        AnnotationType::String
+    });
+    // Rendered as a call so the generated schemas share the library's mapping
+    format!("crate::schemas::annotation::AnnotationType::{annotation_type:?}.data_type()")
 }
 
 /// Generate Arrow schema constant for a parsed schema
-fn generate_arrow_schema(const_name: &str, schema: &Schema) -> String {
+fn generate_arrow_schema(const_name: &str, schema: &AnnotatedSchema) -> String {
     let mut output = String::new();
 
     output.push_str(&format!(
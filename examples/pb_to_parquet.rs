@@ -42,7 +42,7 @@ fn convert_logs(testdata_dir: &Path, output_dir: &Path) -> Result<(), Box<dyn st
         }
 
         let bytes = fs::read(&input_path)?;
-        let batch = transform_logs(&bytes, InputFormat::Protobuf)?;
+        let batch = transform_logs(&bytes, InputFormat::Protobuf)?.batch;
 
         let output_filename = filename.replace(".pb", ".parquet");
         let output_path = output_dir.join(&output_filename);
@@ -74,7 +74,7 @@ fn convert_traces(
         }
 
         let bytes = fs::read(&input_path)?;
-        let batch = transform_traces(&bytes, InputFormat::Protobuf)?;
+        let batch = transform_traces(&bytes, InputFormat::Protobuf)?.batch;
 
         let output_filename = filename.replace(".pb", ".parquet");
         let output_path = output_dir.join(&output_filename);
@@ -166,6 +166,18 @@ fn convert_metrics(
             );
         }
 
+        if let Some(summary) = &batches.summary {
+            let output_path = output_dir.join(format!("{base_name}_summary.parquet"));
+            let parquet_bytes = to_parquet(summary)?;
+            fs::write(&output_path, parquet_bytes)?;
+            println!(
+                "Converted {} -> {}_summary.parquet ({} rows)",
+                filename,
+                base_name,
+                summary.num_rows()
+            );
+        }
+
         // Report skipped metrics
         if batches.skipped.has_skipped() {
             println!("  Skipped: {:?}", batches.skipped);
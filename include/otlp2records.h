@@ -84,6 +84,10 @@ typedef enum OtlpSignalType {
     OTLP_SIGNAL_METRICS_HISTOGRAM = 4,
     /** Exponential histogram metrics */
     OTLP_SIGNAL_METRICS_EXP_HISTOGRAM = 5,
+    /** Summary metrics */
+    OTLP_SIGNAL_METRICS_SUMMARY = 6,
+    /** Profile samples */
+    OTLP_SIGNAL_PROFILES = 7,
 } OtlpSignalType;
 
 /**
@@ -98,8 +102,24 @@ typedef enum OtlpInputFormat {
     OTLP_FORMAT_JSON = 2,
     /** Newline-delimited JSON */
     OTLP_FORMAT_JSONL = 3,
+    /** Varint length-prefixed protobuf messages */
+    OTLP_FORMAT_PROTOBUF_DELIMITED = 4,
 } OtlpInputFormat;
 
+/**
+ * @brief Content encoding of pushed input.
+ *
+ * Gzip and zstd require the library to be built with the `compression` feature.
+ */
+typedef enum OtlpContentEncoding {
+    /** Uncompressed input */
+    OTLP_ENCODING_IDENTITY = 0,
+    /** gzip-compressed input */
+    OTLP_ENCODING_GZIP = 1,
+    /** zstd-compressed input */
+    OTLP_ENCODING_ZSTD = 2,
+} OtlpContentEncoding;
+
 /**
  * @brief Status codes returned by FFI functions.
  */
@@ -118,6 +138,38 @@ typedef enum OtlpStatus {
     OTLP_ERROR_INTERNAL = 5,
 } OtlpStatus;
 
+/**
+ * @brief Record accounting for a transform.
+ *
+ * records_in always equals records_out plus the dropped_* counts. For metric
+ * signals the counts cover every metric type in the input, not only the type
+ * the handle emits.
+ */
+typedef struct OtlpTransformStats {
+    /** Records in the input */
+    size_t records_in;
+    /** Rows written to the output batches */
+    size_t records_out;
+    /** Metric data points dropped for a NaN value */
+    size_t dropped_nan_values;
+    /** Metric data points dropped for an infinite value */
+    size_t dropped_infinity_values;
+    /** Metric data points dropped for a missing value */
+    size_t dropped_missing_values;
+    /** Records dropped because they failed to decode */
+    size_t dropped_decode_errors;
+    /** Records dropped because their VRL program failed */
+    size_t dropped_vrl_errors;
+    /** Records dropped because they didn't fit the Arrow schema */
+    size_t dropped_arrow_errors;
+    /** Values stored as null although the transformed value was not null */
+    size_t null_coerced_values;
+    /** Trace/span IDs that are not 32/16 hex characters */
+    size_t invalid_ids;
+    /** Timestamps past the year 2262 */
+    size_t timestamp_overflows;
+} OtlpTransformStats;
+
 /**
  * @brief Opaque parser handle for streaming OTLP data.
  *
@@ -153,6 +205,38 @@ OtlpStatus otlp_parser_create(
  */
 void otlp_parser_destroy(OtlpParserHandle* handle);
 
+/**
+ * @brief Set the content encoding of input pushed to the parser.
+ *
+ * @param handle Parser handle
+ * @param encoding Content encoding of pushed bytes
+ * @param max_decompressed_size Maximum decompressed size in bytes (0 = 64 MiB default)
+ * @return OTLP_OK on success, error code otherwise
+ *
+ * @note Buffered input is decompressed when the final chunk is pushed.
+ * @note Exceeding max_decompressed_size fails the push with OTLP_ERROR_PARSE_FAILED.
+ */
+OtlpStatus otlp_parser_set_content_encoding(
+    OtlpParserHandle* handle,
+    OtlpContentEncoding encoding,
+    size_t max_decompressed_size
+);
+
+/**
+ * @brief Set the target row count for batches emitted by the parser.
+ *
+ * @param handle Parser handle
+ * @param batch_size Target rows per batch (0 = single batch, the default)
+ * @return OTLP_OK on success, error code otherwise
+ *
+ * @note Without a batch size, all parsed rows are emitted as one batch when
+ *       the final chunk is pushed.
+ */
+OtlpStatus otlp_parser_set_batch_size(
+    OtlpParserHandle* handle,
+    size_t batch_size
+);
+
 /* ============================================================================
  * Streaming Interface
  * ============================================================================ */
@@ -167,8 +251,12 @@ void otlp_parser_destroy(OtlpParserHandle* handle);
  * @return OTLP_OK on success, error code otherwise
  *
  * @note Caller retains ownership of data buffer.
- * @note For JSONL: Each complete line is parsed immediately.
+ * @note For JSONL and delimited protobuf: Each complete line or message is
+ *       parsed immediately; only the trailing partial record is buffered.
+ *       Compressed input is buffered until is_final.
  * @note For JSON/protobuf: Data is buffered until is_final=true.
+ * @note Rows are emitted in batches of the configured batch size; the
+ *       remainder is emitted when is_final is set.
  */
 OtlpStatus otlp_parser_push(
     OtlpParserHandle* handle,
@@ -193,6 +281,18 @@ OtlpStatus otlp_parser_drain(
     struct ArrowArrayStream* out_stream
 );
 
+/**
+ * @brief Get the stats of everything pushed to a parser so far.
+ *
+ * @param handle Parser handle
+ * @param out_stats Output: stats to populate
+ * @return OTLP_OK on success, error code otherwise
+ */
+OtlpStatus otlp_parser_get_stats(
+    const OtlpParserHandle* handle,
+    OtlpTransformStats* out_stats
+);
+
 /* ============================================================================
  * Schema Access
  * ============================================================================ */
@@ -238,6 +338,49 @@ OtlpStatus otlp_transform(
     struct ArrowSchema* out_schema
 );
 
+/**
+ * @brief Transform OTLP bytes to Arrow in one call, also reporting stats.
+ *
+ * @param out_stats Output: stats of the transform (may be NULL)
+ * @return OTLP_OK on success, error code otherwise
+ *
+ * @note Other parameters are as for otlp_transform().
+ * @note out_stats is only written on success.
+ */
+OtlpStatus otlp_transform_with_stats(
+    OtlpSignalType signal_type,
+    OtlpInputFormat format,
+    const uint8_t* data,
+    size_t len,
+    struct ArrowArray* out_array,
+    struct ArrowSchema* out_schema,
+    OtlpTransformStats* out_stats
+);
+
+/**
+ * @brief Transform OTLP bytes to Arrow in one call, with one batch per service.
+ *
+ * Rows are grouped by service_name (null names as "unknown"), in the order
+ * each service first appears in the input.
+ *
+ * @param signal_type The OTLP signal type
+ * @param format Input format
+ * @param data Input bytes
+ * @param len Length of input bytes
+ * @param out_stream Output: ArrowArrayStream yielding one batch per service
+ * @return OTLP_OK on success, error code otherwise
+ *
+ * @note Caller must call out_stream->release() when done.
+ * @note Stream yields 0 batches if the input had no data of this type.
+ */
+OtlpStatus otlp_transform_partitioned(
+    OtlpSignalType signal_type,
+    OtlpInputFormat format,
+    const uint8_t* data,
+    size_t len,
+    struct ArrowArrayStream* out_stream
+);
+
 /* ============================================================================
  * Error Handling
  * ============================================================================ */
@@ -9,6 +9,31 @@ extern "C" {
 #endif
 
 
+/*
+ Default cap on decompressed body size (64 MiB).
+ */
+#define DEFAULT_MAX_DECOMPRESSED_SIZE ((64 * 1024) * 1024)
+
+/*
+ Content encoding of pushed input.
+
+ C names: OTLP_ENCODING_IDENTITY, OTLP_ENCODING_GZIP, etc.
+ */
+typedef enum OtlpContentEncoding {
+  /*
+   Uncompressed input (C: OTLP_ENCODING_IDENTITY)
+   */
+  Identity = 0,
+  /*
+   gzip-compressed input (C: OTLP_ENCODING_GZIP)
+   */
+  Gzip = 1,
+  /*
+   zstd-compressed input (C: OTLP_ENCODING_ZSTD)
+   */
+  Zstd = 2,
+} OtlpContentEncoding;
+
 /*
  Input format for OTLP data.
 
@@ -31,6 +56,10 @@ typedef enum OtlpInputFormat {
    Newline-delimited JSON (C: OTLP_FORMAT_JSONL)
    */
   Jsonl = 3,
+  /*
+   Varint length-prefixed protobuf messages (C: OTLP_FORMAT_PROTOBUF_DELIMITED)
+   */
+  ProtobufDelimited = 4,
 } OtlpInputFormat;
 
 /*
@@ -63,6 +92,14 @@ typedef enum OtlpSignalType {
    Exponential histogram metrics (C: OTLP_SIGNAL_METRICS_EXP_HISTOGRAM)
    */
   MetricsExpHistogram = 5,
+  /*
+   Summary metrics (C: OTLP_SIGNAL_METRICS_SUMMARY)
+   */
+  MetricsSummary = 6,
+  /*
+   Profile samples (C: OTLP_SIGNAL_PROFILES)
+   */
+  Profiles = 7,
 } OtlpSignalType;
 
 /*
@@ -101,10 +138,66 @@ typedef enum OtlpStatus {
  Opaque parser handle for streaming OTLP data.
 
  This handle maintains state for parsing OTLP data and producing Arrow batches.
+ JSONL input is parsed as complete lines arrive, so only the trailing partial
+ line and the decoded rows not yet emitted are held between pushes. Rows are
+ emitted as one batch on the final push unless a batch size has been set.
  It is NOT thread-safe - use one handle per thread.
  */
 typedef struct OtlpParserHandle OtlpParserHandle;
 
+/*
+ Record accounting for a transform (see `TransformStats`).
+
+ For metric signals the counts cover every metric type in the input, not
+ only the type the handle emits.
+ */
+typedef struct OtlpTransformStats {
+  /*
+   Records in the input
+   */
+  uintptr_t records_in;
+  /*
+   Rows written to the output batches
+   */
+  uintptr_t records_out;
+  /*
+   Metric data points dropped for a NaN value
+   */
+  uintptr_t dropped_nan_values;
+  /*
+   Metric data points dropped for an infinite value
+   */
+  uintptr_t dropped_infinity_values;
+  /*
+   Metric data points dropped for a missing value
+   */
+  uintptr_t dropped_missing_values;
+  /*
+   Records dropped because they failed to decode
+   */
+  uintptr_t dropped_decode_errors;
+  /*
+   Records dropped because their VRL program failed
+   */
+  uintptr_t dropped_vrl_errors;
+  /*
+   Records dropped because they didn't fit the Arrow schema
+   */
+  uintptr_t dropped_arrow_errors;
+  /*
+   Values stored as null although the transformed value was not null
+   */
+  uintptr_t null_coerced_values;
+  /*
+   Trace/span IDs that are not 32/16 hex characters
+   */
+  uintptr_t invalid_ids;
+  /*
+   Timestamps past the year 2262
+   */
+  uintptr_t timestamp_overflows;
+} OtlpTransformStats;
+
 #ifdef __cplusplus
 extern "C" {
 #endif // __cplusplus
@@ -135,6 +228,55 @@ enum OtlpStatus otlp_parser_create(enum OtlpSignalType signal_type,
  */
 void otlp_parser_destroy(struct OtlpParserHandle *handle);
 
+/*
+ Set the content encoding of input pushed to the parser.
+
+ The buffered input is decompressed when the final chunk is pushed.
+ Gzip and zstd require the crate's `compression` feature; otherwise the
+ final push fails with `OTLP_ERROR_PARSE_FAILED`.
+
+ # Safety
+
+ - `handle` must be a valid parser handle
+
+ # Arguments
+
+ - `handle`: Parser handle
+ - `encoding`: Content encoding of pushed bytes
+ - `max_decompressed_size`: Maximum decompressed size in bytes, or 0 for
+   the default (64 MiB)
+
+ # Returns
+
+ `OTLP_OK` on success, error code otherwise.
+ */
+enum OtlpStatus otlp_parser_set_content_encoding(struct OtlpParserHandle *handle,
+                                                 enum OtlpContentEncoding encoding,
+                                                 uintptr_t max_decompressed_size);
+
+/*
+ Set the target row count for batches emitted by the parser.
+
+ Parsed rows are emitted as batches of exactly `batch_size` rows; any
+ remainder is emitted as a shorter batch when the final chunk is pushed.
+ By default no batch size is set and all rows are emitted as a single
+ batch on the final push.
+
+ # Safety
+
+ - `handle` must be a valid parser handle
+
+ # Arguments
+
+ - `handle`: Parser handle
+ - `batch_size`: Target rows per batch, or 0 to emit a single batch
+
+ # Returns
+
+ `OTLP_OK` on success, error code otherwise.
+ */
+enum OtlpStatus otlp_parser_set_batch_size(struct OtlpParserHandle *handle, uintptr_t batch_size);
+
 /*
  Push input bytes to the parser.
 
@@ -151,6 +293,10 @@ void otlp_parser_destroy(struct OtlpParserHandle *handle);
  - `len`: Length of input bytes
  - `is_final`: Non-zero if this is the last chunk (triggers parsing)
 
+ For uncompressed JSONL and length-delimited protobuf, each complete line or
+ message is parsed as it arrives and full batches become available to
+ `otlp_parser_drain()` immediately. Other formats are buffered until `is_final`.
+
  # Returns
 
  `OTLP_OK` on success, error code otherwise.
@@ -178,6 +324,21 @@ enum OtlpStatus otlp_parser_push(struct OtlpParserHandle *handle,
 enum OtlpStatus otlp_parser_drain(struct OtlpParserHandle *handle,
                                   FFI_ArrowArrayStream *out_stream);
 
+/*
+ Get the stats of everything pushed to a parser so far.
+
+ # Safety
+
+ - `handle` must be a valid parser handle
+ - `out_stats` must be a valid pointer
+
+ # Returns
+
+ `OTLP_OK` on success, error code otherwise.
+ */
+enum OtlpStatus otlp_parser_get_stats(const struct OtlpParserHandle *handle,
+                                      struct OtlpTransformStats *out_stats);
+
 /*
  Get the Arrow schema for a signal type.
 
@@ -212,6 +373,53 @@ enum OtlpStatus otlp_transform(enum OtlpSignalType signal_type,
                                FFI_ArrowArray *out_array,
                                FFI_ArrowSchema *out_schema);
 
+/*
+ Transform OTLP bytes to Arrow in one call, also reporting the transform's
+ stats.
+
+ # Safety
+
+ - Same requirements as `otlp_transform()`
+ - `out_stats` must be a valid pointer, or null to skip the stats
+
+ # Returns
+
+ `OTLP_OK` on success, error code otherwise. `out_stats` is only written
+ on success.
+ */
+enum OtlpStatus otlp_transform_with_stats(enum OtlpSignalType signal_type,
+                                          enum OtlpInputFormat format,
+                                          const uint8_t *data,
+                                          uintptr_t len,
+                                          FFI_ArrowArray *out_array,
+                                          FFI_ArrowSchema *out_schema,
+                                          struct OtlpTransformStats *out_stats);
+
+/*
+ Transform OTLP bytes to Arrow in one call, with one batch per service.
+
+ Rows are grouped by their `service_name` column (null names are grouped
+ as `unknown`), in the order each service first appears in the input.
+ Each batch holds a single service, so hosts can route batches to
+ per-service storage without grouping them again.
+
+ # Safety
+
+ - `data` must be valid for `len` bytes
+ - `out_stream` must be a valid pointer to FFI_ArrowArrayStream
+ - Caller must call `out_stream->release()` when done
+
+ # Returns
+
+ `OTLP_OK` on success, error code otherwise.
+ Stream yields 0 batches if the input had no data of this type.
+ */
+enum OtlpStatus otlp_transform_partitioned(enum OtlpSignalType signal_type,
+                                           enum OtlpInputFormat format,
+                                           const uint8_t *data,
+                                           uintptr_t len,
+                                           FFI_ArrowArrayStream *out_stream);
+
 /*
  Get the last error message for a parser handle.
 
@@ -3,15 +3,21 @@
 //! Converts VRL Values to Arrow RecordBatches using schema-driven building.
 
 use arrow::array::{
-    ArrayRef, BooleanBuilder, Float64Builder, Int32Builder, Int64Builder, StringBuilder,
-    TimestampMicrosecondBuilder,
+    Array, ArrayRef, BooleanBuilder, DurationMicrosecondBuilder, DurationNanosecondBuilder,
+    Float64Builder, Int32Builder, Int64Builder, ListArray, ListBuilder, MapArray, PrimitiveBuilder,
+    StringBuilder, StringDictionaryBuilder, StructArray, TimestampMicrosecondBuilder,
+    TimestampNanosecondBuilder, UInt64Builder,
 };
-use arrow::datatypes::{DataType, Schema, TimeUnit};
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{ArrowPrimitiveType, DataType, Int32Type, Schema, TimeUnit};
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
+use std::borrow::Cow;
 use std::sync::Arc;
 use vrl::value::{KeyString, Value};
 
+use crate::decode::safe_timestamp_conversion;
+
 /// Converts a slice of VRL Values to an Arrow RecordBatch.
 ///
 /// # Arguments
@@ -26,11 +32,15 @@ use vrl::value::{KeyString, Value};
 /// # Type Mapping
 ///
 /// VRL types are converted to Arrow types as follows:
-/// - `Value::Integer` -> Int64/Int32/TimestampMicrosecond (depending on schema)
+/// - `Value::Integer` -> Int64/Int32/Timestamp/Duration in microseconds or
+///   nanoseconds (depending on schema)
 /// - `Value::Float` -> Float64
 /// - `Value::Boolean` -> Boolean
-/// - `Value::Bytes` -> Utf8 (String)
+/// - `Value::Bytes` -> Utf8 (String) or Dictionary(Int32, Utf8)
 /// - `Value::Null` -> null in the appropriate column
+/// - `Value::Object` / `Value::Array` -> Map, Struct or List columns; a
+///   `Value::Bytes` holding JSON is parsed first, so the JSON blobs produced by
+///   the built-in VRL programs can fill native nested columns
 ///
 /// # Example
 ///
@@ -51,55 +61,112 @@ use vrl::value::{KeyString, Value};
 /// let batch = values_to_arrow(&values, &schema)?;
 /// ```
 pub fn values_to_arrow(values: &[Value], schema: &Schema) -> Result<RecordBatch, ArrowError> {
-    let num_rows = values.len();
-    let num_fields = schema.fields().len();
+    build_batch(values, schema).map(|(batch, _)| batch)
+}
+
+/// Like [`values_to_arrow`], also returning how many values were stored as
+/// null although the input value was not null (e.g. an empty string in a
+/// nested column).
+pub(crate) fn build_batch(
+    values: &[Value],
+    schema: &Schema,
+) -> Result<(RecordBatch, usize), ArrowError> {
+    let mut builder = BatchBuilder::new(schema, values.len());
 
-    // Pre-allocate column builders based on schema types
-    let mut builders: Vec<ColumnBuilder> = schema
+    // Build field name lookup for extraction
+    let field_keys: Vec<KeyString> = schema
         .fields()
         .iter()
-        .map(|field| ColumnBuilder::new(field.data_type(), num_rows))
+        .map(|f| f.name().as_str().into())
         .collect();
 
-    // Build field name lookup for extraction
-    let field_names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
-
     // Iterate over values and populate builders
     for value in values {
         match value {
             Value::Object(map) => {
-                for (idx, field_name) in field_names.iter().enumerate() {
-                    let key: KeyString = (*field_name).into();
-                    let field_value = map.get(&key);
-                    builders[idx].append(field_value)?;
+                for (idx, key) in field_keys.iter().enumerate() {
+                    builder.append(idx, map.get(key))?;
                 }
             }
             _ => {
                 // Non-object values: append nulls for all fields
-                for builder in &mut builders {
-                    builder.append(None)?;
+                for idx in 0..field_keys.len() {
+                    builder.append(idx, None)?;
                 }
             }
         }
     }
 
-    // Build arrays from builders
-    let arrays: Vec<ArrayRef> = builders
-        .into_iter()
-        .zip(schema.fields().iter())
-        .map(|(builder, field)| builder.finish(field.data_type()))
-        .collect();
+    builder.finish()
+}
 
-    // Validate we have the right number of columns
-    if arrays.len() != num_fields {
-        return Err(ArrowError::InvalidArgumentError(format!(
-            "Expected {} columns but got {}",
-            num_fields,
-            arrays.len()
-        )));
+/// Row-by-row RecordBatch builder.
+///
+/// Each row must append one value to every column, in any order. Values are
+/// converted exactly as in [`values_to_arrow`].
+pub(crate) struct BatchBuilder {
+    schema: Schema,
+    builders: Vec<ColumnBuilder>,
+    /// Nulls in the input, per column, to tell coerced nulls apart
+    input_nulls: Vec<usize>,
+}
+
+impl BatchBuilder {
+    /// Create a builder for `schema` with room for `capacity` rows.
+    pub(crate) fn new(schema: &Schema, capacity: usize) -> Self {
+        // Pre-allocate column builders based on schema types
+        let builders = schema
+            .fields()
+            .iter()
+            .map(|field| ColumnBuilder::new(field.data_type(), capacity))
+            .collect();
+
+        Self {
+            schema: schema.clone(),
+            builders,
+            input_nulls: vec![0; schema.fields().len()],
+        }
+    }
+
+    /// Append a value to the column at `index`.
+    pub(crate) fn append(&mut self, index: usize, value: Option<&Value>) -> Result<(), ArrowError> {
+        if matches!(value, None | Some(Value::Null)) {
+            self.input_nulls[index] += 1;
+        }
+        self.builders[index].append(value)
     }
 
-    RecordBatch::try_new(Arc::new(schema.clone()), arrays)
+    /// Build the batch, also returning the number of coerced nulls (see
+    /// [`build_batch`]).
+    pub(crate) fn finish(self) -> Result<(RecordBatch, usize), ArrowError> {
+        let num_fields = self.schema.fields().len();
+
+        // Build arrays from builders
+        let arrays: Vec<ArrayRef> = self
+            .builders
+            .into_iter()
+            .zip(self.schema.fields().iter())
+            .map(|(builder, field)| builder.finish(field.data_type()))
+            .collect::<Result<_, _>>()?;
+
+        // Validate we have the right number of columns
+        if arrays.len() != num_fields {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Expected {} columns but got {}",
+                num_fields,
+                arrays.len()
+            )));
+        }
+
+        let coerced_nulls = arrays
+            .iter()
+            .zip(&self.input_nulls)
+            .map(|(array, nulls)| array.null_count().saturating_sub(*nulls))
+            .sum();
+
+        let batch = RecordBatch::try_new(Arc::new(self.schema), arrays)?;
+        Ok((batch, coerced_nulls))
+    }
 }
 
 /// Internal builder enum for different Arrow column types.
@@ -107,11 +174,20 @@ pub fn values_to_arrow(values: &[Value], schema: &Schema) -> Result<RecordBatch,
 /// This allows dynamic column building based on schema without complex generics.
 enum ColumnBuilder {
     Timestamp(TimestampMicrosecondBuilder),
+    TimestampNanos(TimestampNanosecondBuilder),
+    Duration(DurationMicrosecondBuilder),
+    DurationNanos(DurationNanosecondBuilder),
     Int64(Int64Builder),
     Int32(Int32Builder),
     Float64(Float64Builder),
     Boolean(BooleanBuilder),
     String(StringBuilder),
+    DictString(StringDictionaryBuilder<Int32Type>),
+    UInt64(UInt64Builder),
+    UInt64List(ListBuilder<UInt64Builder>),
+    Float64List(ListBuilder<Float64Builder>),
+    /// Map, Struct and other List columns, built from the collected values at finish
+    Nested(Vec<Value>),
 }
 
 impl ColumnBuilder {
@@ -120,17 +196,30 @@ impl ColumnBuilder {
     /// # Panics
     ///
     /// Panics if the data type is not supported. Supported types:
-    /// - Timestamp(Millisecond, _)
+    /// - Timestamp(Microsecond, _), Timestamp(Nanosecond, _)
+    /// - Duration(Microsecond), Duration(Nanosecond)
     /// - Int64
     /// - Int32
     /// - Float64
     /// - Boolean
-    /// - Utf8
+    /// - Utf8, Dictionary(Int32, Utf8)
+    /// - UInt64
+    /// - List(UInt64), List(Float64)
+    /// - Map, Struct and List of the above
     fn new(data_type: &DataType, capacity: usize) -> Self {
         match data_type {
             DataType::Timestamp(TimeUnit::Microsecond, _) => {
                 ColumnBuilder::Timestamp(TimestampMicrosecondBuilder::with_capacity(capacity))
             }
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                ColumnBuilder::TimestampNanos(TimestampNanosecondBuilder::with_capacity(capacity))
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                ColumnBuilder::Duration(DurationMicrosecondBuilder::with_capacity(capacity))
+            }
+            DataType::Duration(TimeUnit::Nanosecond) => {
+                ColumnBuilder::DurationNanos(DurationNanosecondBuilder::with_capacity(capacity))
+            }
             DataType::Int64 => ColumnBuilder::Int64(Int64Builder::with_capacity(capacity)),
             DataType::Int32 => ColumnBuilder::Int32(Int32Builder::with_capacity(capacity)),
             DataType::Float64 => ColumnBuilder::Float64(Float64Builder::with_capacity(capacity)),
@@ -138,9 +227,34 @@ impl ColumnBuilder {
             DataType::Utf8 => {
                 ColumnBuilder::String(StringBuilder::with_capacity(capacity, capacity * 32))
             }
+            DataType::Dictionary(key, value)
+                if key.as_ref() == &DataType::Int32 && value.as_ref() == &DataType::Utf8 =>
+            {
+                ColumnBuilder::DictString(StringDictionaryBuilder::with_capacity(
+                    capacity,
+                    capacity.min(1024),
+                    capacity * 8,
+                ))
+            }
+            DataType::UInt64 => ColumnBuilder::UInt64(UInt64Builder::with_capacity(capacity)),
+            DataType::List(item) if item.data_type() == &DataType::UInt64 => {
+                ColumnBuilder::UInt64List(
+                    ListBuilder::with_capacity(UInt64Builder::new(), capacity)
+                        .with_field(item.clone()),
+                )
+            }
+            DataType::List(item) if item.data_type() == &DataType::Float64 => {
+                ColumnBuilder::Float64List(
+                    ListBuilder::with_capacity(Float64Builder::new(), capacity)
+                        .with_field(item.clone()),
+                )
+            }
+            DataType::Map(_, _) | DataType::Struct(_) | DataType::List(_) => {
+                ColumnBuilder::Nested(Vec::with_capacity(capacity))
+            }
             unsupported => {
                 panic!(
-                    "Unsupported Arrow data type: {unsupported:?}. Supported types: Timestamp(Microsecond), Int64, Int32, Float64, Boolean, Utf8"
+                    "Unsupported Arrow data type: {unsupported:?}. Supported types: Timestamp(Microsecond), Timestamp(Nanosecond), Duration(Microsecond), Duration(Nanosecond), Int64, Int32, Float64, Boolean, Utf8, Dictionary(Int32, Utf8), UInt64, Map, Struct, List"
                 );
             }
         }
@@ -150,18 +264,32 @@ impl ColumnBuilder {
     fn append(&mut self, value: Option<&Value>) -> Result<(), ArrowError> {
         match self {
             ColumnBuilder::Timestamp(builder) => append_timestamp(builder, value),
+            ColumnBuilder::TimestampNanos(builder) => append_timestamp_nanos(builder, value),
+            ColumnBuilder::Duration(builder) => append_timestamp(builder, value),
+            ColumnBuilder::DurationNanos(builder) => append_timestamp_nanos(builder, value),
             ColumnBuilder::Int64(builder) => append_int64(builder, value),
             ColumnBuilder::Int32(builder) => append_int32(builder, value),
             ColumnBuilder::Float64(builder) => append_float64(builder, value),
             ColumnBuilder::Boolean(builder) => append_boolean(builder, value),
             ColumnBuilder::String(builder) => append_string(builder, value),
+            ColumnBuilder::DictString(builder) => append_dict_string(builder, value),
+            ColumnBuilder::UInt64(builder) => append_uint64(builder, value),
+            ColumnBuilder::UInt64List(builder) => append_uint64_list(builder, value),
+            ColumnBuilder::Float64List(builder) => append_float64_list(builder, value),
+            ColumnBuilder::Nested(values) => {
+                values.push(nested_value(value)?);
+                Ok(())
+            }
         }
     }
 
     /// Finish building and return the ArrayRef.
-    fn finish(self, data_type: &DataType) -> ArrayRef {
-        match self {
+    fn finish(self, data_type: &DataType) -> Result<ArrayRef, ArrowError> {
+        let array: ArrayRef = match self {
             ColumnBuilder::Timestamp(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::TimestampNanos(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::Duration(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::DurationNanos(mut builder) => Arc::new(builder.finish()),
             ColumnBuilder::Int64(mut builder) => Arc::new(builder.finish()),
             ColumnBuilder::Int32(mut builder) => Arc::new(builder.finish()),
             ColumnBuilder::Float64(mut builder) => Arc::new(builder.finish()),
@@ -173,13 +301,176 @@ impl ColumnBuilder {
                     _ => Arc::new(builder.finish()),
                 }
             }
+            ColumnBuilder::DictString(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::UInt64(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::UInt64List(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::Float64List(mut builder) => Arc::new(builder.finish()),
+            ColumnBuilder::Nested(values) => return build_nested(&values, data_type),
+        };
+        Ok(array)
+    }
+}
+
+// ============================================================================
+// Nested columns
+// ============================================================================
+
+/// Normalize a value destined for a nested column.
+///
+/// JSON strings are parsed so the JSON blobs emitted by the VRL programs can be
+/// stored natively; an empty string is treated as null.
+fn nested_value(value: Option<&Value>) -> Result<Value, ArrowError> {
+    match value {
+        None => Ok(Value::Null),
+        Some(Value::Bytes(b)) if b.is_empty() => Ok(Value::Null),
+        Some(Value::Bytes(b)) => serde_json::from_slice::<serde_json::Value>(b)
+            .map(Value::from)
+            .map_err(|e| {
+                ArrowError::InvalidArgumentError(format!(
+                    "Cannot convert bytes to nested column: invalid JSON: {e}"
+                ))
+            }),
+        Some(other) => Ok(other.clone()),
+    }
+}
+
+/// Build a Map, Struct or List array (or a leaf array) from VRL values.
+fn build_nested(values: &[Value], data_type: &DataType) -> Result<ArrayRef, ArrowError> {
+    match data_type {
+        DataType::List(item_field) => {
+            let mut offsets = Vec::with_capacity(values.len() + 1);
+            let mut validity = Vec::with_capacity(values.len());
+            let mut items = Vec::new();
+            offsets.push(0i32);
+            for value in values {
+                match value {
+                    Value::Array(array) => {
+                        items.extend(array.iter().cloned());
+                        validity.push(true);
+                    }
+                    Value::Null => validity.push(false),
+                    other => return Err(nested_type_error(other, "list")),
+                }
+                offsets.push(list_offset(items.len())?);
+            }
+            let child = build_nested(&items, item_field.data_type())?;
+            Ok(Arc::new(ListArray::try_new(
+                item_field.clone(),
+                OffsetBuffer::new(offsets.into()),
+                child,
+                null_buffer(validity),
+            )?))
+        }
+        DataType::Struct(fields) => {
+            let mut validity = Vec::with_capacity(values.len());
+            for value in values {
+                match value {
+                    Value::Object(_) => validity.push(true),
+                    Value::Null => validity.push(false),
+                    other => return Err(nested_type_error(other, "struct")),
+                }
+            }
+            let children = fields
+                .iter()
+                .map(|field| {
+                    let key: KeyString = field.name().as_str().into();
+                    let child_values: Vec<Value> = values
+                        .iter()
+                        .map(|value| match value {
+                            Value::Object(map) => map.get(&key).cloned().unwrap_or(Value::Null),
+                            _ => Value::Null,
+                        })
+                        .collect();
+                    build_nested(&child_values, field.data_type())
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Arc::new(StructArray::try_new(
+                fields.clone(),
+                children,
+                null_buffer(validity),
+            )?))
         }
+        DataType::Map(entries_field, sorted) => {
+            let DataType::Struct(entry_fields) = entries_field.data_type() else {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Map entries must be a struct, got {:?}",
+                    entries_field.data_type()
+                )));
+            };
+            if entry_fields.len() != 2 {
+                return Err(ArrowError::InvalidArgumentError(
+                    "Map entries must have a key and a value field".to_string(),
+                ));
+            }
+            let mut offsets = Vec::with_capacity(values.len() + 1);
+            let mut validity = Vec::with_capacity(values.len());
+            let mut keys = Vec::new();
+            let mut entry_values = Vec::new();
+            offsets.push(0i32);
+            for value in values {
+                match value {
+                    Value::Object(map) => {
+                        for (key, entry) in map.iter() {
+                            keys.push(Value::Bytes(key.as_str().to_owned().into()));
+                            entry_values.push(entry.clone());
+                        }
+                        validity.push(true);
+                    }
+                    Value::Null => validity.push(false),
+                    other => return Err(nested_type_error(other, "map")),
+                }
+                offsets.push(list_offset(keys.len())?);
+            }
+            let entries = StructArray::try_new(
+                entry_fields.clone(),
+                vec![
+                    build_nested(&keys, entry_fields[0].data_type())?,
+                    build_nested(&entry_values, entry_fields[1].data_type())?,
+                ],
+                None,
+            )?;
+            Ok(Arc::new(MapArray::try_new(
+                entries_field.clone(),
+                OffsetBuffer::new(offsets.into()),
+                entries,
+                null_buffer(validity),
+                *sorted,
+            )?))
+        }
+        leaf => {
+            let mut builder = ColumnBuilder::new(leaf, values.len());
+            for value in values {
+                builder.append(Some(value))?;
+            }
+            builder.finish(leaf)
+        }
+    }
+}
+
+fn list_offset(len: usize) -> Result<i32, ArrowError> {
+    i32::try_from(len).map_err(|_| {
+        ArrowError::InvalidArgumentError(format!("Nested column offset {len} overflows i32"))
+    })
+}
+
+fn null_buffer(validity: Vec<bool>) -> Option<NullBuffer> {
+    if validity.iter().all(|valid| *valid) {
+        None
+    } else {
+        Some(NullBuffer::from(validity))
     }
 }
 
-/// Append a VRL value to a TimestampMicrosecondBuilder.
-fn append_timestamp(
-    builder: &mut TimestampMicrosecondBuilder,
+fn nested_type_error(value: &Value, expected: &str) -> ArrowError {
+    ArrowError::InvalidArgumentError(format!(
+        "Cannot convert {:?} to {expected}",
+        value_type_name(value)
+    ))
+}
+
+/// Append a VRL value to a microsecond Timestamp or Duration builder.
+fn append_timestamp<T: ArrowPrimitiveType<Native = i64>>(
+    builder: &mut PrimitiveBuilder<T>,
     value: Option<&Value>,
 ) -> Result<(), ArrowError> {
     match value {
@@ -188,7 +479,41 @@ fn append_timestamp(
             Ok(())
         }
         Some(Value::Float(f)) => {
-            // Convert float to integer milliseconds
+            // Truncate float to integer microseconds
+            builder.append_value(f.into_inner() as i64);
+            Ok(())
+        }
+        Some(Value::Null) | None => {
+            builder.append_null();
+            Ok(())
+        }
+        Some(other) => Err(ArrowError::InvalidArgumentError(format!(
+            "Cannot convert {:?} to timestamp",
+            value_type_name(other)
+        ))),
+    }
+}
+
+/// Append a VRL value to a nanosecond Timestamp or Duration builder.
+///
+/// Floats past `i64::MAX` nanoseconds (year 2262) are rejected rather than
+/// saturated.
+fn append_timestamp_nanos<T: ArrowPrimitiveType<Native = i64>>(
+    builder: &mut PrimitiveBuilder<T>,
+    value: Option<&Value>,
+) -> Result<(), ArrowError> {
+    match value {
+        Some(Value::Integer(i)) => {
+            builder.append_value(*i);
+            Ok(())
+        }
+        Some(Value::Float(f)) if f.into_inner() >= 0.0 => {
+            let nanos = safe_timestamp_conversion(f.into_inner() as u64, "timestamp")
+                .map_err(|e| ArrowError::InvalidArgumentError(e.to_string()))?;
+            builder.append_value(nanos);
+            Ok(())
+        }
+        Some(Value::Float(f)) => {
             builder.append_value(f.into_inner() as i64);
             Ok(())
         }
@@ -293,11 +618,24 @@ fn append_float64(builder: &mut Float64Builder, value: Option<&Value>) -> Result
     }
 }
 
-/// Append a VRL value to a BooleanBuilder.
-fn append_boolean(builder: &mut BooleanBuilder, value: Option<&Value>) -> Result<(), ArrowError> {
+/// Append a VRL value to a UInt64Builder.
+fn append_uint64(builder: &mut UInt64Builder, value: Option<&Value>) -> Result<(), ArrowError> {
     match value {
-        Some(Value::Boolean(b)) => {
-            builder.append_value(*b);
+        Some(Value::Integer(i)) => {
+            let val = u64::try_from(*i).map_err(|_| {
+                ArrowError::InvalidArgumentError(format!("Integer {i} is out of range for u64"))
+            })?;
+            builder.append_value(val);
+            Ok(())
+        }
+        Some(Value::Float(f)) => {
+            let float_val = f.into_inner();
+            if !float_val.is_finite() || float_val < 0.0 || float_val > u64::MAX as f64 {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "Float {float_val} is out of range for u64"
+                )));
+            }
+            builder.append_value(float_val as u64);
             Ok(())
         }
         Some(Value::Null) | None => {
@@ -305,64 +643,146 @@ fn append_boolean(builder: &mut BooleanBuilder, value: Option<&Value>) -> Result
             Ok(())
         }
         Some(other) => Err(ArrowError::InvalidArgumentError(format!(
-            "Cannot convert {:?} to boolean",
+            "Cannot convert {:?} to uint64",
             value_type_name(other)
         ))),
     }
 }
 
-/// Append a VRL value to a StringBuilder.
-///
-/// # Note on UTF-8 handling
+/// Append a VRL array, or a JSON array string, to a `List<UInt64>` builder.
 ///
-/// When converting `Value::Bytes` to strings, invalid UTF-8 sequences are replaced
-/// with the Unicode replacement character (U+FFFD) using lossy conversion. This
-/// ensures the function never fails for byte data, but may result in data modification
-/// if the input contains invalid UTF-8.
-fn append_string(builder: &mut StringBuilder, value: Option<&Value>) -> Result<(), ArrowError> {
+/// JSON strings are accepted because the decoders emit histogram buckets as
+/// JSON arrays (see `bucket_counts`).
+fn append_uint64_list(
+    builder: &mut ListBuilder<UInt64Builder>,
+    value: Option<&Value>,
+) -> Result<(), ArrowError> {
     match value {
-        Some(Value::Bytes(b)) => {
-            // Note: Uses lossy conversion - invalid UTF-8 becomes U+FFFD
-            let s = String::from_utf8_lossy(b);
-            builder.append_value(&s);
-            Ok(())
+        Some(Value::Array(items)) => {
+            for item in items.iter() {
+                append_uint64(builder.values(), Some(item))?;
+            }
+            builder.append(true);
         }
-        Some(Value::Integer(i)) => {
-            // Convert integer to string
-            builder.append_value(i.to_string());
-            Ok(())
+        Some(Value::Bytes(b)) if !b.is_empty() => {
+            let items: Vec<Option<u64>> = parse_json_list(b)?;
+            builder.values().extend(items);
+            builder.append(true);
         }
-        Some(Value::Float(f)) => {
-            // Convert float to string
-            builder.append_value(f.to_string());
-            Ok(())
+        Some(Value::Bytes(_)) | Some(Value::Null) | None => builder.append(false),
+        Some(other) => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Cannot convert {:?} to list<uint64>",
+                value_type_name(other)
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// Append a VRL array, or a JSON array string, to a `List<Float64>` builder.
+fn append_float64_list(
+    builder: &mut ListBuilder<Float64Builder>,
+    value: Option<&Value>,
+) -> Result<(), ArrowError> {
+    match value {
+        Some(Value::Array(items)) => {
+            for item in items.iter() {
+                append_float64(builder.values(), Some(item))?;
+            }
+            builder.append(true);
         }
+        Some(Value::Bytes(b)) if !b.is_empty() => {
+            let items: Vec<Option<f64>> = parse_json_list(b)?;
+            builder.values().extend(items);
+            builder.append(true);
+        }
+        Some(Value::Bytes(_)) | Some(Value::Null) | None => builder.append(false),
+        Some(other) => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Cannot convert {:?} to list<float64>",
+                value_type_name(other)
+            )))
+        }
+    }
+    Ok(())
+}
+
+fn parse_json_list<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<Vec<T>, ArrowError> {
+    serde_json::from_slice(bytes).map_err(|e| {
+        ArrowError::InvalidArgumentError(format!("Cannot convert bytes to list: invalid JSON: {e}"))
+    })
+}
+
+/// Append a VRL value to a BooleanBuilder.
+fn append_boolean(builder: &mut BooleanBuilder, value: Option<&Value>) -> Result<(), ArrowError> {
+    match value {
         Some(Value::Boolean(b)) => {
-            // Convert boolean to string
-            builder.append_value(b.to_string());
+            builder.append_value(*b);
             Ok(())
         }
         Some(Value::Null) | None => {
             builder.append_null();
             Ok(())
         }
-        Some(Value::Object(_)) | Some(Value::Array(_)) => {
-            // Serialize complex types as JSON
-            let json_str = serde_json::to_string(value.unwrap())
-                .map_err(|e| ArrowError::ExternalError(Box::new(e)))?;
-            builder.append_value(&json_str);
-            Ok(())
-        }
-        Some(Value::Timestamp(ts)) => {
-            // Convert timestamp to ISO string
-            builder.append_value(ts.to_string());
-            Ok(())
-        }
-        Some(Value::Regex(r)) => {
-            builder.append_value(r.to_string());
-            Ok(())
+        Some(other) => Err(ArrowError::InvalidArgumentError(format!(
+            "Cannot convert {:?} to boolean",
+            value_type_name(other)
+        ))),
+    }
+}
+
+/// Append a VRL value to a StringBuilder.
+fn append_string(builder: &mut StringBuilder, value: Option<&Value>) -> Result<(), ArrowError> {
+    match string_value(value)? {
+        Some(s) => builder.append_value(s),
+        None => builder.append_null(),
+    }
+    Ok(())
+}
+
+/// Append a VRL value to a dictionary-encoded string column.
+///
+/// Values are converted exactly as for plain string columns.
+fn append_dict_string(
+    builder: &mut StringDictionaryBuilder<Int32Type>,
+    value: Option<&Value>,
+) -> Result<(), ArrowError> {
+    match string_value(value)? {
+        Some(s) => {
+            builder.append(s)?;
         }
+        None => builder.append_null(),
     }
+    Ok(())
+}
+
+/// Convert a VRL value to the string stored in a string column.
+///
+/// # Note on UTF-8 handling
+///
+/// When converting `Value::Bytes` to strings, invalid UTF-8 sequences are replaced
+/// with the Unicode replacement character (U+FFFD) using lossy conversion. This
+/// ensures the function never fails for byte data, but may result in data modification
+/// if the input contains invalid UTF-8.
+fn string_value(value: Option<&Value>) -> Result<Option<Cow<'_, str>>, ArrowError> {
+    let s = match value {
+        // Note: Uses lossy conversion - invalid UTF-8 becomes U+FFFD
+        Some(Value::Bytes(b)) => String::from_utf8_lossy(b),
+        // Convert scalars to their string form
+        Some(Value::Integer(i)) => Cow::Owned(i.to_string()),
+        Some(Value::Float(f)) => Cow::Owned(f.to_string()),
+        Some(Value::Boolean(b)) => Cow::Owned(b.to_string()),
+        Some(Value::Null) | None => return Ok(None),
+        // Serialize complex types as JSON
+        Some(complex @ (Value::Object(_) | Value::Array(_))) => Cow::Owned(
+            serde_json::to_string(complex).map_err(|e| ArrowError::ExternalError(Box::new(e)))?,
+        ),
+        // Convert timestamp to ISO string
+        Some(Value::Timestamp(ts)) => Cow::Owned(ts.to_string()),
+        Some(Value::Regex(r)) => Cow::Owned(r.to_string()),
+    };
+    Ok(Some(s))
 }
 
 /// Get a human-readable name for a VRL Value type.
@@ -385,7 +805,7 @@ mod tests {
     use super::*;
     use arrow::array::{
         Array, BooleanArray, Float64Array, Int32Array, Int64Array, StringArray,
-        TimestampMicrosecondArray,
+        TimestampMicrosecondArray, TimestampNanosecondArray, UInt64Array,
     };
     use arrow::datatypes::{Field, TimeUnit};
     use bytes::Bytes;
@@ -431,6 +851,35 @@ mod tests {
         assert_eq!(col.value(1), "bob");
     }
 
+    #[test]
+    fn test_dictionary_string_column() {
+        use arrow::array::DictionaryArray;
+
+        let dict_type = DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8));
+        let schema = Schema::new(vec![Field::new("service", dict_type.clone(), true)]);
+
+        let values = vec![
+            make_object(vec![("service", Value::Bytes(Bytes::from("api")))]),
+            make_object(vec![("service", Value::Null)]),
+            make_object(vec![("service", Value::Bytes(Bytes::from("api")))]),
+            make_object(vec![("service", Value::Integer(7))]),
+        ];
+
+        let batch = values_to_arrow(&values, &schema).unwrap();
+
+        assert_eq!(batch.column(0).data_type(), &dict_type);
+        let col = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<DictionaryArray<Int32Type>>()
+            .unwrap();
+        // Repeated values share one dictionary entry
+        assert_eq!(col.values().len(), 2);
+        let col = col.downcast_dict::<StringArray>().unwrap();
+        let strings: Vec<Option<&str>> = col.into_iter().collect();
+        assert_eq!(strings, [Some("api"), None, Some("api"), Some("7")]);
+    }
+
     #[test]
     fn test_int64_column() {
         let schema = Schema::new(vec![Field::new("count", DataType::Int64, false)]);
@@ -535,6 +984,54 @@ mod tests {
         assert_eq!(col.value(1), 1700000001000);
     }
 
+    #[test]
+    fn test_timestamp_nanosecond_column() {
+        let schema = Schema::new(vec![Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            true,
+        )]);
+
+        let values = vec![
+            make_object(vec![(
+                "timestamp",
+                Value::Integer(1_700_000_000_123_456_789),
+            )]),
+            make_object(vec![(
+                "timestamp",
+                Value::Float(NotNan::new(1.5e18).unwrap()),
+            )]),
+            make_object(vec![("timestamp", Value::Null)]),
+        ];
+
+        let batch = values_to_arrow(&values, &schema).unwrap();
+
+        let col = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<TimestampNanosecondArray>()
+            .unwrap();
+        assert_eq!(col.value(0), 1_700_000_000_123_456_789);
+        assert_eq!(col.value(1), 1_500_000_000_000_000_000);
+        assert!(col.is_null(2));
+    }
+
+    #[test]
+    fn test_timestamp_nanosecond_rejects_past_2262() {
+        let schema = Schema::new(vec![Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Nanosecond, None),
+            false,
+        )]);
+        let values = vec![make_object(vec![(
+            "timestamp",
+            Value::Float(NotNan::new(1e19).unwrap()),
+        )])];
+
+        let err = values_to_arrow(&values, &schema).unwrap_err();
+        assert!(err.to_string().contains("year 2262"), "{err}");
+    }
+
     #[test]
     fn test_null_values() {
         let schema = Schema::new(vec![
@@ -694,6 +1191,34 @@ mod tests {
         assert!(value_col.is_null(1));
     }
 
+    #[test]
+    fn test_build_batch_counts_coerced_nulls() {
+        let schema = Schema::new(vec![
+            Field::new(
+                "bucket_counts",
+                DataType::List(Arc::new(Field::new("item", DataType::UInt64, true))),
+                true,
+            ),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+
+        let values = vec![
+            make_object(vec![
+                ("bucket_counts", Value::Bytes(Bytes::from("[1,2]"))),
+                ("name", Value::Null),
+            ]),
+            // An empty string is stored as null
+            make_object(vec![("bucket_counts", Value::Bytes(Bytes::new()))]),
+        ];
+
+        let (batch, coerced) = build_batch(&values, &schema).unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(0).null_count(), 1);
+        assert_eq!(batch.column(1).null_count(), 2);
+        assert_eq!(coerced, 1);
+    }
+
     #[test]
     fn test_non_object_value_produces_nulls() {
         let schema = Schema::new(vec![
@@ -749,4 +1274,185 @@ mod tests {
         assert_eq!(col1.value(0), "alpha"); // a
         assert_eq!(col2.value(0), "beta"); // b
     }
+
+    #[test]
+    fn test_map_column_from_json_and_object() {
+        let schema = Schema::new(vec![Field::new(
+            "attrs",
+            crate::arrow::attributes_map_type(),
+            true,
+        )]);
+
+        let values = vec![
+            make_object(vec![(
+                "attrs",
+                Value::Bytes(Bytes::from(
+                    r#"{"http.route":"/a","retries":3,"tags":["x"]}"#,
+                )),
+            )]),
+            make_object(vec![(
+                "attrs",
+                make_object(vec![("ok", Value::Boolean(true))]),
+            )]),
+            make_object(vec![("attrs", Value::Null)]),
+        ];
+
+        let batch = values_to_arrow(&values, &schema).unwrap();
+        let map = batch.column(0).as_any().downcast_ref::<MapArray>().unwrap();
+
+        assert_eq!(map.len(), 3);
+        assert!(map.is_null(2));
+        let keys = map.keys().as_any().downcast_ref::<StringArray>().unwrap();
+        let vals = map.values().as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(map.value_offsets(), &[0, 3, 4, 4]);
+        assert_eq!(keys.value(0), "http.route");
+        assert_eq!(vals.value(0), "/a");
+        assert_eq!(keys.value(1), "retries");
+        assert_eq!(vals.value(1), "3");
+        assert_eq!(vals.value(2), r#"["x"]"#);
+        assert_eq!(vals.value(3), "true");
+    }
+
+    #[test]
+    fn test_list_of_struct_column() {
+        let schema = Schema::new(vec![Field::new(
+            "events",
+            crate::arrow::span_events_type(),
+            true,
+        )]);
+
+        let values = vec![
+            make_object(vec![(
+                "events",
+                Value::Bytes(Bytes::from(
+                    r#"[{"time_unix_nano":5,"name":"a","attributes":{"k":"v"}},{"name":"b"}]"#,
+                )),
+            )]),
+            make_object(vec![]),
+        ];
+
+        let batch = values_to_arrow(&values, &schema).unwrap();
+        let list = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap();
+
+        assert!(list.is_valid(0));
+        assert!(list.is_null(1));
+        let events = list.value(0);
+        let events = events.as_any().downcast_ref::<StructArray>().unwrap();
+        assert_eq!(events.len(), 2);
+        let names = events
+            .column_by_name("name")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(names.value(1), "b");
+        let times = events
+            .column_by_name("time_unix_nano")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(times.value(0), 5);
+        assert!(times.is_null(1));
+        assert!(events.column_by_name("attributes").unwrap().is_null(1));
+    }
+
+    #[test]
+    fn test_nested_column_rejects_invalid_json() {
+        let schema = Schema::new(vec![Field::new(
+            "attrs",
+            crate::arrow::attributes_map_type(),
+            true,
+        )]);
+        let values = vec![make_object(vec![(
+            "attrs",
+            Value::Bytes(Bytes::from("not json")),
+        )])];
+
+        assert!(values_to_arrow(&values, &schema).is_err());
+    }
+
+    #[test]
+    fn test_uint64_list_column() {
+        let schema = Schema::new(vec![Field::new(
+            "bucket_counts",
+            crate::arrow::bucket_counts_type(),
+            true,
+        )]);
+
+        let values = vec![
+            make_object(vec![(
+                "bucket_counts",
+                Value::Bytes(Bytes::from("[1,0,7]")),
+            )]),
+            make_object(vec![(
+                "bucket_counts",
+                Value::Array(vec![Value::Integer(2), Value::Integer(3)]),
+            )]),
+            make_object(vec![("bucket_counts", Value::Bytes(Bytes::from("[]")))]),
+            make_object(vec![("bucket_counts", Value::Null)]),
+        ];
+
+        let batch = values_to_arrow(&values, &schema).unwrap();
+        let list = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap();
+
+        assert_eq!(list.value_offsets(), &[0, 3, 5, 5, 5]);
+        assert!(list.is_valid(2));
+        assert!(list.is_null(3));
+        let counts = list
+            .values()
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap();
+        assert_eq!(counts.values(), &[1, 0, 7, 2, 3]);
+    }
+
+    #[test]
+    fn test_float64_list_column() {
+        let schema = Schema::new(vec![Field::new(
+            "explicit_bounds",
+            crate::arrow::explicit_bounds_type(),
+            false,
+        )]);
+
+        let values = vec![make_object(vec![(
+            "explicit_bounds",
+            Value::Bytes(Bytes::from("[0.5,1.0,null]")),
+        )])];
+
+        let batch = values_to_arrow(&values, &schema).unwrap();
+        let list = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap();
+        let bounds = list.value(0);
+        let bounds = bounds.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(bounds.len(), 3);
+        assert_eq!(bounds.value(1), 1.0);
+        assert!(bounds.is_null(2));
+    }
+
+    #[test]
+    fn test_uint64_rejects_negative() {
+        let schema = Schema::new(vec![Field::new("n", DataType::UInt64, true)]);
+        let values = vec![make_object(vec![("n", Value::Integer(-1))])];
+        assert!(values_to_arrow(&values, &schema).is_err());
+
+        let schema = Schema::new(vec![Field::new(
+            "n",
+            crate::arrow::bucket_counts_type(),
+            true,
+        )]);
+        let values = vec![make_object(vec![("n", Value::Bytes(Bytes::from("[-1]")))])];
+        assert!(values_to_arrow(&values, &schema).is_err());
+    }
 }
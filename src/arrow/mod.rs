@@ -3,17 +3,27 @@
 //! Provides Arrow RecordBatch construction from VRL-transformed values:
 //! - Schema accessors for logs, traces, and metrics
 //! - RecordBatch builder for converting VRL Values to Arrow arrays
-//! - Partitioning utilities for service-based grouping
+//! - Partitioning utilities for service-, column- and time-based grouping
+//! - Output timestamp precision
 
 mod builder;
 mod partition;
 mod schema;
+mod timestamp;
 
 pub use builder::values_to_arrow;
+pub(crate) use builder::{build_batch, BatchBuilder};
 pub use partition::{
-    extract_min_timestamp_micros, extract_service_name, group_batch_by_service, PartitionedBatch,
-    PartitionedMetrics, ServiceGroupedBatches,
+    extract_min_timestamp_micros, extract_service_name, group_batch_by, group_batch_by_service,
+    try_group_batch_by, try_group_batch_by_service, PartitionKey, PartitionValue, PartitionedBatch,
+    PartitionedBatches, PartitionedMetrics, ServiceGroupedBatches, HIVE_DEFAULT_PARTITION,
 };
+#[cfg(feature = "parquet")]
+pub(crate) use partition::{MICROS_PER_DAY, MICROS_PER_HOUR};
 pub use schema::{
-    exp_histogram_schema, gauge_schema, histogram_schema, logs_schema, sum_schema, traces_schema,
+    attributes_map_type, bucket_counts_type, dict_string_type, exemplars_type,
+    exp_histogram_schema, explicit_bounds_type, gauge_schema, histogram_schema, logs_schema,
+    native_nested_schema, profiles_schema, quantile_values_type, span_events_type, span_links_type,
+    sum_schema, summary_schema, traces_schema, SignalType,
 };
+pub use timestamp::{cast_timestamps, nanosecond_schema, TimestampUnit, TIME_UNIX_NANO};
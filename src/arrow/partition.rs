@@ -1,47 +1,119 @@
 //! Partition utilities for Arrow RecordBatches.
 //!
-//! Provides service-based partitioning and metadata extraction for
-//! storage routing without exposing VRL internals.
+//! Provides service-, column- and time-based partitioning and metadata
+//! extraction for storage routing without exposing VRL internals.
 
 use std::sync::Arc;
 
-use arrow::array::{ArrayRef, AsArray};
-use arrow::compute::take;
-use arrow::datatypes::{Int64Type, TimestampMillisecondType};
+use arrow::array::{Array, ArrayRef, AsArray, Int64Array, UInt32Array};
+use arrow::compute::{cast, take};
+use arrow::datatypes::{DataType, Int64Type, TimeUnit};
 use arrow::record_batch::RecordBatch;
+use arrow::temporal_conversions::timestamp_us_to_datetime;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
 use indexmap::IndexMap;
 
 use crate::decode::SkippedMetrics;
+use crate::error::{Error, Result};
+
+/// Path segment value of a null partition key, as written by Hive and Spark.
+pub const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+pub(crate) const MICROS_PER_HOUR: i64 = 3_600_000_000;
+pub(crate) const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
 
 /// A RecordBatch with partition metadata for storage routing.
 ///
 /// Contains the batch data along with pre-extracted metadata needed
-/// for partitioned storage paths (service name and timestamp).
+/// for partitioned storage paths (key values, service name and timestamps).
 #[derive(Debug, Clone)]
 pub struct PartitionedBatch {
     /// The Arrow RecordBatch containing signal records
     pub batch: RecordBatch,
     /// Service name extracted from the batch
     pub service_name: Arc<str>,
-    /// Minimum timestamp in microseconds (for partition path generation)
+    /// Minimum timestamp in microseconds, whatever the precision of the
+    /// batch (for partition path generation)
     pub min_timestamp_micros: i64,
+    /// Maximum timestamp in microseconds, whatever the precision of the batch
+    pub max_timestamp_micros: i64,
     /// Number of records in this batch
     pub record_count: usize,
+    /// Values of the partition keys, shared by every row of the batch
+    pub partition_values: Vec<PartitionValue>,
+    /// Hive-style path of the partition, e.g.
+    /// `service_name=api/date=2024-01-02/hour=13`
+    pub path: String,
+}
+
+/// A column or time bucket to partition on, see [`group_batch_by`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PartitionKey {
+    /// The values of a column, formatted as strings. Use promoted attributes
+    /// to partition on an attribute such as `deployment.environment`.
+    Column(String),
+    /// The `timestamp` column truncated to the hour, written as `hour=HH`.
+    /// Combine it with [`PartitionKey::Day`] for unique paths.
+    Hour,
+    /// The `timestamp` column truncated to the day (UTC), written as
+    /// `date=YYYY-MM-DD`
+    Day,
+}
+
+impl PartitionKey {
+    /// Partition on the values of a column.
+    pub fn column(name: impl Into<String>) -> Self {
+        PartitionKey::Column(name.into())
+    }
+
+    /// Name of the key in partition paths: the column name, `hour` or `date`.
+    pub fn name(&self) -> &str {
+        match self {
+            PartitionKey::Column(name) => name,
+            PartitionKey::Hour => "hour",
+            PartitionKey::Day => "date",
+        }
+    }
 }
 
-/// Multiple batches grouped by service name.
+/// The value of one partition key of a [`PartitionedBatch`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionValue {
+    /// Key name, see [`PartitionKey::name`]
+    pub name: Arc<str>,
+    /// Formatted value, or `None` if the key is null for the partition
+    pub value: Option<Arc<str>>,
+}
+
+impl PartitionValue {
+    /// The `name=value` path segment, with both sides escaped like Hive
+    /// partition paths and nulls written as [`HIVE_DEFAULT_PARTITION`].
+    pub fn path_segment(&self) -> String {
+        let value = match &self.value {
+            Some(value) => escape_path_name(value),
+            None => HIVE_DEFAULT_PARTITION.to_string(),
+        };
+        format!("{}={value}", escape_path_name(&self.name))
+    }
+}
+
+/// Multiple batches split into partitions.
 ///
-/// This is the primary output type when service-based partitioning is needed.
-/// Batches are ordered by first occurrence of each service name.
+/// Returned by [`group_batch_by`] and [`group_batch_by_service`]. Batches
+/// are ordered by first occurrence of their partition values.
 #[derive(Debug, Clone, Default)]
-pub struct ServiceGroupedBatches {
-    /// Batches keyed by service name, in insertion order
+pub struct PartitionedBatches {
+    /// Batches with their partition values, in insertion order
     pub batches: Vec<PartitionedBatch>,
     /// Total record count across all batches
     pub total_records: usize,
 }
 
-impl ServiceGroupedBatches {
+/// Batches grouped by service name, the original name of
+/// [`PartitionedBatches`].
+pub type ServiceGroupedBatches = PartitionedBatches;
+
+impl PartitionedBatches {
     /// Iterate over (service_name, batch) pairs
     pub fn iter(&self) -> impl Iterator<Item = (&str, &RecordBatch)> {
         self.batches
@@ -54,13 +126,13 @@ impl ServiceGroupedBatches {
         self.batches.is_empty()
     }
 
-    /// Number of service groups
+    /// Number of partitions
     pub fn len(&self) -> usize {
         self.batches.len()
     }
 }
 
-impl IntoIterator for ServiceGroupedBatches {
+impl IntoIterator for PartitionedBatches {
     type Item = PartitionedBatch;
     type IntoIter = std::vec::IntoIter<PartitionedBatch>;
 
@@ -80,6 +152,8 @@ pub struct PartitionedMetrics {
     pub histogram: ServiceGroupedBatches,
     /// Exponential histogram metrics grouped by service
     pub exp_histogram: ServiceGroupedBatches,
+    /// Summary metrics grouped by service
+    pub summary: ServiceGroupedBatches,
     /// Metrics that were skipped during processing
     pub skipped: SkippedMetrics,
 }
@@ -87,22 +161,32 @@ pub struct PartitionedMetrics {
 /// Group a RecordBatch by service_name column.
 ///
 /// Returns batches grouped by service name, preserving insertion order
-/// (order in which services first appear in the input batch).
+/// (order in which services first appear in the input batch). Null service
+/// names are grouped as `unknown`, and each batch has a
+/// `service_name=<name>` path.
 ///
 /// # Panics
-/// Panics if the batch does not contain a "service_name" column of string type.
+/// Panics if the batch does not contain a "service_name" column of string
+/// type. Use [`try_group_batch_by_service`] for batches of custom schemas.
 pub fn group_batch_by_service(batch: RecordBatch) -> ServiceGroupedBatches {
+    try_group_batch_by_service(batch).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Group a RecordBatch by service_name column, see [`group_batch_by_service`].
+///
+/// The column may be `Utf8`, `LargeUtf8`, `Utf8View` or dictionary-encoded
+/// strings. Returns [`Error::SchemaMismatch`] if the column is missing or of
+/// another type.
+pub fn try_group_batch_by_service(batch: RecordBatch) -> Result<ServiceGroupedBatches> {
     if batch.num_rows() == 0 {
-        return ServiceGroupedBatches::default();
+        return Ok(ServiceGroupedBatches::default());
     }
 
-    let service_col = batch
-        .column_by_name("service_name")
-        .expect("service_name column required");
-
-    let service_array = service_col
-        .as_string_opt::<i32>()
-        .expect("service_name must be Utf8 string type");
+    let service_col = batch.column_by_name("service_name").ok_or_else(|| {
+        Error::SchemaMismatch("partitioning needs a 'service_name' column".to_string())
+    })?;
+    let service_array = utf8_service_names(service_col)?;
+    let service_array = service_array.as_string::<i32>();
 
     // Group row indices by service name (preserving insertion order)
     let mut groups: IndexMap<&str, Vec<u32>> = IndexMap::new();
@@ -111,96 +195,291 @@ pub fn group_batch_by_service(batch: RecordBatch) -> ServiceGroupedBatches {
         groups.entry(service).or_default().push(idx as u32);
     }
 
-    let total_records = batch.num_rows();
-    let schema = batch.schema();
+    let name: Arc<str> = Arc::from("service_name");
+    let groups = groups.into_iter().map(|(service, indices)| {
+        let values = vec![PartitionValue {
+            name: name.clone(),
+            value: Some(Arc::from(service)),
+        }];
+        (values, indices)
+    });
+    grouped_batches(&batch, groups)
+}
+
+/// Group a RecordBatch by columns and time buckets.
+///
+/// Rows with the same values for every key end up in the same batch, in
+/// order of first occurrence. Column values are formatted as strings (nulls
+/// stay `None`), and the time buckets read the `timestamp` column at
+/// whatever precision it has. For example, a `service/date/hour` lake
+/// layout:
+///
+/// ```ignore
+/// use otlp2records::{group_batch_by, PartitionKey};
+///
+/// let keys = [PartitionKey::column("service_name"), PartitionKey::Day, PartitionKey::Hour];
+/// for partition in group_batch_by(batch, &keys) {
+///     // "service_name=api/date=2024-01-02/hour=13"
+///     write(&partition.path, partition.batch);
+/// }
+/// ```
+///
+/// Without keys the whole batch is a single partition with an empty path.
+///
+/// # Panics
+/// Panics if a key column is missing, or a time bucket is requested and the
+/// batch has no `timestamp` column of Timestamp or Int64 type. Use
+/// [`try_group_batch_by`] to get these as errors.
+pub fn group_batch_by(batch: RecordBatch, keys: &[PartitionKey]) -> PartitionedBatches {
+    try_group_batch_by(batch, keys).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Group a RecordBatch by columns and time buckets, see [`group_batch_by`].
+///
+/// Returns [`Error::SchemaMismatch`] if a key column is missing, or a time
+/// bucket is requested and the batch has no usable `timestamp` column.
+pub fn try_group_batch_by(batch: RecordBatch, keys: &[PartitionKey]) -> Result<PartitionedBatches> {
+    let batch = &batch;
+    if batch.num_rows() == 0 {
+        return Ok(PartitionedBatches::default());
+    }
+
+    let columns = keys
+        .iter()
+        .map(|key| KeyColumn::new(batch, key))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Group row indices by key values (preserving insertion order)
+    let mut groups: IndexMap<Vec<Option<Arc<str>>>, Vec<u32>> = IndexMap::new();
+    for row in 0..batch.num_rows() {
+        let values = columns
+            .iter()
+            .map(|column| column.value(row))
+            .collect::<Result<Vec<_>>>()?;
+        groups.entry(values).or_default().push(row as u32);
+    }
+
+    let names: Vec<Arc<str>> = keys.iter().map(|key| Arc::from(key.name())).collect();
+    let groups = groups.into_iter().map(|(values, indices)| {
+        let values = names
+            .iter()
+            .zip(values)
+            .map(|(name, value)| PartitionValue {
+                name: name.clone(),
+                value,
+            })
+            .collect();
+        (values, indices)
+    });
+    grouped_batches(batch, groups)
+}
+
+/// Values of one partition key for each row.
+enum KeyColumn<'a> {
+    Column(ArrayFormatter<'a>, &'a ArrayRef),
+    /// Timestamps in microseconds
+    Hour(Int64Array),
+    Day(Int64Array),
+}
+
+impl<'a> KeyColumn<'a> {
+    fn new(batch: &'a RecordBatch, key: &PartitionKey) -> Result<Self> {
+        match key {
+            PartitionKey::Column(name) => {
+                let column = batch.column_by_name(name).ok_or_else(|| {
+                    Error::SchemaMismatch(format!("missing partition column '{name}'"))
+                })?;
+                let formatter =
+                    ArrayFormatter::try_new(column.as_ref(), &FormatOptions::default())?;
+                Ok(KeyColumn::Column(formatter, column))
+            }
+            PartitionKey::Hour => Ok(KeyColumn::Hour(required_timestamp_micros(batch)?)),
+            PartitionKey::Day => Ok(KeyColumn::Day(required_timestamp_micros(batch)?)),
+        }
+    }
 
+    fn value(&self, row: usize) -> Result<Option<Arc<str>>> {
+        let value = match self {
+            KeyColumn::Column(_, column) if column.is_null(row) => None,
+            KeyColumn::Column(formatter, _) => Some(formatter.value(row).try_to_string()?),
+            KeyColumn::Hour(micros) | KeyColumn::Day(micros) if micros.is_null(row) => None,
+            KeyColumn::Hour(micros) => {
+                let hour = micros.value(row).div_euclid(MICROS_PER_HOUR) * MICROS_PER_HOUR;
+                timestamp_us_to_datetime(hour).map(|hour| hour.format("%H").to_string())
+            }
+            KeyColumn::Day(micros) => {
+                let day = micros.value(row).div_euclid(MICROS_PER_DAY) * MICROS_PER_DAY;
+                timestamp_us_to_datetime(day).map(|day| day.format("%Y-%m-%d").to_string())
+            }
+        };
+        Ok(value.map(Arc::from))
+    }
+}
+
+/// Build a [`PartitionedBatch`] from the rows of each group.
+fn grouped_batches(
+    batch: &RecordBatch,
+    groups: impl Iterator<Item = (Vec<PartitionValue>, Vec<u32>)>,
+) -> Result<PartitionedBatches> {
     let batches = groups
-        .into_iter()
-        .map(|(service, indices)| {
-            let indices = arrow::array::UInt32Array::from(indices);
-            let columns: Vec<ArrayRef> = batch
+        .map(|(partition_values, indices)| {
+            let indices = UInt32Array::from(indices);
+            let columns = batch
                 .columns()
                 .iter()
-                .map(|col| take(col.as_ref(), &indices, None).expect("take should succeed"))
-                .collect();
-            let grouped =
-                RecordBatch::try_new(schema.clone(), columns).expect("schema should match");
-            let min_ts = extract_min_timestamp_micros(&grouped);
+                .map(|col| take(col.as_ref(), &indices, None))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            let grouped = RecordBatch::try_new(batch.schema(), columns)?;
+            let (min_timestamp_micros, max_timestamp_micros) = timestamp_range_micros(&grouped);
+            let path = partition_values
+                .iter()
+                .map(PartitionValue::path_segment)
+                .collect::<Vec<_>>()
+                .join("/");
 
-            PartitionedBatch {
+            Ok(PartitionedBatch {
                 record_count: grouped.num_rows(),
+                service_name: extract_service_name(&grouped),
                 batch: grouped,
-                service_name: Arc::from(service),
-                min_timestamp_micros: min_ts,
-            }
+                min_timestamp_micros,
+                max_timestamp_micros,
+                partition_values,
+                path,
+            })
         })
-        .collect();
+        .collect::<Result<Vec<_>>>()?;
 
-    ServiceGroupedBatches {
+    Ok(PartitionedBatches {
         batches,
-        total_records,
+        total_records: batch.num_rows(),
+    })
+}
+
+/// Escape a partition path name or value like Hive does: `/`, `=`, `%`,
+/// control and a few other characters are percent-encoded.
+fn escape_path_name(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '\u{01}'..='\u{1F}'
+            | '"'
+            | '#'
+            | '%'
+            | '\''
+            | '*'
+            | '/'
+            | ':'
+            | '='
+            | '?'
+            | '\\'
+            | '\u{7F}'
+            | '{'
+            | '['
+            | ']'
+            | '^' => escaped.push_str(&format!("%{:02X}", c as u32)),
+            c => escaped.push(c),
+        }
     }
+    escaped
+}
+
+/// The `timestamp` column in microseconds, whatever its precision. Int64
+/// columns are assumed to hold microseconds.
+fn timestamp_micros(batch: &RecordBatch) -> Option<Int64Array> {
+    let column = batch.column_by_name("timestamp")?;
+    let unit = match column.data_type() {
+        DataType::Timestamp(unit, _) => *unit,
+        DataType::Int64 => TimeUnit::Microsecond,
+        _ => return None,
+    };
+    let values = cast(column, &DataType::Int64).ok()?;
+    let values = values.as_primitive::<Int64Type>();
+    Some(match unit {
+        TimeUnit::Second => values.unary(|v| v.saturating_mul(1_000_000)),
+        TimeUnit::Millisecond => values.unary(|v| v.saturating_mul(1_000)),
+        TimeUnit::Microsecond => values.clone(),
+        // Floor, so the partition of a pre-epoch nanosecond is not rounded up
+        TimeUnit::Nanosecond => values.unary(|v| v.div_euclid(1_000)),
+    })
+}
+
+fn required_timestamp_micros(batch: &RecordBatch) -> Result<Int64Array> {
+    timestamp_micros(batch).ok_or_else(|| {
+        Error::SchemaMismatch(
+            "time partitions need a 'timestamp' column of Timestamp or Int64 type".to_string(),
+        )
+    })
+}
+
+/// Minimum and maximum of the `timestamp` column in microseconds, or zeros.
+fn timestamp_range_micros(batch: &RecordBatch) -> (i64, i64) {
+    let Some(micros) = timestamp_micros(batch) else {
+        return (0, 0);
+    };
+    let min = arrow::compute::min(&micros).unwrap_or(0);
+    let max = arrow::compute::max(&micros).unwrap_or(0);
+    (min, max)
 }
 
 /// Extract the minimum timestamp from a RecordBatch.
 ///
-/// Looks for a "timestamp" column and returns the minimum value in microseconds.
-/// The timestamp column stores milliseconds, so we multiply by 1000.
+/// Looks for a "timestamp" column and returns its minimum value converted to
+/// microseconds, whatever the precision of the column (see
+/// [`TimestampUnit`](crate::TimestampUnit)). Int64 columns are assumed to hold
+/// microseconds, the default precision of the built-in schemas.
 /// Returns 0 if no valid timestamps found.
 pub fn extract_min_timestamp_micros(batch: &RecordBatch) -> i64 {
-    if batch.num_rows() == 0 {
-        return 0;
-    }
-
-    let ts_col = match batch.column_by_name("timestamp") {
-        Some(col) => col,
-        None => return 0,
-    };
-
-    // Timestamp column is TimestampMillisecond
-    if let Some(ts_array) = ts_col.as_primitive_opt::<TimestampMillisecondType>() {
-        let min_ms = ts_array.iter().flatten().min().unwrap_or(0);
-        // Convert milliseconds to microseconds
-        min_ms * 1000
-    } else if let Some(ts_array) = ts_col.as_primitive_opt::<Int64Type>() {
-        // Fallback for Int64 timestamp column
-        let min_ms = ts_array.iter().flatten().min().unwrap_or(0);
-        min_ms * 1000
-    } else {
-        0
-    }
+    timestamp_range_micros(batch).0
 }
 
 /// Extract the first service name from a RecordBatch.
 ///
 /// Returns "unknown" if no valid service name found.
 pub fn extract_service_name(batch: &RecordBatch) -> Arc<str> {
+    let unknown = || Arc::from("unknown");
     if batch.num_rows() == 0 {
-        return Arc::from("unknown");
+        return unknown();
     }
 
-    let service_col = match batch.column_by_name("service_name") {
-        Some(col) => col,
-        None => return Arc::from("unknown"),
+    let Some(service_col) = batch.column_by_name("service_name") else {
+        return unknown();
     };
 
-    if let Some(service_array) = service_col.as_string_opt::<i32>() {
-        service_array
+    match utf8_service_names(service_col) {
+        Ok(service_array) => service_array
+            .as_string::<i32>()
             .iter()
             .flatten()
             .next()
             .map(Arc::from)
-            .unwrap_or_else(|| Arc::from("unknown"))
-    } else {
-        Arc::from("unknown")
+            .unwrap_or_else(unknown),
+        Err(_) => unknown(),
+    }
+}
+
+/// The service_name column as `Utf8`, casting the other string encodings.
+fn utf8_service_names(column: &ArrayRef) -> Result<ArrayRef> {
+    let is_string = |data_type: &DataType| {
+        matches!(
+            data_type,
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+        )
+    };
+    match column.data_type() {
+        DataType::Utf8 => Ok(column.clone()),
+        DataType::Dictionary(_, values) if is_string(values) => Ok(cast(column, &DataType::Utf8)?),
+        data_type if is_string(data_type) => Ok(cast(column, &DataType::Utf8)?),
+        data_type => Err(Error::SchemaMismatch(format!(
+            "service_name must be a string column, found {data_type}"
+        ))),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow::array::{Int32Array, StringBuilder, TimestampMillisecondBuilder};
-    use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+    use arrow::array::{Int32Array, Int64Array, StringBuilder, TimestampMillisecondBuilder};
+    use arrow::datatypes::{Field, Schema};
     use std::sync::Arc as StdArc;
 
     fn create_test_batch(services: &[&str], timestamps_ms: &[i64]) -> RecordBatch {
@@ -290,6 +569,208 @@ mod tests {
         assert_eq!(grouped.total_records, 0);
     }
 
+    #[test]
+    fn test_group_batch_by_service_paths() {
+        let batch = create_test_batch(&["svc-a", "svc-a", "svc-b"], &[100, 300, 200]);
+        let grouped = group_batch_by_service(batch);
+
+        assert_eq!(grouped.batches[0].path, "service_name=svc-a");
+        assert_eq!(grouped.batches[0].max_timestamp_micros, 300_000);
+        assert_eq!(
+            grouped.batches[1].partition_values,
+            vec![PartitionValue {
+                name: Arc::from("service_name"),
+                value: Some(Arc::from("svc-b")),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_group_batch_by_service_day_and_hour() {
+        // 2024-01-02T13:00:00Z, +30 minutes, +1 hour, and the next day
+        let base = 1_704_200_400_000;
+        let batch = create_test_batch(
+            &["api", "api", "api", "db", "api"],
+            &[
+                base,
+                base + 1_800_000,
+                base + 3_600_000,
+                base + 60_000,
+                base + 86_400_000,
+            ],
+        );
+        let keys = [
+            PartitionKey::column("service_name"),
+            PartitionKey::Day,
+            PartitionKey::Hour,
+        ];
+        let grouped = group_batch_by(batch, &keys);
+
+        let paths: Vec<&str> = grouped.batches.iter().map(|pb| pb.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "service_name=api/date=2024-01-02/hour=13",
+                "service_name=api/date=2024-01-02/hour=14",
+                "service_name=db/date=2024-01-02/hour=13",
+                "service_name=api/date=2024-01-03/hour=13",
+            ]
+        );
+        assert_eq!(grouped.total_records, 5);
+
+        let first = &grouped.batches[0];
+        assert_eq!(first.record_count, 2);
+        assert_eq!(first.service_name.as_ref(), "api");
+        assert_eq!(first.min_timestamp_micros, base * 1000);
+        assert_eq!(first.max_timestamp_micros, (base + 1_800_000) * 1000);
+        let values: Vec<(&str, Option<&str>)> = first
+            .partition_values
+            .iter()
+            .map(|v| (v.name.as_ref(), v.value.as_deref()))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("service_name", Some("api")),
+                ("date", Some("2024-01-02")),
+                ("hour", Some("13")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_batch_by_nulls_and_escaping() {
+        let schema = StdArc::new(Schema::new(vec![
+            Field::new("env", DataType::Utf8, true),
+            Field::new("status", DataType::Int32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                StdArc::new(arrow::array::StringArray::from(vec![
+                    Some("prod/eu=1"),
+                    None,
+                    Some("prod/eu=1"),
+                ])),
+                StdArc::new(Int32Array::from(vec![Some(200), Some(500), Some(200)])),
+            ],
+        )
+        .unwrap();
+
+        let keys = [PartitionKey::column("env"), PartitionKey::column("status")];
+        let grouped = group_batch_by(batch, &keys);
+
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped.batches[0].path, "env=prod%2Feu%3D1/status=200");
+        assert_eq!(grouped.batches[0].record_count, 2);
+        assert_eq!(
+            grouped.batches[1].path,
+            "env=__HIVE_DEFAULT_PARTITION__/status=500"
+        );
+        assert_eq!(grouped.batches[1].partition_values[0].value, None);
+        // No timestamp or service_name columns
+        assert_eq!(grouped.batches[1].min_timestamp_micros, 0);
+        assert_eq!(grouped.batches[1].service_name.as_ref(), "unknown");
+    }
+
+    #[test]
+    fn test_group_batch_by_without_keys() {
+        let batch = create_test_batch(&["svc-a", "svc-b"], &[100, 200]);
+        let grouped = group_batch_by(batch, &[]);
+
+        assert_eq!(grouped.len(), 1);
+        assert_eq!(grouped.batches[0].record_count, 2);
+        assert_eq!(grouped.batches[0].path, "");
+    }
+
+    #[test]
+    fn test_hour_bucket_before_epoch() {
+        let batch = create_test_batch(&["svc-a"], &[-1]);
+        let grouped = group_batch_by(batch, &[PartitionKey::Day, PartitionKey::Hour]);
+        assert_eq!(grouped.batches[0].path, "date=1969-12-31/hour=23");
+    }
+
+    #[test]
+    fn test_try_group_batch_by_missing_column() {
+        let batch = create_test_batch(&["svc-a"], &[100]);
+        let err = try_group_batch_by(batch, &[PartitionKey::column("region")]).unwrap_err();
+        assert!(err.to_string().contains("region"), "{err}");
+    }
+
+    #[test]
+    #[should_panic(expected = "timestamp")]
+    fn test_group_batch_by_time_without_timestamp_panics() {
+        let schema = StdArc::new(Schema::new(vec![Field::new(
+            "service_name",
+            DataType::Utf8,
+            false,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![StdArc::new(arrow::array::StringArray::from(vec!["a"]))],
+        )
+        .unwrap();
+        group_batch_by(batch, &[PartitionKey::Hour]);
+    }
+
+    fn service_batch(service: ArrayRef) -> RecordBatch {
+        RecordBatch::try_from_iter([("service_name", service)]).unwrap()
+    }
+
+    #[test]
+    fn test_try_group_batch_by_service_string_encodings() {
+        use arrow::array::{DictionaryArray, LargeStringArray, StringViewArray};
+        use arrow::datatypes::Int32Type;
+
+        let services = [Some("svc-a"), None, Some("svc-b"), Some("svc-a")];
+        let columns: Vec<ArrayRef> = vec![
+            StdArc::new(LargeStringArray::from(services.to_vec())),
+            StdArc::new(StringViewArray::from(services.to_vec())),
+            StdArc::new(services.into_iter().collect::<DictionaryArray<Int32Type>>()),
+        ];
+
+        for column in columns {
+            let data_type = column.data_type().clone();
+            let batch = service_batch(column);
+            assert_eq!(extract_service_name(&batch).as_ref(), "svc-a");
+
+            let grouped = try_group_batch_by_service(batch).unwrap();
+            let services: Vec<(&str, usize)> = grouped
+                .batches
+                .iter()
+                .map(|pb| (pb.service_name.as_ref(), pb.record_count))
+                .collect();
+            assert_eq!(
+                services,
+                [("svc-a", 2), ("unknown", 1), ("svc-b", 1)],
+                "{data_type}"
+            );
+            // The batches keep the original column type
+            assert_eq!(grouped.batches[0].batch.column(0).data_type(), &data_type);
+        }
+    }
+
+    #[test]
+    fn test_try_group_batch_by_service_errors() {
+        let batch = service_batch(StdArc::new(Int32Array::from(vec![1, 2])));
+        let err = try_group_batch_by_service(batch.clone()).unwrap_err();
+        assert!(matches!(err, Error::SchemaMismatch(_)), "{err}");
+        assert!(err.to_string().contains("Int32"), "{err}");
+        assert_eq!(extract_service_name(&batch).as_ref(), "unknown");
+
+        let batch = create_test_batch(&["svc-a"], &[100]);
+        let batch = batch.project(&[0, 2]).unwrap();
+        let err = try_group_batch_by_service(batch).unwrap_err();
+        assert!(err.to_string().contains("service_name"), "{err}");
+    }
+
+    #[test]
+    #[should_panic(expected = "service_name")]
+    fn test_group_batch_by_service_missing_column_panics() {
+        let batch = create_test_batch(&["svc-a"], &[100]);
+        group_batch_by_service(batch.project(&[0]).unwrap());
+    }
+
     #[test]
     fn test_extract_min_timestamp_micros() {
         let batch = create_test_batch(&["svc-a", "svc-a"], &[100, 50]);
@@ -297,6 +778,39 @@ mod tests {
         assert_eq!(min_ts, 50_000); // 50ms * 1000 = 50000 micros
     }
 
+    #[test]
+    fn test_extract_min_timestamp_micros_honors_unit() {
+        let batch = create_test_batch(&["svc-a", "svc-a"], &[1_700_000_000_123, 1_700_000_000_456]);
+
+        for unit in [
+            crate::TimestampUnit::Millisecond,
+            crate::TimestampUnit::Microsecond,
+            crate::TimestampUnit::Nanosecond,
+        ] {
+            let cast = crate::cast_timestamps(&batch, unit).unwrap();
+            assert_eq!(
+                extract_min_timestamp_micros(&cast),
+                1_700_000_000_123_000,
+                "{unit:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_min_timestamp_micros_int64() {
+        let schema = StdArc::new(Schema::new(vec![Field::new(
+            "timestamp",
+            DataType::Int64,
+            true,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![StdArc::new(Int64Array::from(vec![None, Some(42), Some(7)]))],
+        )
+        .unwrap();
+        assert_eq!(extract_min_timestamp_micros(&batch), 7);
+    }
+
     #[test]
     fn test_extract_service_name() {
         let batch = create_test_batch(&["my-service", "other"], &[100, 200]);
@@ -3,7 +3,9 @@
 //! Provides public access to the Arrow schemas generated from VRL @schema annotations.
 //! These schemas are generated at build time by build.rs.
 
-use arrow::datatypes::Schema;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Fields, Schema};
 use once_cell::sync::Lazy;
 
 // Include compiled VRL schemas from build.rs
@@ -12,19 +14,19 @@ include!(concat!(env!("OUT_DIR"), "/compiled_vrl.rs"));
 /// Returns the Arrow schema for OTLP logs.
 ///
 /// Schema fields:
-/// - timestamp: TimestampMillisecond (required)
-/// - observed_timestamp: Int64 (required)
+/// - timestamp: TimestampMicrosecond (required)
+/// - observed_timestamp: TimestampMicrosecond (required)
 /// - trace_id: Utf8 (optional)
 /// - span_id: Utf8 (optional)
-/// - service_name: Utf8 (required)
-/// - service_namespace: Utf8 (optional)
+/// - service_name: Dictionary<Int32, Utf8> (required)
+/// - service_namespace: Dictionary<Int32, Utf8> (optional)
 /// - service_instance_id: Utf8 (optional)
 /// - severity_number: Int32 (required)
-/// - severity_text: Utf8 (required)
+/// - severity_text: Dictionary<Int32, Utf8> (required)
 /// - body: Utf8 (optional)
 /// - resource_attributes: Utf8/JSON (optional)
-/// - scope_name: Utf8 (optional)
-/// - scope_version: Utf8 (optional)
+/// - scope_name: Dictionary<Int32, Utf8> (optional)
+/// - scope_version: Dictionary<Int32, Utf8> (optional)
 /// - scope_attributes: Utf8/JSON (optional)
 /// - log_attributes: Utf8/JSON (optional)
 pub fn logs_schema() -> Schema {
@@ -35,22 +37,22 @@ pub fn logs_schema() -> Schema {
 ///
 /// Schema fields:
 /// - timestamp: TimestampMicrosecond (required) - span start time
-/// - end_timestamp: Int64 (required) - span end time in microseconds
-/// - duration: Int64 (required) - span duration in microseconds
+/// - end_timestamp: TimestampMicrosecond (required) - span end time
+/// - duration: DurationMicrosecond (required) - span duration
 /// - trace_id: Utf8 (optional)
 /// - span_id: Utf8 (optional)
 /// - parent_span_id: Utf8 (optional)
 /// - trace_state: Utf8 (optional)
-/// - service_name: Utf8 (required)
-/// - service_namespace: Utf8 (optional)
+/// - service_name: Dictionary<Int32, Utf8> (required)
+/// - service_namespace: Dictionary<Int32, Utf8> (optional)
 /// - service_instance_id: Utf8 (optional)
-/// - span_name: Utf8 (required)
+/// - span_name: Dictionary<Int32, Utf8> (required)
 /// - span_kind: Int32 (required)
 /// - status_code: Int32 (required)
 /// - status_message: Utf8 (optional)
 /// - resource_attributes: Utf8/JSON (optional)
-/// - scope_name: Utf8 (optional)
-/// - scope_version: Utf8 (optional)
+/// - scope_name: Dictionary<Int32, Utf8> (optional)
+/// - scope_version: Dictionary<Int32, Utf8> (optional)
 /// - scope_attributes: Utf8/JSON (optional)
 /// - span_attributes: Utf8/JSON (optional)
 /// - events_json: Utf8/JSON (optional)
@@ -66,18 +68,18 @@ pub fn traces_schema() -> Schema {
 /// Returns the Arrow schema for OTLP gauge metrics.
 ///
 /// Schema fields:
-/// - timestamp: TimestampMillisecond (required)
-/// - start_timestamp: Int64 (optional)
-/// - metric_name: Utf8 (required)
+/// - timestamp: TimestampMicrosecond (required)
+/// - start_timestamp: TimestampMicrosecond (optional)
+/// - metric_name: Dictionary<Int32, Utf8> (required)
 /// - metric_description: Utf8 (optional)
-/// - metric_unit: Utf8 (optional)
+/// - metric_unit: Dictionary<Int32, Utf8> (optional)
 /// - value: Float64 (required)
-/// - service_name: Utf8 (required)
-/// - service_namespace: Utf8 (optional)
+/// - service_name: Dictionary<Int32, Utf8> (required)
+/// - service_namespace: Dictionary<Int32, Utf8> (optional)
 /// - service_instance_id: Utf8 (optional)
 /// - resource_attributes: Utf8/JSON (optional)
-/// - scope_name: Utf8 (optional)
-/// - scope_version: Utf8 (optional)
+/// - scope_name: Dictionary<Int32, Utf8> (optional)
+/// - scope_version: Dictionary<Int32, Utf8> (optional)
 /// - scope_attributes: Utf8/JSON (optional)
 /// - metric_attributes: Utf8/JSON (optional)
 /// - flags: Int32 (optional)
@@ -89,18 +91,18 @@ pub fn gauge_schema() -> Schema {
 /// Returns the Arrow schema for OTLP sum metrics.
 ///
 /// Schema fields:
-/// - timestamp: TimestampMillisecond (required)
-/// - start_timestamp: Int64 (optional)
-/// - metric_name: Utf8 (required)
+/// - timestamp: TimestampMicrosecond (required)
+/// - start_timestamp: TimestampMicrosecond (optional)
+/// - metric_name: Dictionary<Int32, Utf8> (required)
 /// - metric_description: Utf8 (optional)
-/// - metric_unit: Utf8 (optional)
+/// - metric_unit: Dictionary<Int32, Utf8> (optional)
 /// - value: Float64 (required)
-/// - service_name: Utf8 (required)
-/// - service_namespace: Utf8 (optional)
+/// - service_name: Dictionary<Int32, Utf8> (required)
+/// - service_namespace: Dictionary<Int32, Utf8> (optional)
 /// - service_instance_id: Utf8 (optional)
 /// - resource_attributes: Utf8/JSON (optional)
-/// - scope_name: Utf8 (optional)
-/// - scope_version: Utf8 (optional)
+/// - scope_name: Dictionary<Int32, Utf8> (optional)
+/// - scope_version: Dictionary<Int32, Utf8> (optional)
 /// - scope_attributes: Utf8/JSON (optional)
 /// - metric_attributes: Utf8/JSON (optional)
 /// - flags: Int32 (optional)
@@ -114,23 +116,23 @@ pub fn sum_schema() -> Schema {
 /// Returns the Arrow schema for OTLP histogram metrics.
 ///
 /// Schema fields:
-/// - timestamp: TimestampMillisecond (required)
-/// - start_timestamp: Int64 (optional)
-/// - metric_name: Utf8 (required)
+/// - timestamp: TimestampMicrosecond (required)
+/// - start_timestamp: TimestampMicrosecond (optional)
+/// - metric_name: Dictionary<Int32, Utf8> (required)
 /// - metric_description: Utf8 (optional)
-/// - metric_unit: Utf8 (optional)
+/// - metric_unit: Dictionary<Int32, Utf8> (optional)
 /// - count: Int64 (required)
 /// - sum: Float64 (optional)
 /// - min: Float64 (optional)
 /// - max: Float64 (optional)
 /// - bucket_counts: Utf8/JSON (required) - JSON array of u64
 /// - explicit_bounds: Utf8/JSON (required) - JSON array of f64
-/// - service_name: Utf8 (required)
-/// - service_namespace: Utf8 (optional)
+/// - service_name: Dictionary<Int32, Utf8> (required)
+/// - service_namespace: Dictionary<Int32, Utf8> (optional)
 /// - service_instance_id: Utf8 (optional)
 /// - resource_attributes: Utf8/JSON (optional)
-/// - scope_name: Utf8 (optional)
-/// - scope_version: Utf8 (optional)
+/// - scope_name: Dictionary<Int32, Utf8> (optional)
+/// - scope_version: Dictionary<Int32, Utf8> (optional)
 /// - scope_attributes: Utf8/JSON (optional)
 /// - metric_attributes: Utf8/JSON (optional)
 /// - flags: Int32 (optional)
@@ -143,11 +145,11 @@ pub fn histogram_schema() -> Schema {
 /// Returns the Arrow schema for OTLP exponential histogram metrics.
 ///
 /// Schema fields:
-/// - timestamp: TimestampMillisecond (required)
-/// - start_timestamp: Int64 (optional)
-/// - metric_name: Utf8 (required)
+/// - timestamp: TimestampMicrosecond (required)
+/// - start_timestamp: TimestampMicrosecond (optional)
+/// - metric_name: Dictionary<Int32, Utf8> (required)
 /// - metric_description: Utf8 (optional)
-/// - metric_unit: Utf8 (optional)
+/// - metric_unit: Dictionary<Int32, Utf8> (optional)
 /// - count: Int64 (required)
 /// - sum: Float64 (optional)
 /// - min: Float64 (optional)
@@ -159,12 +161,12 @@ pub fn histogram_schema() -> Schema {
 /// - positive_bucket_counts: Utf8/JSON (optional) - JSON array of u64
 /// - negative_offset: Int32 (optional)
 /// - negative_bucket_counts: Utf8/JSON (optional) - JSON array of u64
-/// - service_name: Utf8 (required)
-/// - service_namespace: Utf8 (optional)
+/// - service_name: Dictionary<Int32, Utf8> (required)
+/// - service_namespace: Dictionary<Int32, Utf8> (optional)
 /// - service_instance_id: Utf8 (optional)
 /// - resource_attributes: Utf8/JSON (optional)
-/// - scope_name: Utf8 (optional)
-/// - scope_version: Utf8 (optional)
+/// - scope_name: Dictionary<Int32, Utf8> (optional)
+/// - scope_version: Dictionary<Int32, Utf8> (optional)
 /// - scope_attributes: Utf8/JSON (optional)
 /// - metric_attributes: Utf8/JSON (optional)
 /// - flags: Int32 (optional)
@@ -174,6 +176,289 @@ pub fn exp_histogram_schema() -> Schema {
     OTLP_EXP_HISTOGRAM_SCHEMA.clone()
 }
 
+/// Returns the Arrow schema for OTLP summary metrics.
+///
+/// Schema fields:
+/// - timestamp: TimestampMicrosecond (required)
+/// - start_timestamp: TimestampMicrosecond (optional)
+/// - metric_name: Dictionary<Int32, Utf8> (required)
+/// - metric_description: Utf8 (optional)
+/// - metric_unit: Dictionary<Int32, Utf8> (optional)
+/// - count: Int64 (required)
+/// - sum: Float64 (optional)
+/// - quantile_values: Utf8/JSON (required) - JSON array of {quantile, value}
+/// - service_name: Dictionary<Int32, Utf8> (required)
+/// - service_namespace: Dictionary<Int32, Utf8> (optional)
+/// - service_instance_id: Utf8 (optional)
+/// - resource_attributes: Utf8/JSON (optional)
+/// - scope_name: Dictionary<Int32, Utf8> (optional)
+/// - scope_version: Dictionary<Int32, Utf8> (optional)
+/// - scope_attributes: Utf8/JSON (optional)
+/// - metric_attributes: Utf8/JSON (optional)
+/// - flags: Int32 (optional)
+pub fn summary_schema() -> Schema {
+    OTLP_SUMMARY_SCHEMA.clone()
+}
+
+/// Returns the Arrow schema for OTLP profile samples.
+///
+/// Schema fields:
+/// - timestamp: TimestampMicrosecond (required)
+/// - duration: DurationMicrosecond (required) - profile duration
+/// - profile_id: Utf8 (optional)
+/// - service_name: Dictionary<Int32, Utf8> (required)
+/// - service_namespace: Dictionary<Int32, Utf8> (optional)
+/// - service_instance_id: Utf8 (optional)
+/// - sample_type: Utf8 (required)
+/// - sample_unit: Utf8 (required)
+/// - period_type: Utf8 (optional)
+/// - period_unit: Utf8 (optional)
+/// - period: Int64 (optional)
+/// - value: Int64 (required)
+/// - function_name: Utf8 (optional) - leaf frame function
+/// - frames_json: Utf8/JSON (optional) - JSON array of resolved frames
+/// - trace_id: Utf8 (optional)
+/// - span_id: Utf8 (optional)
+/// - resource_attributes: Utf8/JSON (optional)
+/// - scope_name: Dictionary<Int32, Utf8> (optional)
+/// - scope_version: Dictionary<Int32, Utf8> (optional)
+/// - scope_attributes: Utf8/JSON (optional)
+/// - profile_attributes: Utf8/JSON (optional)
+/// - sample_attributes: Utf8/JSON (optional)
+pub fn profiles_schema() -> Schema {
+    OTLP_PROFILES_SCHEMA.clone()
+}
+
+/// Output table of an OTLP signal, one per built-in schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SignalType {
+    /// Log records ([`logs_schema`])
+    Logs,
+    /// Trace spans ([`traces_schema`])
+    Traces,
+    /// Gauge data points ([`gauge_schema`])
+    Gauge,
+    /// Sum data points ([`sum_schema`])
+    Sum,
+    /// Histogram data points ([`histogram_schema`])
+    Histogram,
+    /// Exponential histogram data points ([`exp_histogram_schema`])
+    ExpHistogram,
+    /// Summary data points ([`summary_schema`])
+    Summary,
+    /// Profile samples ([`profiles_schema`])
+    Profiles,
+}
+
+impl SignalType {
+    /// Every signal, in schema order.
+    pub const ALL: [SignalType; 8] = [
+        SignalType::Logs,
+        SignalType::Traces,
+        SignalType::Gauge,
+        SignalType::Sum,
+        SignalType::Histogram,
+        SignalType::ExpHistogram,
+        SignalType::Summary,
+        SignalType::Profiles,
+    ];
+
+    /// Lowercase name, e.g. `"logs"` or `"exp_histogram"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            SignalType::Logs => "logs",
+            SignalType::Traces => "traces",
+            SignalType::Gauge => "gauge",
+            SignalType::Sum => "sum",
+            SignalType::Histogram => "histogram",
+            SignalType::ExpHistogram => "exp_histogram",
+            SignalType::Summary => "summary",
+            SignalType::Profiles => "profiles",
+        }
+    }
+
+    /// Built-in Arrow schema of the signal.
+    pub fn schema(self) -> Schema {
+        match self {
+            SignalType::Logs => logs_schema(),
+            SignalType::Traces => traces_schema(),
+            SignalType::Gauge => gauge_schema(),
+            SignalType::Sum => sum_schema(),
+            SignalType::Histogram => histogram_schema(),
+            SignalType::ExpHistogram => exp_histogram_schema(),
+            SignalType::Summary => summary_schema(),
+            SignalType::Profiles => profiles_schema(),
+        }
+    }
+
+    /// Guess the signal of a batch from its column names.
+    ///
+    /// Picks the signal with the largest built-in schema whose columns all
+    /// appear in `schema`, so batches with promoted attributes, native
+    /// nested columns or nanosecond timestamps are still recognized.
+    pub fn detect(schema: &Schema) -> Option<Self> {
+        SignalType::ALL
+            .into_iter()
+            .map(|signal| (signal, signal.schema()))
+            .filter(|(_, builtin)| {
+                builtin
+                    .fields()
+                    .iter()
+                    .all(|field| schema.column_with_name(field.name()).is_some())
+            })
+            .max_by_key(|(_, builtin)| builtin.fields().len())
+            .map(|(signal, _)| signal)
+    }
+}
+
+// ============================================================================
+// Native nested types
+// ============================================================================
+
+/// Arrow type of `dict_string` columns such as `service_name`:
+/// `Dictionary<Int32, Utf8>`.
+pub fn dict_string_type() -> DataType {
+    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+}
+
+/// Arrow type for attribute blobs in native nested mode: `Map<Utf8, Utf8>`.
+///
+/// String values are stored as-is; numbers and booleans are stringified and
+/// arrays/kvlists are stored as JSON.
+pub fn attributes_map_type() -> DataType {
+    let entries = Fields::from(vec![
+        Field::new("keys", DataType::Utf8, false),
+        Field::new("values", DataType::Utf8, true),
+    ]);
+    DataType::Map(
+        Arc::new(Field::new("entries", DataType::Struct(entries), false)),
+        false,
+    )
+}
+
+/// Arrow type for span events in native nested mode.
+///
+/// `List<Struct<time_unix_nano: Int64, name: Utf8, attributes: Map>>`
+pub fn span_events_type() -> DataType {
+    list_of(vec![
+        Field::new("time_unix_nano", DataType::Int64, true),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("attributes", attributes_map_type(), true),
+    ])
+}
+
+/// Arrow type for span links in native nested mode.
+///
+/// `List<Struct<trace_id: Utf8, span_id: Utf8, trace_state: Utf8, attributes: Map>>`
+pub fn span_links_type() -> DataType {
+    list_of(vec![
+        Field::new("trace_id", DataType::Utf8, true),
+        Field::new("span_id", DataType::Utf8, true),
+        Field::new("trace_state", DataType::Utf8, true),
+        Field::new("attributes", attributes_map_type(), true),
+    ])
+}
+
+/// Arrow type for metric exemplars in native nested mode.
+///
+/// `List<Struct<time_unix_nano: Int64, value: Float64, trace_id: Utf8,
+/// span_id: Utf8, filtered_attributes: Map>>`
+pub fn exemplars_type() -> DataType {
+    list_of(vec![
+        Field::new("time_unix_nano", DataType::Int64, true),
+        Field::new("value", DataType::Float64, true),
+        Field::new("trace_id", DataType::Utf8, true),
+        Field::new("span_id", DataType::Utf8, true),
+        Field::new("filtered_attributes", attributes_map_type(), true),
+    ])
+}
+
+/// Arrow type for histogram bucket counts in native nested mode: `List<UInt64>`.
+pub fn bucket_counts_type() -> DataType {
+    DataType::List(Arc::new(Field::new("item", DataType::UInt64, true)))
+}
+
+/// Arrow type for histogram bucket boundaries in native nested mode:
+/// `List<Float64>`.
+pub fn explicit_bounds_type() -> DataType {
+    DataType::List(Arc::new(Field::new("item", DataType::Float64, true)))
+}
+
+/// Arrow type for summary quantiles in native nested mode.
+///
+/// `List<Struct<quantile: Float64, value: Float64>>`
+pub fn quantile_values_type() -> DataType {
+    list_of(vec![
+        Field::new("quantile", DataType::Float64, true),
+        Field::new("value", DataType::Float64, true),
+    ])
+}
+
+fn list_of(fields: Vec<Field>) -> DataType {
+    DataType::List(Arc::new(Field::new(
+        "item",
+        DataType::Struct(Fields::from(fields)),
+        true,
+    )))
+}
+
+/// Converts a signal schema to native nested mode.
+///
+/// JSON string columns are replaced with native Arrow types so Parquet
+/// readers can push predicates into nested fields:
+/// - `*_attributes` columns become [`attributes_map_type`]
+/// - `events_json` becomes [`span_events_type`]
+/// - `links_json` becomes [`span_links_type`]
+/// - `exemplars_json` becomes [`exemplars_type`]
+/// - `bucket_counts`, `positive_bucket_counts` and `negative_bucket_counts`
+///   become [`bucket_counts_type`]
+/// - `explicit_bounds` becomes [`explicit_bounds_type`]
+/// - `quantile_values` becomes [`quantile_values_type`]
+///
+/// Column names are unchanged, so the output of the built-in VRL programs
+/// (JSON strings) can be passed to `values_to_arrow` with the converted
+/// schema directly. Other columns are left as they are.
+///
+/// # Example
+///
+/// ```ignore
+/// let schema = native_nested_schema(&traces_schema());
+/// let batch = values_to_arrow(&apply_trace_transform(decoded)?, &schema)?;
+/// ```
+pub fn native_nested_schema(schema: &Schema) -> Schema {
+    let fields: Vec<Field> = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let native = match (field.name().as_str(), field.data_type()) {
+                (_, data_type) if data_type != &DataType::Utf8 => None,
+                (
+                    "resource_attributes"
+                    | "scope_attributes"
+                    | "log_attributes"
+                    | "span_attributes"
+                    | "metric_attributes",
+                    _,
+                ) => Some(attributes_map_type()),
+                ("events_json", _) => Some(span_events_type()),
+                ("links_json", _) => Some(span_links_type()),
+                ("exemplars_json", _) => Some(exemplars_type()),
+                ("bucket_counts" | "positive_bucket_counts" | "negative_bucket_counts", _) => {
+                    Some(bucket_counts_type())
+                }
+                ("explicit_bounds", _) => Some(explicit_bounds_type()),
+                ("quantile_values", _) => Some(quantile_values_type()),
+                _ => None,
+            };
+            match native {
+                Some(data_type) => field.as_ref().clone().with_data_type(data_type),
+                None => field.as_ref().clone(),
+            }
+        })
+        .collect();
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,7 +478,7 @@ mod tests {
 
         // Check service_name field
         let service_field = schema.field_with_name("service_name").unwrap();
-        assert_eq!(service_field.data_type(), &DataType::Utf8);
+        assert_eq!(service_field.data_type(), &dict_string_type());
         assert!(!service_field.is_nullable());
 
         // Check trace_id is optional
@@ -220,9 +505,15 @@ mod tests {
         let kind_field = schema.field_with_name("span_kind").unwrap();
         assert_eq!(kind_field.data_type(), &DataType::Int32);
 
-        // Check duration field
-        let duration_field = schema.field_with_name("duration").unwrap();
-        assert_eq!(duration_field.data_type(), &DataType::Int64);
+        // The end time and duration share the timestamp precision
+        assert_eq!(
+            schema.field_with_name("end_timestamp").unwrap().data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        assert_eq!(
+            schema.field_with_name("duration").unwrap().data_type(),
+            &DataType::Duration(TimeUnit::Microsecond)
+        );
     }
 
     #[test]
@@ -236,7 +527,7 @@ mod tests {
 
         // Check metric_name field
         let name_field = schema.field_with_name("metric_name").unwrap();
-        assert_eq!(name_field.data_type(), &DataType::Utf8);
+        assert_eq!(name_field.data_type(), &dict_string_type());
     }
 
     #[test]
@@ -254,6 +545,23 @@ mod tests {
         assert!(!mono_field.is_nullable());
     }
 
+    #[test]
+    fn test_summary_schema_has_expected_fields() {
+        let schema = summary_schema();
+
+        let count_field = schema.field_with_name("count").unwrap();
+        assert_eq!(count_field.data_type(), &DataType::Int64);
+        assert!(!count_field.is_nullable());
+
+        let sum_field = schema.field_with_name("sum").unwrap();
+        assert_eq!(sum_field.data_type(), &DataType::Float64);
+        assert!(sum_field.is_nullable());
+
+        let quantiles_field = schema.field_with_name("quantile_values").unwrap();
+        assert_eq!(quantiles_field.data_type(), &DataType::Utf8);
+        assert!(!quantiles_field.is_nullable());
+    }
+
     #[test]
     fn test_schemas_are_cloneable() {
         // Multiple calls should return independent clones
@@ -262,4 +570,106 @@ mod tests {
 
         assert_eq!(schema1.fields().len(), schema2.fields().len());
     }
+
+    #[test]
+    fn test_native_nested_schema_traces() {
+        let schema = native_nested_schema(&traces_schema());
+        assert_eq!(schema.fields().len(), traces_schema().fields().len());
+        assert_eq!(
+            schema
+                .field_with_name("span_attributes")
+                .unwrap()
+                .data_type(),
+            &attributes_map_type()
+        );
+        assert_eq!(
+            schema.field_with_name("events_json").unwrap().data_type(),
+            &span_events_type()
+        );
+        assert_eq!(
+            schema.field_with_name("links_json").unwrap().data_type(),
+            &span_links_type()
+        );
+        assert_eq!(
+            schema.field_with_name("span_name").unwrap().data_type(),
+            &dict_string_type()
+        );
+    }
+
+    #[test]
+    fn test_native_nested_schema_metrics() {
+        for schema in [gauge_schema(), sum_schema(), histogram_schema()] {
+            let schema = native_nested_schema(&schema);
+            assert_eq!(
+                schema
+                    .field_with_name("metric_attributes")
+                    .unwrap()
+                    .data_type(),
+                &attributes_map_type()
+            );
+            assert_eq!(
+                schema
+                    .field_with_name("exemplars_json")
+                    .unwrap()
+                    .data_type(),
+                &exemplars_type()
+            );
+        }
+        let histogram = native_nested_schema(&histogram_schema());
+        assert_eq!(
+            histogram
+                .field_with_name("bucket_counts")
+                .unwrap()
+                .data_type(),
+            &bucket_counts_type()
+        );
+        assert_eq!(
+            histogram
+                .field_with_name("explicit_bounds")
+                .unwrap()
+                .data_type(),
+            &explicit_bounds_type()
+        );
+        let exp_histogram = native_nested_schema(&exp_histogram_schema());
+        for name in ["positive_bucket_counts", "negative_bucket_counts"] {
+            assert_eq!(
+                exp_histogram.field_with_name(name).unwrap().data_type(),
+                &bucket_counts_type()
+            );
+        }
+        assert_eq!(
+            native_nested_schema(&summary_schema())
+                .field_with_name("quantile_values")
+                .unwrap()
+                .data_type(),
+            &quantile_values_type()
+        );
+
+        // Idempotent
+        let once = native_nested_schema(&logs_schema());
+        assert_eq!(native_nested_schema(&once), once);
+    }
+
+    #[test]
+    fn test_signal_type_detect() {
+        for signal in SignalType::ALL {
+            let schema = signal.schema();
+            assert_eq!(
+                SignalType::detect(&schema),
+                Some(signal),
+                "{}",
+                signal.name()
+            );
+            let extended = crate::arrow::nanosecond_schema(&native_nested_schema(&schema));
+            assert_eq!(
+                SignalType::detect(&extended),
+                Some(signal),
+                "{}",
+                signal.name()
+            );
+        }
+
+        let partial = Schema::new(vec![Field::new("timestamp", DataType::Int64, false)]);
+        assert_eq!(SignalType::detect(&partial), None);
+    }
 }
@@ -0,0 +1,196 @@
+//! Output timestamp precision
+
+use std::sync::Arc;
+
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+
+/// Precision of the `Timestamp` columns in output batches.
+///
+/// Every timestamp-like column of the built-in schemas is in microseconds:
+/// `timestamp`, `observed_timestamp`, `end_timestamp` and `start_timestamp`
+/// are `Timestamp` columns and `duration` is a `Duration` column, so one unit
+/// covers them all.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TimestampUnit {
+    /// Milliseconds since the Unix epoch
+    Millisecond,
+    /// Microseconds since the Unix epoch
+    #[default]
+    Microsecond,
+    /// Nanoseconds since the Unix epoch
+    Nanosecond,
+}
+
+impl TimestampUnit {
+    /// The matching Arrow time unit.
+    pub fn time_unit(self) -> TimeUnit {
+        match self {
+            TimestampUnit::Millisecond => TimeUnit::Millisecond,
+            TimestampUnit::Microsecond => TimeUnit::Microsecond,
+            TimestampUnit::Nanosecond => TimeUnit::Nanosecond,
+        }
+    }
+}
+
+/// Cast every `Timestamp` and `Duration` column of `batch` to `unit`.
+///
+/// Other columns, field names, nullability and metadata are kept. Casting to
+/// a coarser unit truncates, and casting to a finer one never adds precision:
+/// the extra digits are zero.
+pub fn cast_timestamps(
+    batch: &RecordBatch,
+    unit: TimestampUnit,
+) -> Result<RecordBatch, ArrowError> {
+    let target = unit.time_unit();
+    let schema = batch.schema();
+    if !schema
+        .fields()
+        .iter()
+        .any(|field| with_unit(field.data_type(), target).is_some())
+    {
+        return Ok(batch.clone());
+    }
+
+    let mut fields = Vec::with_capacity(schema.fields().len());
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        match with_unit(field.data_type(), target) {
+            Some(data_type) => {
+                columns.push(cast(column, &data_type)?);
+                fields.push(field.as_ref().clone().with_data_type(data_type));
+            }
+            None => {
+                columns.push(column.clone());
+                fields.push(field.as_ref().clone());
+            }
+        }
+    }
+
+    let schema = Schema::new_with_metadata(fields, schema.metadata().clone());
+    RecordBatch::try_new(Arc::new(schema), columns)
+}
+
+/// The `Timestamp` or `Duration` type of `data_type` in `unit`, if it is one
+/// of those and has another unit.
+fn with_unit(data_type: &DataType, unit: TimeUnit) -> Option<DataType> {
+    match data_type {
+        DataType::Timestamp(current, tz) if *current != unit => {
+            Some(DataType::Timestamp(unit, tz.clone()))
+        }
+        DataType::Duration(current) if *current != unit => Some(DataType::Duration(unit)),
+        _ => None,
+    }
+}
+
+/// Column holding the original OTLP time of each record in nanosecond mode:
+/// `time_unix_nano`, or `start_time_unix_nano` for spans.
+pub const TIME_UNIX_NANO: &str = "time_unix_nano";
+
+/// The schema of `schema` in full-nanosecond mode.
+///
+/// Every `Timestamp` and `Duration` column switches to nanoseconds and a required
+/// Int64 [`TIME_UNIX_NANO`] column is appended, unless the schema already
+/// has one.
+pub fn nanosecond_schema(schema: &Schema) -> Schema {
+    let mut fields: Vec<Field> = schema
+        .fields()
+        .iter()
+        .map(
+            |field| match with_unit(field.data_type(), TimeUnit::Nanosecond) {
+                Some(data_type) => field.as_ref().clone().with_data_type(data_type),
+                None => field.as_ref().clone(),
+            },
+        )
+        .collect();
+    if schema.field_with_name(TIME_UNIX_NANO).is_err() {
+        fields.push(Field::new(TIME_UNIX_NANO, DataType::Int64, false));
+    }
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{
+        Array, DurationMicrosecondArray, DurationMillisecondArray, Int64Array,
+        TimestampMicrosecondArray, TimestampMillisecondArray,
+    };
+
+    fn micros_batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                false,
+            ),
+            Field::new("duration", DataType::Duration(TimeUnit::Microsecond), false),
+            Field::new("count", DataType::Int64, false),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(TimestampMicrosecondArray::from(vec![1_700_000_000_123_456])),
+                Arc::new(DurationMicrosecondArray::from(vec![2_500_999])),
+                Arc::new(Int64Array::from(vec![42])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cast_timestamps_to_millis() {
+        let batch = cast_timestamps(&micros_batch(), TimestampUnit::Millisecond).unwrap();
+
+        let field = batch.schema().field(0).clone();
+        assert_eq!(
+            field.data_type(),
+            &DataType::Timestamp(TimeUnit::Millisecond, None)
+        );
+        assert!(!field.is_nullable());
+        let col = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(col.value(0), 1_700_000_000_123);
+        let duration = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<DurationMillisecondArray>()
+            .unwrap();
+        assert_eq!(duration.value(0), 2_500);
+        // Non-timestamp columns are untouched
+        assert_eq!(batch.schema().field(2).data_type(), &DataType::Int64);
+    }
+
+    #[test]
+    fn test_nanosecond_schema() {
+        let schema = nanosecond_schema(&micros_batch().schema());
+
+        assert_eq!(
+            schema.field(0).data_type(),
+            &DataType::Timestamp(TimeUnit::Nanosecond, None)
+        );
+        assert_eq!(
+            schema.field(1).data_type(),
+            &DataType::Duration(TimeUnit::Nanosecond)
+        );
+        assert_eq!(schema.field(2).data_type(), &DataType::Int64);
+        let time = schema.field_with_name(TIME_UNIX_NANO).unwrap();
+        assert_eq!(time.data_type(), &DataType::Int64);
+        assert!(!time.is_nullable());
+        // Applying it twice doesn't add a second column
+        assert_eq!(nanosecond_schema(&schema), schema);
+    }
+
+    #[test]
+    fn test_cast_timestamps_same_unit_is_noop() {
+        let batch = micros_batch();
+        let cast = cast_timestamps(&batch, TimestampUnit::Microsecond).unwrap();
+        assert_eq!(cast, batch);
+        assert_eq!(cast.column(0).len(), 1);
+    }
+}
@@ -11,6 +11,8 @@ use serde::Deserialize;
 use std::sync::Arc;
 use vrl::value::{KeyString, ObjectMap, Value as VrlValue};
 
+use crate::error::{ErrorStage, RecordError, RecordLocation};
+
 // ============================================================================
 // Error types
 // ============================================================================
@@ -26,6 +28,10 @@ pub enum DecodeError {
     Parse(String),
     /// Unsupported or invalid payload
     Unsupported(String),
+    /// Decompression failed or exceeded the size limit
+    Decompression(String),
+    /// A timestamp does not fit in i64 nanoseconds (past the year 2262)
+    TimestampOverflow(String),
 }
 
 impl std::fmt::Display for DecodeError {
@@ -35,6 +41,8 @@ impl std::fmt::Display for DecodeError {
             DecodeError::Protobuf(e) => write!(f, "protobuf decode error: {e}"),
             DecodeError::Parse(msg) => write!(f, "parse error: {msg}"),
             DecodeError::Unsupported(msg) => write!(f, "unsupported payload: {msg}"),
+            DecodeError::Decompression(msg) => write!(f, "decompression error: {msg}"),
+            DecodeError::TimestampOverflow(msg) => write!(f, "timestamp overflow: {msg}"),
         }
     }
 }
@@ -46,6 +54,8 @@ impl std::error::Error for DecodeError {
             DecodeError::Protobuf(e) => Some(e),
             DecodeError::Parse(_) => None,
             DecodeError::Unsupported(_) => None,
+            DecodeError::Decompression(_) => None,
+            DecodeError::TimestampOverflow(_) => None,
         }
     }
 }
@@ -161,43 +171,172 @@ fn kvlist_to_object(kvlist: &KeyValueList) -> VrlValue {
 
 /// Safely convert u64 timestamp to i64, returning error on overflow
 pub fn safe_timestamp_conversion(timestamp: u64, field_name: &str) -> Result<i64, DecodeError> {
-    i64::try_from(timestamp).map_err(|_| {
-        DecodeError::Unsupported(format!(
-            "timestamp overflow: {field_name} value {timestamp} exceeds i64::MAX (year 2262)"
-        ))
-    })
+    i64::try_from(timestamp).map_err(|_| timestamp_overflow(field_name, timestamp))
+}
+
+fn timestamp_overflow(field: &str, value: impl std::fmt::Display) -> DecodeError {
+    DecodeError::TimestampOverflow(format!(
+        "{field} value {value} exceeds i64::MAX (year 2262)"
+    ))
 }
 
 /// Traverse OTLP resources and scopes, reusing resource/scope VRL values via Arc.
+///
+/// The callback also gets the scope's position in the request. Returns the
+/// number of resources visited.
 pub fn for_each_resource_scope<R, S, T, I, J, RF, SF, CF, E>(
     resources: I,
     mut split: RF,
     mut split_scope: SF,
     mut callback: CF,
-) -> Result<(), E>
+) -> Result<usize, E>
 where
     I: IntoIterator<Item = R>,
     J: IntoIterator<Item = S>,
     RF: FnMut(R) -> (VrlValue, J),
     SF: FnMut(S) -> (VrlValue, T),
-    CF: FnMut(T, Arc<VrlValue>, Arc<VrlValue>) -> Result<(), E>,
+    CF: FnMut(T, Arc<VrlValue>, Arc<VrlValue>, RecordLocation) -> Result<(), E>,
 {
-    for resource in resources {
+    let mut count = 0;
+    for (resource_index, resource) in resources.into_iter().enumerate() {
         let (resource_value, scopes) = split(resource);
         let resource_value = Arc::new(resource_value);
 
-        for scope in scopes {
+        for (scope_index, scope) in scopes.into_iter().enumerate() {
             let (scope_value, payload) = split_scope(scope);
             let scope_value = Arc::new(scope_value);
             callback(
                 payload,
                 Arc::clone(&resource_value),
                 Arc::clone(&scope_value),
+                RecordLocation {
+                    resource_index,
+                    scope_index,
+                },
             )?;
         }
+        count += 1;
     }
 
-    Ok(())
+    Ok(count)
+}
+
+// ============================================================================
+// Decoded records
+// ============================================================================
+
+/// Where a decoded record came from: its index in the payload (counting
+/// records dropped in lenient mode) and its resource/scope.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RecordOrigin {
+    pub index: usize,
+    pub location: RecordLocation,
+}
+
+/// Records decoded from a payload, with the origin of each value.
+///
+/// In strict mode the first record that fails to decode aborts the payload;
+/// in lenient mode it is dropped and reported in `errors`.
+///
+/// The values are VRL records, or the decoder's record parts for the native
+/// fast path.
+#[derive(Debug)]
+pub(crate) struct Decoded<T = VrlValue> {
+    pub values: Vec<T>,
+    pub origins: Vec<RecordOrigin>,
+    pub errors: Vec<RecordError>,
+    /// Records dropped because a timestamp overflowed
+    pub timestamp_overflows: usize,
+    lenient: bool,
+    records: usize,
+    resources: usize,
+}
+
+impl<T> Default for Decoded<T> {
+    fn default() -> Self {
+        Self {
+            values: Vec::new(),
+            origins: Vec::new(),
+            errors: Vec::new(),
+            timestamp_overflows: 0,
+            lenient: false,
+            records: 0,
+            resources: 0,
+        }
+    }
+}
+
+impl<T> Decoded<T> {
+    pub fn new(lenient: bool, capacity: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(capacity),
+            origins: Vec::with_capacity(capacity),
+            lenient,
+            ..Self::default()
+        }
+    }
+
+    /// Add the next record of the payload, or its decode error.
+    pub fn push(
+        &mut self,
+        location: RecordLocation,
+        record: Result<T, DecodeError>,
+    ) -> Result<(), DecodeError> {
+        let index = self.records;
+        self.records += 1;
+
+        match record {
+            Ok(value) => {
+                self.values.push(value);
+                self.origins.push(RecordOrigin { index, location });
+            }
+            Err(e) if self.lenient => {
+                if matches!(e, DecodeError::TimestampOverflow(_)) {
+                    self.timestamp_overflows += 1;
+                }
+                self.errors.push(RecordError {
+                    index,
+                    location,
+                    stage: ErrorStage::Decode,
+                    message: e.to_string(),
+                });
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    /// Number of records in the payload, including dropped ones.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Record how many resources the decoded message had, so a following
+    /// message's locations continue the count when appended with `extend`.
+    pub fn set_resources(&mut self, resources: usize) {
+        self.resources = resources;
+    }
+
+    /// Append the records of a later message in the same payload.
+    pub fn extend(&mut self, other: Decoded<T>) {
+        let (records, resources) = (self.records, self.resources);
+        self.values.extend(other.values);
+        self.origins
+            .extend(other.origins.into_iter().map(|mut origin| {
+                origin.index += records;
+                origin.location.resource_index += resources;
+                origin
+            }));
+        self.errors
+            .extend(other.errors.into_iter().map(|mut error| {
+                error.index += records;
+                error.location.resource_index += resources;
+                error
+            }));
+        self.timestamp_overflows += other.timestamp_overflows;
+        self.records += other.records;
+        self.resources += other.resources;
+    }
 }
 
 // ============================================================================
@@ -281,9 +420,7 @@ pub fn json_timestamp_to_i64(value: &JsonNumberOrString, field: &str) -> Result<
                 )));
             }
             if parsed > i64::MAX as i128 {
-                return Err(DecodeError::Unsupported(format!(
-                    "timestamp overflow: {field} value {s} exceeds i64::MAX (year 2262)"
-                )));
+                return Err(timestamp_overflow(field, s));
             }
             Ok(parsed as i64)
         }
@@ -296,11 +433,7 @@ pub fn json_timestamp_to_i64(value: &JsonNumberOrString, field: &str) -> Result<
                 }
                 Ok(i)
             } else if let Some(u) = n.as_u64() {
-                i64::try_from(u).map_err(|_| {
-                    DecodeError::Unsupported(format!(
-                        "timestamp overflow: {field} value {u} exceeds i64::MAX (year 2262)"
-                    ))
-                })
+                i64::try_from(u).map_err(|_| timestamp_overflow(field, u))
             } else {
                 Err(DecodeError::Unsupported(format!(
                     "invalid timestamp: {field} value {n} is not an integer"
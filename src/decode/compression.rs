@@ -0,0 +1,252 @@
+//! Request body decompression
+//!
+//! OTLP/HTTP exporters commonly send `Content-Encoding: gzip` (and sometimes
+//! `zstd`). With the `compression` feature enabled, [`decompress`] inflates
+//! the body before it is handed to `decode_logs`, `decode_traces` or
+//! `decode_metrics`. Both decoders are pure Rust and build for wasm32.
+//!
+//! Output is capped at `max_size` bytes to guard against decompression bombs.
+
+use std::borrow::Cow;
+
+use super::common::DecodeError;
+
+/// Default cap on decompressed body size (64 MiB).
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// Content encoding of an OTLP request body.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ContentEncoding {
+    /// Uncompressed body
+    #[default]
+    Identity,
+    /// gzip (RFC 1952)
+    Gzip,
+    /// Zstandard (RFC 8878)
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// Infer content encoding from a Content-Encoding header.
+    ///
+    /// A missing or empty header is `Identity`. Returns `None` for encodings
+    /// this crate cannot decode (e.g. `br`).
+    pub fn from_content_encoding(content_encoding: Option<&str>) -> Option<Self> {
+        let content_encoding = content_encoding.map(|v| v.trim().to_ascii_lowercase());
+
+        match content_encoding.as_deref() {
+            None | Some("") | Some("identity") => Some(ContentEncoding::Identity),
+            Some("gzip") | Some("x-gzip") => Some(ContentEncoding::Gzip),
+            Some("zstd") => Some(ContentEncoding::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Returns the canonical Content-Encoding string for this encoding.
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            ContentEncoding::Identity => "identity",
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+}
+
+/// Decompress a request body.
+///
+/// # Arguments
+///
+/// * `bytes` - Raw request body
+/// * `encoding` - Content encoding of the body
+/// * `max_size` - Maximum decompressed size in bytes
+///
+/// # Returns
+///
+/// The decompressed body. `Identity` input is borrowed, not copied.
+///
+/// # Errors
+///
+/// Returns `DecodeError::Decompression` if the body is corrupt or larger
+/// than `max_size` once decompressed, and `DecodeError::Unsupported` for
+/// gzip/zstd when the `compression` feature is disabled.
+///
+/// # Example
+///
+/// ```ignore
+/// use otlp2records::{decode_logs, decompress, ContentEncoding, InputFormat};
+///
+/// let encoding = ContentEncoding::from_content_encoding(Some("gzip")).unwrap();
+/// let body = decompress(bytes, encoding, DEFAULT_MAX_DECOMPRESSED_SIZE)?;
+/// let records = decode_logs(&body, InputFormat::Protobuf)?;
+/// ```
+pub fn decompress(
+    bytes: &[u8],
+    encoding: ContentEncoding,
+    max_size: usize,
+) -> Result<Cow<'_, [u8]>, DecodeError> {
+    match encoding {
+        ContentEncoding::Identity => {
+            if bytes.len() > max_size {
+                return Err(too_large(max_size));
+            }
+            Ok(Cow::Borrowed(bytes))
+        }
+        ContentEncoding::Gzip => decompress_gzip(bytes, max_size).map(Cow::Owned),
+        ContentEncoding::Zstd => decompress_zstd(bytes, max_size).map(Cow::Owned),
+    }
+}
+
+fn too_large(max_size: usize) -> DecodeError {
+    DecodeError::Decompression(format!(
+        "decompressed body exceeds limit of {max_size} bytes"
+    ))
+}
+
+#[cfg(feature = "compression")]
+fn read_capped(reader: impl std::io::Read, max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    use std::io::Read;
+
+    let mut out = Vec::new();
+    // Read one byte past the limit to detect overflow without inflating further
+    let limit = (max_size as u64).saturating_add(1);
+    reader
+        .take(limit)
+        .read_to_end(&mut out)
+        .map_err(|e| DecodeError::Decompression(e.to_string()))?;
+    if out.len() > max_size {
+        return Err(too_large(max_size));
+    }
+    Ok(out)
+}
+
+#[cfg(feature = "compression")]
+fn decompress_gzip(bytes: &[u8], max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    read_capped(flate2::read::MultiGzDecoder::new(bytes), max_size)
+}
+
+#[cfg(feature = "compression")]
+fn decompress_zstd(bytes: &[u8], max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    let decoder = ruzstd::decoding::StreamingDecoder::new(bytes)
+        .map_err(|e| DecodeError::Decompression(e.to_string()))?;
+    read_capped(decoder, max_size)
+}
+
+#[cfg(not(feature = "compression"))]
+fn decompress_gzip(_bytes: &[u8], _max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    Err(DecodeError::Unsupported(
+        "gzip decompression requires the `compression` feature".to_string(),
+    ))
+}
+
+#[cfg(not(feature = "compression"))]
+fn decompress_zstd(_bytes: &[u8], _max_size: usize) -> Result<Vec<u8>, DecodeError> {
+    Err(DecodeError::Unsupported(
+        "zstd decompression requires the `compression` feature".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_encoding_from_header() {
+        assert_eq!(
+            ContentEncoding::from_content_encoding(None),
+            Some(ContentEncoding::Identity)
+        );
+        assert_eq!(
+            ContentEncoding::from_content_encoding(Some(" GZIP ")),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(
+            ContentEncoding::from_content_encoding(Some("x-gzip")),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(
+            ContentEncoding::from_content_encoding(Some("zstd")),
+            Some(ContentEncoding::Zstd)
+        );
+        assert_eq!(ContentEncoding::from_content_encoding(Some("br")), None);
+        assert_eq!(ContentEncoding::Gzip.content_encoding(), "gzip");
+    }
+
+    #[test]
+    fn identity_is_borrowed_and_capped() {
+        let body = b"hello";
+        assert!(matches!(
+            decompress(body, ContentEncoding::Identity, 5).unwrap(),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            decompress(body, ContentEncoding::Identity, 4),
+            Err(DecodeError::Decompression(_))
+        ));
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn gzip_requires_feature() {
+        assert!(matches!(
+            decompress(b"", ContentEncoding::Gzip, 10),
+            Err(DecodeError::Unsupported(_))
+        ));
+    }
+
+    #[cfg(feature = "compression")]
+    mod compressed {
+        use super::*;
+        use std::io::Write;
+
+        fn gzip(data: &[u8]) -> Vec<u8> {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data).unwrap();
+            encoder.finish().unwrap()
+        }
+
+        fn zstd(data: &[u8]) -> Vec<u8> {
+            ruzstd::encoding::compress_to_vec(data, ruzstd::encoding::CompressionLevel::Fastest)
+        }
+
+        #[test]
+        fn gzip_round_trip() {
+            let data = b"otlp payload ".repeat(100);
+            let body = gzip(&data);
+            let out = decompress(&body, ContentEncoding::Gzip, data.len()).unwrap();
+            assert_eq!(out.as_ref(), data.as_slice());
+        }
+
+        #[test]
+        fn zstd_round_trip() {
+            let data = b"otlp payload ".repeat(100);
+            let body = zstd(&data);
+            let out = decompress(&body, ContentEncoding::Zstd, data.len()).unwrap();
+            assert_eq!(out.as_ref(), data.as_slice());
+        }
+
+        #[test]
+        fn rejects_bombs() {
+            let data = vec![0u8; 1024 * 1024];
+            for (encoding, body) in [
+                (ContentEncoding::Gzip, gzip(&data)),
+                (ContentEncoding::Zstd, zstd(&data)),
+            ] {
+                let err = decompress(&body, encoding, 1024).unwrap_err();
+                assert!(err.to_string().contains("exceeds limit"), "{err}");
+            }
+        }
+
+        #[test]
+        fn rejects_corrupt_input() {
+            assert!(matches!(
+                decompress(b"not gzip", ContentEncoding::Gzip, 1024),
+                Err(DecodeError::Decompression(_))
+            ));
+            assert!(matches!(
+                decompress(b"not zstd", ContentEncoding::Zstd, 1024),
+                Err(DecodeError::Decompression(_))
+            ));
+        }
+    }
+}
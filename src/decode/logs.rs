@@ -3,6 +3,7 @@
 use bytes::Bytes;
 use const_hex::encode as hex_encode;
 use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::logs::v1::LogRecord;
 use prost::Message;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -11,7 +12,7 @@ use vrl::value::{ObjectMap, Value as VrlValue};
 use super::common::{
     for_each_resource_scope, json_any_value_to_vrl, json_attrs_to_value, json_resource_to_value,
     json_scope_to_value, json_timestamp_to_i64, otlp_any_value_to_vrl, otlp_attributes_to_value,
-    otlp_resource_to_value, otlp_scope_to_value, safe_timestamp_conversion, DecodeError,
+    otlp_resource_to_value, otlp_scope_to_value, safe_timestamp_conversion, DecodeError, Decoded,
     JsonAnyValue, JsonInstrumentationScope, JsonKeyValue, JsonNumberOrString, JsonResource,
 };
 
@@ -19,19 +20,31 @@ use super::common::{
 // Protobuf decoding
 // ============================================================================
 
-pub fn decode_protobuf(body: &[u8]) -> Result<Vec<VrlValue>, DecodeError> {
+pub fn decode_protobuf(body: &[u8], lenient: bool) -> Result<Decoded, DecodeError> {
     let request = ExportLogsServiceRequest::decode(body)?;
-    export_logs_to_vrl_proto(request)
+    export_logs_to_vrl_proto(request, lenient, build_log_record)
 }
 
-fn export_logs_to_vrl_proto(
+/// Decode protobuf logs into record parts for the native fast path
+pub(crate) fn decode_protobuf_parts(
+    body: &[u8],
+    lenient: bool,
+) -> Result<Decoded<LogRecordParts>, DecodeError> {
+    let request = ExportLogsServiceRequest::decode(body)?;
+    export_logs_to_vrl_proto(request, lenient, |parts| parts)
+}
+
+fn export_logs_to_vrl_proto<T>(
     request: ExportLogsServiceRequest,
-) -> Result<Vec<VrlValue>, DecodeError> {
-    let mut values = preallocate_log_values(&request.resource_logs, |rl| {
+    lenient: bool,
+    build: fn(LogRecordParts) -> T,
+) -> Result<Decoded<T>, DecodeError> {
+    let capacity = count_log_records(&request.resource_logs, |rl| {
         rl.scope_logs.iter().map(|sl| sl.log_records.len()).sum()
     });
+    let mut out = Decoded::new(lenient, capacity);
 
-    for_each_resource_scope(
+    let resources = for_each_resource_scope(
         request.resource_logs,
         |resource_logs| {
             (
@@ -45,62 +58,82 @@ fn export_logs_to_vrl_proto(
                 scope_logs.log_records,
             )
         },
-        |log_records, resource, scope| {
+        |log_records, resource, scope, location| {
             for log_record in log_records {
-                let body = log_record
-                    .body
-                    .as_ref()
-                    .map(otlp_any_value_to_vrl)
-                    .unwrap_or(VrlValue::Null);
-
-                let parts = LogRecordParts {
-                    time_unix_nano: safe_timestamp_conversion(
-                        log_record.time_unix_nano,
-                        "log.time_unix_nano",
-                    )?,
-                    observed_time_unix_nano: safe_timestamp_conversion(
-                        log_record.observed_time_unix_nano,
-                        "log.observed_time_unix_nano",
-                    )?,
-                    severity_number: log_record.severity_number as i64,
-                    severity_text: Bytes::from(log_record.severity_text),
-                    body,
-                    trace_id: Bytes::from(hex_encode(&log_record.trace_id)),
-                    span_id: Bytes::from(hex_encode(&log_record.span_id)),
-                    attributes: otlp_attributes_to_value(&log_record.attributes),
-                    resource: Arc::clone(&resource),
-                    scope: Arc::clone(&scope),
-                };
-
-                values.push(build_log_record(parts));
+                out.push(
+                    location,
+                    proto_log_record(log_record, &resource, &scope).map(build),
+                )?;
             }
 
             Ok::<(), DecodeError>(())
         },
     )?;
 
-    Ok(values)
+    out.set_resources(resources);
+    Ok(out)
+}
+
+fn proto_log_record(
+    log_record: LogRecord,
+    resource: &Arc<VrlValue>,
+    scope: &Arc<VrlValue>,
+) -> Result<LogRecordParts, DecodeError> {
+    let body = log_record
+        .body
+        .as_ref()
+        .map(otlp_any_value_to_vrl)
+        .unwrap_or(VrlValue::Null);
+
+    Ok(LogRecordParts {
+        time_unix_nano: safe_timestamp_conversion(log_record.time_unix_nano, "log.time_unix_nano")?,
+        observed_time_unix_nano: safe_timestamp_conversion(
+            log_record.observed_time_unix_nano,
+            "log.observed_time_unix_nano",
+        )?,
+        severity_number: log_record.severity_number as i64,
+        severity_text: Bytes::from(log_record.severity_text),
+        body,
+        trace_id: Bytes::from(hex_encode(&log_record.trace_id)),
+        span_id: Bytes::from(hex_encode(&log_record.span_id)),
+        attributes: otlp_attributes_to_value(&log_record.attributes),
+        resource: Arc::clone(resource),
+        scope: Arc::clone(scope),
+    })
 }
 
 // ============================================================================
 // JSON decoding
 // ============================================================================
 
-pub fn decode_json(body: &[u8]) -> Result<Vec<VrlValue>, DecodeError> {
+pub fn decode_json(body: &[u8], lenient: bool) -> Result<Decoded, DecodeError> {
     // Normalize JSON to convert enum strings to numbers before parsing
     let normalized = super::normalize::normalize_json_bytes(body)?;
     let request: JsonExportLogsServiceRequest = serde_json::from_slice(&normalized)?;
-    export_logs_json_to_vrl(request)
+    export_logs_json_to_vrl(request, lenient, build_log_record)
 }
 
-fn export_logs_json_to_vrl(
+/// Decode JSON logs into record parts for the native fast path
+pub(crate) fn decode_json_parts(
+    body: &[u8],
+    lenient: bool,
+) -> Result<Decoded<LogRecordParts>, DecodeError> {
+    let normalized = super::normalize::normalize_json_bytes(body)?;
+    let request: JsonExportLogsServiceRequest = serde_json::from_slice(&normalized)?;
+    export_logs_json_to_vrl(request, lenient, |parts| parts)
+}
+
+fn export_logs_json_to_vrl<T>(
     request: JsonExportLogsServiceRequest,
-) -> Result<Vec<VrlValue>, DecodeError> {
-    let mut values = preallocate_log_values(&request.resource_logs, |rl| {
+    lenient: bool,
+    build: fn(LogRecordParts) -> T,
+) -> Result<Decoded<T>, DecodeError> {
+    let capacity = count_log_records(&request.resource_logs, |rl| {
         rl.scope_logs.iter().map(|sl| sl.log_records.len()).sum()
     });
+    let mut out = Decoded::new(lenient, capacity);
 
-    for_each_resource_scope(
+    let resources = for_each_resource_scope(
         request.resource_logs,
         |resource_logs| {
             (
@@ -114,40 +147,47 @@ fn export_logs_json_to_vrl(
                 scope_logs.log_records,
             )
         },
-        |log_records, resource, scope| {
+        |log_records, resource, scope, location| {
             for log_record in log_records {
-                let body = log_record
-                    .body
-                    .map(json_any_value_to_vrl)
-                    .unwrap_or(VrlValue::Null);
-
-                let parts = LogRecordParts {
-                    time_unix_nano: json_timestamp_to_i64(
-                        &log_record.time_unix_nano,
-                        "log.time_unix_nano",
-                    )?,
-                    observed_time_unix_nano: json_timestamp_to_i64(
-                        &log_record.observed_time_unix_nano,
-                        "log.observed_time_unix_nano",
-                    )?,
-                    severity_number: log_record.severity_number as i64,
-                    severity_text: Bytes::from(log_record.severity_text),
-                    body,
-                    trace_id: Bytes::from(log_record.trace_id),
-                    span_id: Bytes::from(log_record.span_id),
-                    attributes: json_attrs_to_value(log_record.attributes),
-                    resource: Arc::clone(&resource),
-                    scope: Arc::clone(&scope),
-                };
-
-                values.push(build_log_record(parts));
+                out.push(
+                    location,
+                    json_log_record(log_record, &resource, &scope).map(build),
+                )?;
             }
 
             Ok::<(), DecodeError>(())
         },
     )?;
 
-    Ok(values)
+    out.set_resources(resources);
+    Ok(out)
+}
+
+fn json_log_record(
+    log_record: JsonLogRecord,
+    resource: &Arc<VrlValue>,
+    scope: &Arc<VrlValue>,
+) -> Result<LogRecordParts, DecodeError> {
+    let body = log_record
+        .body
+        .map(json_any_value_to_vrl)
+        .unwrap_or(VrlValue::Null);
+
+    Ok(LogRecordParts {
+        time_unix_nano: json_timestamp_to_i64(&log_record.time_unix_nano, "log.time_unix_nano")?,
+        observed_time_unix_nano: json_timestamp_to_i64(
+            &log_record.observed_time_unix_nano,
+            "log.observed_time_unix_nano",
+        )?,
+        severity_number: log_record.severity_number as i64,
+        severity_text: Bytes::from(log_record.severity_text),
+        body,
+        trace_id: Bytes::from(log_record.trace_id),
+        span_id: Bytes::from(log_record.span_id),
+        attributes: json_attrs_to_value(log_record.attributes),
+        resource: Arc::clone(resource),
+        scope: Arc::clone(scope),
+    })
 }
 
 // ============================================================================
@@ -204,27 +244,28 @@ struct JsonLogRecord {
 // Record builder
 // ============================================================================
 
-/// Precomputed fields for building a log record into VRL values
-struct LogRecordParts {
-    time_unix_nano: i64,
-    observed_time_unix_nano: i64,
-    severity_number: i64,
-    severity_text: Bytes,
-    body: VrlValue,
-    trace_id: Bytes,
-    span_id: Bytes,
-    attributes: VrlValue,
-    resource: Arc<VrlValue>,
-    scope: Arc<VrlValue>,
+/// Precomputed fields for building a log record into VRL values, or into
+/// Arrow columns directly on the native fast path
+#[derive(Debug)]
+pub(crate) struct LogRecordParts {
+    pub time_unix_nano: i64,
+    pub observed_time_unix_nano: i64,
+    pub severity_number: i64,
+    pub severity_text: Bytes,
+    pub body: VrlValue,
+    pub trace_id: Bytes,
+    pub span_id: Bytes,
+    pub attributes: VrlValue,
+    pub resource: Arc<VrlValue>,
+    pub scope: Arc<VrlValue>,
 }
 
-/// Pre-allocate a values Vec sized to the number of log records a request contains
-fn preallocate_log_values<R, F>(resource_logs: &[R], count_logs: F) -> Vec<VrlValue>
+/// Count the log records a request contains, for preallocation
+fn count_log_records<R, F>(resource_logs: &[R], count_logs: F) -> usize
 where
     F: Fn(&R) -> usize,
 {
-    let capacity: usize = resource_logs.iter().map(&count_logs).sum();
-    Vec::with_capacity(capacity)
+    resource_logs.iter().map(&count_logs).sum()
 }
 
 /// Build a VRL-ready log record from parts
@@ -259,6 +300,7 @@ fn build_log_record(parts: LogRecordParts) -> VrlValue {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{ErrorStage, RecordLocation};
     use opentelemetry_proto::tonic::{
         common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
         logs::v1::LogRecord,
@@ -286,7 +328,9 @@ mod tests {
             }]
         }"#;
 
-        let records = decode_json(body.as_bytes()).unwrap();
+        let records = decode_json(body.as_bytes(), false)
+            .map(|decoded| decoded.values)
+            .unwrap();
         assert_eq!(records.len(), 1);
 
         let record = &records[0];
@@ -342,7 +386,9 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let records = decode_protobuf(&body).unwrap();
+        let records = decode_protobuf(&body, false)
+            .map(|decoded| decoded.values)
+            .unwrap();
         assert_eq!(records.len(), 1);
 
         let record = &records[0];
@@ -384,16 +430,15 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body);
+        let result = decode_protobuf(&body, false).map(|decoded| decoded.values);
 
         assert!(result.is_err());
         let err = result.unwrap_err();
         match err {
-            DecodeError::Unsupported(msg) => {
-                assert!(msg.contains("timestamp overflow"));
+            DecodeError::TimestampOverflow(msg) => {
                 assert!(msg.contains("time_unix_nano"));
             }
-            _ => panic!("Expected DecodeError::Unsupported, got: {err:?}"),
+            _ => panic!("Expected DecodeError::TimestampOverflow, got: {err:?}"),
         }
     }
 
@@ -423,19 +468,64 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body);
+        let result = decode_protobuf(&body, false).map(|decoded| decoded.values);
 
         assert!(result.is_err());
         let err = result.unwrap_err();
         match err {
-            DecodeError::Unsupported(msg) => {
-                assert!(msg.contains("timestamp overflow"));
+            DecodeError::TimestampOverflow(msg) => {
                 assert!(msg.contains("observed_time_unix_nano"));
             }
-            _ => panic!("Expected DecodeError::Unsupported, got: {err:?}"),
+            _ => panic!("Expected DecodeError::TimestampOverflow, got: {err:?}"),
         }
     }
 
+    #[test]
+    fn lenient_reports_overflow_log_timestamp() {
+        let log = |time_unix_nano| LogRecord {
+            time_unix_nano,
+            observed_time_unix_nano: 124,
+            ..Default::default()
+        };
+        let resource_logs = |log_records| opentelemetry_proto::tonic::logs::v1::ResourceLogs {
+            resource: Some(Resource::default()),
+            scope_logs: vec![
+                opentelemetry_proto::tonic::logs::v1::ScopeLogs::default(),
+                opentelemetry_proto::tonic::logs::v1::ScopeLogs {
+                    scope: Some(InstrumentationScope::default()),
+                    log_records,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let request = ExportLogsServiceRequest {
+            resource_logs: vec![
+                resource_logs(vec![log(1)]),
+                resource_logs(vec![log(2), log(u64::MAX), log(3)]),
+            ],
+        };
+
+        let decoded = decode_protobuf(&request.encode_to_vec(), true).unwrap();
+
+        assert_eq!(decoded.values.len(), 3);
+        let indices: Vec<usize> = decoded.origins.iter().map(|o| o.index).collect();
+        assert_eq!(indices, vec![0, 1, 3]);
+        assert_eq!(decoded.errors.len(), 1);
+        let error = &decoded.errors[0];
+        assert_eq!(error.index, 2);
+        assert_eq!(
+            error.location,
+            RecordLocation {
+                resource_index: 1,
+                scope_index: 1
+            }
+        );
+        assert_eq!(error.stage, ErrorStage::Decode);
+        assert!(error.message.contains("time_unix_nano"));
+    }
+
     #[test]
     fn accepts_valid_log_timestamps() {
         let log = LogRecord {
@@ -462,7 +552,7 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body);
+        let result = decode_protobuf(&body, false).map(|decoded| decoded.values);
 
         assert!(result.is_ok());
         let logs = result.unwrap();
@@ -476,14 +566,18 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let records = decode_protobuf(&body).unwrap();
+        let records = decode_protobuf(&body, false)
+            .map(|decoded| decoded.values)
+            .unwrap();
         assert!(records.is_empty());
     }
 
     #[test]
     fn handles_empty_json_request() {
         let body = r#"{"resourceLogs": []}"#;
-        let records = decode_json(body.as_bytes()).unwrap();
+        let records = decode_json(body.as_bytes(), false)
+            .map(|decoded| decoded.values)
+            .unwrap();
         assert!(records.is_empty());
     }
 }
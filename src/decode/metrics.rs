@@ -1,7 +1,6 @@
 //! OTLP metrics decoding - protobuf and JSON
 //!
-//! Supports Gauge, Sum, Histogram, and ExponentialHistogram metric types.
-//! Summary metrics are skipped (deprecated in OTLP spec).
+//! Supports Gauge, Sum, Histogram, ExponentialHistogram, and Summary metric types.
 //! Data points with non-finite values (NaN, Infinity) are skipped.
 
 /// Tracks metrics that were skipped during decoding.
@@ -10,8 +9,6 @@
 /// into what data was not processed (unsupported types or invalid values).
 #[derive(Debug, Default, Clone)]
 pub struct SkippedMetrics {
-    /// Count of summary metrics skipped (deprecated in OTLP spec)
-    pub summaries: usize,
     /// Count of data points skipped due to NaN values
     pub nan_values: usize,
     /// Count of data points skipped due to Infinity values
@@ -23,15 +20,12 @@ pub struct SkippedMetrics {
 impl SkippedMetrics {
     /// Returns true if any metrics were skipped
     pub fn has_skipped(&self) -> bool {
-        self.summaries > 0
-            || self.nan_values > 0
-            || self.infinity_values > 0
-            || self.missing_values > 0
+        self.nan_values > 0 || self.infinity_values > 0 || self.missing_values > 0
     }
 
     /// Returns total count of skipped items
     pub fn total(&self) -> usize {
-        self.summaries + self.nan_values + self.infinity_values + self.missing_values
+        self.nan_values + self.infinity_values + self.missing_values
     }
 }
 
@@ -46,10 +40,10 @@ use std::sync::Arc;
 use vrl::value::{ObjectMap, Value as VrlValue};
 
 use super::common::{
-    decode_bytes_field, finite_float_to_vrl, for_each_resource_scope, json_attrs_to_value,
-    json_resource_to_value, json_scope_to_value, json_timestamp_to_i64, otlp_attributes_to_value,
-    otlp_resource_to_value, otlp_scope_to_value, safe_timestamp_conversion, DecodeError,
-    JsonInstrumentationScope, JsonKeyValue, JsonNumberOrString, JsonResource,
+    finite_float_to_vrl, for_each_resource_scope, json_attrs_to_value, json_resource_to_value,
+    json_scope_to_value, json_timestamp_to_i64, otlp_attributes_to_value, otlp_resource_to_value,
+    otlp_scope_to_value, safe_timestamp_conversion, DecodeError, Decoded, JsonInstrumentationScope,
+    JsonKeyValue, JsonNumberOrString, JsonResource,
 };
 
 // ============================================================================
@@ -64,9 +58,56 @@ pub struct DecodeMetricsResult {
     pub skipped: SkippedMetrics,
 }
 
-pub fn decode_protobuf(body: &[u8]) -> Result<DecodeMetricsResult, DecodeError> {
+/// Decoded data points with their origins, plus the skipped value counts.
+///
+/// The values are VRL records, or the decoder's record parts for the native
+/// fast path.
+#[derive(Debug)]
+pub(crate) struct DecodedMetrics<T = VrlValue> {
+    pub records: Decoded<T>,
+    pub skipped: SkippedMetrics,
+}
+
+impl<T> Default for DecodedMetrics<T> {
+    fn default() -> Self {
+        Self {
+            records: Decoded::default(),
+            skipped: SkippedMetrics::default(),
+        }
+    }
+}
+
+impl<T> DecodedMetrics<T> {
+    /// Append the data points of a later message in the same payload.
+    pub fn extend(&mut self, other: DecodedMetrics<T>) {
+        self.records.extend(other.records);
+        self.skipped.nan_values += other.skipped.nan_values;
+        self.skipped.infinity_values += other.skipped.infinity_values;
+        self.skipped.missing_values += other.skipped.missing_values;
+    }
+}
+
+impl DecodedMetrics {
+    pub fn into_result(self) -> DecodeMetricsResult {
+        DecodeMetricsResult {
+            values: self.records.values,
+            skipped: self.skipped,
+        }
+    }
+}
+
+pub fn decode_protobuf(body: &[u8], lenient: bool) -> Result<DecodedMetrics, DecodeError> {
+    let request = ExportMetricsServiceRequest::decode(body)?;
+    export_metrics_to_vrl_proto(request, lenient, build_metric_record)
+}
+
+/// Decode protobuf metrics into record parts for the native fast path
+pub(crate) fn decode_protobuf_parts(
+    body: &[u8],
+    lenient: bool,
+) -> Result<DecodedMetrics<MetricRecordParts>, DecodeError> {
     let request = ExportMetricsServiceRequest::decode(body)?;
-    export_metrics_to_vrl_proto(request)
+    export_metrics_to_vrl_proto(request, lenient, |parts| parts)
 }
 
 /// Shared context for metric metadata to reduce function argument count
@@ -78,19 +119,45 @@ struct MetricContext {
     scope: Arc<VrlValue>,
 }
 
-fn export_metrics_to_vrl_proto(
+impl MetricContext {
+    /// The fields of a data point shared by every metric type
+    fn point(
+        &self,
+        time_unix_nano: i64,
+        start_time_unix_nano: i64,
+        attributes: VrlValue,
+        flags: u32,
+    ) -> MetricPointParts {
+        MetricPointParts {
+            time_unix_nano,
+            start_time_unix_nano,
+            metric_name: self.metric_name.clone(),
+            metric_description: self.metric_description.clone(),
+            metric_unit: self.metric_unit.clone(),
+            attributes,
+            resource: Arc::clone(&self.resource),
+            scope: Arc::clone(&self.scope),
+            flags: flags as i64,
+        }
+    }
+}
+
+fn export_metrics_to_vrl_proto<T>(
     request: ExportMetricsServiceRequest,
-) -> Result<DecodeMetricsResult, DecodeError> {
-    let mut values = preallocate_metric_values(&request.resource_metrics, |rm| {
+    lenient: bool,
+    build: fn(MetricRecordParts) -> T,
+) -> Result<DecodedMetrics<T>, DecodeError> {
+    let capacity = count_metric_points(&request.resource_metrics, |rm| {
         rm.scope_metrics
             .iter()
             .flat_map(|sm| sm.metrics.iter())
             .map(count_data_points)
             .sum()
     });
+    let mut out = Decoded::new(lenient, capacity);
     let mut skipped = SkippedMetrics::default();
 
-    for_each_resource_scope(
+    let resources = for_each_resource_scope(
         request.resource_metrics,
         |resource_metrics| {
             (
@@ -104,7 +171,7 @@ fn export_metrics_to_vrl_proto(
                 scope_metrics.metrics,
             )
         },
-        |metrics, resource, scope| {
+        |metrics, resource, scope, location| {
             for metric in metrics {
                 let ctx = MetricContext {
                     metric_name: Bytes::from(metric.name.clone()),
@@ -117,8 +184,8 @@ fn export_metrics_to_vrl_proto(
                 match metric.data {
                     Some(Data::Gauge(gauge)) => {
                         for point in gauge.data_points {
-                            match build_gauge_from_point(&point, &ctx)? {
-                                Some(record) => values.push(record),
+                            match build_gauge_from_point(&point, &ctx).transpose() {
+                                Some(record) => out.push(location, record.map(build))?,
                                 None => track_skipped_value(&point.value, &mut skipped),
                             }
                         }
@@ -132,8 +199,10 @@ fn export_metrics_to_vrl_proto(
                                 &ctx,
                                 aggregation_temporality,
                                 is_monotonic,
-                            )? {
-                                Some(record) => values.push(record),
+                            )
+                            .transpose()
+                            {
+                                Some(record) => out.push(location, record.map(build))?,
                                 None => track_skipped_value(&point.value, &mut skipped),
                             }
                         }
@@ -142,8 +211,8 @@ fn export_metrics_to_vrl_proto(
                         let aggregation_temporality = h.aggregation_temporality as i64;
                         for point in h.data_points {
                             let record =
-                                build_histogram_from_point(&point, &ctx, aggregation_temporality)?;
-                            values.push(record);
+                                build_histogram_from_point(&point, &ctx, aggregation_temporality);
+                            out.push(location, record.map(build))?;
                         }
                     }
                     Some(Data::ExponentialHistogram(eh)) => {
@@ -153,12 +222,15 @@ fn export_metrics_to_vrl_proto(
                                 &point,
                                 &ctx,
                                 aggregation_temporality,
-                            )?;
-                            values.push(record);
+                            );
+                            out.push(location, record.map(build))?;
                         }
                     }
                     Some(Data::Summary(s)) => {
-                        skipped.summaries += s.data_points.len();
+                        for point in s.data_points {
+                            let record = build_summary_from_point(&point, &ctx);
+                            out.push(location, record.map(build))?;
+                        }
                     }
                     None => {
                         // Metric with no data - nothing to skip
@@ -170,7 +242,11 @@ fn export_metrics_to_vrl_proto(
         },
     )?;
 
-    Ok(DecodeMetricsResult { values, skipped })
+    out.set_resources(resources);
+    Ok(DecodedMetrics {
+        records: out,
+        skipped,
+    })
 }
 
 /// Track why a metric value was skipped
@@ -189,14 +265,15 @@ fn count_data_points(metric: &opentelemetry_proto::tonic::metrics::v1::Metric) -
         Some(Data::Sum(s)) => s.data_points.len(),
         Some(Data::Histogram(h)) => h.data_points.len(),
         Some(Data::ExponentialHistogram(eh)) => eh.data_points.len(),
-        _ => 0,
+        Some(Data::Summary(s)) => s.data_points.len(),
+        None => 0,
     }
 }
 
 fn build_gauge_from_point(
     point: &opentelemetry_proto::tonic::metrics::v1::NumberDataPoint,
     ctx: &MetricContext,
-) -> Result<Option<VrlValue>, DecodeError> {
+) -> Result<Option<MetricRecordParts>, DecodeError> {
     let time_unix_nano = safe_timestamp_conversion(point.time_unix_nano, "gauge.time_unix_nano")?;
     let start_time_unix_nano =
         safe_timestamp_conversion(point.start_time_unix_nano, "gauge.start_time_unix_nano")?;
@@ -217,20 +294,17 @@ fn build_gauge_from_point(
     let exemplars = build_exemplars(&point.exemplars)?;
 
     let parts = GaugeRecordParts {
-        time_unix_nano,
-        start_time_unix_nano,
-        metric_name: ctx.metric_name.clone(),
-        metric_description: ctx.metric_description.clone(),
-        metric_unit: ctx.metric_unit.clone(),
+        point: ctx.point(
+            time_unix_nano,
+            start_time_unix_nano,
+            otlp_attributes_to_value(&point.attributes),
+            point.flags,
+        ),
         value,
-        attributes: otlp_attributes_to_value(&point.attributes),
-        resource: Arc::clone(&ctx.resource),
-        scope: Arc::clone(&ctx.scope),
-        flags: point.flags as i64,
         exemplars,
     };
 
-    Ok(Some(build_gauge_record(parts)))
+    Ok(Some(MetricRecordParts::Gauge(parts)))
 }
 
 fn build_sum_from_point(
@@ -238,7 +312,7 @@ fn build_sum_from_point(
     ctx: &MetricContext,
     aggregation_temporality: i64,
     is_monotonic: bool,
-) -> Result<Option<VrlValue>, DecodeError> {
+) -> Result<Option<MetricRecordParts>, DecodeError> {
     let time_unix_nano = safe_timestamp_conversion(point.time_unix_nano, "sum.time_unix_nano")?;
     let start_time_unix_nano =
         safe_timestamp_conversion(point.start_time_unix_nano, "sum.start_time_unix_nano")?;
@@ -259,29 +333,26 @@ fn build_sum_from_point(
     let exemplars = build_exemplars(&point.exemplars)?;
 
     let parts = SumRecordParts {
-        time_unix_nano,
-        start_time_unix_nano,
-        metric_name: ctx.metric_name.clone(),
-        metric_description: ctx.metric_description.clone(),
-        metric_unit: ctx.metric_unit.clone(),
+        point: ctx.point(
+            time_unix_nano,
+            start_time_unix_nano,
+            otlp_attributes_to_value(&point.attributes),
+            point.flags,
+        ),
         value,
-        attributes: otlp_attributes_to_value(&point.attributes),
-        resource: Arc::clone(&ctx.resource),
-        scope: Arc::clone(&ctx.scope),
-        flags: point.flags as i64,
         exemplars,
         aggregation_temporality,
         is_monotonic,
     };
 
-    Ok(Some(build_sum_record(parts)))
+    Ok(Some(MetricRecordParts::Sum(parts)))
 }
 
 fn build_histogram_from_point(
     point: &opentelemetry_proto::tonic::metrics::v1::HistogramDataPoint,
     ctx: &MetricContext,
     aggregation_temporality: i64,
-) -> Result<VrlValue, DecodeError> {
+) -> Result<MetricRecordParts, DecodeError> {
     let time_unix_nano =
         safe_timestamp_conversion(point.time_unix_nano, "histogram.time_unix_nano")?;
     let start_time_unix_nano =
@@ -296,33 +367,30 @@ fn build_histogram_from_point(
         serde_json::to_string(&point.explicit_bounds).unwrap_or_else(|_| "[]".to_string());
 
     let parts = HistogramRecordParts {
-        time_unix_nano,
-        start_time_unix_nano,
-        metric_name: ctx.metric_name.clone(),
-        metric_description: ctx.metric_description.clone(),
-        metric_unit: ctx.metric_unit.clone(),
+        point: ctx.point(
+            time_unix_nano,
+            start_time_unix_nano,
+            otlp_attributes_to_value(&point.attributes),
+            point.flags,
+        ),
         count: point.count as i64,
         sum: point.sum,
         min: point.min,
         max: point.max,
         bucket_counts: Bytes::from(bucket_counts_json),
         explicit_bounds: Bytes::from(explicit_bounds_json),
-        attributes: otlp_attributes_to_value(&point.attributes),
-        resource: Arc::clone(&ctx.resource),
-        scope: Arc::clone(&ctx.scope),
-        flags: point.flags as i64,
         exemplars,
         aggregation_temporality,
     };
 
-    Ok(build_histogram_record(parts))
+    Ok(MetricRecordParts::Histogram(parts))
 }
 
 fn build_exp_histogram_from_point(
     point: &opentelemetry_proto::tonic::metrics::v1::ExponentialHistogramDataPoint,
     ctx: &MetricContext,
     aggregation_temporality: i64,
-) -> Result<VrlValue, DecodeError> {
+) -> Result<MetricRecordParts, DecodeError> {
     let time_unix_nano =
         safe_timestamp_conversion(point.time_unix_nano, "exp_histogram.time_unix_nano")?;
     let start_time_unix_nano = safe_timestamp_conversion(
@@ -353,11 +421,12 @@ fn build_exp_histogram_from_point(
     };
 
     let parts = ExpHistogramRecordParts {
-        time_unix_nano,
-        start_time_unix_nano,
-        metric_name: ctx.metric_name.clone(),
-        metric_description: ctx.metric_description.clone(),
-        metric_unit: ctx.metric_unit.clone(),
+        point: ctx.point(
+            time_unix_nano,
+            start_time_unix_nano,
+            otlp_attributes_to_value(&point.attributes),
+            point.flags,
+        ),
         count: point.count as i64,
         sum: point.sum,
         min: point.min,
@@ -369,15 +438,46 @@ fn build_exp_histogram_from_point(
         positive_bucket_counts: positive_bucket_counts_json,
         negative_offset,
         negative_bucket_counts: negative_bucket_counts_json,
-        attributes: otlp_attributes_to_value(&point.attributes),
-        resource: Arc::clone(&ctx.resource),
-        scope: Arc::clone(&ctx.scope),
-        flags: point.flags as i64,
         exemplars,
         aggregation_temporality,
     };
 
-    Ok(build_exp_histogram_record(parts))
+    Ok(MetricRecordParts::ExpHistogram(parts))
+}
+
+fn build_summary_from_point(
+    point: &opentelemetry_proto::tonic::metrics::v1::SummaryDataPoint,
+    ctx: &MetricContext,
+) -> Result<MetricRecordParts, DecodeError> {
+    let time_unix_nano = safe_timestamp_conversion(point.time_unix_nano, "summary.time_unix_nano")?;
+    let start_time_unix_nano =
+        safe_timestamp_conversion(point.start_time_unix_nano, "summary.start_time_unix_nano")?;
+
+    let quantile_values_json =
+        quantile_values_to_json(point.quantile_values.iter().map(|q| (q.quantile, q.value)));
+
+    let parts = SummaryRecordParts {
+        point: ctx.point(
+            time_unix_nano,
+            start_time_unix_nano,
+            otlp_attributes_to_value(&point.attributes),
+            point.flags,
+        ),
+        count: point.count as i64,
+        sum: point.sum,
+        quantile_values: Bytes::from(quantile_values_json),
+    };
+
+    Ok(MetricRecordParts::Summary(parts))
+}
+
+/// Serialize (quantile, value) pairs to a JSON array of `{quantile, value}` objects.
+/// Non-finite numbers are written as null.
+fn quantile_values_to_json(quantiles: impl Iterator<Item = (f64, f64)>) -> String {
+    let entries: Vec<serde_json::Value> = quantiles
+        .map(|(quantile, value)| serde_json::json!({ "quantile": quantile, "value": value }))
+        .collect();
+    serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string())
 }
 
 fn build_exemplars(
@@ -415,29 +515,43 @@ fn build_exemplars(
 // JSON decoding
 // ============================================================================
 
-pub fn decode_json(body: &[u8]) -> Result<DecodeMetricsResult, DecodeError> {
+pub fn decode_json(body: &[u8], lenient: bool) -> Result<DecodedMetrics, DecodeError> {
     // Normalize JSON to convert enum strings to numbers before parsing
     let normalized = super::normalize::normalize_json_bytes(body)?;
     let request: JsonExportMetricsServiceRequest = serde_json::from_slice(&normalized)?;
-    export_metrics_to_vrl_json(request)
+    export_metrics_to_vrl_json(request, lenient, build_metric_record)
+}
+
+/// Decode JSON metrics into record parts for the native fast path
+pub(crate) fn decode_json_parts(
+    body: &[u8],
+    lenient: bool,
+) -> Result<DecodedMetrics<MetricRecordParts>, DecodeError> {
+    let normalized = super::normalize::normalize_json_bytes(body)?;
+    let request: JsonExportMetricsServiceRequest = serde_json::from_slice(&normalized)?;
+    export_metrics_to_vrl_json(request, lenient, |parts| parts)
 }
 
-fn export_metrics_to_vrl_json(
+fn export_metrics_to_vrl_json<T>(
     request: JsonExportMetricsServiceRequest,
-) -> Result<DecodeMetricsResult, DecodeError> {
-    let mut values = preallocate_metric_values(&request.resource_metrics, |rm| {
+    lenient: bool,
+    build: fn(MetricRecordParts) -> T,
+) -> Result<DecodedMetrics<T>, DecodeError> {
+    let capacity = count_metric_points(&request.resource_metrics, |rm| {
         rm.scope_metrics
             .iter()
             .flat_map(|sm| sm.metrics.iter())
             .map(|m| {
                 m.gauge.as_ref().map(|g| g.data_points.len()).unwrap_or(0)
                     + m.sum.as_ref().map(|s| s.data_points.len()).unwrap_or(0)
+                    + m.summary.as_ref().map(|s| s.data_points.len()).unwrap_or(0)
             })
             .sum()
     });
+    let mut out = Decoded::new(lenient, capacity);
     let mut skipped = SkippedMetrics::default();
 
-    for_each_resource_scope(
+    let resources = for_each_resource_scope(
         request.resource_metrics,
         |resource_metrics| {
             (
@@ -451,7 +565,7 @@ fn export_metrics_to_vrl_json(
                 scope_metrics.metrics,
             )
         },
-        |metrics, resource, scope| {
+        |metrics, resource, scope, location| {
             for metric in metrics {
                 let ctx = MetricContext {
                     metric_name: Bytes::from(metric.name),
@@ -465,8 +579,8 @@ fn export_metrics_to_vrl_json(
                     for point in gauge.data_points {
                         let as_int = point.as_int.clone();
                         let as_double = point.as_double;
-                        match build_gauge_from_json_point(point, &ctx)? {
-                            Some(record) => values.push(record),
+                        match build_gauge_from_json_point(point, &ctx).transpose() {
+                            Some(record) => out.push(location, record.map(build))?,
                             None => track_skipped_json_value(&as_int, &as_double, &mut skipped),
                         }
                     }
@@ -481,8 +595,10 @@ fn export_metrics_to_vrl_json(
                             &ctx,
                             sum.aggregation_temporality,
                             sum.is_monotonic,
-                        )? {
-                            Some(record) => values.push(record),
+                        )
+                        .transpose()
+                        {
+                            Some(record) => out.push(location, record.map(build))?,
                             None => track_skipped_json_value(&as_int, &as_double, &mut skipped),
                         }
                     }
@@ -494,8 +610,8 @@ fn export_metrics_to_vrl_json(
                             point,
                             &ctx,
                             histogram.aggregation_temporality,
-                        )?;
-                        values.push(record);
+                        );
+                        out.push(location, record.map(build))?;
                     }
                 }
 
@@ -505,14 +621,16 @@ fn export_metrics_to_vrl_json(
                             point,
                             &ctx,
                             exp_histogram.aggregation_temporality,
-                        )?;
-                        values.push(record);
+                        );
+                        out.push(location, record.map(build))?;
                     }
                 }
 
-                // Track skipped summary metrics (deprecated in OTLP spec)
-                if let Some(s) = metric.summary {
-                    skipped.summaries += s.data_points.len();
+                if let Some(summary) = metric.summary {
+                    for point in summary.data_points {
+                        let record = build_summary_from_json_point(point, &ctx);
+                        out.push(location, record.map(build))?;
+                    }
                 }
             }
 
@@ -520,7 +638,11 @@ fn export_metrics_to_vrl_json(
         },
     )?;
 
-    Ok(DecodeMetricsResult { values, skipped })
+    out.set_resources(resources);
+    Ok(DecodedMetrics {
+        records: out,
+        skipped,
+    })
 }
 
 /// Track why a JSON metric value was skipped
@@ -540,7 +662,7 @@ fn track_skipped_json_value(
 fn build_gauge_from_json_point(
     point: JsonNumberDataPoint,
     ctx: &MetricContext,
-) -> Result<Option<VrlValue>, DecodeError> {
+) -> Result<Option<MetricRecordParts>, DecodeError> {
     // Skip records with missing or non-finite values (NaN/Infinity)
     let value = match extract_number_value(&point.as_int, &point.as_double) {
         Some(v) => v,
@@ -550,23 +672,17 @@ fn build_gauge_from_json_point(
     let exemplars = build_json_exemplars(point.exemplars)?;
 
     let parts = GaugeRecordParts {
-        time_unix_nano: json_timestamp_to_i64(&point.time_unix_nano, "gauge.time_unix_nano")?,
-        start_time_unix_nano: json_timestamp_to_i64(
-            &point.start_time_unix_nano,
-            "gauge.start_time_unix_nano",
-        )?,
-        metric_name: ctx.metric_name.clone(),
-        metric_description: ctx.metric_description.clone(),
-        metric_unit: ctx.metric_unit.clone(),
+        point: ctx.point(
+            json_timestamp_to_i64(&point.time_unix_nano, "gauge.time_unix_nano")?,
+            json_timestamp_to_i64(&point.start_time_unix_nano, "gauge.start_time_unix_nano")?,
+            json_attrs_to_value(point.attributes),
+            point.flags,
+        ),
         value,
-        attributes: json_attrs_to_value(point.attributes),
-        resource: Arc::clone(&ctx.resource),
-        scope: Arc::clone(&ctx.scope),
-        flags: point.flags as i64,
         exemplars,
     };
 
-    Ok(Some(build_gauge_record(parts)))
+    Ok(Some(MetricRecordParts::Gauge(parts)))
 }
 
 fn build_sum_from_json_point(
@@ -574,7 +690,7 @@ fn build_sum_from_json_point(
     ctx: &MetricContext,
     aggregation_temporality: i64,
     is_monotonic: bool,
-) -> Result<Option<VrlValue>, DecodeError> {
+) -> Result<Option<MetricRecordParts>, DecodeError> {
     // Skip records with missing or non-finite values (NaN/Infinity)
     let value = match extract_number_value(&point.as_int, &point.as_double) {
         Some(v) => v,
@@ -584,32 +700,26 @@ fn build_sum_from_json_point(
     let exemplars = build_json_exemplars(point.exemplars)?;
 
     let parts = SumRecordParts {
-        time_unix_nano: json_timestamp_to_i64(&point.time_unix_nano, "sum.time_unix_nano")?,
-        start_time_unix_nano: json_timestamp_to_i64(
-            &point.start_time_unix_nano,
-            "sum.start_time_unix_nano",
-        )?,
-        metric_name: ctx.metric_name.clone(),
-        metric_description: ctx.metric_description.clone(),
-        metric_unit: ctx.metric_unit.clone(),
+        point: ctx.point(
+            json_timestamp_to_i64(&point.time_unix_nano, "sum.time_unix_nano")?,
+            json_timestamp_to_i64(&point.start_time_unix_nano, "sum.start_time_unix_nano")?,
+            json_attrs_to_value(point.attributes),
+            point.flags,
+        ),
         value,
-        attributes: json_attrs_to_value(point.attributes),
-        resource: Arc::clone(&ctx.resource),
-        scope: Arc::clone(&ctx.scope),
-        flags: point.flags as i64,
         exemplars,
         aggregation_temporality,
         is_monotonic,
     };
 
-    Ok(Some(build_sum_record(parts)))
+    Ok(Some(MetricRecordParts::Sum(parts)))
 }
 
 fn build_histogram_from_json_point(
     point: JsonHistogramDataPoint,
     ctx: &MetricContext,
     aggregation_temporality: i64,
-) -> Result<VrlValue, DecodeError> {
+) -> Result<MetricRecordParts, DecodeError> {
     let exemplars = build_json_exemplars(point.exemplars)?;
 
     // Convert bucket_counts to JSON string
@@ -626,36 +736,33 @@ fn build_histogram_from_json_point(
         serde_json::to_string(&point.explicit_bounds).unwrap_or_else(|_| "[]".to_string());
 
     let parts = HistogramRecordParts {
-        time_unix_nano: json_timestamp_to_i64(&point.time_unix_nano, "histogram.time_unix_nano")?,
-        start_time_unix_nano: json_timestamp_to_i64(
-            &point.start_time_unix_nano,
-            "histogram.start_time_unix_nano",
-        )?,
-        metric_name: ctx.metric_name.clone(),
-        metric_description: ctx.metric_description.clone(),
-        metric_unit: ctx.metric_unit.clone(),
+        point: ctx.point(
+            json_timestamp_to_i64(&point.time_unix_nano, "histogram.time_unix_nano")?,
+            json_timestamp_to_i64(
+                &point.start_time_unix_nano,
+                "histogram.start_time_unix_nano",
+            )?,
+            json_attrs_to_value(point.attributes),
+            point.flags,
+        ),
         count: point.count.as_i64().unwrap_or(0),
         sum: point.sum,
         min: point.min,
         max: point.max,
         bucket_counts: Bytes::from(bucket_counts_json),
         explicit_bounds: Bytes::from(explicit_bounds_json),
-        attributes: json_attrs_to_value(point.attributes),
-        resource: Arc::clone(&ctx.resource),
-        scope: Arc::clone(&ctx.scope),
-        flags: point.flags as i64,
         exemplars,
         aggregation_temporality,
     };
 
-    Ok(build_histogram_record(parts))
+    Ok(MetricRecordParts::Histogram(parts))
 }
 
 fn build_exp_histogram_from_json_point(
     point: JsonExpHistogramDataPoint,
     ctx: &MetricContext,
     aggregation_temporality: i64,
-) -> Result<VrlValue, DecodeError> {
+) -> Result<MetricRecordParts, DecodeError> {
     let exemplars = build_json_exemplars(point.exemplars)?;
 
     // Extract positive bucket data
@@ -687,17 +794,15 @@ fn build_exp_histogram_from_json_point(
     };
 
     let parts = ExpHistogramRecordParts {
-        time_unix_nano: json_timestamp_to_i64(
-            &point.time_unix_nano,
-            "exp_histogram.time_unix_nano",
-        )?,
-        start_time_unix_nano: json_timestamp_to_i64(
-            &point.start_time_unix_nano,
-            "exp_histogram.start_time_unix_nano",
-        )?,
-        metric_name: ctx.metric_name.clone(),
-        metric_description: ctx.metric_description.clone(),
-        metric_unit: ctx.metric_unit.clone(),
+        point: ctx.point(
+            json_timestamp_to_i64(&point.time_unix_nano, "exp_histogram.time_unix_nano")?,
+            json_timestamp_to_i64(
+                &point.start_time_unix_nano,
+                "exp_histogram.start_time_unix_nano",
+            )?,
+            json_attrs_to_value(point.attributes),
+            point.flags,
+        ),
         count: point.count.as_i64().unwrap_or(0),
         sum: point.sum,
         min: point.min,
@@ -709,15 +814,33 @@ fn build_exp_histogram_from_json_point(
         positive_bucket_counts: positive_bucket_counts_json,
         negative_offset,
         negative_bucket_counts: negative_bucket_counts_json,
-        attributes: json_attrs_to_value(point.attributes),
-        resource: Arc::clone(&ctx.resource),
-        scope: Arc::clone(&ctx.scope),
-        flags: point.flags as i64,
         exemplars,
         aggregation_temporality,
     };
 
-    Ok(build_exp_histogram_record(parts))
+    Ok(MetricRecordParts::ExpHistogram(parts))
+}
+
+fn build_summary_from_json_point(
+    point: JsonSummaryDataPoint,
+    ctx: &MetricContext,
+) -> Result<MetricRecordParts, DecodeError> {
+    let quantile_values_json =
+        quantile_values_to_json(point.quantile_values.iter().map(|q| (q.quantile, q.value)));
+
+    let parts = SummaryRecordParts {
+        point: ctx.point(
+            json_timestamp_to_i64(&point.time_unix_nano, "summary.time_unix_nano")?,
+            json_timestamp_to_i64(&point.start_time_unix_nano, "summary.start_time_unix_nano")?,
+            json_attrs_to_value(point.attributes),
+            point.flags,
+        ),
+        count: point.count.as_i64().unwrap_or(0),
+        sum: point.sum,
+        quantile_values: Bytes::from(quantile_values_json),
+    };
+
+    Ok(MetricRecordParts::Summary(parts))
 }
 
 /// Extract numeric value, always producing Float for schema compatibility
@@ -752,15 +875,21 @@ fn build_json_exemplars(exemplars: Vec<JsonExemplar>) -> Result<Vec<ExemplarPart
         .into_iter()
         .map(|e| {
             // Exemplar values can be null - they're supplementary metadata
-            let value = extract_number_value(&e.as_int, &e.as_double).unwrap_or(VrlValue::Null);
+            let (as_int, as_double) = match e.value {
+                Some(nested) if e.as_int.is_none() && e.as_double.is_none() => {
+                    (nested.as_int, nested.as_double)
+                }
+                _ => (e.as_int, e.as_double),
+            };
+            let value = extract_number_value(&as_int, &as_double).unwrap_or(VrlValue::Null);
             Ok(ExemplarParts {
                 time_unix_nano: json_timestamp_to_i64(
                     &e.time_unix_nano,
                     "exemplar.time_unix_nano",
                 )?,
                 value,
-                trace_id: Bytes::from(decode_bytes_field(&e.trace_id)),
-                span_id: Bytes::from(decode_bytes_field(&e.span_id)),
+                trace_id: Bytes::from(e.trace_id),
+                span_id: Bytes::from(e.span_id),
                 filtered_attributes: json_attrs_to_value(e.filtered_attributes),
             })
         })
@@ -924,12 +1053,40 @@ struct JsonExpHistogramBuckets {
     bucket_counts: Vec<JsonNumberOrString>,
 }
 
-/// Minimal struct for summary metrics (skipped, only count data_points)
+/// Struct for summary metrics
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JsonSummary {
     #[serde(default)]
-    data_points: Vec<serde_json::Value>,
+    data_points: Vec<JsonSummaryDataPoint>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSummaryDataPoint {
+    #[serde(default)]
+    time_unix_nano: JsonNumberOrString,
+    #[serde(default)]
+    start_time_unix_nano: JsonNumberOrString,
+    #[serde(default)]
+    count: JsonNumberOrString,
+    #[serde(default)]
+    sum: f64,
+    #[serde(default)]
+    quantile_values: Vec<JsonValueAtQuantile>,
+    #[serde(default)]
+    attributes: Vec<JsonKeyValue>,
+    #[serde(default)]
+    flags: u32,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonValueAtQuantile {
+    #[serde(default)]
+    quantile: f64,
+    #[serde(default)]
+    value: f64,
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -960,6 +1117,10 @@ struct JsonExemplar {
     as_int: Option<JsonNumberOrString>,
     #[serde(default)]
     as_double: Option<f64>,
+    /// The value oneof nested under `value`, as written by the
+    /// `opentelemetry-proto` serde derives (and by `encode_request`)
+    #[serde(default)]
+    value: Option<JsonExemplarValue>,
     #[serde(default)]
     trace_id: String,
     #[serde(default)]
@@ -968,26 +1129,54 @@ struct JsonExemplar {
     filtered_attributes: Vec<JsonKeyValue>,
 }
 
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonExemplarValue {
+    #[serde(default)]
+    as_int: Option<JsonNumberOrString>,
+    #[serde(default)]
+    as_double: Option<f64>,
+}
+
 // ============================================================================
 // Record builder
 // ============================================================================
 
-/// Precomputed fields for building a gauge metric record into VRL values
-struct GaugeRecordParts {
-    time_unix_nano: i64,
-    start_time_unix_nano: i64,
-    metric_name: Bytes,
-    metric_description: Bytes,
-    metric_unit: Bytes,
-    value: VrlValue,
-    attributes: VrlValue,
-    resource: Arc<VrlValue>,
-    scope: Arc<VrlValue>,
-    flags: i64,
-    exemplars: Vec<ExemplarParts>,
+/// Precomputed fields of a metric data point, for building it into a VRL
+/// record or into Arrow columns directly on the native fast path
+#[derive(Debug)]
+pub(crate) enum MetricRecordParts {
+    Gauge(GaugeRecordParts),
+    Sum(SumRecordParts),
+    Histogram(HistogramRecordParts),
+    ExpHistogram(ExpHistogramRecordParts),
+    Summary(SummaryRecordParts),
+}
+
+/// Fields shared by the data points of every metric type
+#[derive(Debug)]
+pub(crate) struct MetricPointParts {
+    pub time_unix_nano: i64,
+    pub start_time_unix_nano: i64,
+    pub metric_name: Bytes,
+    pub metric_description: Bytes,
+    pub metric_unit: Bytes,
+    pub attributes: VrlValue,
+    pub resource: Arc<VrlValue>,
+    pub scope: Arc<VrlValue>,
+    pub flags: i64,
 }
 
-struct ExemplarParts {
+/// Precomputed fields of a gauge data point
+#[derive(Debug)]
+pub(crate) struct GaugeRecordParts {
+    pub point: MetricPointParts,
+    pub value: VrlValue,
+    pub exemplars: Vec<ExemplarParts>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ExemplarParts {
     time_unix_nano: i64,
     value: VrlValue,
     trace_id: Bytes,
@@ -995,81 +1184,68 @@ struct ExemplarParts {
     filtered_attributes: VrlValue,
 }
 
-/// Precomputed fields for building a sum metric record into VRL values
-struct SumRecordParts {
-    time_unix_nano: i64,
-    start_time_unix_nano: i64,
-    metric_name: Bytes,
-    metric_description: Bytes,
-    metric_unit: Bytes,
-    value: VrlValue,
-    attributes: VrlValue,
-    resource: Arc<VrlValue>,
-    scope: Arc<VrlValue>,
-    flags: i64,
-    exemplars: Vec<ExemplarParts>,
-    aggregation_temporality: i64,
-    is_monotonic: bool,
+/// Precomputed fields of a sum data point
+#[derive(Debug)]
+pub(crate) struct SumRecordParts {
+    pub point: MetricPointParts,
+    pub value: VrlValue,
+    pub exemplars: Vec<ExemplarParts>,
+    pub aggregation_temporality: i64,
+    pub is_monotonic: bool,
 }
 
-/// Precomputed fields for building a histogram metric record into VRL values
-struct HistogramRecordParts {
-    time_unix_nano: i64,
-    start_time_unix_nano: i64,
-    metric_name: Bytes,
-    metric_description: Bytes,
-    metric_unit: Bytes,
-    count: i64,
-    sum: Option<f64>,
-    min: Option<f64>,
-    max: Option<f64>,
-    bucket_counts: Bytes,
-    explicit_bounds: Bytes,
-    attributes: VrlValue,
-    resource: Arc<VrlValue>,
-    scope: Arc<VrlValue>,
-    flags: i64,
-    exemplars: Vec<ExemplarParts>,
-    aggregation_temporality: i64,
+/// Precomputed fields of a histogram data point
+#[derive(Debug)]
+pub(crate) struct HistogramRecordParts {
+    pub point: MetricPointParts,
+    pub count: i64,
+    pub sum: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub bucket_counts: Bytes,
+    pub explicit_bounds: Bytes,
+    pub exemplars: Vec<ExemplarParts>,
+    pub aggregation_temporality: i64,
 }
 
-/// Precomputed fields for building an exponential histogram metric record into VRL values
-struct ExpHistogramRecordParts {
-    time_unix_nano: i64,
-    start_time_unix_nano: i64,
-    metric_name: Bytes,
-    metric_description: Bytes,
-    metric_unit: Bytes,
-    count: i64,
-    sum: Option<f64>,
-    min: Option<f64>,
-    max: Option<f64>,
-    scale: i64,
-    zero_count: i64,
-    zero_threshold: f64,
-    positive_offset: i64,
-    positive_bucket_counts: Bytes,
-    negative_offset: i64,
-    negative_bucket_counts: Bytes,
-    attributes: VrlValue,
-    resource: Arc<VrlValue>,
-    scope: Arc<VrlValue>,
-    flags: i64,
-    exemplars: Vec<ExemplarParts>,
-    aggregation_temporality: i64,
+/// Precomputed fields of an exponential histogram data point
+#[derive(Debug)]
+pub(crate) struct ExpHistogramRecordParts {
+    pub point: MetricPointParts,
+    pub count: i64,
+    pub sum: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub scale: i64,
+    pub zero_count: i64,
+    pub zero_threshold: f64,
+    pub positive_offset: i64,
+    pub positive_bucket_counts: Bytes,
+    pub negative_offset: i64,
+    pub negative_bucket_counts: Bytes,
+    pub exemplars: Vec<ExemplarParts>,
+    pub aggregation_temporality: i64,
+}
+
+/// Precomputed fields of a summary data point
+#[derive(Debug)]
+pub(crate) struct SummaryRecordParts {
+    pub point: MetricPointParts,
+    pub count: i64,
+    pub sum: f64,
+    pub quantile_values: Bytes,
 }
 
-/// Pre-allocate values Vec for metrics
-fn preallocate_metric_values<R, F>(resource_metrics: &[R], count_points: F) -> Vec<VrlValue>
+/// Count the data points a request contains, for preallocation
+fn count_metric_points<R, F>(resource_metrics: &[R], count_points: F) -> usize
 where
     F: Fn(&R) -> usize,
 {
-    let capacity: usize = resource_metrics.iter().map(&count_points).sum();
-    Vec::with_capacity(capacity)
+    resource_metrics.iter().map(&count_points).sum()
 }
 
-/// Helper function to build exemplars array from parts
-fn build_exemplars_array(exemplars: Vec<ExemplarParts>) -> VrlValue {
+/// Build the VRL array of a data point's exemplars
+pub(crate) fn exemplars_value(exemplars: Vec<ExemplarParts>) -> VrlValue {
     let exemplars_array: Vec<VrlValue> = exemplars
         .into_iter()
         .map(|e| {
@@ -1085,142 +1261,95 @@ fn build_exemplars_array(exemplars: Vec<ExemplarParts>) -> VrlValue {
     VrlValue::Array(exemplars_array)
 }
 
-fn build_gauge_record(parts: GaugeRecordParts) -> VrlValue {
-    let mut map = ObjectMap::new();
-    map.insert(
-        "time_unix_nano".into(),
-        VrlValue::Integer(parts.time_unix_nano),
-    );
-    map.insert(
-        "start_time_unix_nano".into(),
-        VrlValue::Integer(parts.start_time_unix_nano),
-    );
-    map.insert("metric_name".into(), VrlValue::Bytes(parts.metric_name));
-    map.insert(
-        "metric_description".into(),
-        VrlValue::Bytes(parts.metric_description),
-    );
-    map.insert("metric_unit".into(), VrlValue::Bytes(parts.metric_unit));
-    map.insert("value".into(), parts.value);
-    map.insert("attributes".into(), parts.attributes);
-    map.insert("resource".into(), (*parts.resource).clone());
-    map.insert("scope".into(), (*parts.scope).clone());
-    map.insert("flags".into(), VrlValue::Integer(parts.flags));
-    map.insert("exemplars".into(), build_exemplars_array(parts.exemplars));
-    map.insert("_metric_type".into(), VrlValue::Bytes(Bytes::from("gauge")));
+/// An optional histogram statistic, null when unset or not finite
+pub(crate) fn optional_float(value: Option<f64>) -> VrlValue {
+    value.map(finite_float_to_vrl).unwrap_or(VrlValue::Null)
+}
+
+/// Build a VRL-ready metric record from parts
+fn build_metric_record(parts: MetricRecordParts) -> VrlValue {
+    let map = match parts {
+        MetricRecordParts::Gauge(parts) => gauge_map(parts),
+        MetricRecordParts::Sum(parts) => sum_map(parts),
+        MetricRecordParts::Histogram(parts) => histogram_map(parts),
+        MetricRecordParts::ExpHistogram(parts) => exp_histogram_map(parts),
+        MetricRecordParts::Summary(parts) => summary_map(parts),
+    };
     VrlValue::Object(map)
 }
 
-fn build_sum_record(parts: SumRecordParts) -> VrlValue {
+/// The fields of a record every metric type has, tagged with its type
+fn point_map(point: MetricPointParts, metric_type: &'static str) -> ObjectMap {
     let mut map = ObjectMap::new();
     map.insert(
         "time_unix_nano".into(),
-        VrlValue::Integer(parts.time_unix_nano),
+        VrlValue::Integer(point.time_unix_nano),
     );
     map.insert(
         "start_time_unix_nano".into(),
-        VrlValue::Integer(parts.start_time_unix_nano),
+        VrlValue::Integer(point.start_time_unix_nano),
     );
-    map.insert("metric_name".into(), VrlValue::Bytes(parts.metric_name));
+    map.insert("metric_name".into(), VrlValue::Bytes(point.metric_name));
     map.insert(
         "metric_description".into(),
-        VrlValue::Bytes(parts.metric_description),
+        VrlValue::Bytes(point.metric_description),
     );
-    map.insert("metric_unit".into(), VrlValue::Bytes(parts.metric_unit));
+    map.insert("metric_unit".into(), VrlValue::Bytes(point.metric_unit));
+    map.insert("attributes".into(), point.attributes);
+    map.insert("resource".into(), (*point.resource).clone());
+    map.insert("scope".into(), (*point.scope).clone());
+    map.insert("flags".into(), VrlValue::Integer(point.flags));
+    map.insert(
+        "_metric_type".into(),
+        VrlValue::Bytes(Bytes::from_static(metric_type.as_bytes())),
+    );
+    map
+}
+
+fn gauge_map(parts: GaugeRecordParts) -> ObjectMap {
+    let mut map = point_map(parts.point, "gauge");
     map.insert("value".into(), parts.value);
-    map.insert("attributes".into(), parts.attributes);
-    map.insert("resource".into(), (*parts.resource).clone());
-    map.insert("scope".into(), (*parts.scope).clone());
-    map.insert("flags".into(), VrlValue::Integer(parts.flags));
-    map.insert("exemplars".into(), build_exemplars_array(parts.exemplars));
+    map.insert("exemplars".into(), exemplars_value(parts.exemplars));
+    map
+}
+
+fn sum_map(parts: SumRecordParts) -> ObjectMap {
+    let mut map = point_map(parts.point, "sum");
+    map.insert("value".into(), parts.value);
+    map.insert("exemplars".into(), exemplars_value(parts.exemplars));
     map.insert(
         "aggregation_temporality".into(),
         VrlValue::Integer(parts.aggregation_temporality),
     );
     map.insert("is_monotonic".into(), VrlValue::Boolean(parts.is_monotonic));
-    map.insert("_metric_type".into(), VrlValue::Bytes(Bytes::from("sum")));
-    VrlValue::Object(map)
+    map
 }
 
-fn build_histogram_record(parts: HistogramRecordParts) -> VrlValue {
-    let mut map = ObjectMap::new();
-    map.insert(
-        "time_unix_nano".into(),
-        VrlValue::Integer(parts.time_unix_nano),
-    );
-    map.insert(
-        "start_time_unix_nano".into(),
-        VrlValue::Integer(parts.start_time_unix_nano),
-    );
-    map.insert("metric_name".into(), VrlValue::Bytes(parts.metric_name));
-    map.insert(
-        "metric_description".into(),
-        VrlValue::Bytes(parts.metric_description),
-    );
-    map.insert("metric_unit".into(), VrlValue::Bytes(parts.metric_unit));
+fn histogram_map(parts: HistogramRecordParts) -> ObjectMap {
+    let mut map = point_map(parts.point, "histogram");
     map.insert("count".into(), VrlValue::Integer(parts.count));
-    map.insert(
-        "sum".into(),
-        parts.sum.map(finite_float_to_vrl).unwrap_or(VrlValue::Null),
-    );
-    map.insert(
-        "min".into(),
-        parts.min.map(finite_float_to_vrl).unwrap_or(VrlValue::Null),
-    );
-    map.insert(
-        "max".into(),
-        parts.max.map(finite_float_to_vrl).unwrap_or(VrlValue::Null),
-    );
+    map.insert("sum".into(), optional_float(parts.sum));
+    map.insert("min".into(), optional_float(parts.min));
+    map.insert("max".into(), optional_float(parts.max));
     map.insert("bucket_counts".into(), VrlValue::Bytes(parts.bucket_counts));
     map.insert(
         "explicit_bounds".into(),
         VrlValue::Bytes(parts.explicit_bounds),
     );
-    map.insert("attributes".into(), parts.attributes);
-    map.insert("resource".into(), (*parts.resource).clone());
-    map.insert("scope".into(), (*parts.scope).clone());
-    map.insert("flags".into(), VrlValue::Integer(parts.flags));
-    map.insert("exemplars".into(), build_exemplars_array(parts.exemplars));
+    map.insert("exemplars".into(), exemplars_value(parts.exemplars));
     map.insert(
         "aggregation_temporality".into(),
         VrlValue::Integer(parts.aggregation_temporality),
     );
-    map.insert(
-        "_metric_type".into(),
-        VrlValue::Bytes(Bytes::from("histogram")),
-    );
-    VrlValue::Object(map)
+    map
 }
 
-fn build_exp_histogram_record(parts: ExpHistogramRecordParts) -> VrlValue {
-    let mut map = ObjectMap::new();
-    map.insert(
-        "time_unix_nano".into(),
-        VrlValue::Integer(parts.time_unix_nano),
-    );
-    map.insert(
-        "start_time_unix_nano".into(),
-        VrlValue::Integer(parts.start_time_unix_nano),
-    );
-    map.insert("metric_name".into(), VrlValue::Bytes(parts.metric_name));
-    map.insert(
-        "metric_description".into(),
-        VrlValue::Bytes(parts.metric_description),
-    );
-    map.insert("metric_unit".into(), VrlValue::Bytes(parts.metric_unit));
+fn exp_histogram_map(parts: ExpHistogramRecordParts) -> ObjectMap {
+    let mut map = point_map(parts.point, "exp_histogram");
     map.insert("count".into(), VrlValue::Integer(parts.count));
-    map.insert(
-        "sum".into(),
-        parts.sum.map(finite_float_to_vrl).unwrap_or(VrlValue::Null),
-    );
-    map.insert(
-        "min".into(),
-        parts.min.map(finite_float_to_vrl).unwrap_or(VrlValue::Null),
-    );
-    map.insert(
-        "max".into(),
-        parts.max.map(finite_float_to_vrl).unwrap_or(VrlValue::Null),
-    );
+    map.insert("sum".into(), optional_float(parts.sum));
+    map.insert("min".into(), optional_float(parts.min));
+    map.insert("max".into(), optional_float(parts.max));
     map.insert("scale".into(), VrlValue::Integer(parts.scale));
     map.insert("zero_count".into(), VrlValue::Integer(parts.zero_count));
     map.insert(
@@ -1243,20 +1372,23 @@ fn build_exp_histogram_record(parts: ExpHistogramRecordParts) -> VrlValue {
         "negative_bucket_counts".into(),
         VrlValue::Bytes(parts.negative_bucket_counts),
     );
-    map.insert("attributes".into(), parts.attributes);
-    map.insert("resource".into(), (*parts.resource).clone());
-    map.insert("scope".into(), (*parts.scope).clone());
-    map.insert("flags".into(), VrlValue::Integer(parts.flags));
-    map.insert("exemplars".into(), build_exemplars_array(parts.exemplars));
+    map.insert("exemplars".into(), exemplars_value(parts.exemplars));
     map.insert(
         "aggregation_temporality".into(),
         VrlValue::Integer(parts.aggregation_temporality),
     );
+    map
+}
+
+fn summary_map(parts: SummaryRecordParts) -> ObjectMap {
+    let mut map = point_map(parts.point, "summary");
+    map.insert("count".into(), VrlValue::Integer(parts.count));
+    map.insert("sum".into(), finite_float_to_vrl(parts.sum));
     map.insert(
-        "_metric_type".into(),
-        VrlValue::Bytes(Bytes::from("exp_histogram")),
+        "quantile_values".into(),
+        VrlValue::Bytes(parts.quantile_values),
     );
-    VrlValue::Object(map)
+    map
 }
 
 // ============================================================================
@@ -1268,7 +1400,10 @@ mod tests {
     use super::*;
     use opentelemetry_proto::tonic::{
         common::v1::InstrumentationScope,
-        metrics::v1::{Gauge, Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum},
+        metrics::v1::{
+            summary_data_point::ValueAtQuantile, Gauge, Metric, NumberDataPoint, ResourceMetrics,
+            ScopeMetrics, Sum, Summary, SummaryDataPoint,
+        },
         resource::v1::Resource,
     };
 
@@ -1302,7 +1437,7 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body);
+        let result = decode_protobuf(&body, false).map(DecodedMetrics::into_result);
 
         assert!(result.is_ok());
         let decode_result = result.unwrap();
@@ -1354,7 +1489,7 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body);
+        let result = decode_protobuf(&body, false).map(DecodedMetrics::into_result);
 
         assert!(result.is_ok());
         let decode_result = result.unwrap();
@@ -1379,6 +1514,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decodes_summary_metric() {
+        let point = SummaryDataPoint {
+            time_unix_nano: 1_000_000_000,
+            start_time_unix_nano: 900_000_000,
+            count: 10,
+            sum: 123.5,
+            quantile_values: vec![
+                ValueAtQuantile {
+                    quantile: 0.5,
+                    value: 11.0,
+                },
+                ValueAtQuantile {
+                    quantile: 0.99,
+                    value: 42.25,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource::default()),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope::default()),
+                    metrics: vec![Metric {
+                        name: "test.summary".to_string(),
+                        data: Some(Data::Summary(Summary {
+                            data_points: vec![point],
+                        })),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+
+        let body = request.encode_to_vec();
+        let decode_result = decode_protobuf(&body, false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
+        assert_eq!(decode_result.values.len(), 1);
+        assert!(!decode_result.skipped.has_skipped());
+
+        if let VrlValue::Object(map) = &decode_result.values[0] {
+            assert_eq!(
+                map.get("_metric_type"),
+                Some(&VrlValue::Bytes(Bytes::from("summary")))
+            );
+            assert_eq!(map.get("count"), Some(&VrlValue::Integer(10)));
+            assert_eq!(map.get("sum"), Some(&finite_float_to_vrl(123.5)));
+            assert_eq!(
+                map.get("quantile_values"),
+                Some(&VrlValue::Bytes(Bytes::from(
+                    r#"[{"quantile":0.5,"value":11.0},{"quantile":0.99,"value":42.25}]"#
+                )))
+            );
+        } else {
+            panic!("expected object");
+        }
+    }
+
     fn make_gauge_request(points: Vec<NumberDataPoint>) -> ExportMetricsServiceRequest {
         ExportMetricsServiceRequest {
             resource_metrics: vec![ResourceMetrics {
@@ -1409,7 +1607,9 @@ mod tests {
 
         let request = make_gauge_request(vec![point]);
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body).unwrap();
+        let result = decode_protobuf(&body, false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
 
         assert_eq!(result.values.len(), 0, "NaN value should be skipped");
         assert_eq!(result.skipped.nan_values, 1, "Should track NaN skip");
@@ -1425,7 +1625,9 @@ mod tests {
 
         let request = make_gauge_request(vec![point]);
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body).unwrap();
+        let result = decode_protobuf(&body, false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
 
         assert_eq!(result.values.len(), 0, "Infinity value should be skipped");
         assert_eq!(
@@ -1444,7 +1646,9 @@ mod tests {
 
         let request = make_gauge_request(vec![point]);
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body).unwrap();
+        let result = decode_protobuf(&body, false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
 
         assert_eq!(
             result.values.len(),
@@ -1467,7 +1671,9 @@ mod tests {
 
         let request = make_gauge_request(vec![point]);
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body).unwrap();
+        let result = decode_protobuf(&body, false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
 
         assert_eq!(result.values.len(), 0, "Missing value should be skipped");
         assert_eq!(
@@ -1496,7 +1702,9 @@ mod tests {
 
         let request = make_gauge_request(vec![valid_point, nan_point, missing_point]);
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body).unwrap();
+        let result = decode_protobuf(&body, false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
 
         assert_eq!(result.values.len(), 1, "Only valid point should be kept");
         assert!(result.skipped.has_skipped(), "Should have skipped metrics");
@@ -1511,7 +1719,9 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body).unwrap();
+        let result = decode_protobuf(&body, false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
         assert!(result.values.is_empty());
         assert!(!result.skipped.has_skipped());
     }
@@ -1519,7 +1729,9 @@ mod tests {
     #[test]
     fn handles_empty_json_request() {
         let body = r#"{"resourceMetrics": []}"#;
-        let result = decode_json(body.as_bytes()).unwrap();
+        let result = decode_json(body.as_bytes(), false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
         assert!(result.values.is_empty());
         assert!(!result.skipped.has_skipped());
     }
@@ -1562,7 +1774,9 @@ mod tests {
     #[test]
     fn skips_json_gauge_with_missing_value() {
         let json = make_gauge_json(None, None);
-        let result = decode_json(json.as_bytes()).unwrap();
+        let result = decode_json(json.as_bytes(), false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
         assert_eq!(result.values.len(), 0, "Missing value should be skipped");
         assert_eq!(result.skipped.missing_values, 1);
     }
@@ -1570,14 +1784,18 @@ mod tests {
     #[test]
     fn accepts_json_gauge_with_valid_double() {
         let json = make_gauge_json(Some(42.5), None);
-        let result = decode_json(json.as_bytes()).unwrap();
+        let result = decode_json(json.as_bytes(), false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
         assert_eq!(result.values.len(), 1, "Valid double should be accepted");
     }
 
     #[test]
     fn accepts_json_gauge_with_valid_int() {
         let json = make_gauge_json(None, Some("42"));
-        let result = decode_json(json.as_bytes()).unwrap();
+        let result = decode_json(json.as_bytes(), false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
         assert_eq!(result.values.len(), 1, "Valid int should be accepted");
     }
 
@@ -1603,7 +1821,9 @@ mod tests {
             }]
         }"#;
 
-        let result = decode_json(body.as_bytes()).unwrap();
+        let result = decode_json(body.as_bytes(), false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
         assert_eq!(result.values.len(), 1);
 
         let record = &result.values[0];
@@ -1620,4 +1840,106 @@ mod tests {
             panic!("expected object");
         }
     }
+
+    #[test]
+    fn decodes_json_summary_payload() {
+        let body = r#"{
+            "resourceMetrics": [{
+                "scopeMetrics": [{
+                    "metrics": [{
+                        "name": "rpc.latency",
+                        "summary": {
+                            "dataPoints": [{
+                                "timeUnixNano": "1000000000",
+                                "count": "3",
+                                "sum": 7.5,
+                                "quantileValues": [
+                                    { "quantile": 0.5, "value": 2.0 },
+                                    { "quantile": 1.0, "value": 4.0 }
+                                ]
+                            }]
+                        }
+                    }]
+                }]
+            }]
+        }"#;
+
+        let result = decode_json(body.as_bytes(), false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
+        assert_eq!(result.values.len(), 1);
+
+        if let VrlValue::Object(map) = &result.values[0] {
+            assert_eq!(
+                map.get("_metric_type"),
+                Some(&VrlValue::Bytes(Bytes::from("summary")))
+            );
+            assert_eq!(map.get("count"), Some(&VrlValue::Integer(3)));
+            assert_eq!(map.get("sum"), Some(&finite_float_to_vrl(7.5)));
+            assert_eq!(
+                map.get("quantile_values"),
+                Some(&VrlValue::Bytes(Bytes::from(
+                    r#"[{"quantile":0.5,"value":2.0},{"quantile":1.0,"value":4.0}]"#
+                )))
+            );
+        } else {
+            panic!("expected object");
+        }
+    }
+
+    #[test]
+    fn decodes_json_exemplars_like_protobuf() {
+        // Canonical OTLP JSON flattens the value; the opentelemetry-proto serde
+        // derives nest it under "value"
+        let body = r#"{
+            "resourceMetrics": [{
+                "scopeMetrics": [{
+                    "metrics": [{
+                        "name": "test.gauge",
+                        "gauge": {
+                            "dataPoints": [{
+                                "timeUnixNano": "1000000000",
+                                "asDouble": 1.0,
+                                "exemplars": [
+                                    {"timeUnixNano": "1000", "asInt": "7",
+                                     "traceId": "0af7651916cd43dd8448eb211c80319c",
+                                     "spanId": "b7ad6b7169203331"},
+                                    {"timeUnixNano": "2000", "value": {"asDouble": 1.5}}
+                                ]
+                            }]
+                        }
+                    }]
+                }]
+            }]
+        }"#;
+
+        let result = decode_json(body.as_bytes(), false)
+            .map(DecodedMetrics::into_result)
+            .unwrap();
+
+        let VrlValue::Object(map) = &result.values[0] else {
+            panic!("expected object");
+        };
+        let Some(VrlValue::Array(exemplars)) = map.get("exemplars") else {
+            panic!("expected exemplars array");
+        };
+        let VrlValue::Object(first) = &exemplars[0] else {
+            panic!("expected object");
+        };
+        assert_eq!(first.get("value"), Some(&finite_float_to_vrl(7.0)));
+        assert_eq!(
+            first.get("trace_id"),
+            Some(&VrlValue::Bytes(Bytes::from(
+                "0af7651916cd43dd8448eb211c80319c"
+            )))
+        );
+        assert_eq!(
+            first.get("span_id"),
+            Some(&VrlValue::Bytes(Bytes::from("b7ad6b7169203331")))
+        );
+        let VrlValue::Object(second) = &exemplars[1] else {
+            panic!("expected object");
+        };
+        assert_eq!(second.get("value"), Some(&finite_float_to_vrl(1.5)));
+    }
 }
@@ -1,6 +1,6 @@
 //! OTLP decode layer - transforms raw bytes into VRL Values
 //!
-//! This module provides decoders for OTLP logs, traces, and metrics in both
+//! This module provides decoders for OTLP logs, traces, metrics, and profiles in both
 //! protobuf and JSON formats. The output is `Vec<Value>` where each Value
 //! represents a single record ready for transformation.
 //!
@@ -12,21 +12,32 @@
 //! let records = decode_logs(bytes, InputFormat::Protobuf)?;
 //! ```
 //!
-//! # Simplifications from otlp2pipeline
+//! # Compression
 //!
-//! - No Gzip decompression (caller's responsibility)
+//! Gzip and zstd request bodies can be inflated with [`decompress`] before
+//! decoding. The decoders require the `compression` feature; without it only
+//! [`ContentEncoding::Identity`] is accepted.
 
 mod common;
+mod compression;
 mod logs;
 mod metrics;
 mod normalize;
+mod profiles;
 mod traces;
 
+pub(crate) use common::{finite_float_to_vrl, safe_timestamp_conversion, Decoded, RecordOrigin};
 pub use common::{looks_like_json, DecodeError};
+pub use compression::{decompress, ContentEncoding, DEFAULT_MAX_DECOMPRESSED_SIZE};
+pub(crate) use logs::LogRecordParts;
+pub(crate) use metrics::{
+    exemplars_value, optional_float, DecodedMetrics, MetricPointParts, MetricRecordParts,
+};
 pub use metrics::{DecodeMetricsResult, SkippedMetrics};
 pub use normalize::{
     count_skipped_metric_data_points, normalise_json_value, normalize_json_bytes, MetricSkipCounts,
 };
+pub(crate) use traces::{span_events_value, span_links_value, SpanRecordParts};
 use vrl::value::Value;
 
 /// Input format for OTLP decoding
@@ -38,6 +49,8 @@ pub enum InputFormat {
     Json,
     /// Newline-delimited JSON (JSONL/NDJSON) format
     Jsonl,
+    /// Back-to-back protobuf messages, each prefixed with its varint length
+    ProtobufDelimited,
     /// Auto-detect JSON vs protobuf, with fallback decoding
     Auto,
 }
@@ -49,6 +62,7 @@ impl InputFormat {
 
         match content_type.as_deref() {
             Some("application/x-ndjson") | Some("application/jsonl") => InputFormat::Jsonl,
+            Some("application/x-protobuf-delimited") => InputFormat::ProtobufDelimited,
             Some("application/json") | Some("application/otlp+json") => InputFormat::Json,
             Some("application/x-protobuf")
             | Some("application/protobuf")
@@ -63,6 +77,7 @@ impl InputFormat {
             InputFormat::Protobuf => "application/x-protobuf",
             InputFormat::Json => "application/json",
             InputFormat::Jsonl => "application/x-ndjson",
+            InputFormat::ProtobufDelimited => "application/x-protobuf-delimited",
             InputFormat::Auto => "application/x-protobuf", // Default to protobuf
         }
     }
@@ -82,38 +97,37 @@ impl InputFormat {
 /// - `resource`: object with `attributes`
 /// - `scope`: object with `name`, `version`, `attributes`
 pub fn decode_logs(bytes: &[u8], format: InputFormat) -> Result<Vec<Value>, DecodeError> {
-    match format {
-        InputFormat::Protobuf => logs::decode_protobuf(bytes),
-        InputFormat::Json => logs::decode_json(bytes),
-        InputFormat::Jsonl => decode_jsonl(bytes, logs::decode_json),
-        InputFormat::Auto => {
-            if looks_like_json(bytes) {
-                match logs::decode_json(bytes) {
-                    Ok(values) => Ok(values),
-                    Err(json_err) => {
-                        // Try JSONL if JSON failed (e.g., multiple JSON objects)
-                        match decode_jsonl(bytes, logs::decode_json) {
-                            Ok(values) => Ok(values),
-                            Err(_jsonl_err) => logs::decode_protobuf(bytes).map_err(|proto_err| {
-                                DecodeError::Unsupported(format!(
-                                    "json decode failed: {json_err}; protobuf fallback failed: {proto_err}"
-                                ))
-                            }),
-                        }
-                    }
-                }
-            } else {
-                match logs::decode_protobuf(bytes) {
-                    Ok(values) => Ok(values),
-                    Err(proto_err) => logs::decode_json(bytes).map_err(|json_err| {
-                        DecodeError::Unsupported(format!(
-                            "protobuf decode failed: {proto_err}; json fallback failed: {json_err}"
-                        ))
-                    }),
-                }
-            }
-        }
-    }
+    decode_log_records(bytes, format, false).map(|records| records.values)
+}
+
+/// Decode OTLP logs, keeping each record's origin. In lenient mode records
+/// that fail to decode are reported instead of failing the payload.
+pub(crate) fn decode_log_records(
+    bytes: &[u8],
+    format: InputFormat,
+    lenient: bool,
+) -> Result<Decoded, DecodeError> {
+    decode_payload(
+        bytes,
+        format,
+        lenient,
+        logs::decode_protobuf,
+        logs::decode_json,
+    )
+}
+
+/// Decode OTLP logs into record parts for the native fast path.
+pub(crate) fn decode_log_parts(
+    bytes: &[u8],
+    format: InputFormat,
+) -> Result<Decoded<LogRecordParts>, DecodeError> {
+    decode_payload(
+        bytes,
+        format,
+        false,
+        logs::decode_protobuf_parts,
+        logs::decode_json_parts,
+    )
 }
 
 /// Decode OTLP traces from raw bytes into VRL Values.
@@ -138,40 +152,36 @@ pub fn decode_logs(bytes: &[u8], format: InputFormat) -> Result<Vec<Value>, Deco
 /// - `dropped_*_count`: i64
 /// - `flags`: i64
 pub fn decode_traces(bytes: &[u8], format: InputFormat) -> Result<Vec<Value>, DecodeError> {
-    match format {
-        InputFormat::Protobuf => traces::decode_protobuf(bytes),
-        InputFormat::Json => traces::decode_json(bytes),
-        InputFormat::Jsonl => decode_jsonl(bytes, traces::decode_json),
-        InputFormat::Auto => {
-            if looks_like_json(bytes) {
-                match traces::decode_json(bytes) {
-                    Ok(values) => Ok(values),
-                    Err(json_err) => {
-                        // Try JSONL if JSON failed (e.g., multiple JSON objects)
-                        match decode_jsonl(bytes, traces::decode_json) {
-                            Ok(values) => Ok(values),
-                            Err(_jsonl_err) => {
-                                traces::decode_protobuf(bytes).map_err(|proto_err| {
-                                    DecodeError::Unsupported(format!(
-                                        "json decode failed: {json_err}; protobuf fallback failed: {proto_err}"
-                                    ))
-                                })
-                            }
-                        }
-                    }
-                }
-            } else {
-                match traces::decode_protobuf(bytes) {
-                    Ok(values) => Ok(values),
-                    Err(proto_err) => traces::decode_json(bytes).map_err(|json_err| {
-                        DecodeError::Unsupported(format!(
-                            "protobuf decode failed: {proto_err}; json fallback failed: {json_err}"
-                        ))
-                    }),
-                }
-            }
-        }
-    }
+    decode_trace_records(bytes, format, false).map(|records| records.values)
+}
+
+/// Decode OTLP traces, keeping each span's origin. See [`decode_log_records`].
+pub(crate) fn decode_trace_records(
+    bytes: &[u8],
+    format: InputFormat,
+    lenient: bool,
+) -> Result<Decoded, DecodeError> {
+    decode_payload(
+        bytes,
+        format,
+        lenient,
+        traces::decode_protobuf,
+        traces::decode_json,
+    )
+}
+
+/// Decode OTLP traces into span parts for the native fast path.
+pub(crate) fn decode_trace_parts(
+    bytes: &[u8],
+    format: InputFormat,
+) -> Result<Decoded<SpanRecordParts>, DecodeError> {
+    decode_payload(
+        bytes,
+        format,
+        false,
+        traces::decode_protobuf_parts,
+        traces::decode_json_parts,
+    )
 }
 
 /// Decode OTLP metrics from raw bytes into VRL Values.
@@ -188,16 +198,18 @@ pub fn decode_traces(bytes: &[u8], format: InputFormat) -> Result<Vec<Value>, De
 /// - `scope`: object with `name`, `version`, `attributes`
 /// - `flags`: i64
 /// - `exemplars`: array of exemplar objects
-/// - `_metric_type`: "gauge" or "sum"
+/// - `_metric_type`: "gauge", "sum", "histogram", "exp_histogram", or "summary"
 ///
 /// For sum metrics, additional fields:
 /// - `aggregation_temporality`: i64
 /// - `is_monotonic`: bool
 ///
+/// Summary metrics carry `count`, `sum`, and `quantile_values` (a JSON string)
+/// in place of `value` and have no exemplars.
+///
 /// # Skipped Metrics
 ///
 /// The following are skipped and tracked in the returned [`DecodeMetricsResult::skipped`]:
-/// - Summary metric types (deprecated in OTLP spec)
 /// - Data points with non-finite values (NaN, Infinity)
 /// - Data points with missing values
 ///
@@ -206,32 +218,140 @@ pub fn decode_metrics(
     bytes: &[u8],
     format: InputFormat,
 ) -> Result<DecodeMetricsResult, DecodeError> {
+    decode_metric_records(bytes, format, false).map(DecodedMetrics::into_result)
+}
+
+/// Decode OTLP metrics, keeping each data point's origin. See
+/// [`decode_log_records`].
+pub(crate) fn decode_metric_records(
+    bytes: &[u8],
+    format: InputFormat,
+    lenient: bool,
+) -> Result<DecodedMetrics, DecodeError> {
+    decode_payload(
+        bytes,
+        format,
+        lenient,
+        metrics::decode_protobuf,
+        metrics::decode_json,
+    )
+}
+
+/// Decode OTLP metrics into record parts for the native fast path.
+pub(crate) fn decode_metric_parts(
+    bytes: &[u8],
+    format: InputFormat,
+) -> Result<DecodedMetrics<MetricRecordParts>, DecodeError> {
+    decode_payload(
+        bytes,
+        format,
+        false,
+        metrics::decode_protobuf_parts,
+        metrics::decode_json_parts,
+    )
+}
+
+/// Decode OTLP profiles from raw bytes into VRL Values.
+///
+/// Each returned Value represents a single profile sample with fields:
+/// - `time_unix_nano`: i64 (first sample timestamp, else profile time)
+/// - `duration_nano`: i64
+/// - `profile_id`: hex string
+/// - `sample_type` / `sample_unit`: string
+/// - `period_type` / `period_unit`: string
+/// - `period`: i64
+/// - `value`: i64 (sum of sample values, or the timestamp count)
+/// - `frames`: array of frame objects, leaf first, with `function`, `file`,
+///   `line`, `column`, `address` and `mapping` (unset fields omitted)
+/// - `trace_id` / `span_id`: hex strings from the sample's link
+/// - `attributes`: object (sample attributes)
+/// - `profile_attributes`: object
+/// - `resource`: object with `attributes`
+/// - `scope`: object with `name`, `version`, `attributes`
+pub fn decode_profiles(bytes: &[u8], format: InputFormat) -> Result<Vec<Value>, DecodeError> {
+    decode_profile_records(bytes, format, false).map(|records| records.values)
+}
+
+/// Decode OTLP profiles, keeping each sample's origin. See
+/// [`decode_log_records`].
+pub(crate) fn decode_profile_records(
+    bytes: &[u8],
+    format: InputFormat,
+    lenient: bool,
+) -> Result<Decoded, DecodeError> {
+    decode_payload(
+        bytes,
+        format,
+        lenient,
+        profiles::decode_protobuf,
+        profiles::decode_json,
+    )
+}
+
+// ============================================================================
+// Format dispatch
+// ============================================================================
+
+/// Records decoded from one message, appendable across the messages of a
+/// JSONL or length-delimited payload.
+trait Messages: Default {
+    fn extend(&mut self, other: Self);
+}
+
+impl<T> Messages for Decoded<T> {
+    fn extend(&mut self, other: Self) {
+        Decoded::extend(self, other)
+    }
+}
+
+impl<T> Messages for DecodedMetrics<T> {
+    fn extend(&mut self, other: Self) {
+        DecodedMetrics::extend(self, other)
+    }
+}
+
+/// Decode a payload in the given format with a signal's protobuf and JSON
+/// message decoders.
+fn decode_payload<T, P, J>(
+    bytes: &[u8],
+    format: InputFormat,
+    lenient: bool,
+    decode_protobuf: P,
+    decode_json: J,
+) -> Result<T, DecodeError>
+where
+    T: Messages,
+    P: Fn(&[u8], bool) -> Result<T, DecodeError>,
+    J: Fn(&[u8], bool) -> Result<T, DecodeError>,
+{
+    let decode_protobuf = |bytes: &[u8]| decode_protobuf(bytes, lenient);
+    let decode_json = |bytes: &[u8]| decode_json(bytes, lenient);
+
     match format {
-        InputFormat::Protobuf => metrics::decode_protobuf(bytes),
-        InputFormat::Json => metrics::decode_json(bytes),
-        InputFormat::Jsonl => decode_metrics_jsonl(bytes),
+        InputFormat::Protobuf => decode_protobuf(bytes),
+        InputFormat::Json => decode_json(bytes),
+        InputFormat::Jsonl => decode_jsonl(bytes, decode_json),
+        InputFormat::ProtobufDelimited => decode_delimited(bytes, decode_protobuf),
         InputFormat::Auto => {
             if looks_like_json(bytes) {
-                match metrics::decode_json(bytes) {
+                match decode_json(bytes) {
                     Ok(values) => Ok(values),
                     Err(json_err) => {
                         // Try JSONL if JSON failed (e.g., multiple JSON objects)
-                        match decode_metrics_jsonl(bytes) {
+                        match decode_jsonl(bytes, decode_json) {
                             Ok(values) => Ok(values),
-                            Err(_jsonl_err) => {
-                                metrics::decode_protobuf(bytes).map_err(|proto_err| {
-                                    DecodeError::Unsupported(format!(
-                                        "json decode failed: {json_err}; protobuf fallback failed: {proto_err}"
-                                    ))
-                                })
-                            }
+                            Err(_jsonl_err) => decode_protobuf(bytes).map_err(|proto_err| {
+                                DecodeError::Unsupported(format!(
+                                    "json decode failed: {json_err}; protobuf fallback failed: {proto_err}"
+                                ))
+                            }),
                         }
                     }
                 }
             } else {
-                match metrics::decode_protobuf(bytes) {
+                match decode_protobuf(bytes) {
                     Ok(values) => Ok(values),
-                    Err(proto_err) => metrics::decode_json(bytes).map_err(|json_err| {
+                    Err(proto_err) => decode_json(bytes).map_err(|json_err| {
                         DecodeError::Unsupported(format!(
                             "protobuf decode failed: {proto_err}; json fallback failed: {json_err}"
                         ))
@@ -246,14 +366,15 @@ pub fn decode_metrics(
 // JSONL decoding helpers
 // ============================================================================
 
-/// Generic JSONL decoder for logs and traces.
+/// Generic JSONL decoder.
 /// Processes each non-empty line as a separate JSON payload and combines results.
-fn decode_jsonl<F>(bytes: &[u8], decode_json_fn: F) -> Result<Vec<Value>, DecodeError>
+fn decode_jsonl<T, F>(bytes: &[u8], decode_json_fn: F) -> Result<T, DecodeError>
 where
-    F: Fn(&[u8]) -> Result<Vec<Value>, DecodeError>,
+    T: Messages,
+    F: Fn(&[u8]) -> Result<T, DecodeError>,
 {
     let text = std::str::from_utf8(bytes).map_err(|e| DecodeError::Parse(e.to_string()))?;
-    let mut all_values = Vec::new();
+    let mut all = T::default();
     let mut saw_line = false;
 
     for (line_num, line) in text.lines().enumerate() {
@@ -263,9 +384,9 @@ where
         }
         saw_line = true;
 
-        let values = decode_json_fn(trimmed.as_bytes())
+        let decoded = decode_json_fn(trimmed.as_bytes())
             .map_err(|e| DecodeError::Parse(format!("line {}: {}", line_num + 1, e)))?;
-        all_values.extend(values);
+        all.extend(decoded);
     }
 
     if !saw_line {
@@ -274,45 +395,51 @@ where
         ));
     }
 
-    Ok(all_values)
+    Ok(all)
 }
 
-/// JSONL decoder for metrics - handles the special DecodeMetricsResult return type.
-fn decode_metrics_jsonl(bytes: &[u8]) -> Result<DecodeMetricsResult, DecodeError> {
-    let text = std::str::from_utf8(bytes).map_err(|e| DecodeError::Parse(e.to_string()))?;
-    let mut all_values = Vec::new();
-    let mut combined_skipped = SkippedMetrics::default();
-    let mut saw_line = false;
+// ============================================================================
+// Length-delimited protobuf decoding helpers
+// ============================================================================
 
-    for (line_num, line) in text.lines().enumerate() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
+/// Split a length-delimited protobuf stream into its framed messages.
+fn delimited_frames(mut bytes: &[u8]) -> Result<Vec<&[u8]>, DecodeError> {
+    let mut frames = Vec::new();
+
+    while !bytes.is_empty() {
+        let frame_num = frames.len() + 1;
+        let len = prost::decode_length_delimiter(&mut bytes)
+            .map_err(|e| DecodeError::Parse(format!("message {frame_num}: {e}")))?;
+        if len > bytes.len() {
+            return Err(DecodeError::Parse(format!(
+                "message {frame_num}: length {len} exceeds remaining {} bytes",
+                bytes.len()
+            )));
         }
-        saw_line = true;
-
-        let result = metrics::decode_json(trimmed.as_bytes())
-            .map_err(|e| DecodeError::Parse(format!("line {}: {}", line_num + 1, e)))?;
+        let (frame, rest) = bytes.split_at(len);
+        frames.push(frame);
+        bytes = rest;
+    }
 
-        all_values.extend(result.values);
+    Ok(frames)
+}
 
-        // Merge skipped counts
-        combined_skipped.summaries += result.skipped.summaries;
-        combined_skipped.nan_values += result.skipped.nan_values;
-        combined_skipped.infinity_values += result.skipped.infinity_values;
-        combined_skipped.missing_values += result.skipped.missing_values;
-    }
+/// Generic length-delimited protobuf decoder.
+/// Decodes each framed message and combines results.
+fn decode_delimited<T, F>(bytes: &[u8], decode_protobuf_fn: F) -> Result<T, DecodeError>
+where
+    T: Messages,
+    F: Fn(&[u8]) -> Result<T, DecodeError>,
+{
+    let mut all = T::default();
 
-    if !saw_line {
-        return Err(DecodeError::Parse(
-            "jsonl payload contained no records".to_string(),
-        ));
+    for (frame_num, frame) in delimited_frames(bytes)?.into_iter().enumerate() {
+        let decoded = decode_protobuf_fn(frame)
+            .map_err(|e| DecodeError::Parse(format!("message {}: {}", frame_num + 1, e)))?;
+        all.extend(decoded);
     }
 
-    Ok(DecodeMetricsResult {
-        values: all_values,
-        skipped: combined_skipped,
-    })
+    Ok(all)
 }
 
 #[cfg(test)]
@@ -356,6 +483,109 @@ mod tests {
         assert_eq!(InputFormat::Json.content_type(), "application/json");
         assert_eq!(InputFormat::Jsonl.content_type(), "application/x-ndjson");
         assert_eq!(InputFormat::Auto.content_type(), "application/x-protobuf");
+        assert_eq!(
+            InputFormat::ProtobufDelimited.content_type(),
+            "application/x-protobuf-delimited"
+        );
+    }
+
+    fn delimited<M: prost::Message>(messages: &[M]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for message in messages {
+            message.encode_length_delimited(&mut out).unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn decode_logs_protobuf_delimited() {
+        use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+        use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
+
+        let request = |count: usize| ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                scope_logs: vec![ScopeLogs {
+                    log_records: vec![LogRecord::default(); count],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let bytes = delimited(&[request(2), request(3)]);
+
+        let values = decode_logs(&bytes, InputFormat::ProtobufDelimited).unwrap();
+        assert_eq!(values.len(), 5);
+        assert!(decode_logs(b"", InputFormat::ProtobufDelimited)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn decode_log_records_lenient_jsonl_locations() {
+        use crate::error::RecordLocation;
+
+        let line1 = r#"{"resourceLogs":[{"scopeLogs":[{"logRecords":[{"timeUnixNano":"1"}]}]},{"scopeLogs":[{"logRecords":[{"timeUnixNano":"2"}]}]}]}"#;
+        let line2 = r#"{"resourceLogs":[{"scopeLogs":[{},{"logRecords":[{"timeUnixNano":"3"},{"timeUnixNano":"18446744073709551615"}]}]}]}"#;
+        let jsonl = format!("{line1}\n{line2}");
+
+        let decoded = decode_log_records(jsonl.as_bytes(), InputFormat::Jsonl, true).unwrap();
+        assert_eq!(decoded.values.len(), 3);
+        assert_eq!(decoded.errors.len(), 1);
+        assert_eq!(decoded.errors[0].index, 3);
+        assert_eq!(
+            decoded.errors[0].location,
+            RecordLocation {
+                resource_index: 2,
+                scope_index: 1
+            }
+        );
+
+        let err = decode_log_records(jsonl.as_bytes(), InputFormat::Jsonl, false).unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+    }
+
+    #[test]
+    fn decode_traces_protobuf_delimited_truncated() {
+        use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+
+        let mut bytes = delimited(&[ExportTraceServiceRequest::default()]);
+        bytes.extend_from_slice(&[0x05, 0x0a]);
+
+        let err = decode_traces(&bytes, InputFormat::ProtobufDelimited).unwrap_err();
+        assert!(err.to_string().contains("message 2"), "{err}");
+    }
+
+    #[test]
+    fn decode_metrics_protobuf_delimited() {
+        use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+        use opentelemetry_proto::tonic::metrics::v1::{
+            metric, number_data_point, Gauge, Metric, NumberDataPoint, ResourceMetrics,
+            ScopeMetrics,
+        };
+
+        let request = |value: f64| ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                scope_metrics: vec![ScopeMetrics {
+                    metrics: vec![Metric {
+                        name: "g".to_string(),
+                        data: Some(metric::Data::Gauge(Gauge {
+                            data_points: vec![NumberDataPoint {
+                                value: Some(number_data_point::Value::AsDouble(value)),
+                                ..Default::default()
+                            }],
+                        })),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let bytes = delimited(&[request(1.0), request(f64::NAN)]);
+
+        let result = decode_metrics(&bytes, InputFormat::ProtobufDelimited).unwrap();
+        assert_eq!(result.values.len(), 1);
+        assert_eq!(result.skipped.nan_values, 1);
     }
 
     #[test]
@@ -0,0 +1,1099 @@
+//! OTLP profile decoding - protobuf and JSON
+//!
+//! Profiles reference a request-wide dictionary (strings, functions,
+//! locations, stacks, links, attributes) by index. Each sample is flattened
+//! into one record with its stack resolved to frames. JSON requests are
+//! converted to the protobuf types first so both formats share one resolver.
+
+use bytes::Bytes;
+use const_hex::encode as hex_encode;
+use opentelemetry_proto::tonic::collector::profiles::v1development::ExportProfilesServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{
+    any_value, AnyValue, ArrayValue, InstrumentationScope, KeyValue, KeyValueList,
+};
+use opentelemetry_proto::tonic::profiles::v1development::{
+    Function, KeyValueAndUnit, Line, Link, Location, Mapping, Profile, ProfilesDictionary,
+    ResourceProfiles, Sample, ScopeProfiles, Stack, ValueType,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use prost::Message;
+use serde::Deserialize;
+use std::sync::Arc;
+use vrl::value::{KeyString, ObjectMap, Value as VrlValue};
+
+use super::common::{
+    decode_bytes_field, for_each_resource_scope, otlp_any_value_to_vrl, otlp_resource_to_value,
+    otlp_scope_to_value, safe_timestamp_conversion, DecodeError, Decoded, JsonAnyValue,
+    JsonInstrumentationScope, JsonKeyValue, JsonNumberOrString, JsonResource,
+};
+
+// ============================================================================
+// Protobuf decoding
+// ============================================================================
+
+pub fn decode_protobuf(body: &[u8], lenient: bool) -> Result<Decoded, DecodeError> {
+    let request = ExportProfilesServiceRequest::decode(body)?;
+    export_profiles_to_vrl_proto(request, lenient)
+}
+
+fn export_profiles_to_vrl_proto(
+    request: ExportProfilesServiceRequest,
+    lenient: bool,
+) -> Result<Decoded, DecodeError> {
+    let capacity: usize = request
+        .resource_profiles
+        .iter()
+        .flat_map(|rp| &rp.scope_profiles)
+        .flat_map(|sp| &sp.profiles)
+        .map(|p| p.sample.len())
+        .sum();
+    let mut out = Decoded::new(lenient, capacity);
+    let mut dictionary = Dictionary::new(request.dictionary.unwrap_or_default());
+
+    let resources = for_each_resource_scope(
+        request.resource_profiles,
+        |resource_profiles| {
+            (
+                otlp_resource_to_value(resource_profiles.resource.as_ref()),
+                resource_profiles.scope_profiles,
+            )
+        },
+        |scope_profiles| {
+            (
+                otlp_scope_to_value(scope_profiles.scope.as_ref()),
+                scope_profiles.profiles,
+            )
+        },
+        |profiles, resource, scope, location| {
+            for profile in profiles {
+                let profile_parts = profile_parts(&profile, &dictionary);
+                for sample in profile.sample {
+                    let record = match &profile_parts {
+                        Ok(parts) => {
+                            sample_record(sample, parts, &mut dictionary, &resource, &scope)
+                        }
+                        // A bad profile rejects each of its samples
+                        Err(e) => Err(profile_error(e)),
+                    };
+                    out.push(location, record)?;
+                }
+            }
+
+            Ok::<(), DecodeError>(())
+        },
+    )?;
+
+    out.set_resources(resources);
+    Ok(out)
+}
+
+fn profile_parts(profile: &Profile, dictionary: &Dictionary) -> Result<ProfileParts, DecodeError> {
+    Ok(ProfileParts {
+        time_unix_nano: safe_timestamp_conversion(
+            profile.time_unix_nano,
+            "profile.time_unix_nano",
+        )?,
+        duration_nano: safe_timestamp_conversion(profile.duration_nano, "profile.duration_nano")?,
+        profile_id: Bytes::from(hex_encode(&profile.profile_id)),
+        sample_type: dictionary.value_type(profile.sample_type.as_ref())?,
+        period_type: dictionary.value_type(profile.period_type.as_ref())?,
+        period: profile.period,
+        attributes: dictionary.attributes(&profile.attribute_indices)?,
+    })
+}
+
+fn sample_record(
+    sample: Sample,
+    profile: &ProfileParts,
+    dictionary: &mut Dictionary,
+    resource: &Arc<VrlValue>,
+    scope: &Arc<VrlValue>,
+) -> Result<VrlValue, DecodeError> {
+    let time_unix_nano = match sample.timestamps_unix_nano.first() {
+        Some(ts) => safe_timestamp_conversion(*ts, "sample.timestamps_unix_nano")?,
+        None => profile.time_unix_nano,
+    };
+    // A sample without values counts one event per timestamp
+    let value = if sample.values.is_empty() {
+        sample.timestamps_unix_nano.len() as i64
+    } else {
+        sample
+            .values
+            .iter()
+            .fold(0i64, |acc, v| acc.saturating_add(*v))
+    };
+    let (trace_id, span_id) = dictionary.link(sample.link_index)?;
+
+    let parts = SampleRecordParts {
+        time_unix_nano,
+        value,
+        frames: dictionary.frames(sample.stack_index)?,
+        trace_id,
+        span_id,
+        attributes: dictionary.attributes(&sample.attribute_indices)?,
+        profile,
+        resource: Arc::clone(resource),
+        scope: Arc::clone(scope),
+    };
+    Ok(build_sample_record(parts))
+}
+
+/// Copy a profile-level error for each sample it rejects.
+fn profile_error(error: &DecodeError) -> DecodeError {
+    match error {
+        DecodeError::Unsupported(message) => DecodeError::Unsupported(message.clone()),
+        other => DecodeError::Parse(other.to_string()),
+    }
+}
+
+// ============================================================================
+// JSON decoding
+// ============================================================================
+
+pub fn decode_json(body: &[u8], lenient: bool) -> Result<Decoded, DecodeError> {
+    // Normalize JSON to convert enum strings to numbers before parsing
+    let normalized = super::normalize::normalize_json_bytes(body)?;
+    let request: JsonExportProfilesServiceRequest = serde_json::from_slice(&normalized)?;
+    export_profiles_to_vrl_proto(request.try_into()?, lenient)
+}
+
+// ============================================================================
+// Dictionary resolution
+// ============================================================================
+
+/// Request-wide lookup tables, with resolved stacks cached by index.
+struct Dictionary {
+    tables: ProfilesDictionary,
+    frames: Vec<Option<VrlValue>>,
+}
+
+impl Dictionary {
+    fn new(tables: ProfilesDictionary) -> Self {
+        let frames = vec![None; tables.stack_table.len()];
+        Self { tables, frames }
+    }
+
+    fn string(&self, index: i32, field: &str) -> Result<&str, DecodeError> {
+        Ok(lookup(&self.tables.string_table, index, field)?
+            .map(String::as_str)
+            .unwrap_or_default())
+    }
+
+    /// Resolve a ValueType to its (type, unit) strings.
+    fn value_type(&self, value_type: Option<&ValueType>) -> Result<(Bytes, Bytes), DecodeError> {
+        let Some(value_type) = value_type else {
+            return Ok((Bytes::new(), Bytes::new()));
+        };
+        Ok((
+            Bytes::from(
+                self.string(value_type.type_strindex, "value_type.type_strindex")?
+                    .to_string(),
+            ),
+            Bytes::from(
+                self.string(value_type.unit_strindex, "value_type.unit_strindex")?
+                    .to_string(),
+            ),
+        ))
+    }
+
+    fn attributes(&self, indices: &[i32]) -> Result<VrlValue, DecodeError> {
+        let mut map = ObjectMap::new();
+        for index in indices {
+            let Some(attribute) = lookup(&self.tables.attribute_table, *index, "attribute_index")?
+            else {
+                continue;
+            };
+            let Some(value) = attribute.value.as_ref() else {
+                continue;
+            };
+            let key = self.string(attribute.key_strindex, "attribute.key_strindex")?;
+            map.insert(KeyString::from(key), otlp_any_value_to_vrl(value));
+        }
+        Ok(VrlValue::Object(map))
+    }
+
+    /// Resolve a link index to hex (trace_id, span_id); index 0 is no link.
+    fn link(&self, index: i32) -> Result<(Bytes, Bytes), DecodeError> {
+        Ok(
+            match lookup(&self.tables.link_table, index, "sample.link_index")? {
+                Some(link) => (
+                    Bytes::from(hex_encode(&link.trace_id)),
+                    Bytes::from(hex_encode(&link.span_id)),
+                ),
+                None => (Bytes::new(), Bytes::new()),
+            },
+        )
+    }
+
+    /// Resolve a stack to frames, leaf first. Inlined functions of a
+    /// location each get their own frame.
+    fn frames(&mut self, stack_index: i32) -> Result<VrlValue, DecodeError> {
+        let Some(stack) = lookup(&self.tables.stack_table, stack_index, "sample.stack_index")?
+        else {
+            return Ok(VrlValue::Array(Vec::new()));
+        };
+        if let Some(frames) = &self.frames[stack_index as usize] {
+            return Ok(frames.clone());
+        }
+
+        let mut frames = Vec::with_capacity(stack.location_indices.len());
+        for location_index in &stack.location_indices {
+            let Some(location) = lookup(
+                &self.tables.location_table,
+                *location_index,
+                "stack.location_indices",
+            )?
+            else {
+                continue;
+            };
+            let mapping = match lookup(
+                &self.tables.mapping_table,
+                location.mapping_index,
+                "location.mapping_index",
+            )? {
+                Some(mapping) => {
+                    self.string(mapping.filename_strindex, "mapping.filename_strindex")?
+                }
+                None => "",
+            };
+
+            if location.line.is_empty() {
+                frames.push(self.frame(None, location, mapping)?);
+            }
+            for line in &location.line {
+                frames.push(self.frame(Some(line), location, mapping)?);
+            }
+        }
+
+        let frames = VrlValue::Array(frames);
+        self.frames[stack_index as usize] = Some(frames.clone());
+        Ok(frames)
+    }
+
+    /// Build one frame object, omitting unknown fields.
+    fn frame(
+        &self,
+        line: Option<&Line>,
+        location: &Location,
+        mapping: &str,
+    ) -> Result<VrlValue, DecodeError> {
+        let mut map = ObjectMap::new();
+        if let Some(line) = line {
+            if let Some(function) = lookup(
+                &self.tables.function_table,
+                line.function_index,
+                "line.function_index",
+            )? {
+                let mut name = self.string(function.name_strindex, "function.name_strindex")?;
+                if name.is_empty() {
+                    name = self.string(
+                        function.system_name_strindex,
+                        "function.system_name_strindex",
+                    )?;
+                }
+                let file = self.string(function.filename_strindex, "function.filename_strindex")?;
+                insert_str(&mut map, "function", name);
+                insert_str(&mut map, "file", file);
+            }
+            if line.line != 0 {
+                map.insert("line".into(), VrlValue::Integer(line.line));
+            }
+            if line.column != 0 {
+                map.insert("column".into(), VrlValue::Integer(line.column));
+            }
+        }
+        if location.address != 0 {
+            insert_str(&mut map, "address", &format!("0x{:x}", location.address));
+        }
+        insert_str(&mut map, "mapping", mapping);
+        Ok(VrlValue::Object(map))
+    }
+}
+
+fn insert_str(map: &mut ObjectMap, key: &str, value: &str) {
+    if !value.is_empty() {
+        map.insert(key.into(), VrlValue::Bytes(Bytes::from(value.to_string())));
+    }
+}
+
+/// Look up a dictionary entry. Index 0 into an empty table is "not set".
+fn lookup<'a, T>(table: &'a [T], index: i32, field: &str) -> Result<Option<&'a T>, DecodeError> {
+    if index == 0 && table.is_empty() {
+        return Ok(None);
+    }
+    usize::try_from(index)
+        .ok()
+        .and_then(|i| table.get(i))
+        .map(Some)
+        .ok_or_else(|| {
+            DecodeError::Unsupported(format!(
+                "invalid profile dictionary reference: {field} {index} out of range (table has {} entries)",
+                table.len()
+            ))
+        })
+}
+
+// ============================================================================
+// JSON struct definitions
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonExportProfilesServiceRequest {
+    #[serde(default)]
+    resource_profiles: Vec<JsonResourceProfiles>,
+    #[serde(default)]
+    dictionary: JsonProfilesDictionary,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonResourceProfiles {
+    #[serde(default)]
+    resource: JsonResource,
+    #[serde(default)]
+    scope_profiles: Vec<JsonScopeProfiles>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonScopeProfiles {
+    #[serde(default)]
+    scope: JsonInstrumentationScope,
+    #[serde(default)]
+    profiles: Vec<JsonProfile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonProfile {
+    #[serde(default)]
+    sample_type: Option<JsonValueType>,
+    #[serde(default)]
+    sample: Vec<JsonSample>,
+    #[serde(default)]
+    time_unix_nano: JsonNumberOrString,
+    #[serde(default)]
+    duration_nano: JsonNumberOrString,
+    #[serde(default)]
+    period_type: Option<JsonValueType>,
+    #[serde(default)]
+    period: JsonNumberOrString,
+    #[serde(default)]
+    profile_id: String,
+    #[serde(default)]
+    attribute_indices: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonValueType {
+    #[serde(default)]
+    type_strindex: i32,
+    #[serde(default)]
+    unit_strindex: i32,
+    #[serde(default)]
+    aggregation_temporality: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSample {
+    #[serde(default)]
+    stack_index: i32,
+    #[serde(default)]
+    values: Vec<JsonNumberOrString>,
+    #[serde(default)]
+    attribute_indices: Vec<i32>,
+    #[serde(default)]
+    link_index: i32,
+    #[serde(default)]
+    timestamps_unix_nano: Vec<JsonNumberOrString>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonProfilesDictionary {
+    #[serde(default)]
+    mapping_table: Vec<JsonMapping>,
+    #[serde(default)]
+    location_table: Vec<JsonLocation>,
+    #[serde(default)]
+    function_table: Vec<JsonFunction>,
+    #[serde(default)]
+    link_table: Vec<JsonLink>,
+    #[serde(default)]
+    string_table: Vec<String>,
+    #[serde(default)]
+    attribute_table: Vec<JsonKeyValueAndUnit>,
+    #[serde(default)]
+    stack_table: Vec<JsonStack>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonMapping {
+    #[serde(default)]
+    memory_start: JsonNumberOrString,
+    #[serde(default)]
+    memory_limit: JsonNumberOrString,
+    #[serde(default)]
+    file_offset: JsonNumberOrString,
+    #[serde(default)]
+    filename_strindex: i32,
+    #[serde(default)]
+    attribute_indices: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonLocation {
+    #[serde(default)]
+    mapping_index: i32,
+    #[serde(default)]
+    address: JsonNumberOrString,
+    #[serde(default)]
+    line: Vec<JsonLine>,
+    #[serde(default)]
+    attribute_indices: Vec<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonLine {
+    #[serde(default)]
+    function_index: i32,
+    #[serde(default)]
+    line: JsonNumberOrString,
+    #[serde(default)]
+    column: JsonNumberOrString,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFunction {
+    #[serde(default)]
+    name_strindex: i32,
+    #[serde(default)]
+    system_name_strindex: i32,
+    #[serde(default)]
+    filename_strindex: i32,
+    #[serde(default)]
+    start_line: JsonNumberOrString,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonLink {
+    #[serde(default)]
+    trace_id: String,
+    #[serde(default)]
+    span_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonKeyValueAndUnit {
+    #[serde(default)]
+    key_strindex: i32,
+    #[serde(default)]
+    value: Option<JsonAnyValue>,
+    #[serde(default)]
+    unit_strindex: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonStack {
+    #[serde(default)]
+    location_indices: Vec<i32>,
+}
+
+// ============================================================================
+// JSON -> protobuf conversion
+// ============================================================================
+
+/// Parse a JSON int64 (number or string); missing is 0.
+fn json_int(value: &JsonNumberOrString, field: &str) -> Result<i64, DecodeError> {
+    match value {
+        JsonNumberOrString::Missing => Ok(0),
+        _ => value.as_i64().ok_or_else(|| {
+            DecodeError::Unsupported(format!("invalid integer: {field} is not an int64"))
+        }),
+    }
+}
+
+/// Parse a JSON uint64 (number or string); missing is 0.
+fn json_uint(value: &JsonNumberOrString, field: &str) -> Result<u64, DecodeError> {
+    let parsed = match value {
+        JsonNumberOrString::Missing => Some(0),
+        JsonNumberOrString::String(s) => s.parse().ok(),
+        JsonNumberOrString::Number(n) => n.as_u64(),
+    };
+    parsed.ok_or_else(|| {
+        DecodeError::Unsupported(format!("invalid integer: {field} is not a uint64"))
+    })
+}
+
+fn json_any_value_to_otlp(av: JsonAnyValue) -> AnyValue {
+    let value = if let Some(s) = av.string_value {
+        Some(any_value::Value::StringValue(s))
+    } else if let Some(i) = av.int_value {
+        i.as_i64().map(any_value::Value::IntValue)
+    } else if let Some(d) = av.double_value {
+        Some(any_value::Value::DoubleValue(d))
+    } else if let Some(b) = av.bool_value {
+        Some(any_value::Value::BoolValue(b))
+    } else if let Some(arr) = av.array_value {
+        Some(any_value::Value::ArrayValue(ArrayValue {
+            values: arr.values.into_iter().map(json_any_value_to_otlp).collect(),
+        }))
+    } else if let Some(kv) = av.kvlist_value {
+        Some(any_value::Value::KvlistValue(KeyValueList {
+            values: json_attrs_to_otlp(kv.values),
+        }))
+    } else {
+        av.bytes_value
+            .map(|bytes| any_value::Value::BytesValue(decode_bytes_field(&bytes)))
+    };
+    AnyValue { value }
+}
+
+fn json_attrs_to_otlp(attrs: Vec<JsonKeyValue>) -> Vec<KeyValue> {
+    attrs
+        .into_iter()
+        .map(|kv| KeyValue {
+            key: kv.key,
+            value: kv.value.map(json_any_value_to_otlp),
+        })
+        .collect()
+}
+
+impl From<JsonValueType> for ValueType {
+    fn from(value: JsonValueType) -> Self {
+        ValueType {
+            type_strindex: value.type_strindex,
+            unit_strindex: value.unit_strindex,
+            aggregation_temporality: value.aggregation_temporality,
+        }
+    }
+}
+
+impl TryFrom<JsonExportProfilesServiceRequest> for ExportProfilesServiceRequest {
+    type Error = DecodeError;
+
+    fn try_from(request: JsonExportProfilesServiceRequest) -> Result<Self, DecodeError> {
+        let resource_profiles = request
+            .resource_profiles
+            .into_iter()
+            .map(|rp| {
+                Ok(ResourceProfiles {
+                    resource: Some(Resource {
+                        attributes: json_attrs_to_otlp(rp.resource.attributes),
+                        ..Default::default()
+                    }),
+                    scope_profiles: rp
+                        .scope_profiles
+                        .into_iter()
+                        .map(|sp| {
+                            Ok(ScopeProfiles {
+                                scope: Some(InstrumentationScope {
+                                    name: sp.scope.name,
+                                    version: sp.scope.version,
+                                    attributes: json_attrs_to_otlp(sp.scope.attributes),
+                                    dropped_attributes_count: 0,
+                                }),
+                                profiles: sp
+                                    .profiles
+                                    .into_iter()
+                                    .map(Profile::try_from)
+                                    .collect::<Result<_, _>>()?,
+                                schema_url: String::new(),
+                            })
+                        })
+                        .collect::<Result<_, DecodeError>>()?,
+                    schema_url: String::new(),
+                })
+            })
+            .collect::<Result<_, DecodeError>>()?;
+
+        Ok(ExportProfilesServiceRequest {
+            resource_profiles,
+            dictionary: Some(request.dictionary.try_into()?),
+        })
+    }
+}
+
+impl TryFrom<JsonProfile> for Profile {
+    type Error = DecodeError;
+
+    fn try_from(profile: JsonProfile) -> Result<Self, DecodeError> {
+        let sample = profile
+            .sample
+            .into_iter()
+            .map(|s| {
+                Ok(Sample {
+                    stack_index: s.stack_index,
+                    values: s
+                        .values
+                        .iter()
+                        .map(|v| json_int(v, "sample.values"))
+                        .collect::<Result<_, _>>()?,
+                    attribute_indices: s.attribute_indices,
+                    link_index: s.link_index,
+                    timestamps_unix_nano: s
+                        .timestamps_unix_nano
+                        .iter()
+                        .map(|ts| json_uint(ts, "sample.timestamps_unix_nano"))
+                        .collect::<Result<_, _>>()?,
+                })
+            })
+            .collect::<Result<_, DecodeError>>()?;
+
+        Ok(Profile {
+            sample_type: profile.sample_type.map(ValueType::from),
+            sample,
+            time_unix_nano: json_uint(&profile.time_unix_nano, "profile.time_unix_nano")?,
+            duration_nano: json_uint(&profile.duration_nano, "profile.duration_nano")?,
+            period_type: profile.period_type.map(ValueType::from),
+            period: json_int(&profile.period, "profile.period")?,
+            profile_id: decode_bytes_field(&profile.profile_id),
+            attribute_indices: profile.attribute_indices,
+            ..Default::default()
+        })
+    }
+}
+
+impl TryFrom<JsonProfilesDictionary> for ProfilesDictionary {
+    type Error = DecodeError;
+
+    fn try_from(dictionary: JsonProfilesDictionary) -> Result<Self, DecodeError> {
+        Ok(ProfilesDictionary {
+            mapping_table: dictionary
+                .mapping_table
+                .into_iter()
+                .map(|m| {
+                    Ok(Mapping {
+                        memory_start: json_uint(&m.memory_start, "mapping.memory_start")?,
+                        memory_limit: json_uint(&m.memory_limit, "mapping.memory_limit")?,
+                        file_offset: json_uint(&m.file_offset, "mapping.file_offset")?,
+                        filename_strindex: m.filename_strindex,
+                        attribute_indices: m.attribute_indices,
+                    })
+                })
+                .collect::<Result<_, DecodeError>>()?,
+            location_table: dictionary
+                .location_table
+                .into_iter()
+                .map(|l| {
+                    Ok(Location {
+                        mapping_index: l.mapping_index,
+                        address: json_uint(&l.address, "location.address")?,
+                        line: l
+                            .line
+                            .iter()
+                            .map(|line| {
+                                Ok(Line {
+                                    function_index: line.function_index,
+                                    line: json_int(&line.line, "line.line")?,
+                                    column: json_int(&line.column, "line.column")?,
+                                })
+                            })
+                            .collect::<Result<_, DecodeError>>()?,
+                        attribute_indices: l.attribute_indices,
+                    })
+                })
+                .collect::<Result<_, DecodeError>>()?,
+            function_table: dictionary
+                .function_table
+                .into_iter()
+                .map(|f| {
+                    Ok(Function {
+                        name_strindex: f.name_strindex,
+                        system_name_strindex: f.system_name_strindex,
+                        filename_strindex: f.filename_strindex,
+                        start_line: json_int(&f.start_line, "function.start_line")?,
+                    })
+                })
+                .collect::<Result<_, DecodeError>>()?,
+            link_table: dictionary
+                .link_table
+                .into_iter()
+                .map(|l| Link {
+                    trace_id: decode_bytes_field(&l.trace_id),
+                    span_id: decode_bytes_field(&l.span_id),
+                })
+                .collect(),
+            string_table: dictionary.string_table,
+            attribute_table: dictionary
+                .attribute_table
+                .into_iter()
+                .map(|a| KeyValueAndUnit {
+                    key_strindex: a.key_strindex,
+                    value: a.value.map(json_any_value_to_otlp),
+                    unit_strindex: a.unit_strindex,
+                })
+                .collect(),
+            stack_table: dictionary
+                .stack_table
+                .into_iter()
+                .map(|s| Stack {
+                    location_indices: s.location_indices,
+                })
+                .collect(),
+        })
+    }
+}
+
+// ============================================================================
+// Record builder
+// ============================================================================
+
+/// Fields shared by every sample of a profile
+struct ProfileParts {
+    time_unix_nano: i64,
+    duration_nano: i64,
+    profile_id: Bytes,
+    sample_type: (Bytes, Bytes),
+    period_type: (Bytes, Bytes),
+    period: i64,
+    attributes: VrlValue,
+}
+
+/// Precomputed fields for building a sample record into VRL values
+struct SampleRecordParts<'a> {
+    time_unix_nano: i64,
+    value: i64,
+    frames: VrlValue,
+    trace_id: Bytes,
+    span_id: Bytes,
+    attributes: VrlValue,
+    profile: &'a ProfileParts,
+    resource: Arc<VrlValue>,
+    scope: Arc<VrlValue>,
+}
+
+/// Build a VRL-ready sample record from parts
+fn build_sample_record(parts: SampleRecordParts<'_>) -> VrlValue {
+    let profile = parts.profile;
+    let mut map = ObjectMap::new();
+    map.insert(
+        "time_unix_nano".into(),
+        VrlValue::Integer(parts.time_unix_nano),
+    );
+    map.insert(
+        "duration_nano".into(),
+        VrlValue::Integer(profile.duration_nano),
+    );
+    map.insert(
+        "profile_id".into(),
+        VrlValue::Bytes(profile.profile_id.clone()),
+    );
+    map.insert(
+        "sample_type".into(),
+        VrlValue::Bytes(profile.sample_type.0.clone()),
+    );
+    map.insert(
+        "sample_unit".into(),
+        VrlValue::Bytes(profile.sample_type.1.clone()),
+    );
+    map.insert(
+        "period_type".into(),
+        VrlValue::Bytes(profile.period_type.0.clone()),
+    );
+    map.insert(
+        "period_unit".into(),
+        VrlValue::Bytes(profile.period_type.1.clone()),
+    );
+    map.insert("period".into(), VrlValue::Integer(profile.period));
+    map.insert("value".into(), VrlValue::Integer(parts.value));
+    map.insert("frames".into(), parts.frames);
+    map.insert("trace_id".into(), VrlValue::Bytes(parts.trace_id));
+    map.insert("span_id".into(), VrlValue::Bytes(parts.span_id));
+    map.insert("attributes".into(), parts.attributes);
+    map.insert("profile_attributes".into(), profile.attributes.clone());
+    map.insert("resource".into(), (*parts.resource).clone());
+    map.insert("scope".into(), (*parts.scope).clone());
+    VrlValue::Object(map)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One profile with two samples sharing a two-frame stack, where the
+    /// leaf location has an inlined function.
+    fn sample_request() -> ExportProfilesServiceRequest {
+        let strings = [
+            "",
+            "cpu",
+            "nanoseconds",
+            "main",
+            "main.go",
+            "inlined",
+            "libc.so",
+            "thread.name",
+            "worker",
+        ];
+        ExportProfilesServiceRequest {
+            resource_profiles: vec![ResourceProfiles {
+                resource: Some(Resource {
+                    attributes: vec![KeyValue {
+                        key: "service.name".into(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("profiler".into())),
+                        }),
+                    }],
+                    ..Default::default()
+                }),
+                scope_profiles: vec![ScopeProfiles {
+                    scope: None,
+                    profiles: vec![Profile {
+                        sample_type: Some(ValueType {
+                            type_strindex: 1,
+                            unit_strindex: 2,
+                            aggregation_temporality: 0,
+                        }),
+                        time_unix_nano: 1_700_000_000_000_000_000,
+                        duration_nano: 10_000_000_000,
+                        profile_id: vec![0xab; 16],
+                        sample: vec![
+                            Sample {
+                                stack_index: 1,
+                                values: vec![10, 5],
+                                attribute_indices: vec![1],
+                                link_index: 1,
+                                timestamps_unix_nano: vec![],
+                            },
+                            Sample {
+                                stack_index: 1,
+                                values: vec![],
+                                attribute_indices: vec![],
+                                link_index: 0,
+                                timestamps_unix_nano: vec![
+                                    1_700_000_001_000_000_000,
+                                    1_700_000_002_000_000_000,
+                                ],
+                            },
+                        ],
+                        ..Default::default()
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+            dictionary: Some(ProfilesDictionary {
+                mapping_table: vec![
+                    Mapping::default(),
+                    Mapping {
+                        filename_strindex: 6,
+                        ..Default::default()
+                    },
+                ],
+                location_table: vec![
+                    Location::default(),
+                    Location {
+                        mapping_index: 0,
+                        address: 0,
+                        line: vec![
+                            Line {
+                                function_index: 2,
+                                line: 7,
+                                column: 0,
+                            },
+                            Line {
+                                function_index: 1,
+                                line: 42,
+                                column: 3,
+                            },
+                        ],
+                        attribute_indices: vec![],
+                    },
+                    Location {
+                        mapping_index: 1,
+                        address: 0x7f00,
+                        line: vec![],
+                        attribute_indices: vec![],
+                    },
+                ],
+                function_table: vec![
+                    Function::default(),
+                    Function {
+                        name_strindex: 3,
+                        filename_strindex: 4,
+                        ..Default::default()
+                    },
+                    Function {
+                        name_strindex: 5,
+                        filename_strindex: 4,
+                        ..Default::default()
+                    },
+                ],
+                link_table: vec![
+                    Link::default(),
+                    Link {
+                        trace_id: vec![1; 16],
+                        span_id: vec![2; 8],
+                    },
+                ],
+                string_table: strings.iter().map(|s| s.to_string()).collect(),
+                attribute_table: vec![
+                    KeyValueAndUnit::default(),
+                    KeyValueAndUnit {
+                        key_strindex: 7,
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("worker".into())),
+                        }),
+                        unit_strindex: 0,
+                    },
+                ],
+                stack_table: vec![
+                    Stack::default(),
+                    Stack {
+                        location_indices: vec![1, 2],
+                    },
+                ],
+            }),
+        }
+    }
+
+    fn field<'a>(value: &'a VrlValue, key: &str) -> &'a VrlValue {
+        value.as_object().unwrap().get(key).unwrap()
+    }
+
+    #[test]
+    fn flattens_samples_with_resolved_frames() {
+        let values = decode_protobuf(&sample_request().encode_to_vec(), false)
+            .map(|decoded| decoded.values)
+            .unwrap();
+        assert_eq!(values.len(), 2);
+
+        let first = &values[0];
+        assert_eq!(
+            field(first, "time_unix_nano"),
+            &VrlValue::Integer(1_700_000_000_000_000_000)
+        );
+        assert_eq!(field(first, "value"), &VrlValue::Integer(15));
+        assert_eq!(field(first, "sample_type"), &VrlValue::from("cpu"));
+        assert_eq!(field(first, "sample_unit"), &VrlValue::from("nanoseconds"));
+        assert_eq!(
+            field(first, "trace_id"),
+            &VrlValue::from("01010101010101010101010101010101")
+        );
+        assert_eq!(
+            field(field(first, "attributes"), "thread.name"),
+            &VrlValue::from("worker")
+        );
+
+        let frames = field(first, "frames").as_array().unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(field(&frames[0], "function"), &VrlValue::from("inlined"));
+        assert_eq!(field(&frames[0], "line"), &VrlValue::Integer(7));
+        assert_eq!(field(&frames[1], "function"), &VrlValue::from("main"));
+        assert_eq!(field(&frames[1], "file"), &VrlValue::from("main.go"));
+        assert_eq!(field(&frames[1], "column"), &VrlValue::Integer(3));
+        assert_eq!(field(&frames[2], "address"), &VrlValue::from("0x7f00"));
+        assert_eq!(field(&frames[2], "mapping"), &VrlValue::from("libc.so"));
+        assert!(frames[2].as_object().unwrap().get("function").is_none());
+
+        let second = &values[1];
+        assert_eq!(field(second, "value"), &VrlValue::Integer(2));
+        assert_eq!(
+            field(second, "time_unix_nano"),
+            &VrlValue::Integer(1_700_000_001_000_000_000)
+        );
+        assert_eq!(field(second, "trace_id"), &VrlValue::from(""));
+    }
+
+    #[test]
+    fn json_matches_protobuf() {
+        let json = r#"{
+            "resourceProfiles": [{
+                "resource": {"attributes": [
+                    {"key": "service.name", "value": {"stringValue": "profiler"}}
+                ]},
+                "scopeProfiles": [{"profiles": [{
+                    "sampleType": {"typeStrindex": 1, "unitStrindex": 2},
+                    "timeUnixNano": "1700000000000000000",
+                    "durationNano": "10000000000",
+                    "profileId": "abababababababababababababababab",
+                    "sample": [
+                        {"stackIndex": 1, "values": ["10", 5], "attributeIndices": [1], "linkIndex": 1},
+                        {"stackIndex": 1, "timestampsUnixNano": ["1700000001000000000", "1700000002000000000"]}
+                    ]
+                }]}]
+            }],
+            "dictionary": {
+                "mappingTable": [{}, {"filenameStrindex": 6}],
+                "locationTable": [
+                    {},
+                    {"line": [{"functionIndex": 2, "line": "7"}, {"functionIndex": 1, "line": 42, "column": 3}]},
+                    {"mappingIndex": 1, "address": "32512"}
+                ],
+                "functionTable": [{}, {"nameStrindex": 3, "filenameStrindex": 4}, {"nameStrindex": 5, "filenameStrindex": 4}],
+                "linkTable": [{}, {"traceId": "01010101010101010101010101010101", "spanId": "0202020202020202"}],
+                "stringTable": ["", "cpu", "nanoseconds", "main", "main.go", "inlined", "libc.so", "thread.name", "worker"],
+                "attributeTable": [{}, {"keyStrindex": 7, "value": {"stringValue": "worker"}}],
+                "stackTable": [{}, {"locationIndices": [1, 2]}]
+            }
+        }"#;
+        let from_json = decode_json(json.as_bytes(), false)
+            .map(|decoded| decoded.values)
+            .unwrap();
+        let from_proto = decode_protobuf(&sample_request().encode_to_vec(), false)
+            .map(|decoded| decoded.values)
+            .unwrap();
+        assert_eq!(from_json, from_proto);
+    }
+
+    #[test]
+    fn json_rejects_invalid_integer() {
+        let json = r#"{"resourceProfiles": [{"scopeProfiles": [{"profiles": [{
+            "sample": [{"values": ["ten"]}]
+        }]}]}]}"#;
+        let err = decode_json(json.as_bytes(), false)
+            .map(|decoded| decoded.values)
+            .unwrap_err();
+        assert!(err.to_string().contains("sample.values"), "{err}");
+    }
+
+    #[test]
+    fn rejects_out_of_range_index() {
+        let mut request = sample_request();
+        request.resource_profiles[0].scope_profiles[0].profiles[0].sample[0].stack_index = 9;
+        let err = decode_protobuf(&request.encode_to_vec(), false)
+            .map(|decoded| decoded.values)
+            .unwrap_err();
+        assert!(err.to_string().contains("sample.stack_index 9"), "{err}");
+    }
+
+    #[test]
+    fn empty_dictionary_is_not_set() {
+        let request = ExportProfilesServiceRequest {
+            resource_profiles: vec![ResourceProfiles {
+                scope_profiles: vec![ScopeProfiles {
+                    profiles: vec![Profile {
+                        sample: vec![Sample {
+                            values: vec![1],
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            dictionary: None,
+        };
+        let values = decode_protobuf(&request.encode_to_vec(), false)
+            .map(|decoded| decoded.values)
+            .unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(field(&values[0], "frames"), &VrlValue::Array(vec![]));
+        assert_eq!(field(&values[0], "sample_type"), &VrlValue::from(""));
+    }
+}
@@ -3,6 +3,7 @@
 use bytes::Bytes;
 use const_hex::encode as hex_encode;
 use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::tonic::trace::v1::Span;
 use prost::Message;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -11,7 +12,7 @@ use vrl::value::{ObjectMap, Value as VrlValue};
 use super::common::{
     for_each_resource_scope, json_attrs_to_value, json_resource_to_value, json_scope_to_value,
     json_timestamp_to_i64, otlp_attributes_to_value, otlp_resource_to_value, otlp_scope_to_value,
-    safe_timestamp_conversion, DecodeError, JsonInstrumentationScope, JsonKeyValue,
+    safe_timestamp_conversion, DecodeError, Decoded, JsonInstrumentationScope, JsonKeyValue,
     JsonNumberOrString, JsonResource,
 };
 
@@ -19,19 +20,31 @@ use super::common::{
 // Protobuf decoding
 // ============================================================================
 
-pub fn decode_protobuf(body: &[u8]) -> Result<Vec<VrlValue>, DecodeError> {
+pub fn decode_protobuf(body: &[u8], lenient: bool) -> Result<Decoded, DecodeError> {
     let request = ExportTraceServiceRequest::decode(body)?;
-    export_traces_to_vrl_proto(request)
+    export_traces_to_vrl_proto(request, lenient, build_span_record)
 }
 
-fn export_traces_to_vrl_proto(
+/// Decode protobuf traces into span parts for the native fast path
+pub(crate) fn decode_protobuf_parts(
+    body: &[u8],
+    lenient: bool,
+) -> Result<Decoded<SpanRecordParts>, DecodeError> {
+    let request = ExportTraceServiceRequest::decode(body)?;
+    export_traces_to_vrl_proto(request, lenient, |parts| parts)
+}
+
+fn export_traces_to_vrl_proto<T>(
     request: ExportTraceServiceRequest,
-) -> Result<Vec<VrlValue>, DecodeError> {
-    let mut values = preallocate_span_values(&request.resource_spans, |rs| {
+    lenient: bool,
+    build: fn(SpanRecordParts) -> T,
+) -> Result<Decoded<T>, DecodeError> {
+    let capacity = count_spans(&request.resource_spans, |rs| {
         rs.scope_spans.iter().map(|ss| ss.spans.len()).sum()
     });
+    let mut out = Decoded::new(lenient, capacity);
 
-    for_each_resource_scope(
+    let resources = for_each_resource_scope(
         request.resource_spans,
         |resource_spans| {
             (
@@ -45,98 +58,117 @@ fn export_traces_to_vrl_proto(
                 scope_spans.spans,
             )
         },
-        |spans, resource, scope| {
+        |spans, resource, scope, location| {
             for span in spans {
-                let events: Result<Vec<SpanEventParts>, DecodeError> = span
-                    .events
-                    .iter()
-                    .map(|e| {
-                        Ok(SpanEventParts {
-                            time_unix_nano: safe_timestamp_conversion(
-                                e.time_unix_nano,
-                                "event.time_unix_nano",
-                            )?,
-                            name: Bytes::from(e.name.clone()),
-                            attributes: otlp_attributes_to_value(&e.attributes),
-                        })
-                    })
-                    .collect();
-                let events = events?;
-
-                let links: Vec<SpanLinkParts> = span
-                    .links
-                    .iter()
-                    .map(|l| SpanLinkParts {
-                        trace_id: Bytes::from(hex_encode(&l.trace_id)),
-                        span_id: Bytes::from(hex_encode(&l.span_id)),
-                        trace_state: Bytes::from(l.trace_state.clone()),
-                        attributes: otlp_attributes_to_value(&l.attributes),
-                    })
-                    .collect();
-
-                let (status_code, status_message) = span
-                    .status
-                    .as_ref()
-                    .map(|s| (s.code as i64, Bytes::from(s.message.clone())))
-                    .unwrap_or((0, Bytes::new()));
-
-                let parts = SpanRecordParts {
-                    trace_id: Bytes::from(hex_encode(&span.trace_id)),
-                    span_id: Bytes::from(hex_encode(&span.span_id)),
-                    parent_span_id: Bytes::from(hex_encode(&span.parent_span_id)),
-                    trace_state: Bytes::from(span.trace_state),
-                    name: Bytes::from(span.name),
-                    kind: span.kind as i64,
-                    start_time_unix_nano: safe_timestamp_conversion(
-                        span.start_time_unix_nano,
-                        "span.start_time_unix_nano",
-                    )?,
-                    end_time_unix_nano: safe_timestamp_conversion(
-                        span.end_time_unix_nano,
-                        "span.end_time_unix_nano",
-                    )?,
-                    attributes: otlp_attributes_to_value(&span.attributes),
-                    status_code,
-                    status_message,
-                    events,
-                    links,
-                    resource: Arc::clone(&resource),
-                    scope: Arc::clone(&scope),
-                    dropped_attributes_count: span.dropped_attributes_count as i64,
-                    dropped_events_count: span.dropped_events_count as i64,
-                    dropped_links_count: span.dropped_links_count as i64,
-                    flags: span.flags as i64,
-                };
-
-                values.push(build_span_record(parts));
+                out.push(location, proto_span(span, &resource, &scope).map(build))?;
             }
 
             Ok::<(), DecodeError>(())
         },
     )?;
 
-    Ok(values)
+    out.set_resources(resources);
+    Ok(out)
+}
+
+fn proto_span(
+    span: Span,
+    resource: &Arc<VrlValue>,
+    scope: &Arc<VrlValue>,
+) -> Result<SpanRecordParts, DecodeError> {
+    let events = span
+        .events
+        .iter()
+        .map(|e| {
+            Ok(SpanEventParts {
+                time_unix_nano: safe_timestamp_conversion(
+                    e.time_unix_nano,
+                    "event.time_unix_nano",
+                )?,
+                name: Bytes::from(e.name.clone()),
+                attributes: otlp_attributes_to_value(&e.attributes),
+            })
+        })
+        .collect::<Result<Vec<SpanEventParts>, DecodeError>>()?;
+
+    let links: Vec<SpanLinkParts> = span
+        .links
+        .iter()
+        .map(|l| SpanLinkParts {
+            trace_id: Bytes::from(hex_encode(&l.trace_id)),
+            span_id: Bytes::from(hex_encode(&l.span_id)),
+            trace_state: Bytes::from(l.trace_state.clone()),
+            attributes: otlp_attributes_to_value(&l.attributes),
+        })
+        .collect();
+
+    let (status_code, status_message) = span
+        .status
+        .as_ref()
+        .map(|s| (s.code as i64, Bytes::from(s.message.clone())))
+        .unwrap_or((0, Bytes::new()));
+
+    Ok(SpanRecordParts {
+        trace_id: Bytes::from(hex_encode(&span.trace_id)),
+        span_id: Bytes::from(hex_encode(&span.span_id)),
+        parent_span_id: Bytes::from(hex_encode(&span.parent_span_id)),
+        trace_state: Bytes::from(span.trace_state),
+        name: Bytes::from(span.name),
+        kind: span.kind as i64,
+        start_time_unix_nano: safe_timestamp_conversion(
+            span.start_time_unix_nano,
+            "span.start_time_unix_nano",
+        )?,
+        end_time_unix_nano: safe_timestamp_conversion(
+            span.end_time_unix_nano,
+            "span.end_time_unix_nano",
+        )?,
+        attributes: otlp_attributes_to_value(&span.attributes),
+        status_code,
+        status_message,
+        events,
+        links,
+        resource: Arc::clone(resource),
+        scope: Arc::clone(scope),
+        dropped_attributes_count: span.dropped_attributes_count as i64,
+        dropped_events_count: span.dropped_events_count as i64,
+        dropped_links_count: span.dropped_links_count as i64,
+        flags: span.flags as i64,
+    })
 }
 
 // ============================================================================
 // JSON decoding
 // ============================================================================
 
-pub fn decode_json(body: &[u8]) -> Result<Vec<VrlValue>, DecodeError> {
+pub fn decode_json(body: &[u8], lenient: bool) -> Result<Decoded, DecodeError> {
     // Normalize JSON to convert enum strings to numbers before parsing
     let normalized = super::normalize::normalize_json_bytes(body)?;
     let request: JsonExportTraceServiceRequest = serde_json::from_slice(&normalized)?;
-    export_traces_json_to_vrl(request)
+    export_traces_json_to_vrl(request, lenient, build_span_record)
+}
+
+/// Decode JSON traces into span parts for the native fast path
+pub(crate) fn decode_json_parts(
+    body: &[u8],
+    lenient: bool,
+) -> Result<Decoded<SpanRecordParts>, DecodeError> {
+    let normalized = super::normalize::normalize_json_bytes(body)?;
+    let request: JsonExportTraceServiceRequest = serde_json::from_slice(&normalized)?;
+    export_traces_json_to_vrl(request, lenient, |parts| parts)
 }
 
-fn export_traces_json_to_vrl(
+fn export_traces_json_to_vrl<T>(
     request: JsonExportTraceServiceRequest,
-) -> Result<Vec<VrlValue>, DecodeError> {
-    let mut values = preallocate_span_values(&request.resource_spans, |rs| {
+    lenient: bool,
+    build: fn(SpanRecordParts) -> T,
+) -> Result<Decoded<T>, DecodeError> {
+    let capacity = count_spans(&request.resource_spans, |rs| {
         rs.scope_spans.iter().map(|ss| ss.spans.len()).sum()
     });
+    let mut out = Decoded::new(lenient, capacity);
 
-    for_each_resource_scope(
+    let resources = for_each_resource_scope(
         request.resource_spans,
         |resource_spans| {
             (
@@ -145,70 +177,74 @@ fn export_traces_json_to_vrl(
             )
         },
         |scope_spans| (json_scope_to_value(scope_spans.scope), scope_spans.spans),
-        |spans, resource, scope| {
+        |spans, resource, scope, location| {
             for span in spans {
-                let events: Vec<SpanEventParts> = span
-                    .events
-                    .into_iter()
-                    .map(|e| {
-                        Ok(SpanEventParts {
-                            time_unix_nano: json_timestamp_to_i64(
-                                &e.time_unix_nano,
-                                "event.time_unix_nano",
-                            )?,
-                            name: Bytes::from(e.name),
-                            attributes: json_attrs_to_value(e.attributes),
-                        })
-                    })
-                    .collect::<Result<_, DecodeError>>()?;
-
-                let links: Vec<SpanLinkParts> = span
-                    .links
-                    .into_iter()
-                    .map(|l| SpanLinkParts {
-                        trace_id: Bytes::from(l.trace_id),
-                        span_id: Bytes::from(l.span_id),
-                        trace_state: Bytes::from(l.trace_state),
-                        attributes: json_attrs_to_value(l.attributes),
-                    })
-                    .collect();
-
-                let parts = SpanRecordParts {
-                    trace_id: Bytes::from(span.trace_id),
-                    span_id: Bytes::from(span.span_id),
-                    parent_span_id: Bytes::from(span.parent_span_id),
-                    trace_state: Bytes::from(span.trace_state),
-                    name: Bytes::from(span.name),
-                    kind: span.kind as i64,
-                    start_time_unix_nano: json_timestamp_to_i64(
-                        &span.start_time_unix_nano,
-                        "span.start_time_unix_nano",
-                    )?,
-                    end_time_unix_nano: json_timestamp_to_i64(
-                        &span.end_time_unix_nano,
-                        "span.end_time_unix_nano",
-                    )?,
-                    attributes: json_attrs_to_value(span.attributes),
-                    status_code: span.status.code as i64,
-                    status_message: Bytes::from(span.status.message),
-                    events,
-                    links,
-                    resource: Arc::clone(&resource),
-                    scope: Arc::clone(&scope),
-                    dropped_attributes_count: span.dropped_attributes_count as i64,
-                    dropped_events_count: span.dropped_events_count as i64,
-                    dropped_links_count: span.dropped_links_count as i64,
-                    flags: span.flags as i64,
-                };
-
-                values.push(build_span_record(parts));
+                out.push(location, json_span(span, &resource, &scope).map(build))?;
             }
 
             Ok::<(), DecodeError>(())
         },
     )?;
 
-    Ok(values)
+    out.set_resources(resources);
+    Ok(out)
+}
+
+fn json_span(
+    span: JsonSpan,
+    resource: &Arc<VrlValue>,
+    scope: &Arc<VrlValue>,
+) -> Result<SpanRecordParts, DecodeError> {
+    let events: Vec<SpanEventParts> = span
+        .events
+        .into_iter()
+        .map(|e| {
+            Ok(SpanEventParts {
+                time_unix_nano: json_timestamp_to_i64(&e.time_unix_nano, "event.time_unix_nano")?,
+                name: Bytes::from(e.name),
+                attributes: json_attrs_to_value(e.attributes),
+            })
+        })
+        .collect::<Result<_, DecodeError>>()?;
+
+    let links: Vec<SpanLinkParts> = span
+        .links
+        .into_iter()
+        .map(|l| SpanLinkParts {
+            trace_id: Bytes::from(l.trace_id),
+            span_id: Bytes::from(l.span_id),
+            trace_state: Bytes::from(l.trace_state),
+            attributes: json_attrs_to_value(l.attributes),
+        })
+        .collect();
+
+    Ok(SpanRecordParts {
+        trace_id: Bytes::from(span.trace_id),
+        span_id: Bytes::from(span.span_id),
+        parent_span_id: Bytes::from(span.parent_span_id),
+        trace_state: Bytes::from(span.trace_state),
+        name: Bytes::from(span.name),
+        kind: span.kind as i64,
+        start_time_unix_nano: json_timestamp_to_i64(
+            &span.start_time_unix_nano,
+            "span.start_time_unix_nano",
+        )?,
+        end_time_unix_nano: json_timestamp_to_i64(
+            &span.end_time_unix_nano,
+            "span.end_time_unix_nano",
+        )?,
+        attributes: json_attrs_to_value(span.attributes),
+        status_code: span.status.code as i64,
+        status_message: Bytes::from(span.status.message),
+        events,
+        links,
+        resource: Arc::clone(resource),
+        scope: Arc::clone(scope),
+        dropped_attributes_count: span.dropped_attributes_count as i64,
+        dropped_events_count: span.dropped_events_count as i64,
+        dropped_links_count: span.dropped_links_count as i64,
+        flags: span.flags as i64,
+    })
 }
 
 // ============================================================================
@@ -315,53 +351,64 @@ struct JsonStatus {
 // ============================================================================
 
 /// Precomputed fields for building a span record into VRL values
-struct SpanRecordParts {
-    trace_id: Bytes,
-    span_id: Bytes,
-    parent_span_id: Bytes,
-    trace_state: Bytes,
-    name: Bytes,
-    kind: i64,
-    start_time_unix_nano: i64,
-    end_time_unix_nano: i64,
-    attributes: VrlValue,
-    status_code: i64,
-    status_message: Bytes,
-    events: Vec<SpanEventParts>,
-    links: Vec<SpanLinkParts>,
-    resource: Arc<VrlValue>,
-    scope: Arc<VrlValue>,
-    dropped_attributes_count: i64,
-    dropped_events_count: i64,
-    dropped_links_count: i64,
-    flags: i64,
+#[derive(Debug)]
+pub(crate) struct SpanRecordParts {
+    pub trace_id: Bytes,
+    pub span_id: Bytes,
+    pub parent_span_id: Bytes,
+    pub trace_state: Bytes,
+    pub name: Bytes,
+    pub kind: i64,
+    pub start_time_unix_nano: i64,
+    pub end_time_unix_nano: i64,
+    pub attributes: VrlValue,
+    pub status_code: i64,
+    pub status_message: Bytes,
+    pub events: Vec<SpanEventParts>,
+    pub links: Vec<SpanLinkParts>,
+    pub resource: Arc<VrlValue>,
+    pub scope: Arc<VrlValue>,
+    pub dropped_attributes_count: i64,
+    pub dropped_events_count: i64,
+    pub dropped_links_count: i64,
+    pub flags: i64,
+}
+
+impl SpanRecordParts {
+    /// Span duration, saturating on overflow
+    pub fn duration_ns(&self) -> i64 {
+        self.end_time_unix_nano
+            .saturating_sub(self.start_time_unix_nano)
+    }
 }
 
-struct SpanEventParts {
+#[derive(Debug)]
+pub(crate) struct SpanEventParts {
     time_unix_nano: i64,
     name: Bytes,
     attributes: VrlValue,
 }
 
-struct SpanLinkParts {
+#[derive(Debug)]
+pub(crate) struct SpanLinkParts {
     trace_id: Bytes,
     span_id: Bytes,
     trace_state: Bytes,
     attributes: VrlValue,
 }
 
-/// Pre-allocate a values Vec sized to the number of spans a request contains
-fn preallocate_span_values<R, F>(resource_spans: &[R], count_spans: F) -> Vec<VrlValue>
+/// Count the spans a request contains, for preallocation
+fn count_spans<R, F>(resource_spans: &[R], count: F) -> usize
 where
     F: Fn(&R) -> usize,
 {
-    let capacity: usize = resource_spans.iter().map(&count_spans).sum();
-    Vec::with_capacity(capacity)
+    resource_spans.iter().map(&count).sum()
 }
 
 /// Build a VRL-ready span record from parts
 fn build_span_record(parts: SpanRecordParts) -> VrlValue {
     let mut map = ObjectMap::new();
+    let duration_ns = parts.duration_ns();
 
     // Basic span identifiers
     map.insert("trace_id".into(), VrlValue::Bytes(parts.trace_id));
@@ -383,14 +430,7 @@ fn build_span_record(parts: SpanRecordParts) -> VrlValue {
         "end_time_unix_nano".into(),
         VrlValue::Integer(parts.end_time_unix_nano),
     );
-    map.insert(
-        "duration_ns".into(),
-        VrlValue::Integer(
-            parts
-                .end_time_unix_nano
-                .saturating_sub(parts.start_time_unix_nano),
-        ),
-    );
+    map.insert("duration_ns".into(), VrlValue::Integer(duration_ns));
 
     // Attributes
     map.insert("attributes".into(), parts.attributes);
@@ -402,34 +442,9 @@ fn build_span_record(parts: SpanRecordParts) -> VrlValue {
         VrlValue::Bytes(parts.status_message),
     );
 
-    // Events
-    let events_array: Vec<VrlValue> = parts
-        .events
-        .into_iter()
-        .map(|e| {
-            let mut event_map = ObjectMap::new();
-            event_map.insert("time_unix_nano".into(), VrlValue::Integer(e.time_unix_nano));
-            event_map.insert("name".into(), VrlValue::Bytes(e.name));
-            event_map.insert("attributes".into(), e.attributes);
-            VrlValue::Object(event_map)
-        })
-        .collect();
-    map.insert("events".into(), VrlValue::Array(events_array));
-
-    // Links
-    let links_array: Vec<VrlValue> = parts
-        .links
-        .into_iter()
-        .map(|l| {
-            let mut link_map = ObjectMap::new();
-            link_map.insert("trace_id".into(), VrlValue::Bytes(l.trace_id));
-            link_map.insert("span_id".into(), VrlValue::Bytes(l.span_id));
-            link_map.insert("trace_state".into(), VrlValue::Bytes(l.trace_state));
-            link_map.insert("attributes".into(), l.attributes);
-            VrlValue::Object(link_map)
-        })
-        .collect();
-    map.insert("links".into(), VrlValue::Array(links_array));
+    // Events and links
+    map.insert("events".into(), span_events_value(parts.events));
+    map.insert("links".into(), span_links_value(parts.links));
 
     // Resource and scope
     map.insert("resource".into(), (*parts.resource).clone());
@@ -453,6 +468,37 @@ fn build_span_record(parts: SpanRecordParts) -> VrlValue {
     VrlValue::Object(map)
 }
 
+/// Build the VRL array of a span's events
+pub(crate) fn span_events_value(events: Vec<SpanEventParts>) -> VrlValue {
+    let events_array: Vec<VrlValue> = events
+        .into_iter()
+        .map(|e| {
+            let mut event_map = ObjectMap::new();
+            event_map.insert("time_unix_nano".into(), VrlValue::Integer(e.time_unix_nano));
+            event_map.insert("name".into(), VrlValue::Bytes(e.name));
+            event_map.insert("attributes".into(), e.attributes);
+            VrlValue::Object(event_map)
+        })
+        .collect();
+    VrlValue::Array(events_array)
+}
+
+/// Build the VRL array of a span's links
+pub(crate) fn span_links_value(links: Vec<SpanLinkParts>) -> VrlValue {
+    let links_array: Vec<VrlValue> = links
+        .into_iter()
+        .map(|l| {
+            let mut link_map = ObjectMap::new();
+            link_map.insert("trace_id".into(), VrlValue::Bytes(l.trace_id));
+            link_map.insert("span_id".into(), VrlValue::Bytes(l.span_id));
+            link_map.insert("trace_state".into(), VrlValue::Bytes(l.trace_state));
+            link_map.insert("attributes".into(), l.attributes);
+            VrlValue::Object(link_map)
+        })
+        .collect();
+    VrlValue::Array(links_array)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -488,7 +534,9 @@ mod tests {
             }]
         }"#;
 
-        let records = decode_json(body.as_bytes()).unwrap();
+        let records = decode_json(body.as_bytes(), false)
+            .map(|decoded| decoded.values)
+            .unwrap();
         assert_eq!(records.len(), 1);
 
         let record = &records[0];
@@ -550,7 +598,9 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let records = decode_protobuf(&body).unwrap();
+        let records = decode_protobuf(&body, false)
+            .map(|decoded| decoded.values)
+            .unwrap();
         assert_eq!(records.len(), 1);
 
         let record = &records[0];
@@ -599,16 +649,15 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body);
+        let result = decode_protobuf(&body, false).map(|decoded| decoded.values);
 
         assert!(result.is_err());
         let err = result.unwrap_err();
         match err {
-            DecodeError::Unsupported(msg) => {
-                assert!(msg.contains("timestamp overflow"));
+            DecodeError::TimestampOverflow(msg) => {
                 assert!(msg.contains("start_time_unix_nano"));
             }
-            _ => panic!("Expected DecodeError::Unsupported, got: {err:?}"),
+            _ => panic!("Expected DecodeError::TimestampOverflow, got: {err:?}"),
         }
     }
 
@@ -636,16 +685,15 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body);
+        let result = decode_protobuf(&body, false).map(|decoded| decoded.values);
 
         assert!(result.is_err());
         let err = result.unwrap_err();
         match err {
-            DecodeError::Unsupported(msg) => {
-                assert!(msg.contains("timestamp overflow"));
+            DecodeError::TimestampOverflow(msg) => {
                 assert!(msg.contains("end_time_unix_nano"));
             }
-            _ => panic!("Expected DecodeError::Unsupported, got: {err:?}"),
+            _ => panic!("Expected DecodeError::TimestampOverflow, got: {err:?}"),
         }
     }
 
@@ -679,16 +727,15 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body);
+        let result = decode_protobuf(&body, false).map(|decoded| decoded.values);
 
         assert!(result.is_err());
         let err = result.unwrap_err();
         match err {
-            DecodeError::Unsupported(msg) => {
-                assert!(msg.contains("timestamp overflow"));
+            DecodeError::TimestampOverflow(msg) => {
                 assert!(msg.contains("event.time_unix_nano"));
             }
-            _ => panic!("Expected DecodeError::Unsupported, got: {err:?}"),
+            _ => panic!("Expected DecodeError::TimestampOverflow, got: {err:?}"),
         }
     }
 
@@ -722,7 +769,7 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let result = decode_protobuf(&body);
+        let result = decode_protobuf(&body, false).map(|decoded| decoded.values);
 
         assert!(result.is_ok());
         let spans = result.unwrap();
@@ -736,14 +783,18 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let records = decode_protobuf(&body).unwrap();
+        let records = decode_protobuf(&body, false)
+            .map(|decoded| decoded.values)
+            .unwrap();
         assert!(records.is_empty());
     }
 
     #[test]
     fn handles_empty_json_request() {
         let body = r#"{"resourceSpans": []}"#;
-        let records = decode_json(body.as_bytes()).unwrap();
+        let records = decode_json(body.as_bytes(), false)
+            .map(|decoded| decoded.values)
+            .unwrap();
         assert!(records.is_empty());
     }
 
@@ -778,7 +829,9 @@ mod tests {
         };
 
         let body = request.encode_to_vec();
-        let records = decode_protobuf(&body).unwrap();
+        let records = decode_protobuf(&body, false)
+            .map(|decoded| decoded.values)
+            .unwrap();
         assert_eq!(records.len(), 1);
 
         if let VrlValue::Object(map) = &records[0] {
@@ -0,0 +1,612 @@
+//! Common utilities shared across OTLP encoders
+
+use arrow::array::{Array, ArrayRef, AsArray, BooleanArray, Float64Array, Int64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::{
+    DataType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, TimeUnit,
+    UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::array_value_to_string;
+use indexmap::map::Entry;
+use indexmap::IndexMap;
+use opentelemetry_proto::tonic::common::v1::{
+    any_value, AnyValue, ArrayValue, InstrumentationScope, KeyValue, KeyValueList,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use serde_json::Value as JsonValue;
+
+use crate::error::{Error, Result};
+
+// ============================================================================
+// Column readers
+// ============================================================================
+
+/// Cast a named column to `to`, or `None` if the batch has no such column.
+fn cast_column(batch: &RecordBatch, name: &str, to: &DataType) -> Result<Option<ArrayRef>> {
+    let Some(column) = batch.column_by_name(name) else {
+        return Ok(None);
+    };
+    cast(column, to)
+        .map(Some)
+        .map_err(|e| Error::SchemaMismatch(format!("column '{name}': {e}")))
+}
+
+/// Optional string column.
+pub(crate) struct StringColumn(Option<StringArray>);
+
+impl StringColumn {
+    pub fn new(batch: &RecordBatch, name: &str) -> Result<Self> {
+        Ok(Self(
+            cast_column(batch, name, &DataType::Utf8)?.map(|a| a.as_string::<i32>().clone()),
+        ))
+    }
+
+    pub fn get(&self, row: usize) -> Option<&str> {
+        self.0
+            .as_ref()
+            .filter(|a| a.is_valid(row))
+            .map(|a| a.value(row))
+    }
+
+    /// Value at `row`, with null as the empty string.
+    pub fn string(&self, row: usize) -> String {
+        self.get(row).unwrap_or_default().to_string()
+    }
+}
+
+/// Optional integer column.
+pub(crate) struct IntColumn(Option<Int64Array>);
+
+impl IntColumn {
+    pub fn new(batch: &RecordBatch, name: &str) -> Result<Self> {
+        Ok(Self(
+            cast_column(batch, name, &DataType::Int64)?
+                .map(|a| a.as_primitive::<Int64Type>().clone()),
+        ))
+    }
+
+    pub fn get(&self, row: usize) -> Option<i64> {
+        self.0
+            .as_ref()
+            .filter(|a| a.is_valid(row))
+            .map(|a| a.value(row))
+    }
+
+    /// Value at `row` as an `i32`, with null or out-of-range values as 0.
+    pub fn i32(&self, row: usize) -> i32 {
+        self.get(row)
+            .and_then(|v| i32::try_from(v).ok())
+            .unwrap_or(0)
+    }
+
+    /// Value at `row` as a `u32`, with null or out-of-range values as 0.
+    pub fn u32(&self, row: usize) -> u32 {
+        self.get(row)
+            .and_then(|v| u32::try_from(v).ok())
+            .unwrap_or(0)
+    }
+
+    /// Value at `row` as a `u64`, with null or negative values as 0.
+    pub fn u64(&self, row: usize) -> u64 {
+        self.get(row)
+            .and_then(|v| u64::try_from(v).ok())
+            .unwrap_or(0)
+    }
+}
+
+/// Optional timestamp column, read back as nanoseconds.
+pub(crate) struct NanosColumn {
+    values: IntColumn,
+    nanos_per_unit: i64,
+}
+
+impl NanosColumn {
+    /// Arrow `Timestamp` columns use their own unit; integer columns are
+    /// assumed to hold `unit`.
+    pub fn new(batch: &RecordBatch, name: &str, unit: TimeUnit) -> Result<Self> {
+        let unit = match batch.column_by_name(name).map(|c| c.data_type()) {
+            Some(DataType::Timestamp(unit, _)) => *unit,
+            _ => unit,
+        };
+        let nanos_per_unit = match unit {
+            TimeUnit::Second => 1_000_000_000,
+            TimeUnit::Millisecond => 1_000_000,
+            TimeUnit::Microsecond => 1_000,
+            TimeUnit::Nanosecond => 1,
+        };
+        Ok(Self {
+            values: IntColumn::new(batch, name)?,
+            nanos_per_unit,
+        })
+    }
+
+    /// Like [`NanosColumn::new`], but the column must exist.
+    pub fn required(batch: &RecordBatch, name: &str, unit: TimeUnit) -> Result<Self> {
+        if batch.column_by_name(name).is_none() {
+            return Err(Error::SchemaMismatch(format!("missing column '{name}'")));
+        }
+        Self::new(batch, name, unit)
+    }
+
+    /// Nanoseconds at `row`, with null or negative values as 0.
+    pub fn get(&self, row: usize) -> u64 {
+        self.values
+            .get(row)
+            .and_then(|v| u64::try_from(v.saturating_mul(self.nanos_per_unit)).ok())
+            .unwrap_or(0)
+    }
+}
+
+/// Optional float column.
+pub(crate) struct FloatColumn(Option<Float64Array>);
+
+impl FloatColumn {
+    pub fn new(batch: &RecordBatch, name: &str) -> Result<Self> {
+        Ok(Self(
+            cast_column(batch, name, &DataType::Float64)?
+                .map(|a| a.as_primitive::<Float64Type>().clone()),
+        ))
+    }
+
+    pub fn get(&self, row: usize) -> Option<f64> {
+        self.0
+            .as_ref()
+            .filter(|a| a.is_valid(row))
+            .map(|a| a.value(row))
+    }
+}
+
+/// Optional boolean column.
+pub(crate) struct BoolColumn(Option<BooleanArray>);
+
+impl BoolColumn {
+    pub fn new(batch: &RecordBatch, name: &str) -> Result<Self> {
+        Ok(Self(
+            cast_column(batch, name, &DataType::Boolean)?.map(|a| a.as_boolean().clone()),
+        ))
+    }
+
+    pub fn get(&self, row: usize) -> Option<bool> {
+        self.0
+            .as_ref()
+            .filter(|a| a.is_valid(row))
+            .map(|a| a.value(row))
+    }
+}
+
+/// Optional JSON column: a JSON string, or a native Map/List/Struct column.
+pub(crate) enum JsonColumn {
+    Missing,
+    Text(StringArray),
+    Nested(ArrayRef),
+}
+
+impl JsonColumn {
+    pub fn new(batch: &RecordBatch, name: &str) -> Result<Self> {
+        let Some(column) = batch.column_by_name(name) else {
+            return Ok(JsonColumn::Missing);
+        };
+        match column.data_type() {
+            DataType::List(_)
+            | DataType::LargeList(_)
+            | DataType::Struct(_)
+            | DataType::Map(_, _) => Ok(JsonColumn::Nested(column.clone())),
+            _ => Ok(JsonColumn::Text(
+                StringColumn::new(batch, name)?
+                    .0
+                    .unwrap_or_else(|| unreachable!("column '{name}' exists in the batch")),
+            )),
+        }
+    }
+
+    /// Parsed value at `row`; null and empty strings are `None`.
+    pub fn get(&self, row: usize) -> Result<Option<JsonValue>> {
+        match self {
+            JsonColumn::Missing => Ok(None),
+            JsonColumn::Text(array) => {
+                if array.is_null(row) || array.value(row).is_empty() {
+                    return Ok(None);
+                }
+                Ok(Some(serde_json::from_str(array.value(row))?))
+            }
+            JsonColumn::Nested(array) => {
+                Ok(Some(array_value_to_json(array.as_ref(), row)).filter(|v| !v.is_null()))
+            }
+        }
+    }
+
+    /// Grouping key for the value at `row`.
+    fn key(&self, row: usize) -> String {
+        match self {
+            JsonColumn::Missing => String::new(),
+            JsonColumn::Text(array) if array.is_null(row) => String::new(),
+            JsonColumn::Text(array) => array.value(row).to_string(),
+            JsonColumn::Nested(array) => array_value_to_json(array.as_ref(), row).to_string(),
+        }
+    }
+}
+
+/// Convert a single cell of a (possibly nested) Arrow array to JSON.
+fn array_value_to_json(array: &dyn Array, row: usize) -> JsonValue {
+    if array.is_null(row) {
+        return JsonValue::Null;
+    }
+    match array.data_type() {
+        DataType::Boolean => JsonValue::Bool(array.as_boolean().value(row)),
+        DataType::Int8 => array.as_primitive::<Int8Type>().value(row).into(),
+        DataType::Int16 => array.as_primitive::<Int16Type>().value(row).into(),
+        DataType::Int32 => array.as_primitive::<Int32Type>().value(row).into(),
+        DataType::Int64 => array.as_primitive::<Int64Type>().value(row).into(),
+        DataType::UInt8 => array.as_primitive::<UInt8Type>().value(row).into(),
+        DataType::UInt16 => array.as_primitive::<UInt16Type>().value(row).into(),
+        DataType::UInt32 => array.as_primitive::<UInt32Type>().value(row).into(),
+        DataType::UInt64 => array.as_primitive::<UInt64Type>().value(row).into(),
+        DataType::Float32 => float_to_json(array.as_primitive::<Float32Type>().value(row) as f64),
+        DataType::Float64 => float_to_json(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => JsonValue::String(array.as_string::<i32>().value(row).to_string()),
+        DataType::LargeUtf8 => JsonValue::String(array.as_string::<i64>().value(row).to_string()),
+        DataType::List(_) => list_to_json(array.as_list::<i32>().value(row).as_ref()),
+        DataType::LargeList(_) => list_to_json(array.as_list::<i64>().value(row).as_ref()),
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            JsonValue::Object(
+                fields
+                    .iter()
+                    .zip(array.columns())
+                    .map(|(field, column)| {
+                        (
+                            field.name().clone(),
+                            array_value_to_json(column.as_ref(), row),
+                        )
+                    })
+                    .collect(),
+            )
+        }
+        DataType::Map(_, _) => {
+            let entries = array.as_map().value(row);
+            let (keys, values) = (entries.column(0), entries.column(1));
+            JsonValue::Object(
+                (0..entries.len())
+                    .map(|i| {
+                        let key = match array_value_to_json(keys.as_ref(), i) {
+                            JsonValue::String(key) => key,
+                            other => other.to_string(),
+                        };
+                        (key, array_value_to_json(values.as_ref(), i))
+                    })
+                    .collect(),
+            )
+        }
+        _ => JsonValue::String(array_value_to_string(array, row).unwrap_or_default()),
+    }
+}
+
+fn list_to_json(values: &dyn Array) -> JsonValue {
+    JsonValue::Array(
+        (0..values.len())
+            .map(|i| array_value_to_json(values, i))
+            .collect(),
+    )
+}
+
+fn float_to_json(value: f64) -> JsonValue {
+    serde_json::Number::from_f64(value)
+        .map(JsonValue::Number)
+        .unwrap_or(JsonValue::Null)
+}
+
+// ============================================================================
+// JSON -> OTLP conversion
+// ============================================================================
+
+/// Convert a JSON attributes object to OTLP key-values. Null values are dropped.
+pub(crate) fn json_to_key_values(value: Option<JsonValue>) -> Vec<KeyValue> {
+    match value {
+        Some(JsonValue::Object(map)) => map
+            .into_iter()
+            .filter_map(|(key, value)| {
+                json_to_any_value(value).map(|value| KeyValue {
+                    key,
+                    value: Some(value),
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Convert a JSON value to an OTLP AnyValue. Objects become kvlists.
+pub(crate) fn json_to_any_value(value: JsonValue) -> Option<AnyValue> {
+    let value = match value {
+        JsonValue::Null => return None,
+        JsonValue::Bool(b) => any_value::Value::BoolValue(b),
+        JsonValue::Number(n) => match n.as_i64() {
+            Some(i) => any_value::Value::IntValue(i),
+            None => any_value::Value::DoubleValue(n.as_f64().unwrap_or_default()),
+        },
+        JsonValue::String(s) => any_value::Value::StringValue(s),
+        JsonValue::Array(items) => any_value::Value::ArrayValue(ArrayValue {
+            values: items
+                .into_iter()
+                .map(|item| json_to_any_value(item).unwrap_or_default())
+                .collect(),
+        }),
+        JsonValue::Object(map) => any_value::Value::KvlistValue(KeyValueList {
+            values: json_to_key_values(Some(JsonValue::Object(map))),
+        }),
+    };
+    Some(AnyValue { value: Some(value) })
+}
+
+/// Iterate the items of a JSON array; anything else is empty.
+pub(crate) fn json_array(value: Option<JsonValue>) -> impl Iterator<Item = JsonValue> {
+    match value {
+        Some(JsonValue::Array(items)) => items.into_iter(),
+        _ => Vec::new().into_iter(),
+    }
+}
+
+/// String field of a JSON object, or empty.
+pub(crate) fn json_str(value: &JsonValue, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(JsonValue::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Unsigned integer field of a JSON object (number or numeric string), or 0.
+pub(crate) fn json_u64(value: &JsonValue) -> u64 {
+    match value {
+        JsonValue::Number(n) => n.as_u64().unwrap_or(0),
+        JsonValue::String(s) => s.parse().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// Float field of a JSON object, or `None` if absent or null.
+pub(crate) fn json_f64(value: &JsonValue) -> Option<f64> {
+    match value {
+        JsonValue::Number(n) => n.as_f64(),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Decode a hex trace or span ID; null is the empty ID.
+pub(crate) fn hex_id(value: Option<&str>, column: &str, row: usize) -> Result<Vec<u8>> {
+    match value {
+        None => Ok(Vec::new()),
+        Some(hex) => const_hex::decode(hex)
+            .map_err(|e| Error::InvalidInput(format!("row {row}: invalid {column} '{hex}': {e}"))),
+    }
+}
+
+// ============================================================================
+// Resource/scope regrouping
+// ============================================================================
+
+/// Columns that identify a row's resource and instrumentation scope.
+pub(crate) struct ResourceScopeColumns {
+    resource_attributes: JsonColumn,
+    service_name: StringColumn,
+    service_namespace: StringColumn,
+    service_instance_id: StringColumn,
+    scope_name: StringColumn,
+    scope_version: StringColumn,
+    scope_attributes: JsonColumn,
+}
+
+impl ResourceScopeColumns {
+    pub fn new(batch: &RecordBatch) -> Result<Self> {
+        Ok(Self {
+            resource_attributes: JsonColumn::new(batch, "resource_attributes")?,
+            service_name: StringColumn::new(batch, "service_name")?,
+            service_namespace: StringColumn::new(batch, "service_namespace")?,
+            service_instance_id: StringColumn::new(batch, "service_instance_id")?,
+            scope_name: StringColumn::new(batch, "scope_name")?,
+            scope_version: StringColumn::new(batch, "scope_version")?,
+            scope_attributes: JsonColumn::new(batch, "scope_attributes")?,
+        })
+    }
+
+    fn service_columns(&self) -> [(&'static str, &StringColumn); 3] {
+        [
+            ("service.name", &self.service_name),
+            ("service.namespace", &self.service_namespace),
+            ("service.instance.id", &self.service_instance_id),
+        ]
+    }
+
+    fn resource_key(&self, row: usize) -> String {
+        let mut key = self.resource_attributes.key(row);
+        for (_, column) in self.service_columns() {
+            key.push('\u{1f}');
+            key.push_str(column.get(row).unwrap_or_default());
+        }
+        key
+    }
+
+    fn scope_key(&self, row: usize) -> String {
+        format!(
+            "{}\u{1f}{}\u{1f}{}",
+            self.scope_name.get(row).unwrap_or_default(),
+            self.scope_version.get(row).unwrap_or_default(),
+            self.scope_attributes.key(row)
+        )
+    }
+
+    /// Rebuild the resource. Service columns fill in attributes missing
+    /// from the blob, except the `"unknown"` service name placeholder.
+    fn resource(&self, row: usize) -> Result<Resource> {
+        let mut attributes = json_to_key_values(self.resource_attributes.get(row)?);
+        for (key, column) in self.service_columns() {
+            let Some(value) = column.get(row) else {
+                continue;
+            };
+            if (key == "service.name" && value == "unknown")
+                || attributes.iter().any(|kv| kv.key == key)
+            {
+                continue;
+            }
+            attributes.push(KeyValue {
+                key: key.to_string(),
+                value: Some(AnyValue {
+                    value: Some(any_value::Value::StringValue(value.to_string())),
+                }),
+            });
+        }
+        Ok(Resource {
+            attributes,
+            ..Default::default()
+        })
+    }
+
+    fn scope(&self, row: usize) -> Result<InstrumentationScope> {
+        Ok(InstrumentationScope {
+            name: self.scope_name.string(row),
+            version: self.scope_version.string(row),
+            attributes: json_to_key_values(self.scope_attributes.get(row)?),
+            dropped_attributes_count: 0,
+        })
+    }
+}
+
+/// Rows of one instrumentation scope.
+pub(crate) struct ScopeGroup<T> {
+    pub scope: InstrumentationScope,
+    pub items: T,
+}
+
+/// Scopes of one resource, in first-seen order.
+pub(crate) struct ResourceGroup<T> {
+    pub resource: Resource,
+    pub scopes: IndexMap<String, ScopeGroup<T>>,
+}
+
+/// Rows regrouped by resource and scope, in first-seen order.
+pub(crate) struct ResourceScopeGroups<T> {
+    resources: IndexMap<String, ResourceGroup<T>>,
+}
+
+impl<T: Default> ResourceScopeGroups<T> {
+    pub fn new() -> Self {
+        Self {
+            resources: IndexMap::new(),
+        }
+    }
+
+    /// Items of the resource/scope group that `row` belongs to.
+    pub fn entry(&mut self, columns: &ResourceScopeColumns, row: usize) -> Result<&mut T> {
+        let group = match self.resources.entry(columns.resource_key(row)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(ResourceGroup {
+                resource: columns.resource(row)?,
+                scopes: IndexMap::new(),
+            }),
+        };
+        let scope = match group.scopes.entry(columns.scope_key(row)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(ScopeGroup {
+                scope: columns.scope(row)?,
+                items: T::default(),
+            }),
+        };
+        Ok(&mut scope.items)
+    }
+
+    pub fn into_resources(self) -> impl Iterator<Item = ResourceGroup<T>> {
+        self.resources.into_values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{MapBuilder, StringBuilder};
+    use arrow::datatypes::{Field, Schema};
+    use std::sync::Arc;
+
+    #[test]
+    fn json_attributes_to_key_values() {
+        let value = serde_json::json!({
+            "s": "x",
+            "i": 42,
+            "f": 1.5,
+            "b": true,
+            "a": [1, "two"],
+            "o": {"k": "v"},
+            "n": null
+        });
+        let kvs = json_to_key_values(Some(value));
+        let get = |key: &str| {
+            kvs.iter()
+                .find(|kv| kv.key == key)
+                .and_then(|kv| kv.value.clone())
+                .and_then(|v| v.value)
+        };
+
+        assert_eq!(kvs.len(), 6);
+        assert_eq!(get("s"), Some(any_value::Value::StringValue("x".into())));
+        assert_eq!(get("i"), Some(any_value::Value::IntValue(42)));
+        assert_eq!(get("f"), Some(any_value::Value::DoubleValue(1.5)));
+        assert_eq!(get("b"), Some(any_value::Value::BoolValue(true)));
+        assert!(matches!(get("a"), Some(any_value::Value::ArrayValue(a)) if a.values.len() == 2));
+        assert!(
+            matches!(get("o"), Some(any_value::Value::KvlistValue(kv)) if kv.values.len() == 1)
+        );
+        assert!(get("n").is_none());
+    }
+
+    #[test]
+    fn nanos_column_units() {
+        let schema = Schema::new(vec![
+            Field::new("ts", DataType::Timestamp(TimeUnit::Microsecond, None), true),
+            Field::new("ms", DataType::Int64, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(arrow::array::TimestampMicrosecondArray::from(vec![
+                    Some(2),
+                    None,
+                ])) as ArrayRef,
+                Arc::new(Int64Array::from(vec![Some(3), Some(-1)])),
+            ],
+        )
+        .unwrap();
+
+        let ts = NanosColumn::new(&batch, "ts", TimeUnit::Millisecond).unwrap();
+        assert_eq!(ts.get(0), 2_000);
+        assert_eq!(ts.get(1), 0);
+
+        let ms = NanosColumn::new(&batch, "ms", TimeUnit::Millisecond).unwrap();
+        assert_eq!(ms.get(0), 3_000_000);
+        assert_eq!(ms.get(1), 0);
+
+        assert!(NanosColumn::required(&batch, "missing", TimeUnit::Second).is_err());
+    }
+
+    #[test]
+    fn json_column_reads_native_map() {
+        let mut builder = MapBuilder::new(None, StringBuilder::new(), StringBuilder::new());
+        builder.keys().append_value("k");
+        builder.values().append_value("v");
+        builder.append(true).unwrap();
+        let map = builder.finish();
+        let schema = Schema::new(vec![Field::new("attrs", map.data_type().clone(), true)]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(map)]).unwrap();
+
+        let column = JsonColumn::new(&batch, "attrs").unwrap();
+        assert_eq!(column.get(0).unwrap(), Some(serde_json::json!({"k": "v"})));
+    }
+
+    #[test]
+    fn hex_id_rejects_invalid() {
+        assert_eq!(hex_id(Some("0a0b"), "span_id", 0).unwrap(), vec![10, 11]);
+        assert!(hex_id(None, "span_id", 0).unwrap().is_empty());
+        let err = hex_id(Some("xyz"), "span_id", 3).unwrap_err();
+        assert!(err.to_string().contains("row 3"));
+    }
+}
@@ -0,0 +1,168 @@
+//! Log records -> OTLP export request
+
+use arrow::datatypes::TimeUnit;
+use arrow::record_batch::RecordBatch;
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue};
+use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
+use serde_json::Value as JsonValue;
+
+use super::common::{
+    hex_id, json_to_any_value, json_to_key_values, IntColumn, JsonColumn, NanosColumn,
+    ResourceScopeColumns, ResourceScopeGroups, StringColumn,
+};
+use crate::error::Result;
+
+/// Rebuild an OTLP logs export request from `logs` schema batches.
+///
+/// # Errors
+///
+/// Returns `Error::SchemaMismatch` if a batch has no `timestamp` column or a
+/// column has an unexpected type, and `Error::InvalidInput` for malformed
+/// IDs or attribute JSON.
+pub fn logs_request(batches: &[RecordBatch]) -> Result<ExportLogsServiceRequest> {
+    let mut groups = ResourceScopeGroups::<Vec<LogRecord>>::new();
+
+    for batch in batches {
+        let columns = ResourceScopeColumns::new(batch)?;
+        let timestamp = NanosColumn::required(batch, "timestamp", TimeUnit::Microsecond)?;
+        let observed_timestamp =
+            NanosColumn::new(batch, "observed_timestamp", TimeUnit::Microsecond)?;
+        let severity_number = IntColumn::new(batch, "severity_number")?;
+        let severity_text = StringColumn::new(batch, "severity_text")?;
+        let body = StringColumn::new(batch, "body")?;
+        let trace_id = StringColumn::new(batch, "trace_id")?;
+        let span_id = StringColumn::new(batch, "span_id")?;
+        let log_attributes = JsonColumn::new(batch, "log_attributes")?;
+
+        for row in 0..batch.num_rows() {
+            let record = LogRecord {
+                time_unix_nano: timestamp.get(row),
+                observed_time_unix_nano: observed_timestamp.get(row),
+                severity_number: severity_number.i32(row),
+                severity_text: severity_text.string(row),
+                body: body.get(row).map(body_value),
+                attributes: json_to_key_values(log_attributes.get(row)?),
+                trace_id: hex_id(trace_id.get(row), "trace_id", row)?,
+                span_id: hex_id(span_id.get(row), "span_id", row)?,
+                ..Default::default()
+            };
+            groups.entry(&columns, row)?.push(record);
+        }
+    }
+
+    let resource_logs = groups
+        .into_resources()
+        .map(|group| ResourceLogs {
+            resource: Some(group.resource),
+            scope_logs: group
+                .scopes
+                .into_values()
+                .map(|scope| ScopeLogs {
+                    scope: Some(scope.scope),
+                    log_records: scope.items,
+                    schema_url: String::new(),
+                })
+                .collect(),
+            schema_url: String::new(),
+        })
+        .collect();
+
+    Ok(ExportLogsServiceRequest { resource_logs })
+}
+
+/// Structured bodies are stored as JSON text; anything else is a string.
+fn body_value(body: &str) -> AnyValue {
+    if body.starts_with(['{', '[']) {
+        if let Ok(value @ (JsonValue::Object(_) | JsonValue::Array(_))) =
+            serde_json::from_str::<JsonValue>(body)
+        {
+            if let Some(value) = json_to_any_value(value) {
+                return value;
+            }
+        }
+    }
+    AnyValue {
+        value: Some(any_value::Value::StringValue(body.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transform_logs, InputFormat};
+
+    const LOGS_JSON: &str = r#"{
+        "resourceLogs": [{
+            "resource": {"attributes": [
+                {"key": "service.name", "value": {"stringValue": "api"}}
+            ]},
+            "scopeLogs": [{
+                "scope": {"name": "lib", "version": "1.0"},
+                "logRecords": [
+                    {
+                        "timeUnixNano": "1700000000123456000",
+                        "severityNumber": 9,
+                        "severityText": "INFO",
+                        "body": {"kvlistValue": {"values": [
+                            {"key": "msg", "value": {"stringValue": "hi"}}
+                        ]}},
+                        "traceId": "0102030405060708090a0b0c0d0e0f10",
+                        "attributes": [{"key": "n", "value": {"intValue": "7"}}]
+                    },
+                    {"timeUnixNano": "1700000001000000000", "body": {"stringValue": "plain"}}
+                ]
+            }]
+        }]
+    }"#;
+
+    #[test]
+    fn regroups_and_restores_fields() {
+        let batch = transform_logs(LOGS_JSON.as_bytes(), InputFormat::Json)
+            .unwrap()
+            .batch;
+        let request = logs_request(&[batch.clone(), batch]).unwrap();
+
+        assert_eq!(request.resource_logs.len(), 1);
+        let scope_logs = &request.resource_logs[0].scope_logs;
+        assert_eq!(scope_logs.len(), 1);
+        assert_eq!(scope_logs[0].scope.as_ref().unwrap().name, "lib");
+
+        let records = &scope_logs[0].log_records;
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].time_unix_nano, 1_700_000_000_123_456_000);
+        assert_eq!(records[0].severity_text, "INFO");
+        assert_eq!(records[0].trace_id, (1..=16).collect::<Vec<u8>>());
+        assert!(records[0].span_id.is_empty());
+        assert!(matches!(
+            records[0].body.as_ref().unwrap().value,
+            Some(any_value::Value::KvlistValue(_))
+        ));
+        assert_eq!(
+            records[0].attributes[0].value.as_ref().unwrap().value,
+            Some(any_value::Value::IntValue(7))
+        );
+        assert_eq!(
+            records[1].body.as_ref().unwrap().value,
+            Some(any_value::Value::StringValue("plain".into()))
+        );
+    }
+
+    #[test]
+    fn body_value_keeps_non_json_strings() {
+        assert_eq!(
+            body_value("{not json").value,
+            Some(any_value::Value::StringValue("{not json".into()))
+        );
+        assert_eq!(
+            body_value("42").value,
+            Some(any_value::Value::StringValue("42".into()))
+        );
+    }
+
+    #[test]
+    fn requires_timestamp_column() {
+        let batch = RecordBatch::new_empty(std::sync::Arc::new(arrow::datatypes::Schema::empty()));
+        assert!(logs_request(&[batch]).is_err());
+    }
+}
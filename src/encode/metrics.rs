@@ -0,0 +1,375 @@
+//! Metric records -> OTLP export request
+
+use arrow::datatypes::TimeUnit;
+use arrow::record_batch::RecordBatch;
+use indexmap::IndexMap;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::common::v1::KeyValue;
+use opentelemetry_proto::tonic::metrics::v1::{
+    exemplar, exponential_histogram_data_point::Buckets, metric, number_data_point,
+    summary_data_point::ValueAtQuantile, Exemplar, ExponentialHistogram,
+    ExponentialHistogramDataPoint, Gauge, Histogram, HistogramDataPoint, Metric, NumberDataPoint,
+    ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+};
+use serde_json::Value as JsonValue;
+
+use super::common::{
+    hex_id, json_array, json_f64, json_str, json_to_key_values, json_u64, BoolColumn, FloatColumn,
+    IntColumn, JsonColumn, NanosColumn, ResourceScopeColumns, ResourceScopeGroups, StringColumn,
+};
+use crate::error::Result;
+use crate::MetricBatches;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum MetricKind {
+    Gauge,
+    Sum,
+    Histogram,
+    ExpHistogram,
+    Summary,
+}
+
+impl MetricKind {
+    fn empty_data(self, temporality: i32, is_monotonic: bool) -> metric::Data {
+        match self {
+            MetricKind::Gauge => metric::Data::Gauge(Gauge::default()),
+            MetricKind::Sum => metric::Data::Sum(Sum {
+                data_points: Vec::new(),
+                aggregation_temporality: temporality,
+                is_monotonic,
+            }),
+            MetricKind::Histogram => metric::Data::Histogram(Histogram {
+                data_points: Vec::new(),
+                aggregation_temporality: temporality,
+            }),
+            MetricKind::ExpHistogram => metric::Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: Vec::new(),
+                aggregation_temporality: temporality,
+            }),
+            MetricKind::Summary => metric::Data::Summary(Summary::default()),
+        }
+    }
+}
+
+/// Identity of a metric stream within a scope.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct MetricKey {
+    kind: MetricKind,
+    name: String,
+    description: String,
+    unit: String,
+    temporality: i32,
+    is_monotonic: bool,
+}
+
+type ScopeMetricMap = IndexMap<MetricKey, Metric>;
+
+/// Rebuild an OTLP metrics export request from per-type metric batches.
+///
+/// Data points are regrouped into metrics by name, description, unit and
+/// (for sums and histograms) temporality, within each resource and scope.
+///
+/// # Errors
+///
+/// Returns `Error::SchemaMismatch` if a batch has no `timestamp` column or a
+/// column has an unexpected type, and `Error::InvalidInput` for malformed
+/// IDs or attribute JSON.
+pub fn metrics_request(batches: &MetricBatches) -> Result<ExportMetricsServiceRequest> {
+    let mut groups = ResourceScopeGroups::<ScopeMetricMap>::new();
+
+    let typed = [
+        (MetricKind::Gauge, &batches.gauge),
+        (MetricKind::Sum, &batches.sum),
+        (MetricKind::Histogram, &batches.histogram),
+        (MetricKind::ExpHistogram, &batches.exp_histogram),
+        (MetricKind::Summary, &batches.summary),
+    ];
+    for (kind, batch) in typed {
+        if let Some(batch) = batch {
+            add_batch(&mut groups, kind, batch)?;
+        }
+    }
+
+    let resource_metrics = groups
+        .into_resources()
+        .map(|group| ResourceMetrics {
+            resource: Some(group.resource),
+            scope_metrics: group
+                .scopes
+                .into_values()
+                .map(|scope| ScopeMetrics {
+                    scope: Some(scope.scope),
+                    metrics: scope.items.into_values().collect(),
+                    schema_url: String::new(),
+                })
+                .collect(),
+            schema_url: String::new(),
+        })
+        .collect();
+
+    Ok(ExportMetricsServiceRequest { resource_metrics })
+}
+
+fn add_batch(
+    groups: &mut ResourceScopeGroups<ScopeMetricMap>,
+    kind: MetricKind,
+    batch: &RecordBatch,
+) -> Result<()> {
+    let columns = ResourceScopeColumns::new(batch)?;
+    let common = PointColumns::new(batch)?;
+    let name = StringColumn::new(batch, "metric_name")?;
+    let description = StringColumn::new(batch, "metric_description")?;
+    let unit = StringColumn::new(batch, "metric_unit")?;
+    let temporality = IntColumn::new(batch, "aggregation_temporality")?;
+    let is_monotonic = BoolColumn::new(batch, "is_monotonic")?;
+    let value = FloatColumn::new(batch, "value")?;
+    let count = IntColumn::new(batch, "count")?;
+    let sum = FloatColumn::new(batch, "sum")?;
+    let min = FloatColumn::new(batch, "min")?;
+    let max = FloatColumn::new(batch, "max")?;
+    let bucket_counts = JsonColumn::new(batch, "bucket_counts")?;
+    let explicit_bounds = JsonColumn::new(batch, "explicit_bounds")?;
+    let scale = IntColumn::new(batch, "scale")?;
+    let zero_count = IntColumn::new(batch, "zero_count")?;
+    let zero_threshold = FloatColumn::new(batch, "zero_threshold")?;
+    let positive_offset = IntColumn::new(batch, "positive_offset")?;
+    let positive_bucket_counts = JsonColumn::new(batch, "positive_bucket_counts")?;
+    let negative_offset = IntColumn::new(batch, "negative_offset")?;
+    let negative_bucket_counts = JsonColumn::new(batch, "negative_bucket_counts")?;
+    let quantile_values = JsonColumn::new(batch, "quantile_values")?;
+
+    for row in 0..batch.num_rows() {
+        let key = MetricKey {
+            kind,
+            name: name.string(row),
+            description: description.string(row),
+            unit: unit.string(row),
+            temporality: temporality.i32(row),
+            is_monotonic: is_monotonic.get(row).unwrap_or(false),
+        };
+        let metric = groups
+            .entry(&columns, row)?
+            .entry(key.clone())
+            .or_insert_with(|| Metric {
+                name: key.name,
+                description: key.description,
+                unit: key.unit,
+                metadata: Vec::new(),
+                data: Some(key.kind.empty_data(key.temporality, key.is_monotonic)),
+            });
+
+        match metric.data.as_mut() {
+            Some(metric::Data::Gauge(Gauge { data_points }))
+            | Some(metric::Data::Sum(Sum { data_points, .. })) => {
+                data_points.push(NumberDataPoint {
+                    attributes: common.attributes(row)?,
+                    start_time_unix_nano: common.start_timestamp.get(row),
+                    time_unix_nano: common.timestamp.get(row),
+                    exemplars: common.exemplars(row)?,
+                    flags: common.flags.u32(row),
+                    value: value.get(row).map(number_data_point::Value::AsDouble),
+                });
+            }
+            Some(metric::Data::Histogram(histogram)) => {
+                histogram.data_points.push(HistogramDataPoint {
+                    attributes: common.attributes(row)?,
+                    start_time_unix_nano: common.start_timestamp.get(row),
+                    time_unix_nano: common.timestamp.get(row),
+                    count: count.u64(row),
+                    sum: sum.get(row),
+                    bucket_counts: json_array(bucket_counts.get(row)?)
+                        .map(|v| json_u64(&v))
+                        .collect(),
+                    explicit_bounds: json_array(explicit_bounds.get(row)?)
+                        .map(|v| json_f64(&v).unwrap_or(0.0))
+                        .collect(),
+                    exemplars: common.exemplars(row)?,
+                    flags: common.flags.u32(row),
+                    min: min.get(row),
+                    max: max.get(row),
+                });
+            }
+            Some(metric::Data::ExponentialHistogram(histogram)) => {
+                histogram.data_points.push(ExponentialHistogramDataPoint {
+                    attributes: common.attributes(row)?,
+                    start_time_unix_nano: common.start_timestamp.get(row),
+                    time_unix_nano: common.timestamp.get(row),
+                    count: count.u64(row),
+                    sum: sum.get(row),
+                    scale: scale.i32(row),
+                    zero_count: zero_count.u64(row),
+                    positive: Some(Buckets {
+                        offset: positive_offset.i32(row),
+                        bucket_counts: json_array(positive_bucket_counts.get(row)?)
+                            .map(|v| json_u64(&v))
+                            .collect(),
+                    }),
+                    negative: Some(Buckets {
+                        offset: negative_offset.i32(row),
+                        bucket_counts: json_array(negative_bucket_counts.get(row)?)
+                            .map(|v| json_u64(&v))
+                            .collect(),
+                    }),
+                    flags: common.flags.u32(row),
+                    exemplars: common.exemplars(row)?,
+                    min: min.get(row),
+                    max: max.get(row),
+                    zero_threshold: zero_threshold.get(row).unwrap_or(0.0),
+                });
+            }
+            Some(metric::Data::Summary(summary)) => {
+                summary.data_points.push(SummaryDataPoint {
+                    attributes: common.attributes(row)?,
+                    start_time_unix_nano: common.start_timestamp.get(row),
+                    time_unix_nano: common.timestamp.get(row),
+                    count: count.u64(row),
+                    sum: sum.get(row).unwrap_or(0.0),
+                    quantile_values: json_array(quantile_values.get(row)?)
+                        .map(|v| ValueAtQuantile {
+                            quantile: v.get("quantile").and_then(json_f64).unwrap_or(0.0),
+                            value: v.get("value").and_then(json_f64).unwrap_or(0.0),
+                        })
+                        .collect(),
+                    flags: common.flags.u32(row),
+                });
+            }
+            None => unreachable!("metrics are created with data"),
+        }
+    }
+    Ok(())
+}
+
+/// Columns shared by every metric schema.
+struct PointColumns {
+    timestamp: NanosColumn,
+    start_timestamp: NanosColumn,
+    metric_attributes: JsonColumn,
+    exemplars: JsonColumn,
+    flags: IntColumn,
+}
+
+impl PointColumns {
+    fn new(batch: &RecordBatch) -> Result<Self> {
+        Ok(Self {
+            timestamp: NanosColumn::required(batch, "timestamp", TimeUnit::Microsecond)?,
+            start_timestamp: NanosColumn::new(batch, "start_timestamp", TimeUnit::Microsecond)?,
+            metric_attributes: JsonColumn::new(batch, "metric_attributes")?,
+            exemplars: JsonColumn::new(batch, "exemplars_json")?,
+            flags: IntColumn::new(batch, "flags")?,
+        })
+    }
+
+    fn attributes(&self, row: usize) -> Result<Vec<KeyValue>> {
+        Ok(json_to_key_values(self.metric_attributes.get(row)?))
+    }
+
+    fn exemplars(&self, row: usize) -> Result<Vec<Exemplar>> {
+        json_array(self.exemplars.get(row)?)
+            .map(|mut value: JsonValue| {
+                Ok(Exemplar {
+                    time_unix_nano: value.get("time_unix_nano").map(json_u64).unwrap_or(0),
+                    value: value
+                        .get("value")
+                        .and_then(json_f64)
+                        .map(exemplar::Value::AsDouble),
+                    span_id: hex_id(
+                        Some(&json_str(&value, "span_id")),
+                        "exemplars_json.span_id",
+                        row,
+                    )?,
+                    trace_id: hex_id(
+                        Some(&json_str(&value, "trace_id")),
+                        "exemplars_json.trace_id",
+                        row,
+                    )?,
+                    filtered_attributes: json_to_key_values(
+                        value.get_mut("filtered_attributes").map(JsonValue::take),
+                    ),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transform_metrics, InputFormat};
+
+    const METRICS_JSON: &str = r#"{
+        "resourceMetrics": [{
+            "resource": {"attributes": [
+                {"key": "service.name", "value": {"stringValue": "api"}}
+            ]},
+            "scopeMetrics": [{
+                "scope": {"name": "meter"},
+                "metrics": [
+                    {
+                        "name": "requests",
+                        "unit": "1",
+                        "sum": {
+                            "aggregationTemporality": 2,
+                            "isMonotonic": true,
+                            "dataPoints": [
+                                {"timeUnixNano": "1700000000000000000", "asInt": "5"},
+                                {"timeUnixNano": "1700000001000000000", "asInt": "8"}
+                            ]
+                        }
+                    },
+                    {
+                        "name": "latency",
+                        "histogram": {
+                            "aggregationTemporality": 1,
+                            "dataPoints": [{
+                                "timeUnixNano": "1700000000000000000",
+                                "startTimeUnixNano": "1699999999000000000",
+                                "count": "3",
+                                "sum": 12.5,
+                                "bucketCounts": ["1", "2"],
+                                "explicitBounds": [10.0]
+                            }]
+                        }
+                    }
+                ]
+            }]
+        }]
+    }"#;
+
+    #[test]
+    fn regroups_points_into_metrics() {
+        let batches = transform_metrics(METRICS_JSON.as_bytes(), InputFormat::Json).unwrap();
+        let request = metrics_request(&batches).unwrap();
+
+        assert_eq!(request.resource_metrics.len(), 1);
+        let metrics = &request.resource_metrics[0].scope_metrics[0].metrics;
+        assert_eq!(metrics.len(), 2);
+
+        let Some(metric::Data::Sum(sum)) = &metrics[0].data else {
+            panic!("expected sum");
+        };
+        assert_eq!(metrics[0].name, "requests");
+        assert_eq!(sum.aggregation_temporality, 2);
+        assert!(sum.is_monotonic);
+        assert_eq!(sum.data_points.len(), 2);
+        assert_eq!(
+            sum.data_points[1].value,
+            Some(number_data_point::Value::AsDouble(8.0))
+        );
+
+        let Some(metric::Data::Histogram(histogram)) = &metrics[1].data else {
+            panic!("expected histogram");
+        };
+        let point = &histogram.data_points[0];
+        assert_eq!(point.count, 3);
+        assert_eq!(point.sum, Some(12.5));
+        assert_eq!(point.bucket_counts, vec![1, 2]);
+        assert_eq!(point.explicit_bounds, vec![10.0]);
+        assert_eq!(point.start_time_unix_nano, 1_699_999_999_000_000_000);
+    }
+
+    #[test]
+    fn empty_batches_make_empty_request() {
+        let request = metrics_request(&MetricBatches::default()).unwrap();
+        assert!(request.resource_metrics.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+//! OTLP encode layer
+//!
+//! The inverse of decode + transform: rebuilds OTLP export requests from
+//! RecordBatches in the built-in `logs`, `spans` and metric schemas. Rows are
+//! regrouped by resource and scope (in first-seen order), and the JSON
+//! attribute blobs are parsed back into OTLP `KeyValue`s.
+//!
+//! # Lossy Fields
+//!
+//! The flattened schemas do not keep everything an export request carries,
+//! so a round trip is not byte-identical:
+//!
+//! - Timestamps come back at the precision the schema stored (µs unless the
+//!   batch was cast to another [`TimestampUnit`](crate::TimestampUnit))
+//! - Bytes attribute values come back as strings, as do all values of native
+//!   `Map<Utf8, Utf8>` attribute columns
+//! - Log record flags and all `schema_url`s are not in the schema
+//! - Gauge/sum values and exemplar values are always doubles
+//! - A log body string that is itself a JSON object or array is read back as
+//!   a structured body
+
+mod common;
+mod logs;
+mod metrics;
+mod traces;
+
+pub use logs::logs_request;
+pub use metrics::metrics_request;
+pub use traces::traces_request;
+
+use crate::error::Result;
+
+/// Wire format of an encoded OTLP export request.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EncodeFormat {
+    /// OTLP protobuf
+    #[default]
+    Protobuf,
+    /// OTLP JSON (camelCase fields, hex IDs, string 64-bit integers)
+    Json,
+}
+
+impl EncodeFormat {
+    /// Returns the Content-Type for this format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            EncodeFormat::Protobuf => "application/x-protobuf",
+            EncodeFormat::Json => "application/json",
+        }
+    }
+}
+
+/// Serialize an export request in the given format.
+pub fn encode_request<M>(request: &M, format: EncodeFormat) -> Result<Vec<u8>>
+where
+    M: prost::Message + serde::Serialize,
+{
+    match format {
+        EncodeFormat::Protobuf => Ok(request.encode_to_vec()),
+        EncodeFormat::Json => Ok(serde_json::to_vec(request)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+
+    #[test]
+    fn encode_format_content_type() {
+        assert_eq!(EncodeFormat::default(), EncodeFormat::Protobuf);
+        assert_eq!(EncodeFormat::Json.content_type(), "application/json");
+    }
+
+    #[test]
+    fn encode_empty_request() {
+        let request = ExportLogsServiceRequest::default();
+        assert!(encode_request(&request, EncodeFormat::Protobuf)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            encode_request(&request, EncodeFormat::Json).unwrap(),
+            br#"{"resourceLogs":[]}"#
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Span records -> OTLP export request
+
+use arrow::datatypes::TimeUnit;
+use arrow::record_batch::RecordBatch;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::tonic::trace::v1::{span, ResourceSpans, ScopeSpans, Span, Status};
+use serde_json::Value as JsonValue;
+
+use super::common::{
+    hex_id, json_array, json_str, json_to_key_values, json_u64, IntColumn, JsonColumn, NanosColumn,
+    ResourceScopeColumns, ResourceScopeGroups, StringColumn,
+};
+use crate::error::Result;
+
+/// Rebuild an OTLP traces export request from `spans` schema batches.
+///
+/// # Errors
+///
+/// Returns `Error::SchemaMismatch` if a batch has no `timestamp` column or a
+/// column has an unexpected type, and `Error::InvalidInput` for malformed
+/// IDs or attribute JSON.
+pub fn traces_request(batches: &[RecordBatch]) -> Result<ExportTraceServiceRequest> {
+    let mut groups = ResourceScopeGroups::<Vec<Span>>::new();
+
+    for batch in batches {
+        let columns = ResourceScopeColumns::new(batch)?;
+        let timestamp = NanosColumn::required(batch, "timestamp", TimeUnit::Microsecond)?;
+        let end_timestamp = NanosColumn::new(batch, "end_timestamp", TimeUnit::Microsecond)?;
+        let trace_id = StringColumn::new(batch, "trace_id")?;
+        let span_id = StringColumn::new(batch, "span_id")?;
+        let parent_span_id = StringColumn::new(batch, "parent_span_id")?;
+        let trace_state = StringColumn::new(batch, "trace_state")?;
+        let span_name = StringColumn::new(batch, "span_name")?;
+        let span_kind = IntColumn::new(batch, "span_kind")?;
+        let status_code = IntColumn::new(batch, "status_code")?;
+        let status_message = StringColumn::new(batch, "status_message")?;
+        let span_attributes = JsonColumn::new(batch, "span_attributes")?;
+        let events = JsonColumn::new(batch, "events_json")?;
+        let links = JsonColumn::new(batch, "links_json")?;
+        let dropped_attributes_count = IntColumn::new(batch, "dropped_attributes_count")?;
+        let dropped_events_count = IntColumn::new(batch, "dropped_events_count")?;
+        let dropped_links_count = IntColumn::new(batch, "dropped_links_count")?;
+        let flags = IntColumn::new(batch, "flags")?;
+
+        for row in 0..batch.num_rows() {
+            let span = Span {
+                trace_id: hex_id(trace_id.get(row), "trace_id", row)?,
+                span_id: hex_id(span_id.get(row), "span_id", row)?,
+                trace_state: trace_state.string(row),
+                parent_span_id: hex_id(parent_span_id.get(row), "parent_span_id", row)?,
+                flags: flags.u32(row),
+                name: span_name.string(row),
+                kind: span_kind.i32(row),
+                start_time_unix_nano: timestamp.get(row),
+                end_time_unix_nano: end_timestamp.get(row),
+                attributes: json_to_key_values(span_attributes.get(row)?),
+                dropped_attributes_count: dropped_attributes_count.u32(row),
+                events: json_array(events.get(row)?).map(event).collect(),
+                dropped_events_count: dropped_events_count.u32(row),
+                links: json_array(links.get(row)?)
+                    .map(|l| link(l, row))
+                    .collect::<Result<_>>()?,
+                dropped_links_count: dropped_links_count.u32(row),
+                // Always set: OTLP JSON serializes a missing status as null
+                status: Some(Status {
+                    message: status_message.string(row),
+                    code: status_code.i32(row),
+                }),
+            };
+            groups.entry(&columns, row)?.push(span);
+        }
+    }
+
+    let resource_spans = groups
+        .into_resources()
+        .map(|group| ResourceSpans {
+            resource: Some(group.resource),
+            scope_spans: group
+                .scopes
+                .into_values()
+                .map(|scope| ScopeSpans {
+                    scope: Some(scope.scope),
+                    spans: scope.items,
+                    schema_url: String::new(),
+                })
+                .collect(),
+            schema_url: String::new(),
+        })
+        .collect();
+
+    Ok(ExportTraceServiceRequest { resource_spans })
+}
+
+fn event(mut value: JsonValue) -> span::Event {
+    span::Event {
+        time_unix_nano: value.get("time_unix_nano").map(json_u64).unwrap_or(0),
+        name: json_str(&value, "name"),
+        attributes: json_to_key_values(value.get_mut("attributes").map(JsonValue::take)),
+        dropped_attributes_count: 0,
+    }
+}
+
+fn link(mut value: JsonValue, row: usize) -> Result<span::Link> {
+    let trace_id = json_str(&value, "trace_id");
+    let span_id = json_str(&value, "span_id");
+    Ok(span::Link {
+        trace_id: hex_id(Some(&trace_id), "links_json.trace_id", row)?,
+        span_id: hex_id(Some(&span_id), "links_json.span_id", row)?,
+        trace_state: json_str(&value, "trace_state"),
+        attributes: json_to_key_values(value.get_mut("attributes").map(JsonValue::take)),
+        dropped_attributes_count: 0,
+        flags: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transform_traces, InputFormat};
+
+    const TRACES_JSON: &str = r#"{
+        "resourceSpans": [{
+            "resource": {"attributes": [
+                {"key": "service.name", "value": {"stringValue": "api"}}
+            ]},
+            "scopeSpans": [{
+                "scope": {"name": "lib"},
+                "spans": [{
+                    "traceId": "0102030405060708090a0b0c0d0e0f10",
+                    "spanId": "0102030405060708",
+                    "name": "GET /",
+                    "kind": 2,
+                    "startTimeUnixNano": "1700000000000000000",
+                    "endTimeUnixNano": "1700000000250000000",
+                    "status": {"code": 2, "message": "boom"},
+                    "events": [{
+                        "timeUnixNano": "1700000000100000000",
+                        "name": "retry",
+                        "attributes": [{"key": "n", "value": {"intValue": "1"}}]
+                    }],
+                    "links": [{
+                        "traceId": "100f0e0d0c0b0a090807060504030201",
+                        "spanId": "0807060504030201"
+                    }]
+                }]
+            }]
+        }]
+    }"#;
+
+    #[test]
+    fn restores_span_fields() {
+        let batch = transform_traces(TRACES_JSON.as_bytes(), InputFormat::Json)
+            .unwrap()
+            .batch;
+        let request = traces_request(&[batch]).unwrap();
+
+        let span = &request.resource_spans[0].scope_spans[0].spans[0];
+        assert_eq!(span.name, "GET /");
+        assert_eq!(span.kind, 2);
+        assert_eq!(span.span_id, (1..=8).collect::<Vec<u8>>());
+        assert!(span.parent_span_id.is_empty());
+        assert_eq!(span.end_time_unix_nano, 1_700_000_000_250_000_000);
+        assert_eq!(
+            span.status,
+            Some(Status {
+                message: "boom".into(),
+                code: 2
+            })
+        );
+        assert_eq!(span.events.len(), 1);
+        assert_eq!(span.events[0].name, "retry");
+        assert_eq!(span.events[0].time_unix_nano, 1_700_000_000_100_000_000);
+        assert_eq!(span.events[0].attributes.len(), 1);
+        assert_eq!(span.links.len(), 1);
+        assert_eq!(span.links[0].span_id, (1..=8).rev().collect::<Vec<u8>>());
+    }
+}
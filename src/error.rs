@@ -41,3 +41,64 @@ pub enum Error {
     #[error("schema mismatch: {0}")]
     SchemaMismatch(String),
 }
+
+/// Pipeline stage at which a record was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorStage {
+    /// Converting the OTLP record to a VRL value (e.g. timestamp overflow)
+    Decode,
+    /// Running the built-in or custom VRL program
+    Vrl,
+    /// Appending the transformed record to the Arrow batch
+    Arrow,
+}
+
+impl std::fmt::Display for ErrorStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ErrorStage::Decode => "decode",
+            ErrorStage::Vrl => "vrl",
+            ErrorStage::Arrow => "arrow",
+        })
+    }
+}
+
+/// Position of a record's resource and scope within the request.
+///
+/// For JSONL and length-delimited input, `resource_index` counts resources
+/// across all messages in the payload.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RecordLocation {
+    /// Index of the resource (`resourceLogs`, `resourceSpans`, ...)
+    pub resource_index: usize,
+    /// Index of the scope within its resource
+    pub scope_index: usize,
+}
+
+/// A record dropped in lenient mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordError {
+    /// Index of the record (log record, span, data point or sample) in the
+    /// payload, counting dropped records
+    pub index: usize,
+    /// Resource and scope the record belongs to
+    pub location: RecordLocation,
+    /// Stage that rejected the record
+    pub stage: ErrorStage,
+    /// Error message
+    pub message: String,
+}
+
+impl std::fmt::Display for RecordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "record {} (resource {}, scope {}) {} error: {}",
+            self.index,
+            self.location.resource_index,
+            self.location.scope_index,
+            self.stage,
+            self.message
+        )
+    }
+}
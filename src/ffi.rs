@@ -19,14 +19,16 @@ use std::ptr;
 use std::sync::Arc;
 
 use arrow::array::{Array, RecordBatch};
+use arrow::compute::concat_batches;
 use arrow::datatypes::Schema;
+use arrow::error::ArrowError;
 use arrow::ffi::{FFI_ArrowArray, FFI_ArrowSchema};
 use arrow::ffi_stream::FFI_ArrowArrayStream;
 
-use crate::decode::InputFormat;
+use crate::decode::{decompress, ContentEncoding, InputFormat, DEFAULT_MAX_DECOMPRESSED_SIZE};
 use crate::{
-    exp_histogram_schema, gauge_schema, histogram_schema, logs_schema, sum_schema, traces_schema,
-    transform_logs, transform_metrics, transform_traces,
+    transform_logs, transform_metrics, transform_profiles, transform_traces,
+    try_group_batch_by_service, MetricBatches, SignalType, TransformStats,
 };
 
 // ============================================================================
@@ -51,6 +53,10 @@ pub enum OtlpSignalType {
     MetricsHistogram = 4,
     /// Exponential histogram metrics (C: OTLP_SIGNAL_METRICS_EXP_HISTOGRAM)
     MetricsExpHistogram = 5,
+    /// Summary metrics (C: OTLP_SIGNAL_METRICS_SUMMARY)
+    MetricsSummary = 6,
+    /// Profile samples (C: OTLP_SIGNAL_PROFILES)
+    Profiles = 7,
 }
 
 /// Input format for OTLP data.
@@ -67,6 +73,8 @@ pub enum OtlpInputFormat {
     Json = 2,
     /// Newline-delimited JSON (C: OTLP_FORMAT_JSONL)
     Jsonl = 3,
+    /// Varint length-prefixed protobuf messages (C: OTLP_FORMAT_PROTOBUF_DELIMITED)
+    ProtobufDelimited = 4,
 }
 
 impl From<OtlpInputFormat> for InputFormat {
@@ -76,6 +84,31 @@ impl From<OtlpInputFormat> for InputFormat {
             OtlpInputFormat::Protobuf => InputFormat::Protobuf,
             OtlpInputFormat::Json => InputFormat::Json,
             OtlpInputFormat::Jsonl => InputFormat::Jsonl,
+            OtlpInputFormat::ProtobufDelimited => InputFormat::ProtobufDelimited,
+        }
+    }
+}
+
+/// Content encoding of pushed input.
+///
+/// C names: OTLP_ENCODING_IDENTITY, OTLP_ENCODING_GZIP, etc.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtlpContentEncoding {
+    /// Uncompressed input (C: OTLP_ENCODING_IDENTITY)
+    Identity = 0,
+    /// gzip-compressed input (C: OTLP_ENCODING_GZIP)
+    Gzip = 1,
+    /// zstd-compressed input (C: OTLP_ENCODING_ZSTD)
+    Zstd = 2,
+}
+
+impl From<OtlpContentEncoding> for ContentEncoding {
+    fn from(e: OtlpContentEncoding) -> Self {
+        match e {
+            OtlpContentEncoding::Identity => ContentEncoding::Identity,
+            OtlpContentEncoding::Gzip => ContentEncoding::Gzip,
+            OtlpContentEncoding::Zstd => ContentEncoding::Zstd,
         }
     }
 }
@@ -100,6 +133,59 @@ pub enum OtlpStatus {
     Internal = 5,
 }
 
+// ============================================================================
+// C-compatible structs
+// ============================================================================
+
+/// Record accounting for a transform (see `TransformStats`).
+///
+/// For metric signals the counts cover every metric type in the input, not
+/// only the type the handle emits.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OtlpTransformStats {
+    /// Records in the input
+    pub records_in: usize,
+    /// Rows written to the output batches
+    pub records_out: usize,
+    /// Metric data points dropped for a NaN value
+    pub dropped_nan_values: usize,
+    /// Metric data points dropped for an infinite value
+    pub dropped_infinity_values: usize,
+    /// Metric data points dropped for a missing value
+    pub dropped_missing_values: usize,
+    /// Records dropped because they failed to decode
+    pub dropped_decode_errors: usize,
+    /// Records dropped because their VRL program failed
+    pub dropped_vrl_errors: usize,
+    /// Records dropped because they didn't fit the Arrow schema
+    pub dropped_arrow_errors: usize,
+    /// Values stored as null although the transformed value was not null
+    pub null_coerced_values: usize,
+    /// Trace/span IDs that are not 32/16 hex characters
+    pub invalid_ids: usize,
+    /// Timestamps past the year 2262
+    pub timestamp_overflows: usize,
+}
+
+impl From<&TransformStats> for OtlpTransformStats {
+    fn from(stats: &TransformStats) -> Self {
+        Self {
+            records_in: stats.records_in,
+            records_out: stats.records_out,
+            dropped_nan_values: stats.dropped.nan_values,
+            dropped_infinity_values: stats.dropped.infinity_values,
+            dropped_missing_values: stats.dropped.missing_values,
+            dropped_decode_errors: stats.dropped.decode_errors,
+            dropped_vrl_errors: stats.dropped.vrl_errors,
+            dropped_arrow_errors: stats.dropped.arrow_errors,
+            null_coerced_values: stats.null_coerced_values,
+            invalid_ids: stats.invalid_ids,
+            timestamp_overflows: stats.timestamp_overflows,
+        }
+    }
+}
+
 // ============================================================================
 // Parser Handle
 // ============================================================================
@@ -107,12 +193,21 @@ pub enum OtlpStatus {
 /// Opaque parser handle for streaming OTLP data.
 ///
 /// This handle maintains state for parsing OTLP data and producing Arrow batches.
+/// JSONL input is parsed as complete lines arrive, so only the trailing partial
+/// line and the decoded rows not yet emitted are held between pushes. Rows are
+/// emitted as one batch on the final push unless a batch size has been set.
 /// It is NOT thread-safe - use one handle per thread.
 pub struct OtlpParserHandle {
     signal_type: OtlpSignalType,
     format: InputFormat,
+    encoding: ContentEncoding,
+    max_decompressed_size: usize,
+    batch_size: Option<usize>,
     buffer: Vec<u8>,
+    pending: Vec<RecordBatch>,
+    pending_rows: usize,
     batches: Vec<RecordBatch>,
+    stats: TransformStats,
     last_error: Option<CString>,
 }
 
@@ -121,8 +216,14 @@ impl OtlpParserHandle {
         Self {
             signal_type,
             format: format.into(),
+            encoding: ContentEncoding::Identity,
+            max_decompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+            batch_size: None,
             buffer: Vec::new(),
+            pending: Vec::new(),
+            pending_rows: 0,
             batches: Vec::new(),
+            stats: TransformStats::default(),
             last_error: None,
         }
     }
@@ -139,57 +240,204 @@ impl OtlpParserHandle {
         self.clear_error();
         self.buffer.extend_from_slice(data);
 
-        if !is_final {
-            // For streaming: could parse complete lines for JSONL here
-            // For now, wait until is_final
+        let status = if self.streams_records() {
+            self.push_records(is_final)
+        } else if is_final {
+            self.push_final()
+        } else {
+            // Protobuf and JSON documents can only be parsed once complete
             return OtlpStatus::Ok;
-        }
+        };
 
-        // Parse the complete buffer
-        let result = match self.signal_type {
-            OtlpSignalType::Logs => transform_logs(&self.buffer, self.format).map(Some),
-            OtlpSignalType::Traces => transform_traces(&self.buffer, self.format).map(Some),
-            OtlpSignalType::MetricsGauge => {
-                transform_metrics(&self.buffer, self.format).map(|m| m.gauge)
-            }
-            OtlpSignalType::MetricsSum => {
-                transform_metrics(&self.buffer, self.format).map(|m| m.sum)
-            }
-            OtlpSignalType::MetricsHistogram => {
-                transform_metrics(&self.buffer, self.format).map(|m| m.histogram)
+        if status == OtlpStatus::Ok && is_final {
+            if let Err(e) = self.flush() {
+                self.set_error(&e.to_string());
+                return OtlpStatus::Internal;
             }
-            OtlpSignalType::MetricsExpHistogram => {
-                transform_metrics(&self.buffer, self.format).map(|m| m.exp_histogram)
+        }
+        status
+    }
+
+    /// Uncompressed JSONL and length-delimited protobuf input is parsed
+    /// record by record as it arrives.
+    fn streams_records(&self) -> bool {
+        matches!(
+            self.format,
+            InputFormat::Jsonl | InputFormat::ProtobufDelimited
+        ) && self.encoding == ContentEncoding::Identity
+    }
+
+    /// Parse every complete record in the buffer, keeping the trailing partial one.
+    fn push_records(&mut self, is_final: bool) -> OtlpStatus {
+        let end = if is_final {
+            self.buffer.len()
+        } else if self.format == InputFormat::Jsonl {
+            match self.buffer.iter().rposition(|&b| b == b'\n') {
+                Some(pos) => pos + 1,
+                None => return OtlpStatus::Ok,
             }
+        } else {
+            complete_frames_len(&self.buffer)
         };
 
-        match result {
-            Ok(Some(batch)) => {
-                self.batches.push(batch);
-                self.buffer.clear();
-                OtlpStatus::Ok
-            }
-            Ok(None) => {
-                // No data of this type (e.g., no gauge metrics in input)
+        let records: Vec<u8> = self.buffer.drain(..end).collect();
+        let blank = match self.format {
+            InputFormat::Jsonl => records.iter().all(u8::is_ascii_whitespace),
+            _ => records.is_empty(),
+        };
+        if blank {
+            return OtlpStatus::Ok;
+        }
+
+        match self.transform(&records) {
+            Ok(batch) => self.enqueue(batch),
+            Err(e) => {
+                self.set_error(&e.to_string());
                 self.buffer.clear();
-                OtlpStatus::Ok
+                OtlpStatus::ParseFailed
             }
+        }
+    }
+
+    /// Decompress and parse the complete buffer.
+    fn push_final(&mut self) -> OtlpStatus {
+        let buffer = std::mem::take(&mut self.buffer);
+        let result = decompress(&buffer, self.encoding, self.max_decompressed_size)
+            .map_err(crate::Error::from)
+            .and_then(|bytes| self.transform(&bytes));
+
+        match result {
+            Ok(batch) => self.enqueue(batch),
             Err(e) => {
                 self.set_error(&e.to_string());
-                self.buffer.clear();
                 OtlpStatus::ParseFailed
             }
         }
     }
 
+    fn transform(&mut self, bytes: &[u8]) -> crate::Result<Option<RecordBatch>> {
+        let (batch, stats) = transform_signal(self.signal_type, bytes, self.format)?;
+        self.stats.merge(&stats);
+        Ok(batch)
+    }
+
+    /// Queue parsed rows, emitting batches of `batch_size` rows once enough are
+    /// pending. Without a batch size, rows are held until the final push.
+    fn enqueue(&mut self, batch: Option<RecordBatch>) -> OtlpStatus {
+        // No data of this type (e.g., no gauge metrics in input)
+        let Some(batch) = batch.filter(|b| b.num_rows() > 0) else {
+            return OtlpStatus::Ok;
+        };
+
+        self.pending_rows += batch.num_rows();
+        self.pending.push(batch);
+        let Some(batch_size) = self.batch_size.filter(|&size| self.pending_rows >= size) else {
+            return OtlpStatus::Ok;
+        };
+
+        let combined = match self.take_pending() {
+            Ok(combined) => combined,
+            Err(e) => {
+                self.set_error(&e.to_string());
+                return OtlpStatus::Internal;
+            }
+        };
+
+        let mut offset = 0;
+        while combined.num_rows() - offset >= batch_size {
+            self.batches.push(combined.slice(offset, batch_size));
+            offset += batch_size;
+        }
+        let remaining = combined.num_rows() - offset;
+        if remaining > 0 {
+            self.pending.push(combined.slice(offset, remaining));
+            self.pending_rows = remaining;
+        }
+        OtlpStatus::Ok
+    }
+
+    /// Emit pending rows as a final, possibly short, batch.
+    fn flush(&mut self) -> Result<(), ArrowError> {
+        if !self.pending.is_empty() {
+            let batch = self.take_pending()?;
+            self.batches.push(batch);
+        }
+        Ok(())
+    }
+
+    fn take_pending(&mut self) -> Result<RecordBatch, ArrowError> {
+        let pending = std::mem::take(&mut self.pending);
+        self.pending_rows = 0;
+        concat_batches(&pending[0].schema(), &pending)
+    }
+
     fn get_schema(&self) -> Arc<Schema> {
-        match self.signal_type {
-            OtlpSignalType::Logs => Arc::new(logs_schema()),
-            OtlpSignalType::Traces => Arc::new(traces_schema()),
-            OtlpSignalType::MetricsGauge => Arc::new(gauge_schema()),
-            OtlpSignalType::MetricsSum => Arc::new(sum_schema()),
-            OtlpSignalType::MetricsHistogram => Arc::new(histogram_schema()),
-            OtlpSignalType::MetricsExpHistogram => Arc::new(exp_histogram_schema()),
+        Arc::new(SignalType::from(self.signal_type).schema())
+    }
+}
+
+impl From<OtlpSignalType> for SignalType {
+    fn from(signal_type: OtlpSignalType) -> Self {
+        match signal_type {
+            OtlpSignalType::Logs => SignalType::Logs,
+            OtlpSignalType::Traces => SignalType::Traces,
+            OtlpSignalType::MetricsGauge => SignalType::Gauge,
+            OtlpSignalType::MetricsSum => SignalType::Sum,
+            OtlpSignalType::MetricsHistogram => SignalType::Histogram,
+            OtlpSignalType::MetricsExpHistogram => SignalType::ExpHistogram,
+            OtlpSignalType::MetricsSummary => SignalType::Summary,
+            OtlpSignalType::Profiles => SignalType::Profiles,
+        }
+    }
+}
+
+/// Length of the leading complete frames in a length-delimited protobuf stream.
+fn complete_frames_len(buf: &[u8]) -> usize {
+    let mut pos = 0;
+    while pos < buf.len() {
+        let available = buf.len() - pos;
+        let mut rest = &buf[pos..];
+        match prost::decode_length_delimiter(&mut rest) {
+            Ok(len) => {
+                let prefix = available - rest.len();
+                if len > rest.len() {
+                    break;
+                }
+                pos += prefix + len;
+            }
+            // Length prefix is split across pushes; wait for more data
+            Err(_) if available < 10 => break,
+            // Malformed prefix; hand everything to the decoder to report it
+            Err(_) => return buf.len(),
+        }
+    }
+    pos
+}
+
+/// Transform input for a signal type, returning its batch (if the input had
+/// data of that type) and the transform's stats.
+fn transform_signal(
+    signal_type: OtlpSignalType,
+    bytes: &[u8],
+    format: InputFormat,
+) -> crate::Result<(Option<RecordBatch>, TransformStats)> {
+    let metrics = |select: fn(MetricBatches) -> Option<RecordBatch>| {
+        transform_metrics(bytes, format).map(|mut batches| {
+            let stats = std::mem::take(&mut batches.stats);
+            (select(batches), stats)
+        })
+    };
+
+    match signal_type {
+        OtlpSignalType::Logs => transform_logs(bytes, format).map(|t| (Some(t.batch), t.stats)),
+        OtlpSignalType::Traces => transform_traces(bytes, format).map(|t| (Some(t.batch), t.stats)),
+        OtlpSignalType::MetricsGauge => metrics(|m| m.gauge),
+        OtlpSignalType::MetricsSum => metrics(|m| m.sum),
+        OtlpSignalType::MetricsHistogram => metrics(|m| m.histogram),
+        OtlpSignalType::MetricsExpHistogram => metrics(|m| m.exp_histogram),
+        OtlpSignalType::MetricsSummary => metrics(|m| m.summary),
+        OtlpSignalType::Profiles => {
+            transform_profiles(bytes, format).map(|t| (Some(t.batch), t.stats))
         }
     }
 }
@@ -241,6 +489,87 @@ pub unsafe extern "C" fn otlp_parser_destroy(handle: *mut OtlpParserHandle) {
     }
 }
 
+/// Set the content encoding of input pushed to the parser.
+///
+/// The buffered input is decompressed when the final chunk is pushed.
+/// Gzip and zstd require the crate's `compression` feature; otherwise the
+/// final push fails with `OTLP_ERROR_PARSE_FAILED`.
+///
+/// # Safety
+///
+/// - `handle` must be a valid parser handle
+///
+/// # Arguments
+///
+/// - `handle`: Parser handle
+/// - `encoding`: Content encoding of pushed bytes
+/// - `max_decompressed_size`: Maximum decompressed size in bytes, or 0 for
+///   the default (64 MiB)
+///
+/// # Returns
+///
+/// `OTLP_OK` on success, error code otherwise.
+#[no_mangle]
+pub unsafe extern "C" fn otlp_parser_set_content_encoding(
+    handle: *mut OtlpParserHandle,
+    encoding: OtlpContentEncoding,
+    max_decompressed_size: usize,
+) -> OtlpStatus {
+    if handle.is_null() {
+        return OtlpStatus::InvalidArgument;
+    }
+
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let handle = &mut *handle;
+        handle.clear_error();
+        handle.encoding = encoding.into();
+        handle.max_decompressed_size = if max_decompressed_size == 0 {
+            DEFAULT_MAX_DECOMPRESSED_SIZE
+        } else {
+            max_decompressed_size
+        };
+        OtlpStatus::Ok
+    }))
+    .unwrap_or(OtlpStatus::Internal)
+}
+
+/// Set the target row count for batches emitted by the parser.
+///
+/// Parsed rows are emitted as batches of exactly `batch_size` rows; any
+/// remainder is emitted as a shorter batch when the final chunk is pushed.
+/// By default no batch size is set and all rows are emitted as a single
+/// batch on the final push.
+///
+/// # Safety
+///
+/// - `handle` must be a valid parser handle
+///
+/// # Arguments
+///
+/// - `handle`: Parser handle
+/// - `batch_size`: Target rows per batch, or 0 to emit a single batch
+///
+/// # Returns
+///
+/// `OTLP_OK` on success, error code otherwise.
+#[no_mangle]
+pub unsafe extern "C" fn otlp_parser_set_batch_size(
+    handle: *mut OtlpParserHandle,
+    batch_size: usize,
+) -> OtlpStatus {
+    if handle.is_null() {
+        return OtlpStatus::InvalidArgument;
+    }
+
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let handle = &mut *handle;
+        handle.clear_error();
+        handle.batch_size = (batch_size > 0).then_some(batch_size);
+        OtlpStatus::Ok
+    }))
+    .unwrap_or(OtlpStatus::Internal)
+}
+
 // ============================================================================
 // FFI Functions - Streaming Interface
 // ============================================================================
@@ -260,6 +589,10 @@ pub unsafe extern "C" fn otlp_parser_destroy(handle: *mut OtlpParserHandle) {
 /// - `len`: Length of input bytes
 /// - `is_final`: Non-zero if this is the last chunk (triggers parsing)
 ///
+/// For uncompressed JSONL and length-delimited protobuf, each complete line or
+/// message is parsed as it arrives and full batches become available to
+/// `otlp_parser_drain()` immediately. Other formats are buffered until `is_final`.
+///
 /// # Returns
 ///
 /// `OTLP_OK` on success, error code otherwise.
@@ -342,6 +675,32 @@ pub unsafe extern "C" fn otlp_parser_drain(
     })
 }
 
+/// Get the stats of everything pushed to a parser so far.
+///
+/// # Safety
+///
+/// - `handle` must be a valid parser handle
+/// - `out_stats` must be a valid pointer
+///
+/// # Returns
+///
+/// `OTLP_OK` on success, error code otherwise.
+#[no_mangle]
+pub unsafe extern "C" fn otlp_parser_get_stats(
+    handle: *const OtlpParserHandle,
+    out_stats: *mut OtlpTransformStats,
+) -> OtlpStatus {
+    if handle.is_null() || out_stats.is_null() {
+        return OtlpStatus::InvalidArgument;
+    }
+
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        std::ptr::write(out_stats, OtlpTransformStats::from(&(*handle).stats));
+        OtlpStatus::Ok
+    }))
+    .unwrap_or(OtlpStatus::Internal)
+}
+
 // ============================================================================
 // FFI Functions - Schema Access
 // ============================================================================
@@ -366,14 +725,7 @@ pub unsafe extern "C" fn otlp_get_schema(
     }
 
     std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        let schema = match signal_type {
-            OtlpSignalType::Logs => logs_schema(),
-            OtlpSignalType::Traces => traces_schema(),
-            OtlpSignalType::MetricsGauge => gauge_schema(),
-            OtlpSignalType::MetricsSum => sum_schema(),
-            OtlpSignalType::MetricsHistogram => histogram_schema(),
-            OtlpSignalType::MetricsExpHistogram => exp_histogram_schema(),
-        };
+        let schema = SignalType::from(signal_type).schema();
 
         match FFI_ArrowSchema::try_from(&schema) {
             Ok(ffi_schema) => {
@@ -409,6 +761,39 @@ pub unsafe extern "C" fn otlp_transform(
     len: usize,
     out_array: *mut FFI_ArrowArray,
     out_schema: *mut FFI_ArrowSchema,
+) -> OtlpStatus {
+    otlp_transform_with_stats(
+        signal_type,
+        format,
+        data,
+        len,
+        out_array,
+        out_schema,
+        ptr::null_mut(),
+    )
+}
+
+/// Transform OTLP bytes to Arrow in one call, also reporting the transform's
+/// stats.
+///
+/// # Safety
+///
+/// - Same requirements as `otlp_transform()`
+/// - `out_stats` must be a valid pointer, or null to skip the stats
+///
+/// # Returns
+///
+/// `OTLP_OK` on success, error code otherwise. `out_stats` is only written
+/// on success.
+#[no_mangle]
+pub unsafe extern "C" fn otlp_transform_with_stats(
+    signal_type: OtlpSignalType,
+    format: OtlpInputFormat,
+    data: *const u8,
+    len: usize,
+    out_array: *mut FFI_ArrowArray,
+    out_schema: *mut FFI_ArrowSchema,
+    out_stats: *mut OtlpTransformStats,
 ) -> OtlpStatus {
     if data.is_null() || out_array.is_null() || out_schema.is_null() {
         return OtlpStatus::InvalidArgument;
@@ -418,18 +803,12 @@ pub unsafe extern "C" fn otlp_transform(
         let slice = std::slice::from_raw_parts(data, len);
         let format: InputFormat = format.into();
 
-        let batch_result = match signal_type {
-            OtlpSignalType::Logs => transform_logs(slice, format).map(Some),
-            OtlpSignalType::Traces => transform_traces(slice, format).map(Some),
-            OtlpSignalType::MetricsGauge => transform_metrics(slice, format).map(|m| m.gauge),
-            OtlpSignalType::MetricsSum => transform_metrics(slice, format).map(|m| m.sum),
-            OtlpSignalType::MetricsHistogram => {
-                transform_metrics(slice, format).map(|m| m.histogram)
-            }
-            OtlpSignalType::MetricsExpHistogram => {
-                transform_metrics(slice, format).map(|m| m.exp_histogram)
+        let batch_result = transform_signal(signal_type, slice, format).map(|(batch, stats)| {
+            if !out_stats.is_null() {
+                std::ptr::write(out_stats, OtlpTransformStats::from(&stats));
             }
-        };
+            batch
+        });
 
         match batch_result {
             Ok(Some(batch)) => {
@@ -456,14 +835,7 @@ pub unsafe extern "C" fn otlp_transform(
             }
             Ok(None) => {
                 // No data of this type - create empty batch
-                let schema = match signal_type {
-                    OtlpSignalType::Logs => logs_schema(),
-                    OtlpSignalType::Traces => traces_schema(),
-                    OtlpSignalType::MetricsGauge => gauge_schema(),
-                    OtlpSignalType::MetricsSum => sum_schema(),
-                    OtlpSignalType::MetricsHistogram => histogram_schema(),
-                    OtlpSignalType::MetricsExpHistogram => exp_histogram_schema(),
-                };
+                let schema = SignalType::from(signal_type).schema();
 
                 let empty_batch = RecordBatch::new_empty(Arc::new(schema.clone()));
                 let ffi_schema = match FFI_ArrowSchema::try_from(&schema) {
@@ -486,6 +858,61 @@ pub unsafe extern "C" fn otlp_transform(
     .unwrap_or(OtlpStatus::Internal)
 }
 
+/// Transform OTLP bytes to Arrow in one call, with one batch per service.
+///
+/// Rows are grouped by their `service_name` column (null names are grouped
+/// as `unknown`), in the order each service first appears in the input.
+/// Each batch holds a single service, so hosts can route batches to
+/// per-service storage without grouping them again.
+///
+/// # Safety
+///
+/// - `data` must be valid for `len` bytes
+/// - `out_stream` must be a valid pointer to FFI_ArrowArrayStream
+/// - Caller must call `out_stream->release()` when done
+///
+/// # Returns
+///
+/// `OTLP_OK` on success, error code otherwise.
+/// Stream yields 0 batches if the input had no data of this type.
+#[no_mangle]
+pub unsafe extern "C" fn otlp_transform_partitioned(
+    signal_type: OtlpSignalType,
+    format: OtlpInputFormat,
+    data: *const u8,
+    len: usize,
+    out_stream: *mut FFI_ArrowArrayStream,
+) -> OtlpStatus {
+    if data.is_null() || out_stream.is_null() {
+        return OtlpStatus::InvalidArgument;
+    }
+
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let slice = std::slice::from_raw_parts(data, len);
+
+        let batch = match transform_signal(signal_type, slice, format.into()) {
+            Ok((batch, _)) => batch,
+            Err(_) => return OtlpStatus::ParseFailed,
+        };
+
+        let schema = match &batch {
+            Some(batch) => batch.schema(),
+            None => Arc::new(SignalType::from(signal_type).schema()),
+        };
+        let batches: Vec<RecordBatch> = match batch.map(try_group_batch_by_service) {
+            Some(Ok(grouped)) => grouped.into_iter().map(|pb| pb.batch).collect(),
+            Some(Err(_)) => return OtlpStatus::Internal,
+            None => Vec::new(),
+        };
+
+        let reader =
+            arrow::record_batch::RecordBatchIterator::new(batches.into_iter().map(Ok), schema);
+        std::ptr::write(out_stream, FFI_ArrowArrayStream::new(Box::new(reader)));
+        OtlpStatus::Ok
+    }))
+    .unwrap_or(OtlpStatus::Internal)
+}
+
 // ============================================================================
 // FFI Functions - Error Handling
 // ============================================================================
@@ -546,6 +973,7 @@ pub extern "C" fn otlp_status_message(status: OtlpStatus) -> *const c_char {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sum_schema;
     use arrow::ffi_stream::ArrowArrayStreamReader;
     use opentelemetry_proto::tonic::{
         collector::logs::v1::ExportLogsServiceRequest,
@@ -652,6 +1080,189 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parser_get_stats() {
+        unsafe {
+            let mut handle: *mut OtlpParserHandle = ptr::null_mut();
+            otlp_parser_create(OtlpSignalType::Logs, OtlpInputFormat::Protobuf, &mut handle);
+
+            let bytes = create_test_log_bytes();
+            otlp_parser_push(handle, bytes.as_ptr(), bytes.len(), 0);
+            otlp_parser_push(handle, bytes.as_ptr(), bytes.len(), 1);
+
+            let mut stats = OtlpTransformStats::default();
+            assert_eq!(otlp_parser_get_stats(handle, &mut stats), OtlpStatus::Ok);
+            assert_eq!(stats.records_in, 2);
+            assert_eq!(stats.records_out, 2);
+            assert_eq!(stats.dropped_decode_errors, 0);
+
+            assert_eq!(
+                otlp_parser_get_stats(handle, ptr::null_mut()),
+                OtlpStatus::InvalidArgument
+            );
+            assert_eq!(
+                otlp_parser_get_stats(ptr::null(), &mut stats),
+                OtlpStatus::InvalidArgument
+            );
+
+            otlp_parser_destroy(handle);
+        }
+    }
+
+    fn jsonl_log_lines(count: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        for i in 0..count {
+            let line = format!(
+                r#"{{"resourceLogs":[{{"resource":{{}},"scopeLogs":[{{"scope":{{}},"logRecords":[{{"timeUnixNano":"{}","body":{{"stringValue":"line {i}"}}}}]}}]}}]}}"#,
+                1_700_000_000_000_000_000u64 + i as u64
+            );
+            out.extend_from_slice(line.as_bytes());
+            out.push(b'\n');
+        }
+        out
+    }
+
+    unsafe fn drain_row_counts(handle: *mut OtlpParserHandle) -> Vec<usize> {
+        let mut stream = std::mem::MaybeUninit::<FFI_ArrowArrayStream>::uninit();
+        assert_eq!(
+            otlp_parser_drain(handle, stream.as_mut_ptr()),
+            OtlpStatus::Ok
+        );
+        ArrowArrayStreamReader::try_new(stream.assume_init())
+            .unwrap()
+            .map(|b| b.unwrap().num_rows())
+            .collect()
+    }
+
+    #[test]
+    fn test_parser_jsonl_incremental() {
+        unsafe {
+            let mut handle: *mut OtlpParserHandle = ptr::null_mut();
+            otlp_parser_create(OtlpSignalType::Logs, OtlpInputFormat::Jsonl, &mut handle);
+            assert_eq!(otlp_parser_set_batch_size(handle, 4), OtlpStatus::Ok);
+
+            // 10 lines pushed in chunks that split lines mid-record
+            let data = jsonl_log_lines(10);
+            let mut emitted = Vec::new();
+            for chunk in data.chunks(97) {
+                let status = otlp_parser_push(handle, chunk.as_ptr(), chunk.len(), 0);
+                assert_eq!(status, OtlpStatus::Ok);
+
+                // Only the trailing partial line stays buffered
+                let buffer = &(*handle).buffer;
+                assert!(!buffer.contains(&b'\n'));
+                assert!((*handle).pending_rows < 4);
+
+                emitted.extend(drain_row_counts(handle));
+            }
+            assert_eq!(emitted, vec![4, 4]);
+
+            assert_eq!(otlp_parser_push(handle, ptr::null(), 0, 1), OtlpStatus::Ok);
+            assert_eq!(drain_row_counts(handle), vec![2]);
+
+            otlp_parser_destroy(handle);
+        }
+    }
+
+    #[test]
+    fn test_parser_single_batch_without_batch_size() {
+        unsafe {
+            let mut handle: *mut OtlpParserHandle = ptr::null_mut();
+            otlp_parser_create(OtlpSignalType::Logs, OtlpInputFormat::Jsonl, &mut handle);
+
+            let data = jsonl_log_lines(10);
+            for chunk in data.chunks(97) {
+                let status = otlp_parser_push(handle, chunk.as_ptr(), chunk.len(), 0);
+                assert_eq!(status, OtlpStatus::Ok);
+                assert_eq!(drain_row_counts(handle), Vec::<usize>::new());
+            }
+
+            assert_eq!(otlp_parser_push(handle, ptr::null(), 0, 1), OtlpStatus::Ok);
+            assert_eq!(drain_row_counts(handle), vec![10]);
+
+            otlp_parser_destroy(handle);
+        }
+    }
+
+    #[test]
+    fn test_parser_jsonl_final_partial_line() {
+        unsafe {
+            let mut handle: *mut OtlpParserHandle = ptr::null_mut();
+            otlp_parser_create(OtlpSignalType::Logs, OtlpInputFormat::Jsonl, &mut handle);
+
+            // Last line without a trailing newline is parsed on the final push
+            let mut data = jsonl_log_lines(3);
+            data.pop();
+            let status = otlp_parser_push(handle, data.as_ptr(), data.len(), 0);
+            assert_eq!(status, OtlpStatus::Ok);
+            assert_eq!(drain_row_counts(handle), Vec::<usize>::new());
+
+            assert_eq!(otlp_parser_push(handle, ptr::null(), 0, 1), OtlpStatus::Ok);
+            assert_eq!(drain_row_counts(handle), vec![3]);
+
+            otlp_parser_destroy(handle);
+        }
+    }
+
+    #[test]
+    fn test_parser_jsonl_invalid_line() {
+        unsafe {
+            let mut handle: *mut OtlpParserHandle = ptr::null_mut();
+            otlp_parser_create(OtlpSignalType::Logs, OtlpInputFormat::Jsonl, &mut handle);
+
+            let data = b"{not json}\n";
+            let status = otlp_parser_push(handle, data.as_ptr(), data.len(), 0);
+            assert_eq!(status, OtlpStatus::ParseFailed);
+            assert!(!otlp_parser_last_error(handle).is_null());
+
+            otlp_parser_destroy(handle);
+        }
+    }
+
+    #[test]
+    fn test_parser_protobuf_delimited_incremental() {
+        unsafe {
+            let mut handle: *mut OtlpParserHandle = ptr::null_mut();
+            otlp_parser_create(
+                OtlpSignalType::Logs,
+                OtlpInputFormat::ProtobufDelimited,
+                &mut handle,
+            );
+            otlp_parser_set_batch_size(handle, 2);
+
+            let message = create_test_log_bytes();
+            let mut data = Vec::new();
+            for _ in 0..5 {
+                prost::encode_length_delimiter(message.len(), &mut data).unwrap();
+                data.extend_from_slice(&message);
+            }
+
+            let mut emitted = Vec::new();
+            for chunk in data.chunks(7) {
+                let status = otlp_parser_push(handle, chunk.as_ptr(), chunk.len(), 0);
+                assert_eq!(status, OtlpStatus::Ok);
+                assert!((*handle).buffer.len() <= message.len() + 1);
+                emitted.extend(drain_row_counts(handle));
+            }
+            assert_eq!(emitted, vec![2, 2]);
+
+            assert_eq!(otlp_parser_push(handle, ptr::null(), 0, 1), OtlpStatus::Ok);
+            assert_eq!(drain_row_counts(handle), vec![1]);
+
+            otlp_parser_destroy(handle);
+        }
+    }
+
+    #[test]
+    fn test_complete_frames_len() {
+        assert_eq!(complete_frames_len(&[]), 0);
+        assert_eq!(complete_frames_len(&[0x02, 1, 2, 0x03, 1]), 3);
+        // Length prefix split across pushes
+        assert_eq!(complete_frames_len(&[0x00, 0x80]), 1);
+        // Malformed prefix is handed to the decoder
+        assert_eq!(complete_frames_len(&[0xff; 12]), 12);
+    }
+
     #[test]
     fn test_get_schema() {
         unsafe {
@@ -674,6 +1285,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_summary_schema() {
+        unsafe {
+            let mut ffi_schema = std::mem::MaybeUninit::<FFI_ArrowSchema>::uninit();
+            let status = otlp_get_schema(OtlpSignalType::MetricsSummary, ffi_schema.as_mut_ptr());
+            assert_eq!(status, OtlpStatus::Ok);
+
+            let ffi_schema = ffi_schema.assume_init();
+            let schema =
+                arrow::datatypes::Schema::try_from(&ffi_schema).expect("Failed to convert schema");
+
+            assert!(schema.field_with_name("quantile_values").is_ok());
+        }
+    }
+
     #[test]
     fn test_one_shot_transform() {
         unsafe {
@@ -704,6 +1330,149 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_one_shot_transform_with_stats() {
+        unsafe {
+            let bytes = create_test_log_bytes();
+
+            let mut ffi_array = std::mem::MaybeUninit::<FFI_ArrowArray>::uninit();
+            let mut ffi_schema = std::mem::MaybeUninit::<FFI_ArrowSchema>::uninit();
+            let mut stats = OtlpTransformStats::default();
+
+            let status = otlp_transform_with_stats(
+                OtlpSignalType::Logs,
+                OtlpInputFormat::Protobuf,
+                bytes.as_ptr(),
+                bytes.len(),
+                ffi_array.as_mut_ptr(),
+                ffi_schema.as_mut_ptr(),
+                &mut stats,
+            );
+            assert_eq!(status, OtlpStatus::Ok);
+            drop(ffi_array.assume_init());
+            drop(ffi_schema.assume_init());
+
+            assert_eq!(stats.records_in, 1);
+            assert_eq!(stats.records_out, 1);
+        }
+    }
+
+    #[test]
+    fn test_one_shot_transform_profiles() {
+        let json = br#"{
+            "resourceProfiles": [{"scopeProfiles": [{"profiles": [{
+                "sampleType": {"typeStrindex": 1, "unitStrindex": 2},
+                "timeUnixNano": "1700000000000000000",
+                "sample": [{"stackIndex": 1, "values": ["3"]}]
+            }]}]}],
+            "dictionary": {
+                "stringTable": ["", "cpu", "nanoseconds", "main"],
+                "functionTable": [{}, {"nameStrindex": 3}],
+                "locationTable": [{}, {"line": [{"functionIndex": 1}]}],
+                "stackTable": [{}, {"locationIndices": [1]}]
+            }
+        }"#;
+
+        unsafe {
+            let mut ffi_array = std::mem::MaybeUninit::<FFI_ArrowArray>::uninit();
+            let mut ffi_schema = std::mem::MaybeUninit::<FFI_ArrowSchema>::uninit();
+
+            let status = otlp_transform(
+                OtlpSignalType::Profiles,
+                OtlpInputFormat::Json,
+                json.as_ptr(),
+                json.len(),
+                ffi_array.as_mut_ptr(),
+                ffi_schema.as_mut_ptr(),
+            );
+            assert_eq!(status, OtlpStatus::Ok);
+
+            let ffi_array = ffi_array.assume_init();
+            let ffi_schema = ffi_schema.assume_init();
+            let array_data =
+                arrow::ffi::from_ffi(ffi_array, &ffi_schema).expect("Failed to import array");
+            assert_eq!(array_data.len(), 1);
+
+            let schema =
+                arrow::datatypes::Schema::try_from(&ffi_schema).expect("Failed to convert schema");
+            assert!(schema.field_with_name("function_name").is_ok());
+        }
+    }
+
+    #[test]
+    fn test_one_shot_transform_partitioned() {
+        let resource = |service: &str, bodies: &[&str]| {
+            let records: Vec<String> = bodies
+                .iter()
+                .map(|body| {
+                    format!(r#"{{"timeUnixNano": "1700000000000000000", "body": {{"stringValue": "{body}"}}}}"#)
+                })
+                .collect();
+            format!(
+                r#"{{"resource": {{"attributes": [{{"key": "service.name", "value": {{"stringValue": "{service}"}}}}]}},
+                    "scopeLogs": [{{"logRecords": [{}]}}]}}"#,
+                records.join(",")
+            )
+        };
+        let json = format!(
+            r#"{{"resourceLogs": [{}, {}, {}]}}"#,
+            resource("api", &["a1", "a2"]),
+            resource("db", &["d1"]),
+            resource("api", &["a3"]),
+        );
+
+        unsafe {
+            let mut stream = std::mem::MaybeUninit::<FFI_ArrowArrayStream>::uninit();
+            let status = otlp_transform_partitioned(
+                OtlpSignalType::Logs,
+                OtlpInputFormat::Json,
+                json.as_ptr(),
+                json.len(),
+                stream.as_mut_ptr(),
+            );
+            assert_eq!(status, OtlpStatus::Ok);
+
+            let reader = ArrowArrayStreamReader::try_new(stream.assume_init()).unwrap();
+            let batches: Vec<RecordBatch> = reader.map(|b| b.unwrap()).collect();
+            let services: Vec<(String, usize)> = batches
+                .iter()
+                .map(|b| (crate::extract_service_name(b).to_string(), b.num_rows()))
+                .collect();
+            assert_eq!(services, [("api".to_string(), 3), ("db".to_string(), 1)]);
+        }
+    }
+
+    #[test]
+    fn test_one_shot_transform_partitioned_no_data() {
+        use arrow::record_batch::RecordBatchReader;
+
+        unsafe {
+            let bytes = create_test_log_bytes();
+            let mut stream = std::mem::MaybeUninit::<FFI_ArrowArrayStream>::uninit();
+            let status = otlp_transform_partitioned(
+                OtlpSignalType::MetricsSum,
+                OtlpInputFormat::Protobuf,
+                bytes.as_ptr(),
+                0,
+                stream.as_mut_ptr(),
+            );
+            assert_eq!(status, OtlpStatus::Ok);
+
+            let reader = ArrowArrayStreamReader::try_new(stream.assume_init()).unwrap();
+            assert_eq!(reader.schema().as_ref(), &sum_schema());
+            assert_eq!(reader.count(), 0);
+
+            let status = otlp_transform_partitioned(
+                OtlpSignalType::Logs,
+                OtlpInputFormat::Protobuf,
+                ptr::null(),
+                0,
+                ptr::null_mut(),
+            );
+            assert_eq!(status, OtlpStatus::InvalidArgument);
+        }
+    }
+
     #[test]
     fn test_status_message() {
         let msg = otlp_status_message(OtlpStatus::Ok);
@@ -737,6 +1506,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parser_set_content_encoding_null_handle() {
+        unsafe {
+            let status =
+                otlp_parser_set_content_encoding(ptr::null_mut(), OtlpContentEncoding::Gzip, 0);
+            assert_eq!(status, OtlpStatus::InvalidArgument);
+        }
+    }
+
+    #[test]
+    fn test_parser_decompression_limit() {
+        unsafe {
+            let mut handle: *mut OtlpParserHandle = ptr::null_mut();
+            otlp_parser_create(OtlpSignalType::Logs, OtlpInputFormat::Protobuf, &mut handle);
+            let status = otlp_parser_set_content_encoding(handle, OtlpContentEncoding::Identity, 4);
+            assert_eq!(status, OtlpStatus::Ok);
+
+            let bytes = create_test_log_bytes();
+            let status = otlp_parser_push(handle, bytes.as_ptr(), bytes.len(), 1);
+            assert_eq!(status, OtlpStatus::ParseFailed);
+
+            let err = std::ffi::CStr::from_ptr(otlp_parser_last_error(handle));
+            assert!(err.to_str().unwrap().contains("exceeds limit"));
+
+            otlp_parser_destroy(handle);
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_parser_push_gzip() {
+        use std::io::Write;
+
+        unsafe {
+            let mut handle: *mut OtlpParserHandle = ptr::null_mut();
+            otlp_parser_create(OtlpSignalType::Logs, OtlpInputFormat::Protobuf, &mut handle);
+            otlp_parser_set_content_encoding(handle, OtlpContentEncoding::Gzip, 0);
+
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&create_test_log_bytes()).unwrap();
+            let gzipped = encoder.finish().unwrap();
+
+            // Split across two pushes; decompression happens on the final chunk
+            let (head, tail) = gzipped.split_at(gzipped.len() / 2);
+            assert_eq!(
+                otlp_parser_push(handle, head.as_ptr(), head.len(), 0),
+                OtlpStatus::Ok
+            );
+            assert_eq!(
+                otlp_parser_push(handle, tail.as_ptr(), tail.len(), 1),
+                OtlpStatus::Ok
+            );
+
+            let mut stream = std::mem::MaybeUninit::<FFI_ArrowArrayStream>::uninit();
+            otlp_parser_drain(handle, stream.as_mut_ptr());
+            let reader = ArrowArrayStreamReader::try_new(stream.assume_init()).unwrap();
+            let rows: usize = reader.map(|b| b.unwrap().num_rows()).sum();
+            assert_eq!(rows, 1);
+
+            otlp_parser_destroy(handle);
+        }
+    }
+
     #[test]
     fn test_parser_error_message() {
         unsafe {
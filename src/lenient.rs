@@ -0,0 +1,184 @@
+//! Lenient transforms: records rejected by the decode, VRL or Arrow stage are
+//! dropped and reported instead of failing the whole payload.
+
+use ::arrow::datatypes::Schema;
+use ::arrow::record_batch::RecordBatch;
+use vrl::compiler::Program;
+use vrl::value::Value;
+
+use crate::decode::{Decoded, DecodedMetrics, RecordOrigin};
+use crate::error::{ErrorStage, RecordError};
+use crate::{
+    exp_histogram_schema, extract_metric_type, finish_batch, gauge_schema, histogram_schema,
+    sum_schema, summary_schema, values_to_arrow, Layers, LenientBatch, LenientMetricBatches,
+    MetricBatches, Result, TransformStats, VrlTransformer, OTLP_EXP_HISTOGRAM_PROGRAM,
+    OTLP_GAUGE_PROGRAM, OTLP_HISTOGRAM_PROGRAM, OTLP_SUMMARY_PROGRAM, OTLP_SUM_PROGRAM,
+};
+
+/// Records that passed the stages so far, each with its place in the payload.
+#[derive(Default)]
+struct Records {
+    values: Vec<Value>,
+    origins: Vec<RecordOrigin>,
+}
+
+impl Records {
+    /// Run a record through `program`, keeping it on success and reporting it
+    /// on failure.
+    fn transform(
+        &mut self,
+        transformer: &mut VrlTransformer,
+        layers: Layers<'_>,
+        program: &Program,
+        value: Value,
+        origin: RecordOrigin,
+        errors: &mut Vec<RecordError>,
+    ) {
+        match layers.run(transformer, program, value) {
+            Ok(transformed) => {
+                self.values.push(transformed);
+                self.origins.push(origin);
+            }
+            Err(e) => reject(errors, origin, ErrorStage::Vrl, e.0),
+        }
+    }
+
+    /// Convert the records to a batch. If the batch cannot be built, each
+    /// record is tried on its own and the ones that don't fit the schema are
+    /// dropped.
+    fn into_batch(
+        self,
+        schema: &Schema,
+        errors: &mut Vec<RecordError>,
+        stats: &mut TransformStats,
+    ) -> Result<RecordBatch> {
+        if let Ok(batch) = finish_batch(&self.values, schema, stats) {
+            return Ok(batch);
+        }
+
+        let mut kept = Vec::with_capacity(self.values.len());
+        for (value, origin) in self.values.into_iter().zip(self.origins) {
+            match values_to_arrow(std::slice::from_ref(&value), schema) {
+                Ok(_) => kept.push(value),
+                Err(e) => reject(errors, origin, ErrorStage::Arrow, e.to_string()),
+            }
+        }
+        finish_batch(&kept, schema, stats)
+    }
+}
+
+/// Count the records dropped at each stage.
+fn count_dropped(stats: &mut TransformStats, errors: &[RecordError]) {
+    for error in errors {
+        match error.stage {
+            // Counted from the decoder's output
+            ErrorStage::Decode => {}
+            ErrorStage::Vrl => stats.dropped.vrl_errors += 1,
+            ErrorStage::Arrow => stats.dropped.arrow_errors += 1,
+        }
+    }
+}
+
+fn reject(errors: &mut Vec<RecordError>, origin: RecordOrigin, stage: ErrorStage, message: String) {
+    errors.push(RecordError {
+        index: origin.index,
+        location: origin.location,
+        stage,
+        message,
+    });
+}
+
+/// Transform decoded logs, traces or profiles into a single batch.
+pub(crate) fn transform(
+    transformer: &mut VrlTransformer,
+    decoded: Decoded,
+    program: &Program,
+    layers: Layers<'_>,
+    schema: &Schema,
+) -> Result<LenientBatch> {
+    let mut stats = TransformStats::decoded(&decoded);
+    let Decoded {
+        values,
+        origins,
+        mut errors,
+        ..
+    } = decoded;
+    let mut records = Records::default();
+
+    for (value, origin) in values.into_iter().zip(origins) {
+        records.transform(transformer, layers, program, value, origin, &mut errors);
+    }
+
+    let batch = records.into_batch(schema, &mut errors, &mut stats)?;
+    count_dropped(&mut stats, &errors);
+    errors.sort_by_key(|error| error.index);
+    Ok(LenientBatch {
+        batch,
+        errors,
+        stats,
+    })
+}
+
+/// Transform decoded metrics into one batch per non-empty metric type.
+pub(crate) fn transform_metrics(
+    transformer: &mut VrlTransformer,
+    decoded: DecodedMetrics,
+    layers: Layers<'_>,
+) -> Result<LenientMetricBatches> {
+    let DecodedMetrics { records, skipped } = decoded;
+    let mut stats = TransformStats::decoded(&records);
+    stats.add_skipped(&skipped);
+    let Decoded {
+        values,
+        origins,
+        mut errors,
+        ..
+    } = records;
+    let mut gauge = Records::default();
+    let mut sum = Records::default();
+    let mut histogram = Records::default();
+    let mut exp_histogram = Records::default();
+    let mut summary = Records::default();
+
+    for (value, origin) in values.into_iter().zip(origins) {
+        let (program, out) = match extract_metric_type(&value).as_str() {
+            "gauge" => (&*OTLP_GAUGE_PROGRAM, &mut gauge),
+            "sum" => (&*OTLP_SUM_PROGRAM, &mut sum),
+            "histogram" => (&*OTLP_HISTOGRAM_PROGRAM, &mut histogram),
+            "exp_histogram" => (&*OTLP_EXP_HISTOGRAM_PROGRAM, &mut exp_histogram),
+            "summary" => (&*OTLP_SUMMARY_PROGRAM, &mut summary),
+            // Skip unknown metric types
+            _ => continue,
+        };
+        out.transform(transformer, layers, program, value, origin, &mut errors);
+    }
+
+    let mut to_batch = |records: Records, schema: Schema| -> Result<Option<RecordBatch>> {
+        if records.values.is_empty() {
+            return Ok(None);
+        }
+        let schema = layers.schema(schema)?;
+        let batch = records.into_batch(&schema, &mut errors, &mut stats)?;
+        Ok((batch.num_rows() > 0).then_some(batch))
+    };
+
+    let gauge = to_batch(gauge, gauge_schema())?;
+    let sum = to_batch(sum, sum_schema())?;
+    let histogram = to_batch(histogram, histogram_schema())?;
+    let exp_histogram = to_batch(exp_histogram, exp_histogram_schema())?;
+    let summary = to_batch(summary, summary_schema())?;
+
+    count_dropped(&mut stats, &errors);
+    let batches = MetricBatches {
+        gauge,
+        sum,
+        histogram,
+        exp_histogram,
+        summary,
+        skipped,
+        stats,
+    };
+
+    errors.sort_by_key(|error| error.index);
+    Ok(LenientMetricBatches { batches, errors })
+}
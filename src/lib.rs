@@ -18,10 +18,12 @@
 //! use otlp2records::{transform_logs, transform_traces, transform_metrics, InputFormat};
 //!
 //! // Transform OTLP logs to Arrow RecordBatch
-//! let batch = transform_logs(bytes, InputFormat::Protobuf)?;
+//! let logs = transform_logs(bytes, InputFormat::Protobuf)?;
+//! println!("{} of {} records written", logs.stats.records_out, logs.stats.records_in);
+//! let batch = logs.batch;
 //!
 //! // Transform OTLP traces to Arrow RecordBatch
-//! let batch = transform_traces(bytes, InputFormat::Json)?;
+//! let batch = transform_traces(bytes, InputFormat::Json)?.batch;
 //!
 //! // Transform OTLP metrics to Arrow RecordBatches (separate gauge and sum)
 //! let batches = transform_metrics(bytes, InputFormat::Protobuf)?;
@@ -33,6 +35,18 @@
 //! }
 //! ```
 //!
+//! # Pipeline
+//!
+//! To apply the same options to many payloads, build a [`Pipeline`] once and
+//! reuse it; it owns its VRL runtime:
+//!
+//! ```ignore
+//! use otlp2records::{InputFormat, Pipeline};
+//!
+//! let mut pipeline = Pipeline::builder().with_lenient(true).build()?;
+//! let output = pipeline.transform_logs(bytes, InputFormat::Protobuf)?;
+//! ```
+//!
 //! # Lower-level API
 //!
 //! For more control over individual transformation steps:
@@ -53,53 +67,95 @@
 pub mod arrow;
 pub mod convert;
 pub mod decode;
+pub mod encode;
 pub mod error;
 pub mod output;
+pub mod pipeline;
 pub mod schemas;
+pub mod stats;
+pub mod testing;
 pub mod transform;
 
+mod lenient;
+mod native;
+
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+pub mod parallel;
+
 #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
 pub mod wasm;
 
 #[cfg(feature = "ffi")]
 pub mod ffi;
 
+use ::arrow::datatypes::Schema;
 use ::arrow::record_batch::RecordBatch;
+use vrl::compiler::Program;
 use vrl::value::{KeyString, Value};
 
+use transform::nanos::Nanos;
+
 pub use arrow::{
-    exp_histogram_schema, extract_min_timestamp_micros, extract_service_name, gauge_schema,
-    group_batch_by_service, histogram_schema, logs_schema, sum_schema, traces_schema,
-    values_to_arrow, PartitionedBatch, PartitionedMetrics, ServiceGroupedBatches,
+    attributes_map_type, bucket_counts_type, cast_timestamps, dict_string_type, exemplars_type,
+    exp_histogram_schema, explicit_bounds_type, extract_min_timestamp_micros, extract_service_name,
+    gauge_schema, group_batch_by, group_batch_by_service, histogram_schema, logs_schema,
+    nanosecond_schema, native_nested_schema, profiles_schema, quantile_values_type,
+    span_events_type, span_links_type, sum_schema, summary_schema, traces_schema,
+    try_group_batch_by, try_group_batch_by_service, values_to_arrow, PartitionKey, PartitionValue,
+    PartitionedBatch, PartitionedBatches, PartitionedMetrics, ServiceGroupedBatches, SignalType,
+    TimestampUnit, HIVE_DEFAULT_PARTITION, TIME_UNIX_NANO,
 };
 pub use decode::{
-    count_skipped_metric_data_points, decode_logs, decode_metrics, decode_traces,
-    normalise_json_value, normalize_json_bytes, DecodeMetricsResult, InputFormat, MetricSkipCounts,
-    SkippedMetrics,
+    count_skipped_metric_data_points, decode_logs, decode_metrics, decode_profiles, decode_traces,
+    decompress, normalise_json_value, normalize_json_bytes, ContentEncoding, DecodeMetricsResult,
+    InputFormat, MetricSkipCounts, SkippedMetrics, DEFAULT_MAX_DECOMPRESSED_SIZE,
 };
-pub use error::{Error, Result};
+pub use encode::EncodeFormat;
+pub use error::{Error, ErrorStage, RecordError, RecordLocation, Result};
 #[cfg(feature = "parquet")]
-pub use output::to_parquet;
+pub use output::{
+    iceberg_partition_keys, iceberg_partition_spec, iceberg_schema, parquet_properties_for,
+    to_parquet, IcebergDataFile, ParquetColumnStats, ParquetFileMetadata, ParquetSink,
+    ParquetSinkBuilder, ParquetStatValue,
+};
 pub use output::{to_ipc, to_json};
-pub use schemas::{schema_def, schema_defs, SchemaDef, SchemaField};
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+pub use parallel::{
+    transform_logs_parallel, transform_metrics_parallel, transform_profiles_parallel,
+    transform_traces_parallel,
+};
+pub use pipeline::{Pipeline, PipelineBuilder, PipelineMetricOutput, PipelineOutput};
+pub use schemas::{parse_schema, schema_def, schema_defs, SchemaDef, SchemaField};
+pub use stats::{DroppedRecords, TransformStats};
 pub use transform::{
-    VrlError, VrlTransformer, OTLP_EXP_HISTOGRAM_PROGRAM, OTLP_GAUGE_PROGRAM,
-    OTLP_HISTOGRAM_PROGRAM, OTLP_LOGS_PROGRAM, OTLP_SUM_PROGRAM, OTLP_TRACES_PROGRAM,
+    AttributeScope, CustomProgram, PromotedAttribute, PromotedAttributes, PromotedType, VrlError,
+    VrlTransformer, OTLP_EXP_HISTOGRAM_PROGRAM, OTLP_GAUGE_PROGRAM, OTLP_HISTOGRAM_PROGRAM,
+    OTLP_LOGS_PROGRAM, OTLP_PROFILES_PROGRAM, OTLP_SUMMARY_PROGRAM, OTLP_SUM_PROGRAM,
+    OTLP_TRACES_PROGRAM,
 };
 
 // ============================================================================
 // High-level API types
 // ============================================================================
 
+/// Result of transforming OTLP logs, traces or profiles to an Arrow
+/// RecordBatch.
+#[derive(Debug)]
+pub struct TransformedBatch {
+    /// RecordBatch containing the transformed records
+    pub batch: RecordBatch,
+    /// Record accounting for the transform
+    pub stats: TransformStats,
+}
+
 /// Result of transforming OTLP metrics to Arrow RecordBatches.
 ///
 /// Metrics are separated by type because each metric type has a different schema.
 /// Each field is `None` if there were no metrics of that type in the input.
 ///
-/// The `skipped` field provides visibility into what data was not processed,
-/// including unsupported metric types (summary) and invalid data points
-/// (NaN, Infinity, missing values).
-#[derive(Debug)]
+/// The `skipped` field provides visibility into data points that were not
+/// processed because of invalid values (NaN, Infinity, missing values).
+#[derive(Debug, Default)]
 pub struct MetricBatches {
     /// RecordBatch containing gauge metrics (if any)
     pub gauge: Option<RecordBatch>,
@@ -109,8 +165,12 @@ pub struct MetricBatches {
     pub histogram: Option<RecordBatch>,
     /// RecordBatch containing exponential histogram metrics (if any)
     pub exp_histogram: Option<RecordBatch>,
+    /// RecordBatch containing summary metrics (if any)
+    pub summary: Option<RecordBatch>,
     /// Metrics that were skipped during processing
     pub skipped: SkippedMetrics,
+    /// Record accounting for all metric types together
+    pub stats: TransformStats,
 }
 
 /// Result of transforming OTLP metrics to JSON values.
@@ -124,6 +184,8 @@ pub struct JsonMetricBatches {
     pub histogram: Vec<serde_json::Value>,
     /// JSON values for exponential histogram metrics
     pub exp_histogram: Vec<serde_json::Value>,
+    /// JSON values for summary metrics
+    pub summary: Vec<serde_json::Value>,
     /// Metrics that were skipped during processing
     pub skipped: SkippedMetrics,
 }
@@ -142,6 +204,29 @@ pub struct MetricValues {
     pub histogram: Vec<Value>,
     /// Transformed exponential histogram metric values
     pub exp_histogram: Vec<Value>,
+    /// Transformed summary metric values
+    pub summary: Vec<Value>,
+}
+
+/// Result of a lenient transform: the records that made it through, and a
+/// report of the ones that were dropped.
+#[derive(Debug)]
+pub struct LenientBatch {
+    /// RecordBatch of the records that passed every stage
+    pub batch: RecordBatch,
+    /// Dropped records, ordered by record index
+    pub errors: Vec<RecordError>,
+    /// Record accounting for the transform
+    pub stats: TransformStats,
+}
+
+/// Result of a lenient metrics transform. See [`LenientBatch`].
+#[derive(Debug, Default)]
+pub struct LenientMetricBatches {
+    /// Batches of the data points that passed every stage
+    pub batches: MetricBatches,
+    /// Dropped data points, ordered by record index
+    pub errors: Vec<RecordError>,
 }
 
 // ============================================================================
@@ -152,6 +237,8 @@ pub struct MetricValues {
 ///
 /// This is the simplest way to convert OTLP log data to Arrow format.
 /// It handles decoding, VRL transformation, and Arrow conversion in one step.
+/// The built-in mapping runs natively, without the VRL runtime; the batch is
+/// the same as `otlp_logs.vrl` produces.
 ///
 /// # Arguments
 ///
@@ -160,27 +247,84 @@ pub struct MetricValues {
 ///
 /// # Returns
 ///
-/// An Arrow RecordBatch containing the transformed log data, or an error.
+/// A [`TransformedBatch`] with the transformed log data and its
+/// [`TransformStats`], or an error.
 ///
 /// # Example
 ///
 /// ```ignore
 /// use otlp2records::{transform_logs, InputFormat};
 ///
-/// let batch = transform_logs(otlp_bytes, InputFormat::Protobuf)?;
+/// let batch = transform_logs(otlp_bytes, InputFormat::Protobuf)?.batch;
 /// println!("Transformed {} log records", batch.num_rows());
 /// ```
-pub fn transform_logs(bytes: &[u8], format: InputFormat) -> Result<RecordBatch> {
-    // Step 1: Decode OTLP logs
-    let values = decode_logs(bytes, format)?;
+pub fn transform_logs(bytes: &[u8], format: InputFormat) -> Result<TransformedBatch> {
+    // Step 1: Decode OTLP logs to record parts
+    let decoded = decode::decode_log_parts(bytes, format)?;
 
-    // Step 2: Apply VRL transformation
-    let transformed = apply_log_transform(values)?;
+    // Step 2: Map them straight to Arrow
+    native::transform_logs(decoded, &logs_schema(), false)
+}
 
-    // Step 3: Convert to Arrow
-    let batch = values_to_arrow(&transformed, &logs_schema())?;
+/// Transform OTLP logs to Arrow RecordBatch, running a custom VRL program
+/// after the built-in mapping.
+///
+/// # Arguments
+///
+/// * `bytes` - Raw OTLP log data bytes
+/// * `format` - The input format (Protobuf or JSON)
+/// * `custom` - Compiled custom program (see [`CustomProgram::compile`])
+///
+/// # Example
+///
+/// ```ignore
+/// use otlp2records::{transform_logs_with, CustomProgram, InputFormat};
+///
+/// let redact = CustomProgram::compile(".log_attributes = null")?;
+/// let batch = transform_logs_with(otlp_bytes, InputFormat::Protobuf, &redact)?.batch;
+/// ```
+pub fn transform_logs_with(
+    bytes: &[u8],
+    format: InputFormat,
+    custom: &CustomProgram,
+) -> Result<TransformedBatch> {
+    let values = decode_logs(bytes, format)?;
+    let mut stats = TransformStats::received(values.len());
+    let transformed = apply_log_transform_with(values, custom)?;
+    let batch = finish_batch(&transformed, &logs_schema(), &mut stats)?;
+    Ok(TransformedBatch { batch, stats })
+}
 
-    Ok(batch)
+/// Transform OTLP logs to Arrow RecordBatch with selected attributes promoted
+/// to typed columns.
+///
+/// The promoted columns are appended to [`logs_schema`] (see
+/// [`PromotedAttributes::extend_schema`]).
+///
+/// # Example
+///
+/// ```ignore
+/// use otlp2records::{transform_logs_promoted, InputFormat, PromotedAttribute, PromotedAttributes};
+///
+/// let promoted = PromotedAttributes::new(vec![PromotedAttribute::resource("k8s.pod.name")]);
+/// let batch = transform_logs_promoted(otlp_bytes, InputFormat::Protobuf, &promoted)?.batch;
+/// ```
+pub fn transform_logs_promoted(
+    bytes: &[u8],
+    format: InputFormat,
+    promoted: &PromotedAttributes,
+) -> Result<TransformedBatch> {
+    let schema = promoted.extend_schema(&logs_schema())?;
+    let values = decode_logs(bytes, format)?;
+    let mut stats = TransformStats::received(values.len());
+    let transformed = apply_log_programs(
+        &mut VrlTransformer::new(),
+        values,
+        0,
+        Layers::promoted(promoted),
+    )?;
+    let batch = finish_batch(&transformed, &schema, &mut stats)?;
+    Ok(TransformedBatch { batch, stats })
 }
 
 /// Transform OTLP logs to JSON values.
@@ -194,6 +338,7 @@ pub fn transform_logs_json(bytes: &[u8], format: InputFormat) -> Result<Vec<serd
 ///
 /// This is the simplest way to convert OTLP trace data to Arrow format.
 /// It handles decoding, VRL transformation, and Arrow conversion in one step.
+/// Like [`transform_logs`], the built-in mapping runs natively.
 ///
 /// # Arguments
 ///
@@ -202,27 +347,61 @@ pub fn transform_logs_json(bytes: &[u8], format: InputFormat) -> Result<Vec<serd
 ///
 /// # Returns
 ///
-/// An Arrow RecordBatch containing the transformed trace data, or an error.
+/// A [`TransformedBatch`] with the transformed trace data and its
+/// [`TransformStats`], or an error.
 ///
 /// # Example
 ///
 /// ```ignore
 /// use otlp2records::{transform_traces, InputFormat};
 ///
-/// let batch = transform_traces(otlp_bytes, InputFormat::Protobuf)?;
+/// let batch = transform_traces(otlp_bytes, InputFormat::Protobuf)?.batch;
 /// println!("Transformed {} spans", batch.num_rows());
 /// ```
-pub fn transform_traces(bytes: &[u8], format: InputFormat) -> Result<RecordBatch> {
-    // Step 1: Decode OTLP traces
-    let values = decode_traces(bytes, format)?;
+pub fn transform_traces(bytes: &[u8], format: InputFormat) -> Result<TransformedBatch> {
+    // Step 1: Decode OTLP traces to span parts
+    let decoded = decode::decode_trace_parts(bytes, format)?;
 
-    // Step 2: Apply VRL transformation
-    let transformed = apply_trace_transform(values)?;
+    // Step 2: Map them straight to Arrow
+    native::transform_traces(decoded, &traces_schema(), false)
+}
 
-    // Step 3: Convert to Arrow
-    let batch = values_to_arrow(&transformed, &traces_schema())?;
+/// Transform OTLP traces to Arrow RecordBatch, running a custom VRL program
+/// after the built-in mapping.
+///
+/// See [`transform_logs_with`] for details.
+pub fn transform_traces_with(
+    bytes: &[u8],
+    format: InputFormat,
+    custom: &CustomProgram,
+) -> Result<TransformedBatch> {
+    let values = decode_traces(bytes, format)?;
+    let mut stats = TransformStats::received(values.len());
+    let transformed = apply_trace_transform_with(values, custom)?;
+    let batch = finish_batch(&transformed, &traces_schema(), &mut stats)?;
+    Ok(TransformedBatch { batch, stats })
+}
 
-    Ok(batch)
+/// Transform OTLP traces to Arrow RecordBatch with selected attributes
+/// promoted to typed columns.
+///
+/// See [`transform_logs_promoted`] for details.
+pub fn transform_traces_promoted(
+    bytes: &[u8],
+    format: InputFormat,
+    promoted: &PromotedAttributes,
+) -> Result<TransformedBatch> {
+    let schema = promoted.extend_schema(&traces_schema())?;
+    let values = decode_traces(bytes, format)?;
+    let mut stats = TransformStats::received(values.len());
+    let transformed = apply_trace_programs(
+        &mut VrlTransformer::new(),
+        values,
+        0,
+        Layers::promoted(promoted),
+    )?;
+    let batch = finish_batch(&transformed, &schema, &mut stats)?;
+    Ok(TransformedBatch { batch, stats })
 }
 
 /// Transform OTLP traces to JSON values.
@@ -236,7 +415,8 @@ pub fn transform_traces_json(bytes: &[u8], format: InputFormat) -> Result<Vec<se
 ///
 /// Returns separate batches for gauge and sum metrics because they have
 /// different schemas. Each field in the result is `None` if there were
-/// no metrics of that type in the input.
+/// no metrics of that type in the input. Like [`transform_logs`], the
+/// built-in mappings run natively.
 ///
 /// # Arguments
 ///
@@ -245,8 +425,9 @@ pub fn transform_traces_json(bytes: &[u8], format: InputFormat) -> Result<Vec<se
 ///
 /// # Returns
 ///
-/// A `MetricBatches` struct containing optional RecordBatches for gauge
-/// and sum metrics, or an error.
+/// A `MetricBatches` struct containing optional RecordBatches for each
+/// metric type (gauge, sum, histogram, exp_histogram, summary) and the
+/// [`TransformStats`] of all of them, or an error.
 ///
 /// # Example
 ///
@@ -263,49 +444,83 @@ pub fn transform_traces_json(bytes: &[u8], format: InputFormat) -> Result<Vec<se
 /// }
 /// ```
 pub fn transform_metrics(bytes: &[u8], format: InputFormat) -> Result<MetricBatches> {
-    // Step 1: Decode OTLP metrics
-    let decode_result = decode_metrics(bytes, format)?;
+    // Step 1: Decode OTLP metrics to record parts
+    let decoded = decode::decode_metric_parts(bytes, format)?;
 
-    // Step 2: Apply VRL transformation (partitions by metric type)
-    let metric_values = apply_metric_transform(decode_result.values)?;
+    // Step 2: Map them straight to Arrow, one batch per metric type
+    native::transform_metrics(decoded, false)
+}
 
-    // Step 3: Convert each partition to Arrow (if non-empty)
-    let gauge = if metric_values.gauge.is_empty() {
-        None
-    } else {
-        Some(values_to_arrow(&metric_values.gauge, &gauge_schema())?)
-    };
+/// Transform OTLP metrics to Arrow RecordBatches, running a custom VRL program
+/// after the built-in mapping of every metric type.
+///
+/// The custom program can branch on `._table` (`"gauge"`, `"sum"`,
+/// `"histogram"`, `"exp_histogram"`, `"summary"`). See [`transform_logs_with`].
+pub fn transform_metrics_with(
+    bytes: &[u8],
+    format: InputFormat,
+    custom: &CustomProgram,
+) -> Result<MetricBatches> {
+    let decode_result = decode_metrics(bytes, format)?;
+    let stats = TransformStats::received_metrics(&decode_result);
+    let metric_values = apply_metric_transform_with(decode_result.values, custom)?;
+    metric_values_to_batches(
+        metric_values,
+        decode_result.skipped,
+        stats,
+        Layers::default(),
+    )
+}
 
-    let sum = if metric_values.sum.is_empty() {
-        None
-    } else {
-        Some(values_to_arrow(&metric_values.sum, &sum_schema())?)
-    };
+/// Transform OTLP metrics to Arrow RecordBatches with selected attributes
+/// promoted to typed columns.
+///
+/// The promoted columns are appended to every metric schema. Record
+/// attributes are the data point attributes. See [`transform_logs_promoted`].
+pub fn transform_metrics_promoted(
+    bytes: &[u8],
+    format: InputFormat,
+    promoted: &PromotedAttributes,
+) -> Result<MetricBatches> {
+    // Validate the promoted columns before doing any work
+    promoted.extend_schema(&gauge_schema())?;
+    let decode_result = decode_metrics(bytes, format)?;
+    let stats = TransformStats::received_metrics(&decode_result);
+    let layers = Layers::promoted(promoted);
+    let metric_values =
+        apply_metric_programs(&mut VrlTransformer::new(), decode_result.values, 0, layers)?;
+    metric_values_to_batches(metric_values, decode_result.skipped, stats, layers)
+}
 
-    let histogram = if metric_values.histogram.is_empty() {
-        None
-    } else {
-        Some(values_to_arrow(
-            &metric_values.histogram,
-            &histogram_schema(),
-        )?)
+/// Convert transformed metric values to one RecordBatch per non-empty metric type.
+fn metric_values_to_batches(
+    metric_values: MetricValues,
+    skipped: SkippedMetrics,
+    mut stats: TransformStats,
+    layers: Layers<'_>,
+) -> Result<MetricBatches> {
+    let mut to_batch = |values: &[Value], schema: Schema| -> Result<Option<RecordBatch>> {
+        if values.is_empty() {
+            return Ok(None);
+        }
+        let schema = layers.schema(schema)?;
+        Ok(Some(finish_batch(values, &schema, &mut stats)?))
     };
 
-    let exp_histogram = if metric_values.exp_histogram.is_empty() {
-        None
-    } else {
-        Some(values_to_arrow(
-            &metric_values.exp_histogram,
-            &exp_histogram_schema(),
-        )?)
-    };
+    let gauge = to_batch(&metric_values.gauge, gauge_schema())?;
+    let sum = to_batch(&metric_values.sum, sum_schema())?;
+    let histogram = to_batch(&metric_values.histogram, histogram_schema())?;
+    let exp_histogram = to_batch(&metric_values.exp_histogram, exp_histogram_schema())?;
+    let summary = to_batch(&metric_values.summary, summary_schema())?;
 
     Ok(MetricBatches {
         gauge,
         sum,
         histogram,
         exp_histogram,
-        skipped: decode_result.skipped,
+        summary,
+        skipped,
+        stats,
     })
 }
 
@@ -319,10 +534,150 @@ pub fn transform_metrics_json(bytes: &[u8], format: InputFormat) -> Result<JsonM
         sum: values_to_json(metric_values.sum, "sum metric")?,
         histogram: values_to_json(metric_values.histogram, "histogram metric")?,
         exp_histogram: values_to_json(metric_values.exp_histogram, "exp_histogram metric")?,
+        summary: values_to_json(metric_values.summary, "summary metric")?,
         skipped: decode_result.skipped,
     })
 }
 
+/// Transform OTLP profiles to Arrow RecordBatch.
+///
+/// Each profile sample becomes one row, with its stack resolved against the
+/// request's profile dictionary into `function_name` (the leaf frame) and
+/// `frames_json`.
+///
+/// # Arguments
+///
+/// * `bytes` - Raw OTLP profile data bytes
+/// * `format` - The input format (Protobuf or JSON)
+///
+/// # Returns
+///
+/// A [`TransformedBatch`] with the transformed samples and its
+/// [`TransformStats`], or an error.
+///
+/// # Example
+///
+/// ```ignore
+/// use otlp2records::{transform_profiles, InputFormat};
+///
+/// let batch = transform_profiles(otlp_bytes, InputFormat::Protobuf)?.batch;
+/// println!("Transformed {} samples", batch.num_rows());
+/// ```
+pub fn transform_profiles(bytes: &[u8], format: InputFormat) -> Result<TransformedBatch> {
+    let values = decode_profiles(bytes, format)?;
+    let mut stats = TransformStats::received(values.len());
+    let transformed = apply_profile_transform(values)?;
+    let batch = finish_batch(&transformed, &profiles_schema(), &mut stats)?;
+    Ok(TransformedBatch { batch, stats })
+}
+
+/// Transform OTLP profiles to Arrow RecordBatch, running a custom VRL
+/// program after the built-in mapping.
+///
+/// See [`transform_logs_with`] for details.
+pub fn transform_profiles_with(
+    bytes: &[u8],
+    format: InputFormat,
+    custom: &CustomProgram,
+) -> Result<TransformedBatch> {
+    let values = decode_profiles(bytes, format)?;
+    let mut stats = TransformStats::received(values.len());
+    let transformed = apply_profile_transform_with(values, custom)?;
+    let batch = finish_batch(&transformed, &profiles_schema(), &mut stats)?;
+    Ok(TransformedBatch { batch, stats })
+}
+
+/// Transform OTLP profiles to JSON values.
+pub fn transform_profiles_json(
+    bytes: &[u8],
+    format: InputFormat,
+) -> Result<Vec<serde_json::Value>> {
+    let values = decode_profiles(bytes, format)?;
+    let transformed = apply_profile_transform(values)?;
+    values_to_json(transformed, "profile sample")
+}
+
+// ============================================================================
+// Lenient API functions
+// ============================================================================
+
+/// Transform OTLP logs to Arrow RecordBatch, dropping bad records instead of
+/// failing the payload.
+///
+/// A log record that fails to decode (e.g. an overflowing timestamp), fails
+/// its VRL program, or doesn't fit the schema is left out of the batch and
+/// reported in [`LenientBatch::errors`] with its index, resource/scope
+/// location and stage. A payload that can't be parsed at all is still an
+/// error.
+///
+/// # Example
+///
+/// ```ignore
+/// use otlp2records::{transform_logs_lenient, InputFormat};
+///
+/// let result = transform_logs_lenient(otlp_bytes, InputFormat::Protobuf)?;
+/// for error in &result.errors {
+///     eprintln!("dropped {error}");
+/// }
+/// ```
+pub fn transform_logs_lenient(bytes: &[u8], format: InputFormat) -> Result<LenientBatch> {
+    let decoded = decode::decode_log_records(bytes, format, true)?;
+    lenient::transform(
+        &mut VrlTransformer::new(),
+        decoded,
+        &OTLP_LOGS_PROGRAM,
+        Layers::default(),
+        &logs_schema(),
+    )
+}
+
+/// Transform OTLP traces to Arrow RecordBatch, dropping bad spans instead of
+/// failing the payload.
+///
+/// See [`transform_logs_lenient`] for details.
+pub fn transform_traces_lenient(bytes: &[u8], format: InputFormat) -> Result<LenientBatch> {
+    let decoded = decode::decode_trace_records(bytes, format, true)?;
+    lenient::transform(
+        &mut VrlTransformer::new(),
+        decoded,
+        &OTLP_TRACES_PROGRAM,
+        Layers::default(),
+        &traces_schema(),
+    )
+}
+
+/// Transform OTLP metrics to Arrow RecordBatches, dropping bad data points
+/// instead of failing the payload.
+///
+/// Record indices count data points across all metric types. Data points
+/// skipped for invalid values are still counted in
+/// [`MetricBatches::skipped`], not reported as errors. See
+/// [`transform_logs_lenient`] for details.
+pub fn transform_metrics_lenient(
+    bytes: &[u8],
+    format: InputFormat,
+) -> Result<LenientMetricBatches> {
+    let decoded = decode::decode_metric_records(bytes, format, true)?;
+    lenient::transform_metrics(&mut VrlTransformer::new(), decoded, Layers::default())
+}
+
+/// Transform OTLP profiles to Arrow RecordBatch, dropping bad samples instead
+/// of failing the payload.
+///
+/// A sample whose profile references its dictionary out of range is
+/// reported like any other bad record. See [`transform_logs_lenient`] for
+/// details.
+pub fn transform_profiles_lenient(bytes: &[u8], format: InputFormat) -> Result<LenientBatch> {
+    let decoded = decode::decode_profile_records(bytes, format, true)?;
+    lenient::transform(
+        &mut VrlTransformer::new(),
+        decoded,
+        &OTLP_PROFILES_PROGRAM,
+        Layers::default(),
+        &profiles_schema(),
+    )
+}
+
 // ============================================================================
 // Partitioned API functions
 // ============================================================================
@@ -358,8 +713,8 @@ pub fn transform_logs_partitioned(
     bytes: &[u8],
     format: InputFormat,
 ) -> Result<ServiceGroupedBatches> {
-    let batch = transform_logs(bytes, format)?;
-    Ok(group_batch_by_service(batch))
+    let batch = transform_logs(bytes, format)?.batch;
+    try_group_batch_by_service(batch)
 }
 
 /// Transform OTLP traces with service-based partitioning.
@@ -392,15 +747,15 @@ pub fn transform_traces_partitioned(
     bytes: &[u8],
     format: InputFormat,
 ) -> Result<ServiceGroupedBatches> {
-    let batch = transform_traces(bytes, format)?;
-    Ok(group_batch_by_service(batch))
+    let batch = transform_traces(bytes, format)?.batch;
+    try_group_batch_by_service(batch)
 }
 
 /// Transform OTLP metrics with service-based partitioning.
 ///
 /// This function combines decoding, transformation, and service-based grouping
-/// into a single call. Returns metrics separated by type (gauge, sum) and
-/// grouped by service name.
+/// into a single call. Returns metrics separated by type (gauge, sum,
+/// histogram, exp_histogram, summary) and grouped by service name.
 ///
 /// # Arguments
 ///
@@ -409,8 +764,8 @@ pub fn transform_traces_partitioned(
 ///
 /// # Returns
 ///
-/// A `PartitionedMetrics` containing gauge and sum metrics, each grouped by
-/// service name with pre-extracted metadata.
+/// A `PartitionedMetrics` containing one `ServiceGroupedBatches` per metric
+/// type, each grouped by service name with pre-extracted metadata.
 ///
 /// # Example
 ///
@@ -433,22 +788,27 @@ pub fn transform_metrics_partitioned(
     let batches = transform_metrics(bytes, format)?;
 
     let gauge = match batches.gauge {
-        Some(batch) => group_batch_by_service(batch),
+        Some(batch) => try_group_batch_by_service(batch)?,
         None => ServiceGroupedBatches::default(),
     };
 
     let sum = match batches.sum {
-        Some(batch) => group_batch_by_service(batch),
+        Some(batch) => try_group_batch_by_service(batch)?,
         None => ServiceGroupedBatches::default(),
     };
 
     let histogram = match batches.histogram {
-        Some(batch) => group_batch_by_service(batch),
+        Some(batch) => try_group_batch_by_service(batch)?,
         None => ServiceGroupedBatches::default(),
     };
 
     let exp_histogram = match batches.exp_histogram {
-        Some(batch) => group_batch_by_service(batch),
+        Some(batch) => try_group_batch_by_service(batch)?,
+        None => ServiceGroupedBatches::default(),
+    };
+
+    let summary = match batches.summary {
+        Some(batch) => try_group_batch_by_service(batch)?,
         None => ServiceGroupedBatches::default(),
     };
 
@@ -457,10 +817,91 @@ pub fn transform_metrics_partitioned(
         sum,
         histogram,
         exp_histogram,
+        summary,
         skipped: batches.skipped,
     })
 }
 
+/// Transform OTLP profiles with service-based partitioning.
+///
+/// See [`transform_logs_partitioned`] for details.
+pub fn transform_profiles_partitioned(
+    bytes: &[u8],
+    format: InputFormat,
+) -> Result<ServiceGroupedBatches> {
+    let batch = transform_profiles(bytes, format)?.batch;
+    try_group_batch_by_service(batch)
+}
+
+// ============================================================================
+// Reverse API functions
+// ============================================================================
+
+/// Encode log RecordBatches back into an OTLP logs export request.
+///
+/// The inverse of [`transform_logs`]: rows are regrouped by resource and
+/// scope, and attribute blobs are parsed back into OTLP attributes. Some
+/// fields are lossy; see the [`encode`] module docs.
+///
+/// # Arguments
+///
+/// * `batches` - RecordBatches in the `logs` schema
+/// * `format` - Output format (Protobuf or JSON)
+///
+/// # Returns
+///
+/// A serialized `ExportLogsServiceRequest`, or an error.
+///
+/// # Example
+///
+/// ```ignore
+/// use otlp2records::{records_to_otlp_logs, transform_logs, EncodeFormat, InputFormat};
+///
+/// let batch = transform_logs(bytes, InputFormat::Protobuf)?.batch;
+/// let body = records_to_otlp_logs(&[batch], EncodeFormat::Protobuf)?;
+/// ```
+pub fn records_to_otlp_logs(batches: &[RecordBatch], format: EncodeFormat) -> Result<Vec<u8>> {
+    let request = encode::logs_request(batches)?;
+    encode::encode_request(&request, format)
+}
+
+/// Encode span RecordBatches back into an OTLP traces export request.
+///
+/// The inverse of [`transform_traces`]. Events and links are rebuilt from
+/// `events_json` and `links_json`.
+///
+/// # Arguments
+///
+/// * `batches` - RecordBatches in the `spans` schema
+/// * `format` - Output format (Protobuf or JSON)
+///
+/// # Returns
+///
+/// A serialized `ExportTraceServiceRequest`, or an error.
+pub fn records_to_otlp_traces(batches: &[RecordBatch], format: EncodeFormat) -> Result<Vec<u8>> {
+    let request = encode::traces_request(batches)?;
+    encode::encode_request(&request, format)
+}
+
+/// Encode metric RecordBatches back into an OTLP metrics export request.
+///
+/// The inverse of [`transform_metrics`]. Data points are regrouped into
+/// metrics by name, description, unit and temporality. The `skipped` field
+/// of `batches` is ignored.
+///
+/// # Arguments
+///
+/// * `batches` - Per-type metric RecordBatches
+/// * `format` - Output format (Protobuf or JSON)
+///
+/// # Returns
+///
+/// A serialized `ExportMetricsServiceRequest`, or an error.
+pub fn records_to_otlp_metrics(batches: &MetricBatches, format: EncodeFormat) -> Result<Vec<u8>> {
+    let request = encode::metrics_request(batches)?;
+    encode::encode_request(&request, format)
+}
+
 // ============================================================================
 // Lower-level API functions
 // ============================================================================
@@ -489,12 +930,32 @@ pub fn transform_metrics_partitioned(
 /// let batch = values_to_arrow(&transformed, &logs_schema())?;
 /// ```
 pub fn apply_log_transform(values: Vec<Value>) -> Result<Vec<Value>> {
-    let mut transformer = VrlTransformer::new();
+    apply_log_programs(&mut VrlTransformer::new(), values, 0, Layers::default())
+}
+
+/// Apply the built-in log transformation followed by a custom VRL program.
+///
+/// See [`CustomProgram`] for what the custom program can see and change.
+pub fn apply_log_transform_with(values: Vec<Value>, custom: &CustomProgram) -> Result<Vec<Value>> {
+    apply_log_programs(
+        &mut VrlTransformer::new(),
+        values,
+        0,
+        Layers::custom(custom),
+    )
+}
+
+fn apply_log_programs(
+    transformer: &mut VrlTransformer,
+    values: Vec<Value>,
+    first_index: usize,
+    layers: Layers<'_>,
+) -> Result<Vec<Value>> {
     let mut result = Vec::with_capacity(values.len());
 
-    for (idx, value) in values.into_iter().enumerate() {
-        let (_table, transformed) = transformer
-            .transform(&OTLP_LOGS_PROGRAM, value)
+    for (idx, value) in (first_index..).zip(values) {
+        let transformed = layers
+            .run(transformer, &OTLP_LOGS_PROGRAM, value)
             .map_err(|e| Error::VrlRuntime(format!("log record {}: {}", idx, e.0)))?;
         result.push(transformed);
     }
@@ -526,12 +987,35 @@ pub fn apply_log_transform(values: Vec<Value>) -> Result<Vec<Value>> {
 /// let batch = values_to_arrow(&transformed, &traces_schema())?;
 /// ```
 pub fn apply_trace_transform(values: Vec<Value>) -> Result<Vec<Value>> {
-    let mut transformer = VrlTransformer::new();
+    apply_trace_programs(&mut VrlTransformer::new(), values, 0, Layers::default())
+}
+
+/// Apply the built-in trace transformation followed by a custom VRL program.
+///
+/// See [`CustomProgram`] for what the custom program can see and change.
+pub fn apply_trace_transform_with(
+    values: Vec<Value>,
+    custom: &CustomProgram,
+) -> Result<Vec<Value>> {
+    apply_trace_programs(
+        &mut VrlTransformer::new(),
+        values,
+        0,
+        Layers::custom(custom),
+    )
+}
+
+fn apply_trace_programs(
+    transformer: &mut VrlTransformer,
+    values: Vec<Value>,
+    first_index: usize,
+    layers: Layers<'_>,
+) -> Result<Vec<Value>> {
     let mut result = Vec::with_capacity(values.len());
 
-    for (idx, value) in values.into_iter().enumerate() {
-        let (_table, transformed) = transformer
-            .transform(&OTLP_TRACES_PROGRAM, value)
+    for (idx, value) in (first_index..).zip(values) {
+        let transformed = layers
+            .run(transformer, &OTLP_TRACES_PROGRAM, value)
             .map_err(|e| Error::VrlRuntime(format!("span {}: {}", idx, e.0)))?;
         result.push(transformed);
     }
@@ -568,45 +1052,73 @@ pub fn apply_trace_transform(values: Vec<Value>) -> Result<Vec<Value>> {
 /// }
 /// ```
 pub fn apply_metric_transform(values: Vec<Value>) -> Result<MetricValues> {
-    let mut transformer = VrlTransformer::new();
+    apply_metric_programs(&mut VrlTransformer::new(), values, 0, Layers::default())
+}
+
+/// Apply the built-in metric transformations followed by a custom VRL program.
+///
+/// The same custom program runs for every metric type; it can branch on
+/// `._table`. See [`CustomProgram`] for details.
+pub fn apply_metric_transform_with(
+    values: Vec<Value>,
+    custom: &CustomProgram,
+) -> Result<MetricValues> {
+    apply_metric_programs(
+        &mut VrlTransformer::new(),
+        values,
+        0,
+        Layers::custom(custom),
+    )
+}
+
+fn apply_metric_programs(
+    transformer: &mut VrlTransformer,
+    values: Vec<Value>,
+    first_index: usize,
+    layers: Layers<'_>,
+) -> Result<MetricValues> {
     let mut result = MetricValues::default();
 
     // Partition metrics by type and transform each with appropriate program
-    for (idx, value) in values.into_iter().enumerate() {
+    for (idx, value) in (first_index..).zip(values) {
         // Extract _metric_type field to determine which program to use
         let metric_type = extract_metric_type(&value);
 
         match metric_type.as_str() {
             "gauge" => {
-                let (_table, transformed) = transformer
-                    .transform(&OTLP_GAUGE_PROGRAM, value)
+                let transformed = layers
+                    .run(transformer, &OTLP_GAUGE_PROGRAM, value)
                     .map_err(|e| Error::VrlRuntime(format!("gauge metric {}: {}", idx, e.0)))?;
                 result.gauge.push(transformed);
             }
             "sum" => {
-                let (_table, transformed) = transformer
-                    .transform(&OTLP_SUM_PROGRAM, value)
+                let transformed = layers
+                    .run(transformer, &OTLP_SUM_PROGRAM, value)
                     .map_err(|e| Error::VrlRuntime(format!("sum metric {}: {}", idx, e.0)))?;
                 result.sum.push(transformed);
             }
             "histogram" => {
-                let (_table, transformed) = transformer
-                    .transform(&OTLP_HISTOGRAM_PROGRAM, value)
-                    .map_err(|e| {
-                    Error::VrlRuntime(format!("histogram metric {}: {}", idx, e.0))
-                })?;
+                let transformed = layers
+                    .run(transformer, &OTLP_HISTOGRAM_PROGRAM, value)
+                    .map_err(|e| Error::VrlRuntime(format!("histogram metric {}: {}", idx, e.0)))?;
                 result.histogram.push(transformed);
             }
             "exp_histogram" => {
-                let (_table, transformed) = transformer
-                    .transform(&OTLP_EXP_HISTOGRAM_PROGRAM, value)
+                let transformed = layers
+                    .run(transformer, &OTLP_EXP_HISTOGRAM_PROGRAM, value)
                     .map_err(|e| {
                         Error::VrlRuntime(format!("exp_histogram metric {}: {}", idx, e.0))
                     })?;
                 result.exp_histogram.push(transformed);
             }
+            "summary" => {
+                let transformed = layers
+                    .run(transformer, &OTLP_SUMMARY_PROGRAM, value)
+                    .map_err(|e| Error::VrlRuntime(format!("summary metric {}: {}", idx, e.0)))?;
+                result.summary.push(transformed);
+            }
             _ => {
-                // Skip unknown metric types (summary - deprecated in OTLP spec)
+                // Skip unknown metric types
             }
         }
     }
@@ -614,6 +1126,126 @@ pub fn apply_metric_transform(values: Vec<Value>) -> Result<MetricValues> {
     Ok(result)
 }
 
+/// Apply VRL transformation to decoded profile sample values.
+///
+/// See [`apply_log_transform`] for details.
+pub fn apply_profile_transform(values: Vec<Value>) -> Result<Vec<Value>> {
+    apply_profile_programs(&mut VrlTransformer::new(), values, 0, Layers::default())
+}
+
+/// Apply the built-in profile transformation followed by a custom VRL
+/// program.
+///
+/// See [`CustomProgram`] for what the custom program can see and change.
+pub fn apply_profile_transform_with(
+    values: Vec<Value>,
+    custom: &CustomProgram,
+) -> Result<Vec<Value>> {
+    apply_profile_programs(
+        &mut VrlTransformer::new(),
+        values,
+        0,
+        Layers::custom(custom),
+    )
+}
+
+fn apply_profile_programs(
+    transformer: &mut VrlTransformer,
+    values: Vec<Value>,
+    first_index: usize,
+    layers: Layers<'_>,
+) -> Result<Vec<Value>> {
+    let mut result = Vec::with_capacity(values.len());
+
+    for (idx, value) in (first_index..).zip(values) {
+        let transformed = layers
+            .run(transformer, &OTLP_PROFILES_PROGRAM, value)
+            .map_err(|e| Error::VrlRuntime(format!("profile sample {}: {}", idx, e.0)))?;
+        result.push(transformed);
+    }
+
+    Ok(result)
+}
+
+/// Signature of the `apply_*_programs` functions for logs, traces and
+/// profiles: transformer, records, index of the first record, layers.
+type ApplyPrograms = fn(&mut VrlTransformer, Vec<Value>, usize, Layers<'_>) -> Result<Vec<Value>>;
+
+/// Optional stages layered around a built-in VRL program.
+#[derive(Clone, Copy, Default)]
+struct Layers<'a> {
+    custom: Option<&'a CustomProgram>,
+    promoted: Option<&'a PromotedAttributes>,
+    /// Keep full-nanosecond timestamps
+    nanos: bool,
+}
+
+impl<'a> Layers<'a> {
+    fn custom(custom: &'a CustomProgram) -> Self {
+        Self {
+            custom: Some(custom),
+            ..Self::default()
+        }
+    }
+
+    fn promoted(promoted: &'a PromotedAttributes) -> Self {
+        Self {
+            promoted: Some(promoted),
+            ..Self::default()
+        }
+    }
+
+    /// Run the built-in program, restore nanosecond timestamps (in
+    /// nanosecond mode), run the custom program (if any), then add the
+    /// promoted attribute columns read from the decoded record.
+    fn run(
+        &self,
+        transformer: &mut VrlTransformer,
+        program: &Program,
+        value: Value,
+    ) -> std::result::Result<Value, VrlError> {
+        let promoted = self.promoted.map(|promoted| promoted.extract(&value));
+        let nanos = self.nanos.then(|| Nanos::extract(&value));
+        let (_table, mut transformed) =
+            transformer.transform_layered_with(program, self.custom, value, |builtin| {
+                if let Some(nanos) = nanos {
+                    nanos.insert(builtin);
+                }
+            })?;
+        if let (Some(promoted), Some(values)) = (self.promoted, promoted) {
+            promoted.insert(&mut transformed, values);
+        }
+        Ok(transformed)
+    }
+
+    /// The output schema of a built-in table with these layers.
+    fn schema(&self, base: Schema) -> Result<Schema> {
+        let schema = match self.promoted {
+            Some(promoted) => promoted.extend_schema(&base)?,
+            None => base,
+        };
+        Ok(if self.nanos {
+            nanosecond_schema(&schema)
+        } else {
+            schema
+        })
+    }
+}
+
+/// Convert transformed values to a RecordBatch, counting the output in
+/// `stats`. `stats` is left unchanged if the conversion fails.
+fn finish_batch(
+    values: &[Value],
+    schema: &Schema,
+    stats: &mut TransformStats,
+) -> Result<RecordBatch> {
+    let (batch, coerced_nulls) = arrow::build_batch(values, schema)?;
+    stats.count_ids(values);
+    stats.records_out += batch.num_rows();
+    stats.null_coerced_values += coerced_nulls;
+    Ok(batch)
+}
+
 fn values_to_json(values: Vec<Value>, label: &str) -> Result<Vec<serde_json::Value>> {
     let mut out = Vec::with_capacity(values.len());
 
@@ -904,7 +1536,7 @@ mod tests {
         let request = create_test_log_request();
         let bytes = request.encode_to_vec();
 
-        let batch = transform_logs(&bytes, InputFormat::Protobuf).unwrap();
+        let batch = transform_logs(&bytes, InputFormat::Protobuf).unwrap().batch;
 
         assert_eq!(batch.num_rows(), 1);
         assert!(batch.num_columns() > 0);
@@ -934,7 +1566,9 @@ mod tests {
             }]
         }"#;
 
-        let batch = transform_logs(json.as_bytes(), InputFormat::Json).unwrap();
+        let batch = transform_logs(json.as_bytes(), InputFormat::Json)
+            .unwrap()
+            .batch;
 
         assert_eq!(batch.num_rows(), 1);
     }
@@ -946,7 +1580,7 @@ mod tests {
         };
         let bytes = request.encode_to_vec();
 
-        let batch = transform_logs(&bytes, InputFormat::Protobuf).unwrap();
+        let batch = transform_logs(&bytes, InputFormat::Protobuf).unwrap().batch;
 
         assert_eq!(batch.num_rows(), 0);
     }
@@ -956,7 +1590,9 @@ mod tests {
         let request = create_test_trace_request();
         let bytes = request.encode_to_vec();
 
-        let batch = transform_traces(&bytes, InputFormat::Protobuf).unwrap();
+        let batch = transform_traces(&bytes, InputFormat::Protobuf)
+            .unwrap()
+            .batch;
 
         assert_eq!(batch.num_rows(), 1);
 
@@ -987,7 +1623,9 @@ mod tests {
             }]
         }"#;
 
-        let batch = transform_traces(json.as_bytes(), InputFormat::Json).unwrap();
+        let batch = transform_traces(json.as_bytes(), InputFormat::Json)
+            .unwrap()
+            .batch;
 
         assert_eq!(batch.num_rows(), 1);
     }
@@ -999,7 +1637,9 @@ mod tests {
         };
         let bytes = request.encode_to_vec();
 
-        let batch = transform_traces(&bytes, InputFormat::Protobuf).unwrap();
+        let batch = transform_traces(&bytes, InputFormat::Protobuf)
+            .unwrap()
+            .batch;
 
         assert_eq!(batch.num_rows(), 0);
     }
@@ -1069,6 +1709,55 @@ mod tests {
         assert!(batches.sum.is_none());
     }
 
+    #[test]
+    fn test_transform_metrics_summary_partitioned() {
+        let json = r#"{
+            "resourceMetrics": [{
+                "resource": { "attributes": [{ "key": "service.name", "value": { "stringValue": "legacy-java" } }]},
+                "scopeMetrics": [{
+                    "metrics": [{
+                        "name": "http.server.duration",
+                        "summary": {
+                            "dataPoints": [{
+                                "timeUnixNano": "1703265600000000000",
+                                "count": "4",
+                                "sum": 10.0,
+                                "quantileValues": [{ "quantile": 0.5, "value": 2.5 }]
+                            }]
+                        }
+                    }]
+                }]
+            }]
+        }"#;
+
+        let partitioned =
+            transform_metrics_partitioned(json.as_bytes(), InputFormat::Json).unwrap();
+        assert!(partitioned.gauge.is_empty());
+        assert_eq!(partitioned.summary.len(), 1);
+
+        let group = &partitioned.summary.batches[0];
+        assert_eq!(group.service_name.as_ref(), "legacy-java");
+        assert_eq!(group.record_count, 1);
+
+        let count = group
+            .batch
+            .column_by_name("count")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<::arrow::array::Int64Array>()
+            .unwrap();
+        assert_eq!(count.value(0), 4);
+
+        let quantiles = group
+            .batch
+            .column_by_name("quantile_values")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<::arrow::array::StringArray>()
+            .unwrap();
+        assert_eq!(quantiles.value(0), r#"[{"quantile":0.5,"value":2.5}]"#);
+    }
+
     // ========================================================================
     // Lower-level API tests
     // ========================================================================
@@ -1146,6 +1835,135 @@ mod tests {
         assert!(transformed.sum.is_empty());
     }
 
+    #[test]
+    fn test_transform_logs_with_custom_program() {
+        let request = create_test_log_request();
+        let bytes = request.encode_to_vec();
+
+        let custom =
+            CustomProgram::compile(r#".service_name = "renamed"; .log_attributes = null"#).unwrap();
+        let batch = transform_logs_with(&bytes, InputFormat::Protobuf, &custom)
+            .unwrap()
+            .batch;
+
+        let services = ::arrow::compute::cast(
+            batch.column_by_name("service_name").unwrap(),
+            &::arrow::datatypes::DataType::Utf8,
+        )
+        .unwrap();
+        assert_eq!(
+            ::arrow::array::AsArray::as_string::<i32>(&services).value(0),
+            "renamed"
+        );
+        assert!(batch.column_by_name("log_attributes").unwrap().is_null(0));
+    }
+
+    #[test]
+    fn test_transform_metrics_with_custom_program_sees_table() {
+        let request = create_test_metrics_request();
+        let bytes = request.encode_to_vec();
+
+        let custom =
+            CustomProgram::compile(r#"if ._table == "sum" { .metric_unit = "custom" }"#).unwrap();
+        let batches = transform_metrics_with(&bytes, InputFormat::Protobuf, &custom).unwrap();
+
+        let unit_of = |batch: &RecordBatch| {
+            let units = ::arrow::compute::cast(
+                batch.column_by_name("metric_unit").unwrap(),
+                &::arrow::datatypes::DataType::Utf8,
+            )
+            .unwrap();
+            ::arrow::array::AsArray::as_string::<i32>(&units)
+                .value(0)
+                .to_string()
+        };
+        assert_ne!(unit_of(batches.gauge.as_ref().unwrap()), "custom");
+        assert_eq!(unit_of(batches.sum.as_ref().unwrap()), "custom");
+    }
+
+    #[test]
+    fn test_transform_logs_promoted() {
+        let request = create_test_log_request();
+        let bytes = request.encode_to_vec();
+
+        let promoted = PromotedAttributes::new(vec![
+            PromotedAttribute::resource("service.name").with_column("resource_service"),
+            PromotedAttribute::record("log.key"),
+            PromotedAttribute::record("missing").with_type(PromotedType::Int64),
+        ]);
+        let batch = transform_logs_promoted(&bytes, InputFormat::Protobuf, &promoted)
+            .unwrap()
+            .batch;
+
+        assert_eq!(
+            batch.schema().fields().len(),
+            logs_schema().fields().len() + 3
+        );
+        let string_value = |name: &str| {
+            batch
+                .column_by_name(name)
+                .unwrap()
+                .as_any()
+                .downcast_ref::<::arrow::array::StringArray>()
+                .unwrap()
+                .value(0)
+                .to_string()
+        };
+        assert_eq!(string_value("resource_service"), "test-service");
+        assert_eq!(string_value("log_key"), "log-value");
+        // The JSON blob is still produced alongside the promoted column
+        assert!(string_value("log_attributes").contains("log-value"));
+        assert!(batch.column_by_name("missing").unwrap().is_null(0));
+    }
+
+    #[test]
+    fn test_transform_traces_promoted() {
+        let request = create_test_trace_request();
+        let bytes = request.encode_to_vec();
+
+        let promoted = PromotedAttributes::new(vec![PromotedAttribute::record("span.key")]);
+        let batch = transform_traces_promoted(&bytes, InputFormat::Protobuf, &promoted)
+            .unwrap()
+            .batch;
+
+        let column = batch
+            .column_by_name("span_key")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<::arrow::array::StringArray>()
+            .unwrap();
+        assert_eq!(column.value(0), "span-value");
+    }
+
+    #[test]
+    fn test_transform_metrics_promoted() {
+        let request = create_test_metrics_request();
+        let bytes = request.encode_to_vec();
+
+        let promoted = PromotedAttributes::new(vec![PromotedAttribute::record("metric.key")]);
+        let batches = transform_metrics_promoted(&bytes, InputFormat::Protobuf, &promoted).unwrap();
+
+        for batch in [batches.gauge.unwrap(), batches.sum.unwrap()] {
+            assert!(batch.schema().field_with_name("metric_key").is_ok());
+        }
+    }
+
+    #[test]
+    fn test_transform_promoted_rejects_column_collision() {
+        let request = create_test_log_request();
+        let bytes = request.encode_to_vec();
+
+        let promoted = PromotedAttributes::new(vec![PromotedAttribute::resource("service.name")]);
+        let result = transform_logs_promoted(&bytes, InputFormat::Protobuf, &promoted);
+        assert!(matches!(result, Err(Error::SchemaMismatch(_))));
+    }
+
+    #[test]
+    fn test_custom_program_compile_error() {
+        let result = CustomProgram::compile(".service_name = ");
+        assert!(matches!(result, Err(Error::VrlCompilation(_))));
+    }
+
     // ========================================================================
     // Error handling tests
     // ========================================================================
@@ -1174,6 +1992,177 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ========================================================================
+    // Lenient API tests
+    // ========================================================================
+
+    fn log_request_with_overflow() -> ExportLogsServiceRequest {
+        let mut request = create_test_log_request();
+        let scope = &mut request.resource_logs[0].scope_logs[0];
+        let mut bad = scope.log_records[0].clone();
+        bad.time_unix_nano = u64::MAX;
+        scope.log_records.insert(0, bad);
+        request
+    }
+
+    #[test]
+    fn test_transform_logs_lenient_drops_bad_record() {
+        let body = log_request_with_overflow().encode_to_vec();
+
+        let result = transform_logs_lenient(&body, InputFormat::Protobuf).unwrap();
+        let strict = transform_logs(
+            &create_test_log_request().encode_to_vec(),
+            InputFormat::Protobuf,
+        )
+        .unwrap()
+        .batch;
+
+        assert_eq!(result.batch, strict);
+        assert_eq!(result.errors.len(), 1);
+        let error = &result.errors[0];
+        assert_eq!(error.index, 0);
+        assert_eq!(error.location, RecordLocation::default());
+        assert_eq!(error.stage, ErrorStage::Decode);
+        assert!(error.message.contains("timestamp overflow"));
+
+        // Strict mode still rejects the payload
+        assert!(transform_logs(&body, InputFormat::Protobuf).is_err());
+    }
+
+    #[test]
+    fn test_transform_logs_lenient_reports_vrl_and_arrow_errors() {
+        let body = create_test_log_request().encode_to_vec();
+        let schema = logs_schema();
+
+        let failing = CustomProgram::compile(".x = to_int!(.severity_text)").unwrap();
+        let decoded = decode::decode_log_records(&body, InputFormat::Protobuf, true).unwrap();
+        let result = lenient::transform(
+            &mut VrlTransformer::new(),
+            decoded,
+            &OTLP_LOGS_PROGRAM,
+            Layers::custom(&failing),
+            &schema,
+        )
+        .unwrap();
+        assert_eq!(result.batch.num_rows(), 0);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].stage, ErrorStage::Vrl);
+
+        let mistyped = CustomProgram::compile(".severity_number = \"high\"").unwrap();
+        let decoded = decode::decode_log_records(&body, InputFormat::Protobuf, true).unwrap();
+        let result = lenient::transform(
+            &mut VrlTransformer::new(),
+            decoded,
+            &OTLP_LOGS_PROGRAM,
+            Layers::custom(&mistyped),
+            &schema,
+        )
+        .unwrap();
+        assert_eq!(result.batch.num_rows(), 0);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].stage, ErrorStage::Arrow);
+    }
+
+    #[test]
+    fn test_transform_metrics_lenient_drops_bad_data_point() {
+        let mut request = create_test_metrics_request();
+        let metrics = &mut request.resource_metrics[0].scope_metrics[0].metrics;
+        if let Some(Data::Sum(sum)) = &mut metrics[1].data {
+            sum.data_points[0].time_unix_nano = u64::MAX;
+        }
+        let body = request.encode_to_vec();
+
+        let result = transform_metrics_lenient(&body, InputFormat::Protobuf).unwrap();
+
+        assert_eq!(result.batches.gauge.unwrap().num_rows(), 1);
+        assert!(result.batches.sum.is_none());
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].index, 1);
+        assert_eq!(result.errors[0].stage, ErrorStage::Decode);
+    }
+
+    #[test]
+    fn test_transform_lenient_invalid_payload() {
+        let result = transform_logs_lenient(b"not valid protobuf", InputFormat::Protobuf);
+        assert!(result.is_err());
+    }
+
+    // ========================================================================
+    // Stats tests
+    // ========================================================================
+
+    fn assert_balanced(stats: &TransformStats) {
+        assert_eq!(stats.records_in, stats.records_out + stats.dropped.total());
+    }
+
+    #[test]
+    fn test_transform_traces_stats_counts_invalid_ids() {
+        let mut request = create_test_trace_request();
+        let spans = &mut request.resource_spans[0].scope_spans[0].spans;
+        let mut short = spans[0].clone();
+        short.span_id = vec![1, 2, 3];
+        spans.push(short);
+
+        let result = transform_traces(&request.encode_to_vec(), InputFormat::Protobuf).unwrap();
+
+        assert_eq!(result.stats.records_in, 2);
+        assert_eq!(result.stats.records_out, 2);
+        // The empty parent span ID of the root spans is not counted
+        assert_eq!(result.stats.invalid_ids, 1);
+        assert_balanced(&result.stats);
+    }
+
+    #[test]
+    fn test_transform_metrics_stats_counts_skipped_points() {
+        let mut request = create_test_metrics_request();
+        if let Some(Data::Gauge(gauge)) =
+            &mut request.resource_metrics[0].scope_metrics[0].metrics[0].data
+        {
+            let mut nan = gauge.data_points[0].clone();
+            nan.value = Some(
+                opentelemetry_proto::tonic::metrics::v1::number_data_point::Value::AsDouble(
+                    f64::NAN,
+                ),
+            );
+            gauge.data_points.push(nan);
+        }
+
+        let batches = transform_metrics(&request.encode_to_vec(), InputFormat::Protobuf).unwrap();
+
+        assert_eq!(batches.stats.records_in, 3);
+        assert_eq!(batches.stats.records_out, 2);
+        assert_eq!(batches.stats.dropped.nan_values, 1);
+        assert_balanced(&batches.stats);
+    }
+
+    #[test]
+    fn test_transform_lenient_stats_by_stage() {
+        let result = transform_logs_lenient(
+            &log_request_with_overflow().encode_to_vec(),
+            InputFormat::Protobuf,
+        )
+        .unwrap();
+
+        assert_eq!(result.stats.records_in, 2);
+        assert_eq!(result.stats.records_out, 1);
+        assert_eq!(result.stats.dropped.decode_errors, 1);
+        assert_eq!(result.stats.timestamp_overflows, 1);
+        assert_balanced(&result.stats);
+    }
+
+    #[test]
+    fn test_transform_stats_merge() {
+        let body = create_test_log_request().encode_to_vec();
+        let mut total = TransformStats::default();
+        for _ in 0..3 {
+            total.merge(&transform_logs(&body, InputFormat::Protobuf).unwrap().stats);
+        }
+
+        assert_eq!(total.records_in, 3);
+        assert_eq!(total.records_out, 3);
+        assert_balanced(&total);
+    }
+
     // ========================================================================
     // Struct tests
     // ========================================================================
@@ -1185,7 +2174,9 @@ mod tests {
             sum: None,
             histogram: None,
             exp_histogram: None,
+            summary: None,
             skipped: SkippedMetrics::default(),
+            stats: TransformStats::default(),
         };
         let debug_str = format!("{batches:?}");
         assert!(debug_str.contains("MetricBatches"));
@@ -1198,6 +2189,7 @@ mod tests {
         assert!(values.sum.is_empty());
         assert!(values.histogram.is_empty());
         assert!(values.exp_histogram.is_empty());
+        assert!(values.summary.is_empty());
     }
 
     #[test]
@@ -1216,7 +2208,7 @@ mod tests {
         let request = create_test_log_request();
         let bytes = request.encode_to_vec();
 
-        let batch = transform_logs(&bytes, InputFormat::Protobuf).unwrap();
+        let batch = transform_logs(&bytes, InputFormat::Protobuf).unwrap().batch;
         let json_output = to_json(&batch).unwrap();
 
         // Should be valid NDJSON
@@ -1231,7 +2223,9 @@ mod tests {
         let request = create_test_trace_request();
         let bytes = request.encode_to_vec();
 
-        let batch = transform_traces(&bytes, InputFormat::Protobuf).unwrap();
+        let batch = transform_traces(&bytes, InputFormat::Protobuf)
+            .unwrap()
+            .batch;
         let ipc_output = to_ipc(&batch).unwrap();
 
         // Should produce some bytes
@@ -1293,7 +2287,9 @@ mod tests {
         };
         let bytes = request.encode_to_vec();
 
-        let batch = transform_traces(&bytes, InputFormat::Protobuf).unwrap();
+        let batch = transform_traces(&bytes, InputFormat::Protobuf)
+            .unwrap()
+            .batch;
         assert_eq!(batch.num_rows(), 1);
 
         // Get the timestamp column and verify it's not 0 (epoch)
@@ -1354,7 +2350,7 @@ mod tests {
         };
         let bytes = request.encode_to_vec();
 
-        let batch = transform_logs(&bytes, InputFormat::Protobuf).unwrap();
+        let batch = transform_logs(&bytes, InputFormat::Protobuf).unwrap().batch;
         assert_eq!(batch.num_rows(), 1);
 
         // Get the timestamp column and verify it's not 0 (epoch)
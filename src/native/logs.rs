@@ -0,0 +1,168 @@
+//! Native log mapping, equivalent to `otlp_logs.vrl`
+
+use arrow::datatypes::Schema;
+use vrl::value::Value;
+
+use super::{string_or_empty, timestamp, GroupColumns, RowWriter};
+use crate::arrow::TIME_UNIX_NANO;
+use crate::decode::{Decoded, LogRecordParts};
+use crate::error::Result;
+use crate::transform::functions::{encode_json, json_or_null, string_or_null};
+use crate::{TransformStats, TransformedBatch};
+
+/// Columns computed for each log record, in row order
+const FIELDS: [&str; 16] = [
+    "timestamp",
+    "observed_timestamp",
+    "trace_id",
+    "span_id",
+    "service_name",
+    "service_namespace",
+    "service_instance_id",
+    "severity_number",
+    "severity_text",
+    "body",
+    "resource_attributes",
+    "scope_name",
+    "scope_version",
+    "scope_attributes",
+    "log_attributes",
+    TIME_UNIX_NANO,
+];
+
+/// Build a batch of `schema` from decoded log records, with timestamps in
+/// microseconds or (with `nanos`) nanoseconds.
+pub(crate) fn transform_logs(
+    decoded: Decoded<LogRecordParts>,
+    schema: &Schema,
+    nanos: bool,
+) -> Result<TransformedBatch> {
+    let mut stats = TransformStats::decoded(&decoded);
+    let logs = logs_batch(decoded.values, schema, nanos)?;
+    stats.merge(&logs.stats);
+    Ok(TransformedBatch {
+        batch: logs.batch,
+        stats,
+    })
+}
+
+/// Build a batch of `schema` from log record parts; the stats only count
+/// the output.
+pub(crate) fn logs_batch(
+    values: Vec<LogRecordParts>,
+    schema: &Schema,
+    nanos: bool,
+) -> Result<TransformedBatch> {
+    let mut stats = TransformStats::default();
+    let mut writer = RowWriter::new(schema, &FIELDS, values.len());
+    let mut groups = GroupColumns::default();
+
+    for parts in values {
+        let row = log_row(parts, &mut groups, nanos);
+        stats.count_id(&row[2], 32);
+        stats.count_id(&row[3], 16);
+        writer.append(&row)?;
+    }
+
+    let (batch, coerced_nulls) = writer.finish()?;
+    stats.records_out += batch.num_rows();
+    stats.null_coerced_values += coerced_nulls;
+    Ok(TransformedBatch { batch, stats })
+}
+
+fn log_row(parts: LogRecordParts, groups: &mut GroupColumns, nanos: bool) -> [Value; 16] {
+    let (resource, scope) = groups.get(&parts.resource, &parts.scope);
+    let body = match parts.body {
+        body @ (Value::Object(_) | Value::Array(_)) => encode_json(&body),
+        body => string_or_null(body),
+    };
+
+    [
+        timestamp(parts.time_unix_nano, nanos),
+        timestamp(parts.observed_time_unix_nano, nanos),
+        string_or_null(Value::Bytes(parts.trace_id)),
+        string_or_null(Value::Bytes(parts.span_id)),
+        resource.service_name.clone(),
+        resource.service_namespace.clone(),
+        resource.service_instance_id.clone(),
+        Value::Integer(parts.severity_number),
+        string_or_empty(Value::Bytes(parts.severity_text)),
+        body,
+        resource.resource_attributes.clone(),
+        scope.scope_name.clone(),
+        scope.scope_version.clone(),
+        scope.scope_attributes.clone(),
+        json_or_null(&parts.attributes),
+        Value::Integer(parts.time_unix_nano),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::{decode_log_parts, decode_logs, InputFormat};
+    use crate::{apply_log_transform, logs_schema, values_to_arrow};
+
+    /// The batch of the VRL mapping, for comparison
+    fn vrl_batch(bytes: &[u8], format: InputFormat) -> arrow::record_batch::RecordBatch {
+        let values = decode_logs(bytes, format).unwrap();
+        let transformed = apply_log_transform(values).unwrap();
+        values_to_arrow(&transformed, &logs_schema()).unwrap()
+    }
+
+    #[test]
+    fn test_native_logs_match_vrl_protobuf() {
+        let pb = include_bytes!("../../testdata/logs_large.pb");
+        let decoded = decode_log_parts(pb, InputFormat::Protobuf).unwrap();
+
+        let result = transform_logs(decoded, &logs_schema(), false).unwrap();
+
+        assert!(result.batch.num_rows() > 0);
+        assert_eq!(result.batch, vrl_batch(pb, InputFormat::Protobuf));
+        assert_eq!(result.stats.records_in, result.batch.num_rows());
+    }
+
+    #[test]
+    fn test_native_logs_match_vrl_json() {
+        // Edge cases: object and array bodies, a missing resource, non-string
+        // service.name, empty and 3-byte IDs and an empty severity text
+        let json = br#"{
+            "resourceLogs": [
+                {
+                    "resource": {"attributes": [
+                        {"key": "service.name", "value": {"intValue": "7"}},
+                        {"key": "service.namespace", "value": {"stringValue": ""}}
+                    ]},
+                    "scopeLogs": [{
+                        "scope": {"name": "lib", "attributes": [
+                            {"key": "k", "value": {"boolValue": true}}
+                        ]},
+                        "logRecords": [
+                            {"timeUnixNano": "1700000000123456789", "severityNumber": 9,
+                             "body": {"kvlistValue": {"values": [
+                                {"key": "a", "value": {"doubleValue": 1.5}}
+                             ]}},
+                             "traceId": "0af7651916cd43dd8448eb211c80319c",
+                             "spanId": "AQID"},
+                            {"body": {"arrayValue": {"values": [{"intValue": "1"}]}},
+                             "attributes": [{"key": "n", "value": {"stringValue": "v"}}]}
+                        ]
+                    }]
+                },
+                {
+                    "scopeLogs": [
+                        {"logRecords": [{"body": {"stringValue": ""}, "severityText": "WARN"}]},
+                        {"scope": {"version": "2"}, "logRecords": [{"body": {"intValue": "3"}}]}
+                    ]
+                }
+            ]
+        }"#;
+        let decoded = decode_log_parts(json, InputFormat::Json).unwrap();
+
+        let result = transform_logs(decoded, &logs_schema(), false).unwrap();
+
+        assert_eq!(result.batch.num_rows(), 4);
+        assert_eq!(result.batch, vrl_batch(json, InputFormat::Json));
+        assert_eq!(result.stats.invalid_ids, 1);
+    }
+}
@@ -0,0 +1,372 @@
+//! Native metric mappings, equivalent to `otlp_gauge.vrl`, `otlp_sum.vrl`,
+//! `otlp_histogram.vrl`, `otlp_exp_histogram.vrl` and `otlp_summary.vrl`
+
+use arrow::datatypes::Schema;
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use vrl::value::Value;
+
+use super::{string_or_empty, timestamp, GroupColumns, RowWriter};
+use crate::arrow::{nanosecond_schema, TIME_UNIX_NANO};
+use crate::decode::{
+    exemplars_value, finite_float_to_vrl, optional_float, DecodedMetrics, MetricPointParts,
+    MetricRecordParts, SkippedMetrics,
+};
+use crate::error::Result;
+use crate::transform::functions::json_or_null;
+use crate::{
+    exp_histogram_schema, gauge_schema, histogram_schema, sum_schema, summary_schema,
+    MetricBatches, TransformStats,
+};
+
+/// Columns computed for the data points of every metric type, at the start
+/// of each row
+const POINT_FIELDS: [&str; 15] = [
+    "timestamp",
+    "start_timestamp",
+    "metric_name",
+    "metric_description",
+    "metric_unit",
+    "service_name",
+    "service_namespace",
+    "service_instance_id",
+    "resource_attributes",
+    "scope_name",
+    "scope_version",
+    "scope_attributes",
+    "metric_attributes",
+    "flags",
+    TIME_UNIX_NANO,
+];
+
+/// Columns computed for each metric type after the point columns
+const GAUGE_FIELDS: [&str; 2] = ["value", "exemplars_json"];
+const SUM_FIELDS: [&str; 4] = [
+    "value",
+    "exemplars_json",
+    "aggregation_temporality",
+    "is_monotonic",
+];
+const HISTOGRAM_FIELDS: [&str; 8] = [
+    "count",
+    "sum",
+    "min",
+    "max",
+    "bucket_counts",
+    "explicit_bounds",
+    "exemplars_json",
+    "aggregation_temporality",
+];
+const EXP_HISTOGRAM_FIELDS: [&str; 13] = [
+    "count",
+    "sum",
+    "min",
+    "max",
+    "scale",
+    "zero_count",
+    "zero_threshold",
+    "positive_offset",
+    "positive_bucket_counts",
+    "negative_offset",
+    "negative_bucket_counts",
+    "exemplars_json",
+    "aggregation_temporality",
+];
+const SUMMARY_FIELDS: [&str; 3] = ["count", "sum", "quantile_values"];
+
+/// Build one batch per metric type present in the decoded data points, with
+/// timestamps in microseconds or (with `nanos`) nanoseconds.
+pub(crate) fn transform_metrics(
+    decoded: DecodedMetrics<MetricRecordParts>,
+    nanos: bool,
+) -> Result<MetricBatches> {
+    let DecodedMetrics { records, skipped } = decoded;
+    let mut stats = TransformStats::decoded(&records);
+    stats.add_skipped(&skipped);
+    let batches = metric_batches(records.values, nanos)?;
+    stats.merge(&batches.stats);
+    Ok(MetricBatches {
+        skipped,
+        stats,
+        ..batches
+    })
+}
+
+/// Build the batches of data point parts; the stats only count the output.
+pub(crate) fn metric_batches(values: Vec<MetricRecordParts>, nanos: bool) -> Result<MetricBatches> {
+    let mut stats = TransformStats::default();
+    let mut gauge: Table<17> = Table::new(gauge_schema(), GAUGE_FIELDS, nanos);
+    let mut sum: Table<19> = Table::new(sum_schema(), SUM_FIELDS, nanos);
+    let mut histogram: Table<23> = Table::new(histogram_schema(), HISTOGRAM_FIELDS, nanos);
+    let mut exp_histogram: Table<28> =
+        Table::new(exp_histogram_schema(), EXP_HISTOGRAM_FIELDS, nanos);
+    let mut summary: Table<18> = Table::new(summary_schema(), SUMMARY_FIELDS, nanos);
+    let mut groups = GroupColumns::default();
+
+    for parts in values {
+        match parts {
+            MetricRecordParts::Gauge(parts) => {
+                let point = point_row(parts.point, &mut groups, nanos);
+                let exemplars = json_or_null(&exemplars_value(parts.exemplars));
+                gauge.append(point, [parts.value, exemplars])?;
+            }
+            MetricRecordParts::Sum(parts) => {
+                let point = point_row(parts.point, &mut groups, nanos);
+                let exemplars = json_or_null(&exemplars_value(parts.exemplars));
+                sum.append(
+                    point,
+                    [
+                        parts.value,
+                        exemplars,
+                        Value::Integer(parts.aggregation_temporality),
+                        Value::Boolean(parts.is_monotonic),
+                    ],
+                )?;
+            }
+            MetricRecordParts::Histogram(parts) => {
+                let point = point_row(parts.point, &mut groups, nanos);
+                let exemplars = json_or_null(&exemplars_value(parts.exemplars));
+                histogram.append(
+                    point,
+                    [
+                        Value::Integer(parts.count),
+                        optional_float(parts.sum),
+                        optional_float(parts.min),
+                        optional_float(parts.max),
+                        Value::Bytes(parts.bucket_counts),
+                        Value::Bytes(parts.explicit_bounds),
+                        exemplars,
+                        Value::Integer(parts.aggregation_temporality),
+                    ],
+                )?;
+            }
+            MetricRecordParts::ExpHistogram(parts) => {
+                let point = point_row(parts.point, &mut groups, nanos);
+                let exemplars = json_or_null(&exemplars_value(parts.exemplars));
+                exp_histogram.append(
+                    point,
+                    [
+                        Value::Integer(parts.count),
+                        optional_float(parts.sum),
+                        optional_float(parts.min),
+                        optional_float(parts.max),
+                        Value::Integer(parts.scale),
+                        Value::Integer(parts.zero_count),
+                        finite_float_to_vrl(parts.zero_threshold),
+                        Value::Integer(parts.positive_offset),
+                        Value::Bytes(parts.positive_bucket_counts),
+                        Value::Integer(parts.negative_offset),
+                        Value::Bytes(parts.negative_bucket_counts),
+                        exemplars,
+                        Value::Integer(parts.aggregation_temporality),
+                    ],
+                )?;
+            }
+            MetricRecordParts::Summary(parts) => {
+                let point = point_row(parts.point, &mut groups, nanos);
+                summary.append(
+                    point,
+                    [
+                        Value::Integer(parts.count),
+                        finite_float_to_vrl(parts.sum),
+                        Value::Bytes(parts.quantile_values),
+                    ],
+                )?;
+            }
+        }
+    }
+
+    Ok(MetricBatches {
+        gauge: gauge.finish(&mut stats)?,
+        sum: sum.finish(&mut stats)?,
+        histogram: histogram.finish(&mut stats)?,
+        exp_histogram: exp_histogram.finish(&mut stats)?,
+        summary: summary.finish(&mut stats)?,
+        skipped: SkippedMetrics::default(),
+        stats,
+    })
+}
+
+fn point_row(
+    point: MetricPointParts,
+    groups: &mut GroupColumns,
+    nanos: bool,
+) -> [Value; POINT_FIELDS.len()] {
+    let (resource, scope) = groups.get(&point.resource, &point.scope);
+
+    [
+        timestamp(point.time_unix_nano, nanos),
+        timestamp(point.start_time_unix_nano, nanos),
+        string_or_empty(Value::Bytes(point.metric_name)),
+        string_or_empty(Value::Bytes(point.metric_description)),
+        string_or_empty(Value::Bytes(point.metric_unit)),
+        resource.service_name.clone(),
+        resource.service_namespace.clone(),
+        resource.service_instance_id.clone(),
+        resource.resource_attributes.clone(),
+        scope.scope_name.clone(),
+        scope.scope_version.clone(),
+        scope.scope_attributes.clone(),
+        json_or_null(&point.attributes),
+        Value::Integer(point.flags),
+        Value::Integer(point.time_unix_nano),
+    ]
+}
+
+/// The rows of one metric type, `N` columns each. The builders are created
+/// with the first row, so a type without data points has no batch.
+struct Table<const N: usize> {
+    schema: Schema,
+    fields: [&'static str; N],
+    writer: Option<RowWriter<N>>,
+}
+
+impl<const N: usize> Table<N> {
+    fn new<const E: usize>(schema: Schema, fields: [&'static str; E], nanos: bool) -> Self {
+        Self {
+            schema: if nanos {
+                nanosecond_schema(&schema)
+            } else {
+                schema
+            },
+            fields: concat(POINT_FIELDS, fields),
+            writer: None,
+        }
+    }
+
+    fn append<const E: usize>(
+        &mut self,
+        point: [Value; POINT_FIELDS.len()],
+        values: [Value; E],
+    ) -> std::result::Result<(), ArrowError> {
+        let writer = self
+            .writer
+            .get_or_insert_with(|| RowWriter::new(&self.schema, &self.fields, 0));
+        writer.append(&concat(point, values))
+    }
+
+    fn finish(self, stats: &mut TransformStats) -> Result<Option<RecordBatch>> {
+        let Some(writer) = self.writer else {
+            return Ok(None);
+        };
+        let (batch, coerced_nulls) = writer.finish()?;
+        stats.records_out += batch.num_rows();
+        stats.null_coerced_values += coerced_nulls;
+        Ok(Some(batch))
+    }
+}
+
+/// The items of `a` followed by those of `b`; `N` must be `A + B`.
+fn concat<T, const A: usize, const B: usize, const N: usize>(a: [T; A], b: [T; B]) -> [T; N] {
+    debug_assert_eq!(N, A + B);
+    let mut items = a.into_iter().chain(b);
+    std::array::from_fn(|_| items.next().expect("N is A + B"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::{decode_metric_parts, decode_metrics, InputFormat};
+    use crate::{apply_metric_transform, values_to_arrow};
+
+    /// The batches of the VRL mappings, for comparison
+    fn vrl_batches(bytes: &[u8], format: InputFormat) -> [Option<RecordBatch>; 5] {
+        let values = decode_metrics(bytes, format).unwrap().values;
+        let transformed = apply_metric_transform(values).unwrap();
+        let to_batch = |values: &[Value], schema: Schema| {
+            (!values.is_empty()).then(|| values_to_arrow(values, &schema).unwrap())
+        };
+        [
+            to_batch(&transformed.gauge, gauge_schema()),
+            to_batch(&transformed.sum, sum_schema()),
+            to_batch(&transformed.histogram, histogram_schema()),
+            to_batch(&transformed.exp_histogram, exp_histogram_schema()),
+            to_batch(&transformed.summary, summary_schema()),
+        ]
+    }
+
+    fn native_batches(bytes: &[u8], format: InputFormat) -> [Option<RecordBatch>; 5] {
+        let decoded = decode_metric_parts(bytes, format).unwrap();
+        let batches = transform_metrics(decoded, false).unwrap();
+        [
+            batches.gauge,
+            batches.sum,
+            batches.histogram,
+            batches.exp_histogram,
+            batches.summary,
+        ]
+    }
+
+    #[test]
+    fn test_native_metrics_match_vrl_protobuf() {
+        for pb in [
+            include_bytes!("../../testdata/metrics_mixed.pb").as_slice(),
+            include_bytes!("../../testdata/metrics_summary.pb").as_slice(),
+        ] {
+            let native = native_batches(pb, InputFormat::Protobuf);
+
+            assert!(native.iter().any(Option::is_some));
+            assert_eq!(native, vrl_batches(pb, InputFormat::Protobuf));
+        }
+    }
+
+    #[test]
+    fn test_native_metrics_match_vrl_json() {
+        // Edge cases: every metric type, exemplars, unset histogram statistics,
+        // a missing resource and metric unit, int values and empty buckets
+        let json = br#"{
+            "resourceMetrics": [
+                {
+                    "resource": {"attributes": [
+                        {"key": "service.name", "value": {"stringValue": "api"}}
+                    ]},
+                    "scopeMetrics": [{
+                        "scope": {"name": "meter", "version": "1"},
+                        "metrics": [
+                            {"name": "temp", "unit": "C", "gauge": {"dataPoints": [
+                                {"timeUnixNano": "1700000000123456789", "asDouble": 21.5,
+                                 "attributes": [{"key": "room", "value": {"stringValue": "a"}}],
+                                 "exemplars": [{"timeUnixNano": "1700000000000000000",
+                                                "asInt": "3", "spanId": "eee19b7ec3c1b174",
+                                                "traceId": "5b8efff798038103d269b633813fc60c"}]},
+                                {"timeUnixNano": "1700000001000000000", "asInt": "4", "flags": 1}
+                            ]}},
+                            {"name": "requests", "sum": {"aggregationTemporality": 2,
+                             "isMonotonic": true, "dataPoints": [
+                                {"startTimeUnixNano": "1600000000000000000",
+                                 "timeUnixNano": "1700000000000000000", "asInt": "10"}
+                            ]}},
+                            {"name": "latency", "histogram": {"aggregationTemporality": 1,
+                             "dataPoints": [
+                                {"timeUnixNano": "1700000000000000000", "count": "3",
+                                 "sum": 6.5, "bucketCounts": ["1", "2"], "explicitBounds": [5.0]},
+                                {"timeUnixNano": "1700000000000000000", "count": "0"}
+                            ]}}
+                        ]
+                    }]
+                },
+                {
+                    "scopeMetrics": [{
+                        "metrics": [
+                            {"name": "sizes", "exponentialHistogram": {"dataPoints": [
+                                {"timeUnixNano": "1700000000000000000", "count": "4",
+                                 "scale": 2, "zeroCount": "1", "zeroThreshold": 0.5,
+                                 "min": 0.0, "max": 9.0,
+                                 "positive": {"offset": -1, "bucketCounts": ["1", "2"]}}
+                            ]}},
+                            {"name": "rpc", "description": "RPC time", "summary": {"dataPoints": [
+                                {"timeUnixNano": "1700000000000000000", "count": "2", "sum": 3.0,
+                                 "quantileValues": [{"quantile": 0.5, "value": 1.0}]}
+                            ]}}
+                        ]
+                    }]
+                }
+            ]
+        }"#;
+
+        let native = native_batches(json, InputFormat::Json);
+
+        assert!(native.iter().all(Option::is_some));
+        assert_eq!(native, vrl_batches(json, InputFormat::Json));
+    }
+}
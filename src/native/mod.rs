@@ -0,0 +1,172 @@
+//! Native fast path for the built-in log, trace and metric mappings
+//!
+//! `otlp_logs.vrl`, `otlp_traces.vrl` and the metric programs only rename and
+//! convert fields, so when no custom program or promoted attributes are
+//! configured the decoded record parts are written straight to the Arrow
+//! builders, skipping the VRL record objects and runtime. Each column is
+//! computed with the same helper as the VRL function it replaces and appended
+//! through the same builder, so the batches are identical to the VRL path.
+//! Columns read from the resource and scope are computed once per
+//! resource/scope group.
+
+mod logs;
+mod metrics;
+mod traces;
+
+use std::sync::Arc;
+
+use arrow::datatypes::Schema;
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use vrl::value::Value;
+
+use crate::arrow::BatchBuilder;
+use crate::transform::functions::{get_attr, json_or_null, string_or_null};
+
+pub(crate) use logs::transform_logs;
+pub(crate) use metrics::transform_metrics;
+pub(crate) use traces::transform_traces;
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+pub(crate) use {logs::logs_batch, metrics::metric_batches, traces::spans_batch};
+
+/// Writes rows of `N` computed columns to the builders of a schema.
+///
+/// Schema columns missing from the row are null, like fields a VRL program
+/// doesn't set.
+struct RowWriter<const N: usize> {
+    builder: BatchBuilder,
+    /// Position in the row of each schema column
+    columns: Vec<Option<usize>>,
+}
+
+impl<const N: usize> RowWriter<N> {
+    fn new(schema: &Schema, fields: &[&str; N], capacity: usize) -> Self {
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| fields.iter().position(|name| name == field.name()))
+            .collect();
+
+        Self {
+            builder: BatchBuilder::new(schema, capacity),
+            columns,
+        }
+    }
+
+    fn append(&mut self, row: &[Value; N]) -> Result<(), ArrowError> {
+        for (index, position) in self.columns.iter().enumerate() {
+            self.builder.append(index, position.map(|i| &row[i]))?;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(RecordBatch, usize), ArrowError> {
+        self.builder.finish()
+    }
+}
+
+/// Columns read from a record's resource and scope, cached per group.
+#[derive(Default)]
+struct GroupColumns {
+    resource: Cached<ResourceColumns>,
+    scope: Cached<ScopeColumns>,
+}
+
+impl GroupColumns {
+    /// The columns of a record's resource and scope.
+    fn get(
+        &mut self,
+        resource: &Arc<Value>,
+        scope: &Arc<Value>,
+    ) -> (&ResourceColumns, &ScopeColumns) {
+        (
+            self.resource.get(resource, ResourceColumns::new),
+            self.scope.get(scope, ScopeColumns::new),
+        )
+    }
+}
+
+struct ResourceColumns {
+    service_name: Value,
+    service_namespace: Value,
+    service_instance_id: Value,
+    resource_attributes: Value,
+}
+
+impl ResourceColumns {
+    fn new(resource: &Value) -> Self {
+        let attributes = field(resource, "attributes");
+        Self {
+            service_name: get_attr(attributes, "service.name", "unknown".into()),
+            service_namespace: get_attr(attributes, "service.namespace", Value::Null),
+            service_instance_id: get_attr(attributes, "service.instance.id", Value::Null),
+            resource_attributes: json_or_null(attributes),
+        }
+    }
+}
+
+struct ScopeColumns {
+    scope_name: Value,
+    scope_version: Value,
+    scope_attributes: Value,
+}
+
+impl ScopeColumns {
+    fn new(scope: &Value) -> Self {
+        Self {
+            scope_name: string_or_null(field(scope, "name").clone()),
+            scope_version: string_or_null(field(scope, "version").clone()),
+            scope_attributes: json_or_null(field(scope, "attributes")),
+        }
+    }
+}
+
+/// Value computed from the last resource or scope seen. The decoders share
+/// one `Arc` between the records of a group, so a pointer comparison detects
+/// the next group.
+struct Cached<T> {
+    entry: Option<(Arc<Value>, T)>,
+}
+
+impl<T> Default for Cached<T> {
+    fn default() -> Self {
+        Self { entry: None }
+    }
+}
+
+impl<T> Cached<T> {
+    fn get(&mut self, key: &Arc<Value>, compute: fn(&Value) -> T) -> &T {
+        if !matches!(&self.entry, Some((cached, _)) if Arc::ptr_eq(cached, key)) {
+            self.entry = Some((Arc::clone(key), compute(key)));
+        }
+        &self.entry.as_ref().expect("entry was just set").1
+    }
+}
+
+/// A field of an object value; missing fields read as null, as in VRL.
+fn field<'a>(value: &'a Value, key: &str) -> &'a Value {
+    static NULL: Value = Value::Null;
+    match value {
+        Value::Object(map) => map.get(key).unwrap_or(&NULL),
+        _ => &NULL,
+    }
+}
+
+/// An OTLP nanosecond time as `nanos_to_micros` converts it, or unchanged in
+/// nanosecond mode.
+fn timestamp(time_unix_nano: i64, nanos: bool) -> Value {
+    if nanos {
+        Value::Integer(time_unix_nano)
+    } else {
+        Value::Integer(time_unix_nano / 1_000)
+    }
+}
+
+/// `string_or_null(value)` with null replaced by an empty string, as the
+/// built-in programs do for required string columns.
+fn string_or_empty(value: Value) -> Value {
+    match string_or_null(value) {
+        Value::Null => Value::Bytes(Default::default()),
+        value => value,
+    }
+}
@@ -0,0 +1,179 @@
+//! Native span mapping, equivalent to `otlp_traces.vrl`
+
+use arrow::datatypes::Schema;
+use vrl::value::Value;
+
+use super::{string_or_empty, timestamp, GroupColumns, RowWriter};
+use crate::arrow::TIME_UNIX_NANO;
+use crate::decode::{span_events_value, span_links_value, Decoded, SpanRecordParts};
+use crate::error::Result;
+use crate::transform::functions::{json_or_null, string_or_null};
+use crate::{TransformStats, TransformedBatch};
+
+/// Columns computed for each span, in row order
+const FIELDS: [&str; 26] = [
+    "timestamp",
+    "end_timestamp",
+    "duration",
+    "trace_id",
+    "span_id",
+    "parent_span_id",
+    "trace_state",
+    "service_name",
+    "service_namespace",
+    "service_instance_id",
+    "span_name",
+    "span_kind",
+    "status_code",
+    "status_message",
+    "resource_attributes",
+    "scope_name",
+    "scope_version",
+    "scope_attributes",
+    "span_attributes",
+    "events_json",
+    "links_json",
+    "dropped_attributes_count",
+    "dropped_events_count",
+    "dropped_links_count",
+    "flags",
+    TIME_UNIX_NANO,
+];
+
+/// Build a batch of `schema` from decoded spans, with timestamps in
+/// microseconds or (with `nanos`) nanoseconds.
+pub(crate) fn transform_traces(
+    decoded: Decoded<SpanRecordParts>,
+    schema: &Schema,
+    nanos: bool,
+) -> Result<TransformedBatch> {
+    let mut stats = TransformStats::decoded(&decoded);
+    let spans = spans_batch(decoded.values, schema, nanos)?;
+    stats.merge(&spans.stats);
+    Ok(TransformedBatch {
+        batch: spans.batch,
+        stats,
+    })
+}
+
+/// Build a batch of `schema` from span parts; the stats only count the
+/// output.
+pub(crate) fn spans_batch(
+    values: Vec<SpanRecordParts>,
+    schema: &Schema,
+    nanos: bool,
+) -> Result<TransformedBatch> {
+    let mut stats = TransformStats::default();
+    let mut writer = RowWriter::new(schema, &FIELDS, values.len());
+    let mut groups = GroupColumns::default();
+
+    for parts in values {
+        let row = span_row(parts, &mut groups, nanos);
+        stats.count_id(&row[3], 32);
+        stats.count_id(&row[4], 16);
+        stats.count_id(&row[5], 16);
+        writer.append(&row)?;
+    }
+
+    let (batch, coerced_nulls) = writer.finish()?;
+    stats.records_out += batch.num_rows();
+    stats.null_coerced_values += coerced_nulls;
+    Ok(TransformedBatch { batch, stats })
+}
+
+fn span_row(parts: SpanRecordParts, groups: &mut GroupColumns, nanos: bool) -> [Value; 26] {
+    let (resource, scope) = groups.get(&parts.resource, &parts.scope);
+    let duration_ns = parts.duration_ns();
+
+    [
+        timestamp(parts.start_time_unix_nano, nanos),
+        timestamp(parts.end_time_unix_nano, nanos),
+        timestamp(duration_ns, nanos),
+        string_or_null(Value::Bytes(parts.trace_id)),
+        string_or_null(Value::Bytes(parts.span_id)),
+        string_or_null(Value::Bytes(parts.parent_span_id)),
+        string_or_null(Value::Bytes(parts.trace_state)),
+        resource.service_name.clone(),
+        resource.service_namespace.clone(),
+        resource.service_instance_id.clone(),
+        string_or_empty(Value::Bytes(parts.name)),
+        Value::Integer(parts.kind),
+        Value::Integer(parts.status_code),
+        string_or_null(Value::Bytes(parts.status_message)),
+        resource.resource_attributes.clone(),
+        scope.scope_name.clone(),
+        scope.scope_version.clone(),
+        scope.scope_attributes.clone(),
+        json_or_null(&parts.attributes),
+        json_or_null(&span_events_value(parts.events)),
+        json_or_null(&span_links_value(parts.links)),
+        Value::Integer(parts.dropped_attributes_count),
+        Value::Integer(parts.dropped_events_count),
+        Value::Integer(parts.dropped_links_count),
+        Value::Integer(parts.flags),
+        Value::Integer(parts.start_time_unix_nano),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::{decode_trace_parts, decode_traces, InputFormat};
+    use crate::{apply_trace_transform, traces_schema, values_to_arrow};
+
+    /// The batch of the VRL mapping, for comparison
+    fn vrl_batch(bytes: &[u8], format: InputFormat) -> arrow::record_batch::RecordBatch {
+        let values = decode_traces(bytes, format).unwrap();
+        let transformed = apply_trace_transform(values).unwrap();
+        values_to_arrow(&transformed, &traces_schema()).unwrap()
+    }
+
+    #[test]
+    fn test_native_traces_match_vrl_protobuf() {
+        let pb = include_bytes!("../../testdata/traces_large.pb");
+        let decoded = decode_trace_parts(pb, InputFormat::Protobuf).unwrap();
+
+        let result = transform_traces(decoded, &traces_schema(), false).unwrap();
+
+        assert!(result.batch.num_rows() > 0);
+        assert_eq!(result.batch, vrl_batch(pb, InputFormat::Protobuf));
+    }
+
+    #[test]
+    fn test_native_traces_match_vrl_json() {
+        // Edge cases: events and links, a missing name, a 3-byte parent span ID
+        // and an end time before the start time
+        let json = br#"{
+            "resourceSpans": [{
+                "resource": {"attributes": [
+                    {"key": "service.name", "value": {"stringValue": "api"}},
+                    {"key": "service.instance.id", "value": {"stringValue": "i-1"}}
+                ]},
+                "scopeSpans": [{
+                    "scope": {"name": "tracer", "version": "1.0"},
+                    "spans": [
+                        {"traceId": "0af7651916cd43dd8448eb211c80319c",
+                         "spanId": "b7ad6b7169203331", "parentSpanId": "AQID",
+                         "name": "GET /", "kind": 2,
+                         "startTimeUnixNano": "1700000000000000000",
+                         "endTimeUnixNano": "1700000000250000000",
+                         "status": {"code": 2, "message": "boom"},
+                         "events": [{"timeUnixNano": "1700000000100000000", "name": "retry",
+                                     "attributes": [{"key": "n", "value": {"intValue": "2"}}]}],
+                         "links": [{"traceId": "5b8efff798038103d269b633813fc60c",
+                                    "spanId": "eee19b7ec3c1b174", "traceState": "k=v"}],
+                         "droppedAttributesCount": 3, "flags": 1},
+                        {"startTimeUnixNano": "2000", "endTimeUnixNano": "1000"}
+                    ]
+                }]
+            }]
+        }"#;
+        let decoded = decode_trace_parts(json, InputFormat::Json).unwrap();
+
+        let result = transform_traces(decoded, &traces_schema(), false).unwrap();
+
+        assert_eq!(result.batch.num_rows(), 2);
+        assert_eq!(result.batch, vrl_batch(json, InputFormat::Json));
+        assert_eq!(result.stats.invalid_ids, 1);
+    }
+}
@@ -0,0 +1,694 @@
+//! Iceberg table metadata for Parquet output
+//!
+//! Describes the Parquet files written by this crate in Iceberg terms, so a
+//! writer can create tables and commit manifests without re-reading files:
+//! - [`iceberg_schema`]: the table schema JSON, with the field IDs that
+//!   Parquet output stamps into its files
+//! - [`iceberg_partition_spec`]: the partition spec JSON of a list of
+//!   [`PartitionKey`]s
+//! - [`IcebergDataFile`]: the `data_file` entry of a written file, from the
+//!   [`ParquetFileMetadata`] returned by [`ParquetSink::close`]
+//!
+//! This module is only available when the `parquet` feature is enabled.
+//!
+//! [`ParquetSink::close`]: crate::ParquetSink::close
+
+use std::collections::{BTreeMap, HashMap};
+
+use arrow::datatypes::{DataType, Field, Fields, Schema, TimeUnit};
+use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+use serde_json::{json, Map, Value as JsonValue};
+
+use super::parquet::{add_field_ids_to_schema, ParquetFileMetadata, ParquetStatValue};
+use crate::arrow::{PartitionKey, PartitionedBatch, SignalType, MICROS_PER_DAY, MICROS_PER_HOUR};
+use crate::error::{Error, Result};
+
+/// First partition field ID, as assigned by Iceberg.
+const PARTITION_FIELD_ID_START: i32 = 1000;
+
+/// Iceberg primitive type of a field, and whether its bounds are recorded
+/// (not for fields inside lists and maps).
+type Primitives = HashMap<i32, (String, bool)>;
+
+/// Iceberg schema JSON of a table of `schema`.
+///
+/// Field IDs match the `PARQUET:field_id`s of the files written by
+/// [`write_parquet`](crate::output::write_parquet) and
+/// [`ParquetSink`](crate::ParquetSink) for the same schema: top-level
+/// columns are numbered from 1 in order, nested fields after them.
+/// Dictionary-encoded strings are `string`, unsigned integers up to 32 bits
+/// widen to `int` or `long`, and nanosecond timestamps are `timestamp_ns`
+/// (Iceberg v3).
+///
+/// # Errors
+///
+/// Returns `Error::SchemaMismatch` for a column type without an Iceberg
+/// equivalent, such as a millisecond timestamp or the `UInt64` bucket counts
+/// of [`native_nested_schema`](crate::native_nested_schema), which Iceberg
+/// readers reject.
+///
+/// # Example
+///
+/// ```ignore
+/// use otlp2records::{iceberg_schema, traces_schema};
+///
+/// let schema = iceberg_schema(&traces_schema())?;
+/// assert_eq!(schema["fields"][0]["name"], "timestamp");
+/// ```
+pub fn iceberg_schema(schema: &Schema) -> Result<JsonValue> {
+    iceberg_struct(schema).map(|(schema, _)| schema)
+}
+
+/// Default partition keys of a signal's table: `service_name`, then
+/// `metric_name` for metric signals, then the day of `timestamp`.
+pub fn iceberg_partition_keys(signal: SignalType) -> Vec<PartitionKey> {
+    match signal {
+        SignalType::Logs | SignalType::Traces | SignalType::Profiles => {
+            vec![PartitionKey::column("service_name"), PartitionKey::Day]
+        }
+        SignalType::Gauge
+        | SignalType::Sum
+        | SignalType::Histogram
+        | SignalType::ExpHistogram
+        | SignalType::Summary => vec![
+            PartitionKey::column("service_name"),
+            PartitionKey::column("metric_name"),
+            PartitionKey::Day,
+        ],
+    }
+}
+
+/// Iceberg partition spec JSON for partitioning a table of `schema` by
+/// `keys`, as [`group_batch_by`](crate::group_batch_by) does.
+///
+/// Columns become `identity` partitions, [`PartitionKey::Day`] and
+/// [`PartitionKey::Hour`] `day` and `hour` partitions of `timestamp`, named
+/// `timestamp_day` and `timestamp_hour`. Day and hour together become a
+/// single `hour` partition, as Iceberg rejects redundant time partitions.
+///
+/// # Errors
+///
+/// Returns `Error::SchemaMismatch` if a key column is missing, an identity
+/// column is not a string, integer or boolean column, or a time key has no
+/// `timestamp` column of timestamp type.
+pub fn iceberg_partition_spec(schema: &Schema, keys: &[PartitionKey]) -> Result<JsonValue> {
+    let schema = add_field_ids_to_schema(schema);
+    let fields = spec_fields(keys)
+        .into_iter()
+        .enumerate()
+        .map(|(i, spec)| {
+            let field = source_field(&schema, &spec)?;
+            Ok(json!({
+                "name": spec.name,
+                "transform": spec.transform,
+                "source-id": field_id(field)?,
+                "field-id": PARTITION_FIELD_ID_START + i as i32,
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(json!({ "spec-id": 0, "fields": fields }))
+}
+
+/// The Iceberg `data_file` entry of a written Parquet file.
+///
+/// Column metrics are keyed by Iceberg field ID, see [`iceberg_schema`].
+/// Bounds use Iceberg's single-value binary serialization and are only
+/// recorded for primitive columns outside lists and maps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcebergDataFile {
+    /// Location of the file, as given
+    pub file_path: String,
+    /// Partition values by partition field name; empty for unpartitioned
+    /// tables
+    pub partition: Map<String, JsonValue>,
+    /// Rows in the file
+    pub record_count: u64,
+    /// Size of the file in bytes
+    pub file_size_in_bytes: u64,
+    /// Compressed size of each leaf column
+    pub column_sizes: BTreeMap<i32, u64>,
+    /// Values of each leaf column, nulls included
+    pub value_counts: BTreeMap<i32, u64>,
+    /// Null values of each leaf column
+    pub null_value_counts: BTreeMap<i32, u64>,
+    /// Smallest value of each primitive column
+    pub lower_bounds: BTreeMap<i32, Vec<u8>>,
+    /// Largest value of each primitive column
+    pub upper_bounds: BTreeMap<i32, Vec<u8>>,
+}
+
+impl IcebergDataFile {
+    /// Describe the file at `file_path`, written with `schema`, from the
+    /// metadata returned by [`ParquetSink::close`](crate::ParquetSink::close).
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SchemaMismatch` if `schema` has no Iceberg schema, see
+    /// [`iceberg_schema`].
+    pub fn try_new(
+        file_path: impl Into<String>,
+        schema: &Schema,
+        metadata: &ParquetFileMetadata,
+    ) -> Result<Self> {
+        let (_, primitives) = iceberg_struct(schema)?;
+        let mut file = IcebergDataFile {
+            file_path: file_path.into(),
+            partition: Map::new(),
+            record_count: metadata.num_rows,
+            file_size_in_bytes: metadata.file_size,
+            column_sizes: BTreeMap::new(),
+            value_counts: BTreeMap::new(),
+            null_value_counts: BTreeMap::new(),
+            lower_bounds: BTreeMap::new(),
+            upper_bounds: BTreeMap::new(),
+        };
+
+        for column in &metadata.columns {
+            let Some(id) = column.field_id else {
+                continue;
+            };
+            file.column_sizes.insert(id, column.compressed_size);
+            file.value_counts.insert(id, column.num_values);
+            if let Some(nulls) = column.null_count {
+                file.null_value_counts.insert(id, nulls);
+            }
+            let Some((iceberg_type, true)) = primitives.get(&id) else {
+                continue;
+            };
+            let lower = column
+                .min
+                .as_ref()
+                .and_then(|v| bound_bytes(iceberg_type, v));
+            let upper = column
+                .max
+                .as_ref()
+                .and_then(|v| bound_bytes(iceberg_type, v));
+            if let (Some(lower), Some(upper)) = (lower, upper) {
+                file.lower_bounds.insert(id, lower);
+                file.upper_bounds.insert(id, upper);
+            }
+        }
+        Ok(file)
+    }
+
+    /// Set the partition values of a file holding `partition`, one of the
+    /// batches [`group_batch_by`](crate::group_batch_by) returned for `keys`.
+    ///
+    /// Values follow [`iceberg_partition_spec`]: identity values keep the
+    /// column's type, `day` values are days and `hour` values hours since
+    /// the Unix epoch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SchemaMismatch` if the batch lacks a key column or
+    /// the value of a key.
+    pub fn with_partition(
+        mut self,
+        keys: &[PartitionKey],
+        partition: &PartitionedBatch,
+    ) -> Result<Self> {
+        let schema = partition.batch.schema();
+        for spec in spec_fields(keys) {
+            let field = source_field(&schema, &spec)?;
+            let value = match spec.transform {
+                "day" => json!(partition.min_timestamp_micros.div_euclid(MICROS_PER_DAY)),
+                "hour" => json!(partition.min_timestamp_micros.div_euclid(MICROS_PER_HOUR)),
+                _ => {
+                    let value = partition
+                        .partition_values
+                        .iter()
+                        .find(|value| *value.name == *spec.source)
+                        .ok_or_else(|| {
+                            Error::SchemaMismatch(format!(
+                                "partition has no value for '{}'",
+                                spec.source
+                            ))
+                        })?;
+                    identity_value(field.data_type(), value.value.as_deref())
+                }
+            };
+            self.partition.insert(spec.name, value);
+        }
+        Ok(self)
+    }
+}
+
+/// A field of a partition spec.
+struct SpecField {
+    name: String,
+    transform: &'static str,
+    source: String,
+}
+
+fn spec_fields(keys: &[PartitionKey]) -> Vec<SpecField> {
+    let hour = keys.contains(&PartitionKey::Hour);
+    keys.iter()
+        .filter_map(|key| match key {
+            PartitionKey::Column(name) => Some(SpecField {
+                name: name.clone(),
+                transform: "identity",
+                source: name.clone(),
+            }),
+            PartitionKey::Day if hour => None,
+            PartitionKey::Day => Some(SpecField {
+                name: "timestamp_day".to_string(),
+                transform: "day",
+                source: "timestamp".to_string(),
+            }),
+            PartitionKey::Hour => Some(SpecField {
+                name: "timestamp_hour".to_string(),
+                transform: "hour",
+                source: "timestamp".to_string(),
+            }),
+        })
+        .collect()
+}
+
+/// The source column of a partition field, checked against its transform.
+fn source_field<'a>(schema: &'a Schema, spec: &SpecField) -> Result<&'a Field> {
+    let field = schema.field_with_name(&spec.source).map_err(|_| {
+        Error::SchemaMismatch(format!(
+            "partition column '{}' not found in schema",
+            spec.source
+        ))
+    })?;
+    let supported = match spec.transform {
+        "identity" => matches!(
+            string_value_type(field.data_type()),
+            DataType::Utf8
+                | DataType::LargeUtf8
+                | DataType::Utf8View
+                | DataType::Boolean
+                | DataType::Int8
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::UInt8
+                | DataType::UInt16
+                | DataType::UInt32
+        ),
+        _ => matches!(
+            field.data_type(),
+            DataType::Timestamp(TimeUnit::Microsecond | TimeUnit::Nanosecond, _)
+        ),
+    };
+    if !supported {
+        return Err(Error::SchemaMismatch(format!(
+            "cannot use {} column '{}' for an Iceberg {} partition",
+            field.data_type(),
+            spec.source,
+            spec.transform
+        )));
+    }
+    Ok(field)
+}
+
+/// Value type of a dictionary, or the type itself.
+fn string_value_type(data_type: &DataType) -> &DataType {
+    match data_type {
+        DataType::Dictionary(_, value) => value,
+        other => other,
+    }
+}
+
+/// Identity partition value of the formatted value of a column.
+fn identity_value(data_type: &DataType, value: Option<&str>) -> JsonValue {
+    let Some(value) = value else {
+        return JsonValue::Null;
+    };
+    let typed = match string_value_type(data_type) {
+        DataType::Boolean => value.parse::<bool>().ok().map(JsonValue::from),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => None,
+        _ => value.parse::<i64>().ok().map(JsonValue::from),
+    };
+    typed.unwrap_or_else(|| JsonValue::from(value))
+}
+
+fn iceberg_struct(schema: &Schema) -> Result<(JsonValue, Primitives)> {
+    let schema = add_field_ids_to_schema(schema);
+    let mut primitives = Primitives::new();
+    let fields = struct_fields(schema.fields(), false, &mut primitives)?;
+    let schema = json!({
+        "type": "struct",
+        "schema-id": 0,
+        "fields": fields,
+    });
+    Ok((schema, primitives))
+}
+
+fn struct_fields(
+    fields: &Fields,
+    repeated: bool,
+    primitives: &mut Primitives,
+) -> Result<Vec<JsonValue>> {
+    fields
+        .iter()
+        .map(|field| {
+            let (id, field_type) = nested_type(field, repeated, primitives)?;
+            Ok(json!({
+                "id": id,
+                "name": field.name(),
+                "required": !field.is_nullable(),
+                "type": field_type,
+            }))
+        })
+        .collect()
+}
+
+/// Field ID and Iceberg type of a field, recording primitive types.
+fn nested_type(
+    field: &Field,
+    repeated: bool,
+    primitives: &mut Primitives,
+) -> Result<(i32, JsonValue)> {
+    let id = field_id(field)?;
+    let field_type = iceberg_type(field, repeated, primitives)?;
+    if let JsonValue::String(primitive) = &field_type {
+        primitives.insert(id, (primitive.clone(), !repeated));
+    }
+    Ok((id, field_type))
+}
+
+fn iceberg_type(field: &Field, repeated: bool, primitives: &mut Primitives) -> Result<JsonValue> {
+    let primitive = match string_value_type(field.data_type()) {
+        DataType::Boolean => "boolean",
+        DataType::Int8 | DataType::Int16 | DataType::Int32 => "int",
+        DataType::UInt8 | DataType::UInt16 => "int",
+        DataType::Int64 | DataType::UInt32 => "long",
+        // Iceberg has no duration type; Parquet stores durations as plain int64
+        DataType::Duration(_) => "long",
+        DataType::Float32 => "float",
+        DataType::Float64 => "double",
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => "string",
+        DataType::Binary | DataType::LargeBinary | DataType::BinaryView => "binary",
+        DataType::Date32 => "date",
+        DataType::Timestamp(TimeUnit::Microsecond, None) => "timestamp",
+        DataType::Timestamp(TimeUnit::Microsecond, Some(_)) => "timestamptz",
+        DataType::Timestamp(TimeUnit::Nanosecond, None) => "timestamp_ns",
+        DataType::Timestamp(TimeUnit::Nanosecond, Some(_)) => "timestamptz_ns",
+        DataType::FixedSizeBinary(len) => return Ok(json!(format!("fixed[{len}]"))),
+        DataType::Decimal128(precision, scale) => {
+            return Ok(json!(format!("decimal({precision}, {scale})")))
+        }
+        DataType::List(element) | DataType::LargeList(element) => {
+            let (id, element_type) = nested_type(element, true, primitives)?;
+            return Ok(json!({
+                "type": "list",
+                "element-id": id,
+                "element": element_type,
+                "element-required": !element.is_nullable(),
+            }));
+        }
+        DataType::Map(entries, _) => {
+            let DataType::Struct(key_value) = entries.data_type() else {
+                return Err(unsupported(field));
+            };
+            let [key, value] = key_value.iter().collect::<Vec<_>>()[..] else {
+                return Err(unsupported(field));
+            };
+            let (key_id, key_type) = nested_type(key, true, primitives)?;
+            let (value_id, value_type) = nested_type(value, true, primitives)?;
+            return Ok(json!({
+                "type": "map",
+                "key-id": key_id,
+                "key": key_type,
+                "value-id": value_id,
+                "value": value_type,
+                "value-required": !value.is_nullable(),
+            }));
+        }
+        DataType::Struct(children) => {
+            let fields = struct_fields(children, repeated, primitives)?;
+            return Ok(json!({ "type": "struct", "fields": fields }));
+        }
+        _ => return Err(unsupported(field)),
+    };
+    Ok(json!(primitive))
+}
+
+fn unsupported(field: &Field) -> Error {
+    Error::SchemaMismatch(format!(
+        "column '{}' has type {}, which has no Iceberg equivalent",
+        field.name(),
+        field.data_type()
+    ))
+}
+
+fn field_id(field: &Field) -> Result<i32> {
+    field
+        .metadata()
+        .get(PARQUET_FIELD_ID_META_KEY)
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| Error::SchemaMismatch(format!("field '{}' has no field ID", field.name())))
+}
+
+/// Iceberg single-value serialization of a Parquet statistic.
+fn bound_bytes(iceberg_type: &str, value: &ParquetStatValue) -> Option<Vec<u8>> {
+    use ParquetStatValue as V;
+
+    match (iceberg_type, value) {
+        ("boolean", V::Boolean(v)) => Some(vec![u8::from(*v)]),
+        ("int" | "date", V::Int(v)) => i32::try_from(*v).ok().map(|v| v.to_le_bytes().to_vec()),
+        ("int", V::UInt(v)) => i32::try_from(*v).ok().map(|v| v.to_le_bytes().to_vec()),
+        ("long" | "timestamp" | "timestamptz" | "timestamp_ns" | "timestamptz_ns", V::Int(v)) => {
+            Some(v.to_le_bytes().to_vec())
+        }
+        // Unsigned values above i64::MAX would wrap when read as long
+        ("long", V::UInt(v)) => i64::try_from(*v).ok().map(|v| v.to_le_bytes().to_vec()),
+        ("float", V::Float(v)) => Some((*v as f32).to_le_bytes().to_vec()),
+        ("double", V::Float(v)) => Some(v.to_le_bytes().to_vec()),
+        ("string" | "binary", V::Bytes(v)) => Some(v.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arrow::{
+        gauge_schema, histogram_schema, logs_schema, native_nested_schema, span_events_type,
+        traces_schema,
+    };
+    use crate::{group_batch_by, ParquetSink};
+    use arrow::array::{Int64Array, RecordBatch, StringArray, TimestampMicrosecondArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_iceberg_schema_top_level_fields() {
+        let schema = iceberg_schema(&logs_schema()).unwrap();
+        assert_eq!(schema["type"], "struct");
+        let fields = schema["fields"].as_array().unwrap();
+        assert_eq!(fields.len(), logs_schema().fields().len());
+        assert_eq!(
+            fields[0],
+            json!({"id": 1, "name": "timestamp", "required": true, "type": "timestamp"})
+        );
+        let service = fields.iter().find(|f| f["name"] == "service_name").unwrap();
+        assert_eq!(service["type"], "string");
+        assert_eq!(service["required"], true);
+        let severity = fields
+            .iter()
+            .find(|f| f["name"] == "severity_number")
+            .unwrap();
+        assert_eq!(severity["type"], "int");
+    }
+
+    #[test]
+    fn test_iceberg_schema_nested_field_ids() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("events", span_events_type(), true),
+            Field::new("labels", crate::arrow::attributes_map_type(), true),
+        ]);
+        let iceberg = iceberg_schema(&schema).unwrap();
+        let events = &iceberg["fields"][1]["type"];
+        assert_eq!(events["type"], "list");
+        assert_eq!(events["element-id"], 4);
+        let element = &events["element"]["fields"];
+        assert_eq!(element[0]["id"], 5);
+        assert_eq!(element[0]["type"], "long");
+        let attributes = &element[2]["type"];
+        assert_eq!(attributes["type"], "map");
+        assert_eq!(attributes["key-id"], 8);
+        assert_eq!(attributes["value-id"], 9);
+        let labels = &iceberg["fields"][2]["type"];
+        assert_eq!(labels["key-id"], 10);
+        assert_eq!(labels["value-id"], 11);
+        assert_eq!(labels["key"], "string");
+        assert_eq!(labels["value-required"], false);
+
+        // The same IDs as the Parquet schema
+        let stamped = add_field_ids_to_schema(&schema);
+        let DataType::List(element) = stamped.field(1).data_type() else {
+            panic!("not a list");
+        };
+        assert_eq!(field_id(element).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_iceberg_schema_rejects_unsupported_types() {
+        let schema = Schema::new(vec![Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            false,
+        )]);
+        let err = iceberg_schema(&schema).unwrap_err();
+        assert!(err.to_string().contains("'timestamp'"), "{err}");
+
+        // Iceberg has no unsigned 64-bit type
+        let err = iceberg_schema(&native_nested_schema(&histogram_schema())).unwrap_err();
+        assert!(matches!(err, Error::SchemaMismatch(_)));
+        assert!(err.to_string().contains("UInt64"), "{err}");
+    }
+
+    #[test]
+    fn test_iceberg_partition_spec() {
+        let schema = traces_schema();
+        let keys = iceberg_partition_keys(SignalType::Traces);
+        let spec = iceberg_partition_spec(&schema, &keys).unwrap();
+        let service_id = schema.index_of("service_name").unwrap() + 1;
+        assert_eq!(
+            spec,
+            json!({
+                "spec-id": 0,
+                "fields": [
+                    {"name": "service_name", "transform": "identity", "source-id": service_id, "field-id": 1000},
+                    {"name": "timestamp_day", "transform": "day", "source-id": 1, "field-id": 1001},
+                ]
+            })
+        );
+
+        // Metric tables also partition by metric name
+        let keys = iceberg_partition_keys(SignalType::Gauge);
+        let names: Vec<&str> = keys.iter().map(PartitionKey::name).collect();
+        assert_eq!(names, ["service_name", "metric_name", "date"]);
+        assert!(iceberg_partition_spec(&gauge_schema(), &keys).is_ok());
+
+        let keys = [PartitionKey::Day, PartitionKey::Hour];
+        let spec = iceberg_partition_spec(&schema, &keys).unwrap();
+        let fields = spec["fields"].as_array().unwrap();
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0]["transform"], "hour");
+
+        let err = iceberg_partition_spec(&schema, &[PartitionKey::column("missing")]).unwrap_err();
+        assert!(matches!(err, Error::SchemaMismatch(_)));
+        // JSON attribute columns are strings, native maps are not
+        assert!(
+            iceberg_partition_spec(&schema, &[PartitionKey::column("span_attributes")]).is_ok()
+        );
+        let native = native_nested_schema(&schema);
+        let err = iceberg_partition_spec(&native, &[PartitionKey::column("span_attributes")])
+            .unwrap_err();
+        assert!(err.to_string().contains("identity"), "{err}");
+    }
+
+    fn partition_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                false,
+            ),
+            Field::new("service_name", DataType::Utf8, true),
+            Field::new("status", DataType::Int64, true),
+        ]));
+        // 2024-01-02T13:00:00Z and 2024-01-02T13:30:00Z
+        let ts = 1_704_200_400_000_000;
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(TimestampMicrosecondArray::from(vec![
+                    ts,
+                    ts + 1_800_000_000,
+                ])),
+                Arc::new(StringArray::from(vec![Some("api"), Some("api")])),
+                Arc::new(Int64Array::from(vec![Some(200), Some(500)])),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_iceberg_data_file() {
+        let batch = partition_batch();
+        let mut sink = ParquetSink::try_new(&batch.schema(), Vec::new()).unwrap();
+        sink.write(&batch).unwrap();
+        let metadata = sink.close().unwrap();
+
+        let file =
+            IcebergDataFile::try_new("s3://bucket/data/a.parquet", &batch.schema(), &metadata)
+                .unwrap();
+        assert_eq!(file.file_path, "s3://bucket/data/a.parquet");
+        assert!(file.partition.is_empty());
+        assert_eq!(file.record_count, 2);
+        assert_eq!(file.file_size_in_bytes, metadata.file_size);
+        assert_eq!(file.value_counts, BTreeMap::from([(1, 2), (2, 2), (3, 2)]));
+        assert_eq!(
+            file.null_value_counts,
+            BTreeMap::from([(1, 0), (2, 0), (3, 0)])
+        );
+        assert_eq!(file.column_sizes.len(), 3);
+        assert_eq!(
+            file.lower_bounds[&1],
+            1_704_200_400_000_000_i64.to_le_bytes().to_vec()
+        );
+        assert_eq!(file.lower_bounds[&2], b"api".to_vec());
+        assert_eq!(file.upper_bounds[&3], 500_i64.to_le_bytes().to_vec());
+    }
+
+    #[test]
+    fn test_iceberg_data_file_partition() {
+        let batch = partition_batch();
+        let keys = [
+            PartitionKey::column("service_name"),
+            PartitionKey::column("status"),
+            PartitionKey::Day,
+        ];
+        let partitions = group_batch_by(batch.clone(), &keys);
+        assert_eq!(partitions.batches.len(), 2);
+        let metadata = ParquetFileMetadata {
+            num_rows: 1,
+            num_row_groups: 1,
+            file_size: 100,
+            columns: Vec::new(),
+        };
+        let file = IcebergDataFile::try_new("a.parquet", &batch.schema(), &metadata)
+            .unwrap()
+            .with_partition(&keys, &partitions.batches[0])
+            .unwrap();
+        let day = 1_704_200_400_000_000_i64 / MICROS_PER_DAY;
+        assert_eq!(
+            JsonValue::Object(file.partition),
+            json!({"service_name": "api", "status": 200, "timestamp_day": day})
+        );
+
+        let keys = [PartitionKey::Day, PartitionKey::Hour];
+        let partitions = group_batch_by(batch.clone(), &keys);
+        let file = IcebergDataFile::try_new("a.parquet", &batch.schema(), &metadata)
+            .unwrap()
+            .with_partition(&keys, &partitions.batches[0])
+            .unwrap();
+        let hour = 1_704_200_400_000_000_i64 / MICROS_PER_HOUR;
+        assert_eq!(
+            JsonValue::Object(file.partition),
+            json!({"timestamp_hour": hour})
+        );
+    }
+
+    #[test]
+    fn test_bound_bytes() {
+        use ParquetStatValue as V;
+        assert_eq!(bound_bytes("int", &V::Int(-1)), Some(vec![0xff; 4]));
+        assert_eq!(bound_bytes("long", &V::UInt(u64::MAX)), None);
+        assert_eq!(
+            bound_bytes("long", &V::UInt(7)),
+            Some(7_i64.to_le_bytes().to_vec())
+        );
+        assert_eq!(
+            bound_bytes("float", &V::Float(1.5)),
+            Some(1.5_f32.to_le_bytes().to_vec())
+        );
+        assert_eq!(bound_bytes("boolean", &V::Boolean(true)), Some(vec![1]));
+        assert_eq!(bound_bytes("string", &V::Int(1)), None);
+    }
+}
@@ -3,7 +3,11 @@
 //! Serializes Arrow RecordBatches to newline-delimited JSON (NDJSON).
 //! Each row becomes a JSON object with field names from the schema.
 
+use std::sync::Arc;
+
 use arrow::array::RecordBatch;
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Schema};
 use arrow::json::LineDelimitedWriter;
 
 use crate::error::Error;
@@ -11,7 +15,9 @@ use crate::error::Error;
 /// Serialize a RecordBatch to NDJSON format (newline-delimited JSON)
 ///
 /// Each row is serialized as a JSON object with field names from the schema.
-/// Each row ends with a newline character ('\n').
+/// Each row ends with a newline character ('\n'). `Duration` columns are
+/// written as integers in their unit (microseconds by default) rather than
+/// ISO 8601 strings.
 ///
 /// # Arguments
 ///
@@ -35,20 +41,44 @@ use crate::error::Error;
 /// // {"field1": "value2", "field2": 43}
 /// ```
 pub fn to_json(batch: &RecordBatch) -> Result<Vec<u8>, Error> {
+    let batch = durations_as_integers(batch)?;
     let mut buffer = Vec::new();
     {
         let mut writer = LineDelimitedWriter::new(&mut buffer);
-        writer.write(batch)?;
+        writer.write(&batch)?;
         writer.finish()?;
     }
     Ok(buffer)
 }
 
+/// Cast the `Duration` columns of `batch` to Int64.
+fn durations_as_integers(batch: &RecordBatch) -> Result<RecordBatch, Error> {
+    let schema = batch.schema();
+    let is_duration = |field: &Field| matches!(field.data_type(), DataType::Duration(_));
+    if !schema.fields().iter().any(|field| is_duration(field)) {
+        return Ok(batch.clone());
+    }
+
+    let mut fields = Vec::with_capacity(schema.fields().len());
+    let mut columns = Vec::with_capacity(batch.num_columns());
+    for (field, column) in schema.fields().iter().zip(batch.columns()) {
+        if is_duration(field) {
+            columns.push(cast(column, &DataType::Int64)?);
+            fields.push(field.as_ref().clone().with_data_type(DataType::Int64));
+        } else {
+            columns.push(column.clone());
+            fields.push(field.as_ref().clone());
+        }
+    }
+    let schema = Schema::new_with_metadata(fields, schema.metadata().clone());
+    Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow::array::{Int64Array, StringArray};
-    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::array::{DurationMicrosecondArray, Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
     use std::sync::Arc;
 
     fn create_test_batch() -> RecordBatch {
@@ -137,4 +167,24 @@ mod tests {
         assert_eq!(obj3["name"], "gamma");
         assert!(obj3["value"].is_null());
     }
+
+    #[test]
+    fn test_to_json_durations_are_integers() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "duration",
+            DataType::Duration(TimeUnit::Microsecond),
+            true,
+        )]));
+        let durations = Arc::new(DurationMicrosecondArray::from(vec![Some(10_000_000), None]));
+
+        let batch = RecordBatch::try_new(schema, vec![durations]).unwrap();
+        let json_str = String::from_utf8(to_json(&batch).unwrap()).unwrap();
+
+        let rows: Vec<serde_json::Value> = json_str
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(rows[0]["duration"], 10_000_000);
+        assert!(rows[1]["duration"].is_null());
+    }
 }
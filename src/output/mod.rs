@@ -4,10 +4,14 @@
 //! - JSON (NDJSON - newline-delimited JSON)
 //! - Arrow IPC (streaming format for cross-language interop)
 //! - Parquet (optional, behind feature flag)
+//! - Iceberg schema, partition spec and data-file metadata for Parquet files
+//!   (with Parquet)
 
 mod ipc;
 mod json;
 
+#[cfg(feature = "parquet")]
+mod iceberg;
 #[cfg(feature = "parquet")]
 mod parquet;
 
@@ -15,7 +19,14 @@ pub use ipc::to_ipc;
 pub use json::to_json;
 
 #[cfg(feature = "parquet")]
-pub use parquet::{to_parquet, to_parquet_bytes, write_parquet};
+pub use iceberg::{
+    iceberg_partition_keys, iceberg_partition_spec, iceberg_schema, IcebergDataFile,
+};
+#[cfg(feature = "parquet")]
+pub use parquet::{
+    parquet_properties_for, to_parquet, to_parquet_bytes, write_parquet, ParquetColumnStats,
+    ParquetFileMetadata, ParquetSink, ParquetSinkBuilder, ParquetStatValue,
+};
 
 // Re-export WriterProperties for callers who want to customize parquet output
 #[cfg(feature = "parquet")]
@@ -3,38 +3,139 @@
 //! Serializes Arrow RecordBatches to Parquet format.
 //! This module is only available when the `parquet` feature is enabled.
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::io::{Cursor, Write};
 use std::sync::Arc;
 
 use arrow::array::RecordBatch;
-use arrow::datatypes::{Field, Schema};
+use arrow::compute::SortOptions;
+use arrow::compute::{concat_batches, lexsort_to_indices, take_record_batch, SortColumn};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::error::ArrowError;
 use bytes::Bytes;
+use parquet::arrow::ArrowSchemaConverter;
 use parquet::arrow::ArrowWriter;
 use parquet::arrow::PARQUET_FIELD_ID_META_KEY;
-use parquet::basic::Compression;
-use parquet::file::properties::WriterProperties;
+use parquet::basic::{Compression, SortOrder};
+use parquet::errors::ParquetError;
+use parquet::file::metadata::{ParquetMetaData, SortingColumn};
+use parquet::file::properties::{
+    EnabledStatistics, WriterProperties, WriterPropertiesBuilder, DEFAULT_MAX_ROW_GROUP_SIZE,
+};
+use parquet::file::statistics::Statistics;
+use parquet::schema::types::{ColumnPath, SchemaDescriptor};
 
+use crate::arrow::SignalType;
 use crate::error::Error;
 
+/// Columns with few distinct values besides the `dict_string` ones, which get
+/// dictionary encoding in the presets.
+const LOW_CARDINALITY_COLUMNS: &[&str] = &[
+    "service_instance_id",
+    "severity_number",
+    "span_kind",
+    "status_code",
+    "metric_description",
+    "aggregation_temporality",
+    "sample_type",
+    "sample_unit",
+    "period_type",
+    "period_unit",
+];
+
+/// Columns looked up by exact value, which get bloom filters in the presets.
+const BLOOM_FILTER_COLUMNS: &[&str] = &["trace_id", "span_id"];
+
+/// Columns range queries filter on, which get page-level statistics in the
+/// presets.
+const PAGE_STATISTICS_COLUMNS: &[&str] = &["timestamp", "time_unix_nano"];
+
+/// Row order of the presets for each signal.
+fn preset_sort_columns(signal: SignalType) -> &'static [&'static str] {
+    match signal {
+        SignalType::Logs | SignalType::Traces | SignalType::Profiles => {
+            &["service_name", "timestamp"]
+        }
+        SignalType::Gauge
+        | SignalType::Sum
+        | SignalType::Histogram
+        | SignalType::ExpHistogram
+        | SignalType::Summary => &["service_name", "metric_name", "timestamp"],
+    }
+}
+
 /// Add PARQUET:field_id metadata to each field in the schema.
-/// Field IDs are assigned sequentially starting from 1 (Iceberg convention).
-fn add_field_ids_to_schema(schema: &Schema) -> Schema {
+///
+/// Top-level fields get IDs 1 to n in order (Iceberg convention). List
+/// elements, map keys and values, and struct children follow from n + 1,
+/// assigned like Iceberg does: all children of a type first, then the
+/// children's own nested fields.
+pub(crate) fn add_field_ids_to_schema(schema: &Schema) -> Schema {
+    let mut next_id = schema.fields().len() as i32 + 1;
     let fields_with_ids: Vec<Arc<Field>> = schema
         .fields()
         .iter()
         .enumerate()
-        .map(|(idx, field)| {
-            let field_id = (idx + 1) as i32;
-            let mut metadata: HashMap<String, String> = field.metadata().clone();
-            metadata.insert(PARQUET_FIELD_ID_META_KEY.to_string(), field_id.to_string());
-            Arc::new(field.as_ref().clone().with_metadata(metadata))
-        })
+        .map(|(idx, field)| Arc::new(with_field_id(field, (idx + 1) as i32, &mut next_id)))
         .collect();
 
     Schema::new_with_metadata(fields_with_ids, schema.metadata().clone())
 }
 
+fn with_field_id(field: &Field, field_id: i32, next_id: &mut i32) -> Field {
+    let mut metadata: HashMap<String, String> = field.metadata().clone();
+    metadata.insert(PARQUET_FIELD_ID_META_KEY.to_string(), field_id.to_string());
+    field
+        .clone()
+        .with_data_type(with_nested_field_ids(field.data_type(), next_id))
+        .with_metadata(metadata)
+}
+
+fn with_nested_field_ids(data_type: &DataType, next_id: &mut i32) -> DataType {
+    let mut reserve = |count: usize| {
+        let first = *next_id;
+        *next_id += count as i32;
+        first
+    };
+    match data_type {
+        DataType::List(element) => {
+            let id = reserve(1);
+            DataType::List(Arc::new(with_field_id(element, id, next_id)))
+        }
+        DataType::LargeList(element) => {
+            let id = reserve(1);
+            DataType::LargeList(Arc::new(with_field_id(element, id, next_id)))
+        }
+        DataType::Map(entries, sorted) => {
+            let DataType::Struct(key_value) = entries.data_type() else {
+                return data_type.clone();
+            };
+            let first = reserve(key_value.len());
+            let key_value: Vec<Field> = key_value
+                .iter()
+                .enumerate()
+                .map(|(i, field)| with_field_id(field, first + i as i32, next_id))
+                .collect();
+            let entries = entries
+                .as_ref()
+                .clone()
+                .with_data_type(DataType::Struct(key_value.into()));
+            DataType::Map(Arc::new(entries), *sorted)
+        }
+        DataType::Struct(children) => {
+            let first = reserve(children.len());
+            let children: Vec<Field> = children
+                .iter()
+                .enumerate()
+                .map(|(i, field)| with_field_id(field, first + i as i32, next_id))
+                .collect();
+            DataType::Struct(children.into())
+        }
+        _ => data_type.clone(),
+    }
+}
+
 /// Write a RecordBatch to Parquet format using a streaming writer
 ///
 /// This is the core streaming API that writes directly to any `std::io::Write`
@@ -45,7 +146,10 @@ fn add_field_ids_to_schema(schema: &Schema) -> Schema {
 ///
 /// * `batch` - The RecordBatch to serialize
 /// * `writer` - Any type implementing `std::io::Write + Send`
-/// * `props` - Optional writer properties (uses uncompressed defaults if None)
+/// * `props` - Optional writer properties. If None, uses the compression,
+///   dictionary, bloom filter and statistics settings of the
+///   [`parquet_properties_for`] presets and keeps the batch's row order.
+///   Properties with sorting columns sort the rows.
 ///
 /// # Returns
 ///
@@ -68,34 +172,20 @@ pub fn write_parquet<W: Write + Send>(
     writer: W,
     props: Option<WriterProperties>,
 ) -> Result<(), Error> {
-    let props = props.unwrap_or_else(|| {
-        WriterProperties::builder()
-            .set_compression(Compression::UNCOMPRESSED)
-            .build()
-    });
-
-    // Add field IDs to schema for Iceberg compatibility
-    let schema_with_ids = Arc::new(add_field_ids_to_schema(batch.schema().as_ref()));
-    let batch_with_ids = RecordBatch::try_new(schema_with_ids.clone(), batch.columns().to_vec())
-        .map_err(Error::Arrow)?;
-
-    let mut arrow_writer = ArrowWriter::try_new(writer, schema_with_ids, Some(props))
-        .map_err(|e| Error::Arrow(arrow::error::ArrowError::ExternalError(Box::new(e))))?;
-
-    arrow_writer
-        .write(&batch_with_ids)
-        .map_err(|e| Error::Arrow(arrow::error::ArrowError::ExternalError(Box::new(e))))?;
-
-    arrow_writer
-        .close()
-        .map_err(|e| Error::Arrow(arrow::error::ArrowError::ExternalError(Box::new(e))))?;
-
+    let schema = batch.schema();
+    let props = props.unwrap_or_else(|| preset_properties(&schema, batch.num_rows()));
+    let mut sink = ParquetSinkBuilder::new()
+        .with_properties(props)
+        .build(&schema, writer)?;
+    sink.write(batch)?;
+    sink.close()?;
     Ok(())
 }
 
 /// Serialize a RecordBatch to Parquet format
 ///
-/// Creates a single Parquet file in memory (uncompressed by default).
+/// Creates a single Parquet file in memory, with the column settings of the
+/// [`parquet_properties_for`] presets and the rows in batch order.
 /// The resulting bytes can be written to a file or sent over the network.
 ///
 /// This is a convenience wrapper around [`write_parquet`] that writes to an
@@ -143,10 +233,605 @@ pub fn to_parquet_bytes(batch: &RecordBatch) -> Result<Bytes, Error> {
     Ok(Bytes::from(vec))
 }
 
+/// Writer properties tuned for the output of `signal`.
+///
+/// - Snappy compression
+/// - Dictionary encoding only on low-cardinality columns, such as the
+///   `dict_string` columns, `severity_number` or `span_kind`
+/// - Bloom filters on `trace_id` and `span_id`, sized for row groups of
+///   the default maximum row count
+/// - Page-level statistics on `timestamp`, chunk-level on other columns
+/// - Sorting columns `service_name, timestamp`, with `metric_name` before
+///   `timestamp` for metrics
+///
+/// The column settings match columns by name, and the sorting columns
+/// refer to the built-in schema of the signal. Passing these properties to
+/// [`write_parquet`] or a [`ParquetSink`] sorts each row group as the
+/// sorting columns say. Without properties, [`write_parquet`],
+/// [`to_parquet`] and the sink use the column settings alone and keep the
+/// row order.
+pub fn parquet_properties_for(signal: SignalType) -> WriterProperties {
+    let schema = signal.schema();
+    let mut props = tuned_properties(&schema, DEFAULT_MAX_ROW_GROUP_SIZE);
+
+    // Sort on the longest prefix of the signal's sort columns in the schema
+    if let Ok(descriptor) = ArrowSchemaConverter::new().convert(&schema) {
+        let sorting: Vec<SortingColumn> = preset_sort_columns(signal)
+            .iter()
+            .map_while(|name| leaf_index(&schema, &descriptor, name))
+            .map(|leaf| SortingColumn {
+                column_idx: leaf as i32,
+                descending: false,
+                nulls_first: true,
+            })
+            .collect();
+        if !sorting.is_empty() {
+            props = props.set_sorting_columns(Some(sorting));
+        }
+    }
+
+    props.build()
+}
+
+/// Column settings of the presets for a batch of `schema`, without sorting.
+fn preset_properties(schema: &Schema, rows_per_row_group: usize) -> WriterProperties {
+    tuned_properties(schema, rows_per_row_group).build()
+}
+
+fn tuned_properties(schema: &Schema, rows_per_row_group: usize) -> WriterPropertiesBuilder {
+    let mut props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .set_dictionary_enabled(false)
+        .set_statistics_enabled(EnabledStatistics::Chunk);
+
+    for field in schema.fields() {
+        let name = field.name().as_str();
+        if matches!(field.data_type(), DataType::Dictionary(_, _))
+            || LOW_CARDINALITY_COLUMNS.contains(&name)
+        {
+            props = props.set_column_dictionary_enabled(ColumnPath::from(name), true);
+        }
+        if BLOOM_FILTER_COLUMNS.contains(&name) {
+            props = props
+                .set_column_bloom_filter_enabled(ColumnPath::from(name), true)
+                .set_column_bloom_filter_ndv(
+                    ColumnPath::from(name),
+                    rows_per_row_group.max(1) as u64,
+                );
+        }
+        if PAGE_STATISTICS_COLUMNS.contains(&name) {
+            props = props
+                .set_column_statistics_enabled(ColumnPath::from(name), EnabledStatistics::Page);
+        }
+    }
+
+    props
+}
+
+/// Index of the Parquet leaf column of the top-level primitive column `name`.
+fn leaf_index(schema: &Schema, descriptor: &SchemaDescriptor, name: &str) -> Option<usize> {
+    let field = schema.field_with_name(name).ok()?;
+    if field.data_type().is_nested() {
+        return None;
+    }
+    descriptor
+        .columns()
+        .iter()
+        .position(|column| column.path().parts() == [name])
+}
+
+fn parquet_error(e: ParquetError) -> Error {
+    Error::Arrow(ArrowError::ExternalError(Box::new(e)))
+}
+
+/// Options for a [`ParquetSink`].
+///
+/// By default the sink uses the column settings of the
+/// [`parquet_properties_for`] presets and keeps the row order. It sorts row
+/// groups only with [`with_sort_columns`](Self::with_sort_columns) or
+/// properties that have sorting columns.
+#[derive(Debug, Clone, Default)]
+pub struct ParquetSinkBuilder {
+    properties: Option<WriterProperties>,
+    max_row_group_rows: Option<usize>,
+    max_row_group_bytes: Option<usize>,
+    sort_columns: Option<Vec<String>>,
+}
+
+impl ParquetSinkBuilder {
+    /// Start configuring a sink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writer properties (compression, encodings, statistics and so on).
+    ///
+    /// The sink sorts each row group as the properties' sorting columns say,
+    /// unless [`with_sort_columns`](Self::with_sort_columns) overrides them.
+    pub fn with_properties(mut self, props: WriterProperties) -> Self {
+        self.properties = Some(props);
+        self
+    }
+
+    /// Close a row group once it holds `rows` rows. Overrides the maximum
+    /// row group size of the writer properties.
+    pub fn with_max_row_group_rows(mut self, rows: usize) -> Self {
+        self.max_row_group_rows = Some(rows);
+        self
+    }
+
+    /// Close a row group once it holds about `bytes` bytes.
+    ///
+    /// The size is checked after each write, so a row group can overshoot
+    /// by up to one batch. Unsorted sinks measure the encoded size of the
+    /// row group, sorted sinks the in-memory size of the rows they buffer.
+    pub fn with_max_row_group_bytes(mut self, bytes: usize) -> Self {
+        self.max_row_group_bytes = Some(bytes);
+        self
+    }
+
+    /// Sort the rows of each row group by these columns, ascending with
+    /// nulls first, and record the order as the row groups' sorting columns.
+    /// An empty list turns sorting off.
+    ///
+    /// Sorted sinks buffer rows until a row group is full. Each column must
+    /// be a top-level primitive column, such as `service_name` or
+    /// `timestamp`.
+    pub fn with_sort_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.sort_columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Open a sink that writes batches of `schema` to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidInput` for a zero row or byte threshold and
+    /// `Error::SchemaMismatch` for an unknown or nested sort column, or
+    /// sorting columns of the properties that are not top-level primitive
+    /// columns of `schema`.
+    pub fn build<W: Write + Send>(
+        self,
+        schema: &Schema,
+        writer: W,
+    ) -> Result<ParquetSink<W>, Error> {
+        if self.max_row_group_rows == Some(0) || self.max_row_group_bytes == Some(0) {
+            return Err(Error::InvalidInput(
+                "row group thresholds must be greater than zero".to_string(),
+            ));
+        }
+
+        let props = self.properties.unwrap_or_else(|| {
+            preset_properties(
+                schema,
+                self.max_row_group_rows
+                    .unwrap_or(DEFAULT_MAX_ROW_GROUP_SIZE),
+            )
+        });
+        let descriptor = ArrowSchemaConverter::new()
+            .convert(schema)
+            .map_err(parquet_error)?;
+        let sorting = match &self.sort_columns {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    let field = schema.field_with_name(name).map_err(|_| {
+                        Error::SchemaMismatch(format!("sort column '{name}' not found in schema"))
+                    })?;
+                    let leaf = leaf_index(schema, &descriptor, name).ok_or_else(|| {
+                        Error::SchemaMismatch(format!(
+                            "sort column '{name}' must be a primitive column, found {}",
+                            field.data_type()
+                        ))
+                    })?;
+                    Ok(SortingColumn {
+                        column_idx: leaf as i32,
+                        descending: false,
+                        nulls_first: true,
+                    })
+                })
+                .collect::<Result<Vec<_>, Error>>()?,
+            None => props.sorting_columns().cloned().unwrap_or_default(),
+        };
+        let sort_columns = sorting
+            .iter()
+            .map(|sorting| {
+                let index = descriptor
+                    .columns()
+                    .get(sorting.column_idx as usize)
+                    .and_then(|column| match column.path().parts() {
+                        [name] => schema.index_of(name).ok(),
+                        _ => None,
+                    })
+                    .ok_or_else(|| {
+                        Error::SchemaMismatch(format!(
+                            "sorting column {} is not a top-level primitive column",
+                            sorting.column_idx
+                        ))
+                    })?;
+                let options = SortOptions {
+                    descending: sorting.descending,
+                    nulls_first: sorting.nulls_first,
+                };
+                Ok((index, options))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut props = props
+            .into_builder()
+            .set_sorting_columns((!sorting.is_empty()).then_some(sorting));
+        if let Some(rows) = self.max_row_group_rows {
+            props = props.set_max_row_group_size(rows);
+        }
+
+        // Add field IDs to schema for Iceberg compatibility
+        let batch_schema = Arc::new(schema.clone());
+        let schema = Arc::new(add_field_ids_to_schema(schema));
+        let props = props.build();
+        let max_row_group_rows = props.max_row_group_size();
+        let writer =
+            ArrowWriter::try_new(writer, schema.clone(), Some(props)).map_err(parquet_error)?;
+
+        Ok(ParquetSink {
+            writer,
+            schema,
+            batch_schema,
+            max_row_group_rows,
+            max_row_group_bytes: self.max_row_group_bytes,
+            sort_columns,
+            pending: Vec::new(),
+            pending_rows: 0,
+            pending_bytes: 0,
+        })
+    }
+}
+
+/// A Parquet file that stays open while batches are appended to it.
+///
+/// Every batch must have the sink's schema. Row groups are closed when they
+/// reach the configured row or byte threshold, on [`flush`](Self::flush)
+/// and on [`close`](Self::close), which writes the footer and returns a
+/// [`ParquetFileMetadata`] summary of the file.
+///
+/// ```ignore
+/// use otlp2records::{ParquetSinkBuilder, logs_schema};
+///
+/// let file = std::fs::File::create("logs.parquet")?;
+/// let mut sink = ParquetSinkBuilder::new()
+///     .with_max_row_group_rows(1_000_000)
+///     .with_sort_columns(["service_name", "timestamp"])
+///     .build(&logs_schema(), file)?;
+/// for batch in batches {
+///     sink.write(&batch)?;
+/// }
+/// let metadata = sink.close()?;
+/// ```
+pub struct ParquetSink<W: Write + Send> {
+    writer: ArrowWriter<W>,
+    schema: SchemaRef,
+    /// Schema of the written batches. Field IDs of nested fields change their
+    /// parent's data type, so only the writer's schema carries them.
+    batch_schema: SchemaRef,
+    max_row_group_rows: usize,
+    max_row_group_bytes: Option<usize>,
+    /// Indices and order of the sort columns; empty for unsorted sinks
+    sort_columns: Vec<(usize, SortOptions)>,
+    /// Rows buffered for the next sorted row group
+    pending: Vec<RecordBatch>,
+    pending_rows: usize,
+    pending_bytes: usize,
+}
+
+impl<W: Write + Send> ParquetSink<W> {
+    /// Open a sink with the default options of [`ParquetSinkBuilder`].
+    pub fn try_new(schema: &Schema, writer: W) -> Result<Self, Error> {
+        ParquetSinkBuilder::new().build(schema, writer)
+    }
+
+    /// Schema of the file, with Parquet field IDs.
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Rows accepted but not yet part of a closed row group.
+    pub fn in_progress_rows(&self) -> usize {
+        self.pending_rows + self.writer.in_progress_rows()
+    }
+
+    /// Append the rows of `batch`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SchemaMismatch` if the batch's column names or types
+    /// differ from the sink's schema.
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<(), Error> {
+        let batch = self.conform(batch)?;
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+
+        if self.sort_columns.is_empty() {
+            self.writer.write(&batch).map_err(parquet_error)?;
+            if self
+                .max_row_group_bytes
+                .is_some_and(|max| self.writer.in_progress_size() >= max)
+            {
+                self.writer.flush().map_err(parquet_error)?;
+            }
+            return Ok(());
+        }
+
+        self.pending_rows += batch.num_rows();
+        self.pending_bytes += batch.get_array_memory_size();
+        self.pending.push(batch);
+        let full_bytes = self
+            .max_row_group_bytes
+            .is_some_and(|max| self.pending_bytes >= max);
+        if full_bytes || self.pending_rows >= self.max_row_group_rows {
+            self.write_pending(full_bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Close the current row group, even if it is below the thresholds.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.write_pending(true)?;
+        self.writer.flush().map_err(parquet_error)
+    }
+
+    /// Write the remaining rows and the footer, and summarize the file.
+    pub fn close(mut self) -> Result<ParquetFileMetadata, Error> {
+        self.write_pending(true)?;
+        let metadata = self.writer.finish().map_err(parquet_error)?;
+        Ok(ParquetFileMetadata::new(
+            &metadata,
+            self.writer.bytes_written() as u64,
+        ))
+    }
+
+    /// Check `batch` against the sink's schema.
+    fn conform(&self, batch: &RecordBatch) -> Result<RecordBatch, Error> {
+        let expected = self.batch_schema.fields();
+        let actual = batch.schema_ref().fields();
+        if expected.len() != actual.len() {
+            return Err(Error::SchemaMismatch(format!(
+                "batch has {} columns, Parquet sink expects {}",
+                actual.len(),
+                expected.len()
+            )));
+        }
+        for (expected, actual) in expected.iter().zip(actual.iter()) {
+            if expected.name() != actual.name() || expected.data_type() != actual.data_type() {
+                return Err(Error::SchemaMismatch(format!(
+                    "batch column '{}' ({}) does not match Parquet sink column '{}' ({})",
+                    actual.name(),
+                    actual.data_type(),
+                    expected.name(),
+                    expected.data_type()
+                )));
+            }
+        }
+        Ok(RecordBatch::try_new(
+            self.batch_schema.clone(),
+            batch.columns().to_vec(),
+        )?)
+    }
+
+    /// Sort the buffered rows and write them as full row groups. The rows
+    /// of a partial last row group stay buffered unless `all` is set.
+    fn write_pending(&mut self, all: bool) -> Result<(), Error> {
+        if self.pending_rows == 0 {
+            return Ok(());
+        }
+        let batch = concat_batches(&self.batch_schema, &self.pending)?;
+        let bytes = self.pending_bytes;
+        self.pending.clear();
+        self.pending_rows = 0;
+        self.pending_bytes = 0;
+
+        let batch = self.sort(&batch)?;
+        let rows = batch.num_rows();
+        let mut offset = 0;
+        while rows - offset >= self.max_row_group_rows || (all && offset < rows) {
+            let len = (rows - offset).min(self.max_row_group_rows);
+            self.writer
+                .write(&batch.slice(offset, len))
+                .map_err(parquet_error)?;
+            self.writer.flush().map_err(parquet_error)?;
+            offset += len;
+        }
+        if offset < rows {
+            let rest = batch.slice(offset, rows - offset);
+            self.pending_rows = rest.num_rows();
+            self.pending_bytes = bytes * self.pending_rows / rows;
+            self.pending.push(rest);
+        }
+        Ok(())
+    }
+
+    fn sort(&self, batch: &RecordBatch) -> Result<RecordBatch, Error> {
+        let columns: Vec<SortColumn> = self
+            .sort_columns
+            .iter()
+            .map(|&(index, options)| SortColumn {
+                values: batch.column(index).clone(),
+                options: Some(options),
+            })
+            .collect();
+        let indices = lexsort_to_indices(&columns, None)?;
+        Ok(take_record_batch(batch, &indices)?)
+    }
+}
+
+/// Summary of a Parquet file written by a [`ParquetSink`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParquetFileMetadata {
+    /// Rows in the file
+    pub num_rows: u64,
+    /// Row groups in the file
+    pub num_row_groups: usize,
+    /// Size of the file in bytes, footer included
+    pub file_size: u64,
+    /// Statistics of each leaf column, in schema order
+    pub columns: Vec<ParquetColumnStats>,
+}
+
+/// Statistics of one Parquet leaf column across all row groups.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParquetColumnStats {
+    /// Dotted path of the column, e.g. `service_name`
+    pub path: String,
+    /// Parquet field ID of a top-level column
+    pub field_id: Option<i32>,
+    /// Values in the column, nulls included
+    pub num_values: u64,
+    /// Null values in the column, if every row group records them
+    pub null_count: Option<u64>,
+    /// Compressed size of the column's chunks in bytes
+    pub compressed_size: u64,
+    /// Smallest non-null value, if every row group with values records it
+    pub min: Option<ParquetStatValue>,
+    /// Largest non-null value, if every row group with values records it
+    pub max: Option<ParquetStatValue>,
+}
+
+/// A column minimum or maximum, by Parquet physical type.
+///
+/// Timestamps are `Int` values in the column's unit and strings are `Bytes`
+/// holding UTF-8. Unsigned integer columns give `UInt` values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParquetStatValue {
+    /// `BOOLEAN` value
+    Boolean(bool),
+    /// Signed `INT32` or `INT64` value
+    Int(i64),
+    /// Unsigned `INT32` or `INT64` value
+    UInt(u64),
+    /// `FLOAT` or `DOUBLE` value
+    Float(f64),
+    /// `BYTE_ARRAY` or `FIXED_LEN_BYTE_ARRAY` value
+    Bytes(Vec<u8>),
+}
+
+impl ParquetStatValue {
+    fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Boolean(a), Self::Boolean(b)) => Some(a.cmp(b)),
+            (Self::Int(a), Self::Int(b)) => Some(a.cmp(b)),
+            (Self::UInt(a), Self::UInt(b)) => Some(a.cmp(b)),
+            (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+            (Self::Bytes(a), Self::Bytes(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+}
+
+impl ParquetFileMetadata {
+    fn new(metadata: &ParquetMetaData, file_size: u64) -> Self {
+        let row_groups = metadata.row_groups();
+        let descriptor = metadata.file_metadata().schema_descr();
+        let columns = descriptor
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let basic = column.self_type().get_basic_info();
+                let mut stats = ParquetColumnStats {
+                    path: column.path().string(),
+                    field_id: basic.has_id().then(|| basic.id()),
+                    num_values: 0,
+                    null_count: Some(0),
+                    compressed_size: 0,
+                    min: None,
+                    max: None,
+                };
+                let unsigned = column.sort_order() == SortOrder::UNSIGNED;
+                let ordered = column.sort_order() != SortOrder::UNDEFINED;
+                let mut bounds_known = ordered;
+                for row_group in row_groups {
+                    let chunk = row_group.column(index);
+                    let num_values = chunk.num_values().max(0) as u64;
+                    stats.num_values += num_values;
+                    stats.compressed_size += chunk.compressed_size().max(0) as u64;
+
+                    let statistics = chunk.statistics();
+                    let nulls = statistics.and_then(Statistics::null_count_opt);
+                    stats.null_count = stats.null_count.zip(nulls).map(|(a, b)| a + b);
+                    if nulls == Some(num_values) {
+                        continue;
+                    }
+                    match statistics.map(|s| stat_bounds(s, unsigned)) {
+                        Some((Some(min), Some(max))) if bounds_known => {
+                            merge_bound(&mut stats.min, min, Ordering::Less);
+                            merge_bound(&mut stats.max, max, Ordering::Greater);
+                        }
+                        _ => bounds_known = false,
+                    }
+                }
+                if !bounds_known {
+                    stats.min = None;
+                    stats.max = None;
+                }
+                stats
+            })
+            .collect();
+
+        Self {
+            num_rows: metadata.file_metadata().num_rows().max(0) as u64,
+            num_row_groups: row_groups.len(),
+            file_size,
+            columns,
+        }
+    }
+}
+
+/// Keep `value` in `bound` if it compares as `keep` against the current one.
+fn merge_bound(bound: &mut Option<ParquetStatValue>, value: ParquetStatValue, keep: Ordering) {
+    let replace = match bound {
+        Some(current) => value.compare(current) == Some(keep),
+        None => true,
+    };
+    if replace {
+        *bound = Some(value);
+    }
+}
+
+fn stat_bounds(
+    statistics: &Statistics,
+    unsigned: bool,
+) -> (Option<ParquetStatValue>, Option<ParquetStatValue>) {
+    use ParquetStatValue as V;
+
+    fn bounds<T>(
+        s: &parquet::file::statistics::ValueStatistics<T>,
+        f: impl Fn(&T) -> ParquetStatValue,
+    ) -> (Option<ParquetStatValue>, Option<ParquetStatValue>) {
+        (s.min_opt().map(&f), s.max_opt().map(&f))
+    }
+
+    match statistics {
+        Statistics::Boolean(s) => bounds(s, |v| V::Boolean(*v)),
+        Statistics::Int32(s) if unsigned => bounds(s, |v| V::UInt(u64::from(*v as u32))),
+        Statistics::Int32(s) => bounds(s, |v| V::Int(i64::from(*v))),
+        Statistics::Int64(s) if unsigned => bounds(s, |v| V::UInt(*v as u64)),
+        Statistics::Int64(s) => bounds(s, |v| V::Int(*v)),
+        Statistics::Int96(_) => (None, None),
+        Statistics::Float(s) => bounds(s, |v| V::Float(f64::from(*v))),
+        Statistics::Double(s) => bounds(s, |v| V::Float(*v)),
+        Statistics::ByteArray(s) => bounds(s, |v| V::Bytes(v.data().to_vec())),
+        Statistics::FixedLenByteArray(s) => bounds(s, |v| V::Bytes(v.data().to_vec())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow::array::{Array, Int64Array, StringArray};
+    use crate::arrow::traces_schema;
+    use arrow::array::{Array, Int64Array, StringArray, UInt64Array};
     use arrow::datatypes::{DataType, Field, Schema};
     use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
     use std::sync::Arc;
@@ -343,4 +1028,353 @@ mod tests {
         assert_eq!(batches.len(), 1);
         assert_eq!(batches[0].num_rows(), 3);
     }
+
+    fn int_batch(values: Vec<Option<i64>>) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("value", DataType::Int64, true),
+        ]));
+        let names: StringArray = values.iter().map(|v| v.map(|v| format!("n{v}"))).collect();
+        RecordBatch::try_new(
+            schema,
+            vec![Arc::new(names), Arc::new(Int64Array::from(values))],
+        )
+        .unwrap()
+    }
+
+    fn read_parquet(bytes: Vec<u8>) -> (ParquetMetaData, Vec<RecordBatch>) {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(bytes)).unwrap();
+        let metadata = builder.metadata().as_ref().clone();
+        let batches = builder.build().unwrap().map(|r| r.unwrap()).collect();
+        (metadata, batches)
+    }
+
+    fn int_values(batches: &[RecordBatch]) -> Vec<Option<i64>> {
+        batches
+            .iter()
+            .flat_map(|b| {
+                let col = b.column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+                col.iter().collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sink_appends_batches_into_one_file() {
+        let batch = create_test_batch();
+        let mut buffer = Vec::new();
+        let mut sink = ParquetSink::try_new(&batch.schema(), &mut buffer).unwrap();
+        for _ in 0..3 {
+            sink.write(&batch).unwrap();
+        }
+        assert_eq!(sink.in_progress_rows(), 9);
+        let metadata = sink.close().unwrap();
+
+        assert_eq!(metadata.num_rows, 9);
+        assert_eq!(metadata.num_row_groups, 1);
+        assert_eq!(metadata.file_size, buffer.len() as u64);
+
+        let name = &metadata.columns[0];
+        assert_eq!(name.path, "name");
+        assert_eq!(name.field_id, Some(1));
+        assert_eq!(name.num_values, 9);
+        assert_eq!(name.null_count, Some(0));
+        assert!(name.compressed_size > 0);
+        assert_eq!(name.min, Some(ParquetStatValue::Bytes(b"alpha".to_vec())));
+        assert_eq!(name.max, Some(ParquetStatValue::Bytes(b"gamma".to_vec())));
+        let value = &metadata.columns[1];
+        assert_eq!(value.field_id, Some(2));
+        assert_eq!(value.min, Some(ParquetStatValue::Int(1)));
+        assert_eq!(value.max, Some(ParquetStatValue::Int(3)));
+
+        let (_, batches) = read_parquet(buffer);
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 9);
+    }
+
+    #[test]
+    fn test_sink_closes_row_groups_at_row_threshold() {
+        let batch = int_batch((0..3).map(Some).collect());
+        let mut buffer = Vec::new();
+        let mut sink = ParquetSinkBuilder::new()
+            .with_max_row_group_rows(4)
+            .build(&batch.schema(), &mut buffer)
+            .unwrap();
+        for _ in 0..3 {
+            sink.write(&batch).unwrap();
+        }
+        let metadata = sink.close().unwrap();
+        assert_eq!(metadata.num_rows, 9);
+        assert_eq!(metadata.num_row_groups, 3);
+
+        let (parquet, _) = read_parquet(buffer);
+        let rows: Vec<i64> = parquet
+            .row_groups()
+            .iter()
+            .map(|rg| rg.num_rows())
+            .collect();
+        assert_eq!(rows, vec![4, 4, 1]);
+    }
+
+    #[test]
+    fn test_sink_closes_row_groups_at_byte_threshold() {
+        let batch = int_batch((0..3).map(Some).collect());
+        let mut buffer = Vec::new();
+        let mut sink = ParquetSinkBuilder::new()
+            .with_max_row_group_bytes(1)
+            .build(&batch.schema(), &mut buffer)
+            .unwrap();
+        sink.write(&batch).unwrap();
+        sink.write(&batch).unwrap();
+        assert_eq!(sink.in_progress_rows(), 0);
+        let metadata = sink.close().unwrap();
+        assert_eq!(metadata.num_row_groups, 2);
+    }
+
+    #[test]
+    fn test_sink_flush_closes_row_group() {
+        let batch = create_test_batch();
+        let mut buffer = Vec::new();
+        let mut sink = ParquetSink::try_new(&batch.schema(), &mut buffer).unwrap();
+        sink.write(&batch).unwrap();
+        sink.flush().unwrap();
+        sink.write(&batch).unwrap();
+        let metadata = sink.close().unwrap();
+        assert_eq!(metadata.num_row_groups, 2);
+        assert_eq!(metadata.num_rows, 6);
+    }
+
+    #[test]
+    fn test_sink_sorts_each_row_group() {
+        let mut buffer = Vec::new();
+        let schema = int_batch(vec![]).schema();
+        let mut sink = ParquetSinkBuilder::new()
+            .with_max_row_group_rows(4)
+            .with_sort_columns(["value"])
+            .build(&schema, &mut buffer)
+            .unwrap();
+        sink.write(&int_batch(vec![Some(9), Some(3), None]))
+            .unwrap();
+        sink.write(&int_batch(vec![Some(7), Some(1), Some(5)]))
+            .unwrap();
+        // The first four sorted rows form a row group, the rest stay buffered
+        assert_eq!(sink.in_progress_rows(), 2);
+        sink.write(&int_batch(vec![Some(2)])).unwrap();
+        let metadata = sink.close().unwrap();
+        assert_eq!(metadata.num_rows, 7);
+        assert_eq!(metadata.num_row_groups, 2);
+        assert_eq!(metadata.columns[1].null_count, Some(1));
+        assert_eq!(metadata.columns[1].min, Some(ParquetStatValue::Int(1)));
+        assert_eq!(metadata.columns[1].max, Some(ParquetStatValue::Int(9)));
+
+        let (parquet, batches) = read_parquet(buffer);
+        assert_eq!(
+            int_values(&batches),
+            vec![None, Some(1), Some(3), Some(5), Some(2), Some(7), Some(9)]
+        );
+        for row_group in parquet.row_groups() {
+            assert_eq!(
+                row_group.sorting_columns().unwrap(),
+                &vec![SortingColumn {
+                    column_idx: 1,
+                    descending: false,
+                    nulls_first: true,
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn test_sink_stats_skip_all_null_row_groups() {
+        let mut buffer = Vec::new();
+        let schema = int_batch(vec![]).schema();
+        let mut sink = ParquetSinkBuilder::new()
+            .with_max_row_group_rows(2)
+            .build(&schema, &mut buffer)
+            .unwrap();
+        sink.write(&int_batch(vec![None, None, Some(4), Some(-2)]))
+            .unwrap();
+        let metadata = sink.close().unwrap();
+        assert_eq!(metadata.num_row_groups, 2);
+        let value = &metadata.columns[1];
+        assert_eq!(value.num_values, 4);
+        assert_eq!(value.null_count, Some(2));
+        assert_eq!(value.min, Some(ParquetStatValue::Int(-2)));
+        assert_eq!(value.max, Some(ParquetStatValue::Int(4)));
+    }
+
+    #[test]
+    fn test_sink_unsigned_stats() {
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "count",
+            DataType::UInt64,
+            false,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(UInt64Array::from(vec![u64::MAX, 1]))],
+        )
+        .unwrap();
+        let mut buffer = Vec::new();
+        let mut sink = ParquetSink::try_new(&schema, &mut buffer).unwrap();
+        sink.write(&batch).unwrap();
+        let metadata = sink.close().unwrap();
+        assert_eq!(metadata.columns[0].min, Some(ParquetStatValue::UInt(1)));
+        assert_eq!(
+            metadata.columns[0].max,
+            Some(ParquetStatValue::UInt(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn test_sink_rejects_mismatched_batches() {
+        let batch = create_test_batch();
+        let mut sink = ParquetSink::try_new(&batch.schema(), Vec::new()).unwrap();
+
+        let other = batch.project(&[1]).unwrap();
+        let err = sink.write(&other).unwrap_err();
+        assert!(matches!(err, Error::SchemaMismatch(_)), "{err}");
+
+        let renamed = Arc::new(Schema::new(vec![
+            Field::new("label", DataType::Utf8, false),
+            Field::new("value", DataType::Int64, false),
+        ]));
+        let renamed = RecordBatch::try_new(renamed, batch.columns().to_vec()).unwrap();
+        let err = sink.write(&renamed).unwrap_err();
+        assert!(err.to_string().contains("'label'"), "{err}");
+
+        // Field metadata does not matter
+        sink.write(&batch).unwrap();
+        assert_eq!(sink.close().unwrap().num_rows, 3);
+    }
+
+    #[test]
+    fn test_sink_builder_errors() {
+        let schema = create_test_batch().schema();
+        let err = ParquetSinkBuilder::new()
+            .with_sort_columns(["missing"])
+            .build(&schema, Vec::new())
+            .err()
+            .unwrap();
+        assert!(matches!(err, Error::SchemaMismatch(_)), "{err}");
+
+        let nested = Schema::new(vec![Field::new_list(
+            "tags",
+            Field::new_list_field(DataType::Utf8, true),
+            true,
+        )]);
+        let err = ParquetSinkBuilder::new()
+            .with_sort_columns(["tags"])
+            .build(&nested, Vec::new())
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("primitive"), "{err}");
+
+        let err = ParquetSinkBuilder::new()
+            .with_max_row_group_rows(0)
+            .build(&schema, Vec::new())
+            .err()
+            .unwrap();
+        assert!(matches!(err, Error::InvalidInput(_)), "{err}");
+    }
+
+    #[test]
+    fn test_parquet_properties_for_traces() {
+        let props = parquet_properties_for(SignalType::Traces);
+        let schema = traces_schema();
+        let col = |name: &str| ColumnPath::from(name);
+
+        assert_eq!(
+            props.compression(&col("span_attributes")),
+            Compression::SNAPPY
+        );
+        assert!(props.dictionary_enabled(&col("service_name")));
+        assert!(props.dictionary_enabled(&col("span_name")));
+        assert!(props.dictionary_enabled(&col("span_kind")));
+        assert!(!props.dictionary_enabled(&col("trace_id")));
+        assert!(!props.dictionary_enabled(&col("span_attributes")));
+        for name in ["trace_id", "span_id"] {
+            let bloom = props.bloom_filter_properties(&col(name)).unwrap();
+            assert_eq!(bloom.ndv, DEFAULT_MAX_ROW_GROUP_SIZE as u64);
+        }
+        assert!(props
+            .bloom_filter_properties(&col("parent_span_id"))
+            .is_none());
+        assert_eq!(
+            props.statistics_enabled(&col("timestamp")),
+            EnabledStatistics::Page
+        );
+        assert_eq!(
+            props.statistics_enabled(&col("end_timestamp")),
+            EnabledStatistics::Chunk
+        );
+
+        let sorted: Vec<&str> = props
+            .sorting_columns()
+            .unwrap()
+            .iter()
+            .map(|c| schema.field(c.column_idx as usize).name().as_str())
+            .collect();
+        assert_eq!(sorted, vec!["service_name", "timestamp"]);
+    }
+
+    #[test]
+    fn test_parquet_properties_for_metrics_sort_by_metric_name() {
+        for signal in [SignalType::Gauge, SignalType::Summary] {
+            let props = parquet_properties_for(signal);
+            let schema = signal.schema();
+            let sorted: Vec<&str> = props
+                .sorting_columns()
+                .unwrap()
+                .iter()
+                .map(|c| schema.field(c.column_idx as usize).name().as_str())
+                .collect();
+            assert_eq!(sorted, vec!["service_name", "metric_name", "timestamp"]);
+        }
+    }
+
+    #[test]
+    fn test_to_parquet_unknown_schema_is_compressed_and_unsorted() {
+        let batch = int_batch(vec![Some(3), Some(1), Some(2)]);
+        let (parquet, batches) = read_parquet(to_parquet(&batch).unwrap());
+        let row_group = &parquet.row_groups()[0];
+        assert_eq!(row_group.column(1).compression(), Compression::SNAPPY);
+        assert!(row_group.sorting_columns().is_none());
+        assert_eq!(int_values(&batches), vec![Some(3), Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_sink_follows_sorting_columns_of_properties() {
+        let schema = int_batch(vec![]).schema();
+        let props = WriterProperties::builder()
+            .set_sorting_columns(Some(vec![SortingColumn {
+                column_idx: 1,
+                descending: true,
+                nulls_first: false,
+            }]))
+            .build();
+        let mut buffer = Vec::new();
+        let mut sink = ParquetSinkBuilder::new()
+            .with_properties(props.clone())
+            .build(&schema, &mut buffer)
+            .unwrap();
+        sink.write(&int_batch(vec![Some(1), None, Some(3), Some(2)]))
+            .unwrap();
+        sink.close().unwrap();
+        let (_, batches) = read_parquet(buffer);
+        assert_eq!(int_values(&batches), vec![Some(3), Some(2), Some(1), None]);
+
+        // An empty sort column list keeps the batch order
+        let mut buffer = Vec::new();
+        let mut sink = ParquetSinkBuilder::new()
+            .with_properties(props)
+            .with_sort_columns(Vec::<String>::new())
+            .build(&schema, &mut buffer)
+            .unwrap();
+        sink.write(&int_batch(vec![Some(1), None, Some(3)]))
+            .unwrap();
+        sink.close().unwrap();
+        let (parquet, batches) = read_parquet(buffer);
+        assert!(parquet.row_groups()[0].sorting_columns().is_none());
+        assert_eq!(int_values(&batches), vec![Some(1), None, Some(3)]);
+    }
 }
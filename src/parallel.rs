@@ -0,0 +1,305 @@
+//! Multi-threaded transforms for large payloads.
+//!
+//! Requires the `parallel` feature and is not available on wasm32. The
+//! decoded records are split at resource/scope group boundaries into about one
+//! chunk per thread of the rayon pool. Logs, traces and metrics run the native
+//! mappings on each chunk, while each profile worker runs its own
+//! [`VrlTransformer`]. The batches are concatenated in input order, so the
+//! output matches the single-threaded transforms.
+//!
+//! A payload with a single resource/scope group runs on one thread.
+
+use std::ops::Range;
+
+use ::arrow::compute::concat_batches;
+use ::arrow::datatypes::Schema;
+use ::arrow::record_batch::RecordBatch;
+use rayon::prelude::*;
+use vrl::value::Value;
+
+use crate::decode::{self, Decoded, DecodedMetrics, InputFormat, RecordOrigin};
+use crate::error::Result;
+use crate::{
+    apply_profile_programs, finish_batch, logs_schema, native, profiles_schema, traces_schema,
+    Layers, MetricBatches, TransformStats, TransformedBatch, VrlTransformer,
+};
+
+/// Transform OTLP logs to Arrow RecordBatch on the rayon thread pool.
+///
+/// Same output as [`transform_logs`](crate::transform_logs).
+pub fn transform_logs_parallel(bytes: &[u8], format: InputFormat) -> Result<TransformedBatch> {
+    let decoded = decode::decode_log_parts(bytes, format)?;
+    let schema = logs_schema();
+    let stats = TransformStats::decoded(&decoded);
+    let parts = map_native_chunks(decoded, |values| native::logs_batch(values, &schema, false))?;
+    concat_transformed(parts, &schema, stats)
+}
+
+/// Transform OTLP traces to Arrow RecordBatch on the rayon thread pool.
+///
+/// Same output as [`transform_traces`](crate::transform_traces).
+pub fn transform_traces_parallel(bytes: &[u8], format: InputFormat) -> Result<TransformedBatch> {
+    let decoded = decode::decode_trace_parts(bytes, format)?;
+    let schema = traces_schema();
+    let stats = TransformStats::decoded(&decoded);
+    let parts = map_native_chunks(decoded, |values| {
+        native::spans_batch(values, &schema, false)
+    })?;
+    concat_transformed(parts, &schema, stats)
+}
+
+/// Transform OTLP profiles to Arrow RecordBatch on the rayon thread pool.
+///
+/// Same output as [`transform_profiles`](crate::transform_profiles).
+pub fn transform_profiles_parallel(bytes: &[u8], format: InputFormat) -> Result<TransformedBatch> {
+    let decoded = decode::decode_profile_records(bytes, format, false)?;
+    let schema = profiles_schema();
+    let stats = TransformStats::decoded(&decoded);
+
+    let parts = map_chunks(decoded, |transformer, values, first_index| {
+        let transformed =
+            apply_profile_programs(transformer, values, first_index, Layers::default())?;
+        let mut stats = TransformStats::default();
+        let batch = finish_batch(&transformed, &schema, &mut stats)?;
+        Ok(TransformedBatch { batch, stats })
+    })?;
+    concat_transformed(parts, &schema, stats)
+}
+
+/// Transform OTLP metrics to Arrow RecordBatches on the rayon thread pool.
+///
+/// Same output as [`transform_metrics`](crate::transform_metrics).
+pub fn transform_metrics_parallel(bytes: &[u8], format: InputFormat) -> Result<MetricBatches> {
+    let DecodedMetrics { records, skipped } = decode::decode_metric_parts(bytes, format)?;
+    let mut stats = TransformStats::decoded(&records);
+    stats.add_skipped(&skipped);
+
+    let parts = map_native_chunks(records, |values| native::metric_batches(values, false))?;
+
+    let mut batches = MetricBatches {
+        skipped,
+        ..MetricBatches::default()
+    };
+    let mut gauge = Vec::new();
+    let mut sum = Vec::new();
+    let mut histogram = Vec::new();
+    let mut exp_histogram = Vec::new();
+    let mut summary = Vec::new();
+    for part in parts {
+        stats.merge(&part.stats);
+        gauge.extend(part.gauge);
+        sum.extend(part.sum);
+        histogram.extend(part.histogram);
+        exp_histogram.extend(part.exp_histogram);
+        summary.extend(part.summary);
+    }
+    batches.gauge = concat(gauge)?;
+    batches.sum = concat(sum)?;
+    batches.histogram = concat(histogram)?;
+    batches.exp_histogram = concat(exp_histogram)?;
+    batches.summary = concat(summary)?;
+    batches.stats = stats;
+    Ok(batches)
+}
+
+/// Concatenate the per-chunk batches of logs, traces or profiles, adding
+/// their stats to the decode `stats`.
+fn concat_transformed(
+    parts: Vec<TransformedBatch>,
+    schema: &Schema,
+    mut stats: TransformStats,
+) -> Result<TransformedBatch> {
+    let mut batches = Vec::with_capacity(parts.len());
+    for part in parts {
+        stats.merge(&part.stats);
+        batches.push(part.batch);
+    }
+    let batch = concat_batches(&schema.clone().into(), &batches)?;
+    Ok(TransformedBatch { batch, stats })
+}
+
+/// Run `f` over the chunks of `decoded` on the thread pool, returning the
+/// results in input order. `f` gets the index of the chunk's first record.
+///
+/// If several chunks fail, the error of the first one is returned.
+fn map_chunks<T, F>(decoded: Decoded, f: F) -> Result<Vec<T>>
+where
+    T: Send,
+    F: Fn(&mut VrlTransformer, Vec<Value>, usize) -> Result<T> + Sync,
+{
+    let results: Vec<Result<T>> = split_chunks(decoded)
+        .into_par_iter()
+        .map_init(VrlTransformer::new, |transformer, (first_index, values)| {
+            f(transformer, values, first_index)
+        })
+        .collect();
+    results.into_iter().collect()
+}
+
+/// Run the native mapping `f` over the chunks of `decoded` on the thread
+/// pool, returning the results in input order.
+fn map_native_chunks<R, T, F>(decoded: Decoded<R>, f: F) -> Result<Vec<T>>
+where
+    R: Send,
+    T: Send,
+    F: Fn(Vec<R>) -> Result<T> + Sync,
+{
+    let results: Vec<Result<T>> = split_chunks(decoded)
+        .into_par_iter()
+        .map(|(_, values)| f(values))
+        .collect();
+    results.into_iter().collect()
+}
+
+/// Split the decoded records into chunks, each with the index of its first
+/// record.
+fn split_chunks<R>(decoded: Decoded<R>) -> Vec<(usize, Vec<R>)> {
+    let ranges = chunk_ranges(&decoded.origins, rayon::current_num_threads());
+    let mut values = decoded.values;
+    let mut chunks = Vec::with_capacity(ranges.len());
+    for range in ranges.into_iter().rev() {
+        chunks.push((range.start, values.split_off(range.start)));
+    }
+    chunks.reverse();
+    chunks
+}
+
+/// Split records into about `chunks` contiguous ranges of similar size,
+/// cutting only where the resource/scope group changes.
+fn chunk_ranges(origins: &[RecordOrigin], chunks: usize) -> Vec<Range<usize>> {
+    let target = origins.len().div_ceil(chunks.max(1)).max(1);
+    let mut ranges = Vec::with_capacity(chunks);
+    let mut start = 0;
+
+    for i in 1..origins.len() {
+        if i - start >= target && origins[i].location != origins[i - 1].location {
+            ranges.push(start..i);
+            start = i;
+        }
+    }
+    if start < origins.len() {
+        ranges.push(start..origins.len());
+    }
+    ranges
+}
+
+/// Concatenate the per-chunk batches of one metric type.
+fn concat(batches: Vec<RecordBatch>) -> Result<Option<RecordBatch>> {
+    let Some(first) = batches.first() else {
+        return Ok(None);
+    };
+    Ok(Some(concat_batches(&first.schema(), &batches)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Error, RecordLocation};
+    use crate::{transform_logs, transform_metrics, transform_traces};
+
+    fn origins(groups: &[usize]) -> Vec<RecordOrigin> {
+        let mut out = Vec::new();
+        for (resource_index, &len) in groups.iter().enumerate() {
+            for _ in 0..len {
+                out.push(RecordOrigin {
+                    index: out.len(),
+                    location: RecordLocation {
+                        resource_index,
+                        scope_index: 0,
+                    },
+                });
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_chunk_ranges_cut_at_group_boundaries() {
+        assert_eq!(chunk_ranges(&origins(&[2, 2, 2, 2]), 2), vec![0..4, 4..8]);
+        assert_eq!(chunk_ranges(&origins(&[1, 5, 1]), 3), vec![0..6, 6..7]);
+        // A single group is never split
+        assert_eq!(chunk_ranges(&origins(&[10]), 4), vec![0..10]);
+        assert!(chunk_ranges(&[], 4).is_empty());
+    }
+
+    /// Run `f` on a pool with several threads, so payloads are split even on
+    /// single-core machines.
+    fn in_pool<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap()
+            .install(f)
+    }
+
+    #[test]
+    fn test_parallel_logs_match_sequential() {
+        let pb = include_bytes!("../testdata/logs_large.pb");
+
+        let expected = transform_logs(pb, InputFormat::Protobuf).unwrap();
+        let result = in_pool(|| transform_logs_parallel(pb, InputFormat::Protobuf)).unwrap();
+
+        assert_eq!(result.batch, expected.batch);
+        assert_eq!(result.stats, expected.stats);
+    }
+
+    #[test]
+    fn test_parallel_traces_match_sequential() {
+        let pb = include_bytes!("../testdata/traces_large.pb");
+
+        let expected = transform_traces(pb, InputFormat::Protobuf).unwrap();
+        let result = in_pool(|| transform_traces_parallel(pb, InputFormat::Protobuf)).unwrap();
+
+        assert_eq!(result.batch, expected.batch);
+        assert_eq!(result.stats, expected.stats);
+    }
+
+    #[test]
+    fn test_parallel_metrics_match_sequential() {
+        let payloads: [&[u8]; 3] = [
+            include_bytes!("../testdata/metrics_mixed.pb"),
+            include_bytes!("../testdata/metrics_exponential_histogram.pb"),
+            include_bytes!("../testdata/metrics_summary.pb"),
+        ];
+
+        for pb in payloads {
+            let expected = transform_metrics(pb, InputFormat::Protobuf).unwrap();
+            let result = in_pool(|| transform_metrics_parallel(pb, InputFormat::Protobuf)).unwrap();
+
+            assert_eq!(result.gauge, expected.gauge);
+            assert_eq!(result.sum, expected.sum);
+            assert_eq!(result.histogram, expected.histogram);
+            assert_eq!(result.exp_histogram, expected.exp_histogram);
+            assert_eq!(result.summary, expected.summary);
+            assert_eq!(result.stats, expected.stats);
+        }
+    }
+
+    #[test]
+    fn test_map_chunks_returns_first_error() {
+        let pb = include_bytes!("../testdata/logs_large.pb");
+        let decoded = decode::decode_log_records(pb, InputFormat::Protobuf, false).unwrap();
+        let ranges = chunk_ranges(&decoded.origins, 4);
+        assert!(ranges.len() > 2, "{ranges:?}");
+
+        // Every chunk but the first fails
+        let result = in_pool(|| {
+            map_chunks(decoded, |_, _, first_index| match first_index {
+                0 => Ok(()),
+                _ => Err(Error::VrlRuntime(format!("log record {first_index}"))),
+            })
+        });
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            Error::VrlRuntime(format!("log record {}", ranges[1].start)).to_string()
+        );
+    }
+
+    #[test]
+    fn test_parallel_empty_payload() {
+        let result = transform_logs_parallel(b"", InputFormat::Protobuf).unwrap();
+        assert_eq!(result.batch.num_rows(), 0);
+        assert_eq!(result.batch.schema().as_ref(), &logs_schema());
+    }
+}
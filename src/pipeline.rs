@@ -0,0 +1,720 @@
+//! Reusable, configured transforms.
+//!
+//! A [`Pipeline`] holds the options of a transform (custom VRL, promoted
+//! attributes, timestamp unit or full-nanosecond timestamps, lenient mode,
+//! output batch size) and its own
+//! VRL runtime, so a worker can build one up front and reuse it for every
+//! request:
+//!
+//! ```ignore
+//! use otlp2records::{InputFormat, Pipeline, TimestampUnit};
+//!
+//! let mut pipeline = Pipeline::builder()
+//!     .with_timestamp_unit(TimestampUnit::Millisecond)
+//!     .with_lenient(true)
+//!     .with_batch_size(8192)
+//!     .build()?;
+//!
+//! for body in requests {
+//!     let output = pipeline.transform_logs(&body, InputFormat::Protobuf)?;
+//!     for batch in output.batches {
+//!         write(batch);
+//!     }
+//! }
+//! ```
+
+use ::arrow::datatypes::Schema;
+use ::arrow::record_batch::RecordBatch;
+use vrl::compiler::Program;
+
+use crate::arrow::{cast_timestamps, TimestampUnit};
+use crate::decode::{self, Decoded, DecodedMetrics, InputFormat};
+use crate::error::{Error, RecordError, Result};
+use crate::transform::runtime::init_programs;
+use crate::{
+    apply_log_programs, apply_metric_programs, apply_profile_programs, apply_trace_programs,
+    finish_batch, gauge_schema, lenient, logs_schema, metric_values_to_batches, native,
+    profiles_schema, traces_schema, ApplyPrograms, CustomProgram, Layers, LenientBatch,
+    LenientMetricBatches, MetricBatches, PromotedAttributes, TransformStats, TransformedBatch,
+    VrlTransformer, OTLP_LOGS_PROGRAM, OTLP_PROFILES_PROGRAM, OTLP_TRACES_PROGRAM,
+};
+
+/// Options for a [`Pipeline`].
+///
+/// Every option defaults to the behavior of the free `transform_*`
+/// functions.
+#[derive(Debug, Clone, Default)]
+pub struct PipelineBuilder {
+    custom: Option<CustomProgram>,
+    promoted: Option<PromotedAttributes>,
+    timestamp_unit: Option<TimestampUnit>,
+    nanos: bool,
+    lenient: bool,
+    batch_size: Option<usize>,
+}
+
+impl PipelineBuilder {
+    /// Run `custom` after the built-in mapping of every signal.
+    pub fn with_custom_program(mut self, custom: CustomProgram) -> Self {
+        self.custom = Some(custom);
+        self
+    }
+
+    /// Add typed columns for the given attributes.
+    pub fn with_promoted_attributes(mut self, promoted: PromotedAttributes) -> Self {
+        self.promoted = Some(promoted);
+        self
+    }
+
+    /// Precision of the timestamp-like columns (microseconds by default).
+    ///
+    /// The unit never adds precision: the built-in mappings produce
+    /// microseconds, which [`TimestampUnit::Millisecond`] truncates.
+    /// [`TimestampUnit::Nanosecond`] turns on
+    /// [`with_nanosecond_timestamps`](Self::with_nanosecond_timestamps)
+    /// instead, so the columns hold the original OTLP values.
+    pub fn with_timestamp_unit(mut self, unit: TimestampUnit) -> Self {
+        self.timestamp_unit = Some(unit);
+        self
+    }
+
+    /// Keep the full nanosecond precision of OTLP timestamps.
+    ///
+    /// The timestamp-like columns become `Timestamp(Nanosecond)` holding the
+    /// original OTLP values rather than microseconds cast to nanoseconds, and
+    /// each table gets a [`TIME_UNIX_NANO`](crate::TIME_UNIX_NANO) column with
+    /// the record time (see [`nanosecond_schema`](crate::nanosecond_schema)).
+    /// Custom programs see nanosecond values. Can't be combined with a
+    /// millisecond or microsecond timestamp unit.
+    pub fn with_nanosecond_timestamps(mut self, nanos: bool) -> Self {
+        self.nanos = nanos;
+        self
+    }
+
+    /// Drop and report bad records instead of failing the payload (see
+    /// [`transform_logs_lenient`](crate::transform_logs_lenient)).
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Split the output into batches of at most `rows` rows. By default each
+    /// payload produces a single batch per table.
+    pub fn with_batch_size(mut self, rows: usize) -> Self {
+        self.batch_size = Some(rows);
+        self
+    }
+
+    fn layers(&self) -> Layers<'_> {
+        Layers {
+            custom: self.custom.as_ref(),
+            promoted: self.promoted.as_ref(),
+            nanos: self.nanos,
+        }
+    }
+
+    /// Whether logs, traces and metrics can take the native fast path, which
+    /// only runs the built-in mappings in strict mode.
+    fn native(&self) -> bool {
+        self.custom.is_none() && self.promoted.is_none() && !self.lenient
+    }
+
+    /// Build the pipeline, compiling the built-in VRL programs if they
+    /// haven't been yet.
+    ///
+    /// Fails with [`Error::InvalidInput`] if the batch size is zero, or if
+    /// nanosecond timestamps are combined with a coarser timestamp unit.
+    pub fn build(mut self) -> Result<Pipeline> {
+        if self.batch_size == Some(0) {
+            return Err(Error::InvalidInput(
+                "batch size must be greater than zero".to_string(),
+            ));
+        }
+        match self.timestamp_unit {
+            Some(TimestampUnit::Nanosecond) => self.nanos = true,
+            Some(unit) if self.nanos => {
+                return Err(Error::InvalidInput(format!(
+                    "nanosecond timestamps can't use the {unit:?} timestamp unit"
+                )));
+            }
+            _ => {}
+        }
+        init_programs();
+        Ok(Pipeline {
+            options: self,
+            transformer: VrlTransformer::new(),
+        })
+    }
+}
+
+/// A configured transform with its own VRL runtime.
+///
+/// Build one with [`Pipeline::builder`]. A pipeline is `Send` but its
+/// methods take `&mut self`; give each worker thread its own.
+pub struct Pipeline {
+    options: PipelineBuilder,
+    transformer: VrlTransformer,
+}
+
+/// Output of a [`Pipeline`] transform of logs, traces or profiles.
+#[derive(Debug, Default)]
+pub struct PipelineOutput {
+    /// Output batches in record order. Empty if no record made it through.
+    pub batches: Vec<RecordBatch>,
+    /// Dropped records, ordered by record index (lenient mode only)
+    pub errors: Vec<RecordError>,
+    /// Record accounting for the transform
+    pub stats: TransformStats,
+}
+
+/// Output of a [`Pipeline`] transform of metrics, one list of batches per
+/// metric type.
+#[derive(Debug, Default)]
+pub struct PipelineMetricOutput {
+    /// Gauge batches
+    pub gauge: Vec<RecordBatch>,
+    /// Sum batches
+    pub sum: Vec<RecordBatch>,
+    /// Histogram batches
+    pub histogram: Vec<RecordBatch>,
+    /// Exponential histogram batches
+    pub exp_histogram: Vec<RecordBatch>,
+    /// Summary batches
+    pub summary: Vec<RecordBatch>,
+    /// Dropped data points, ordered by record index (lenient mode only)
+    pub errors: Vec<RecordError>,
+    /// Record accounting for all metric types together
+    pub stats: TransformStats,
+}
+
+impl Pipeline {
+    /// Start configuring a pipeline.
+    pub fn builder() -> PipelineBuilder {
+        PipelineBuilder::default()
+    }
+
+    /// Transform OTLP logs. See [`transform_logs`](crate::transform_logs).
+    pub fn transform_logs(&mut self, bytes: &[u8], format: InputFormat) -> Result<PipelineOutput> {
+        if self.options.native() {
+            let schema = self.schema(logs_schema())?;
+            let decoded = decode::decode_log_parts(bytes, format)?;
+            let nanos = self.options.nanos;
+            return self.native_output(native::transform_logs(decoded, &schema, nanos)?);
+        }
+        let schema = self.schema(logs_schema())?;
+        let decoded = decode::decode_log_records(bytes, format, self.options.lenient)?;
+        self.run(decoded, &OTLP_LOGS_PROGRAM, apply_log_programs, &schema)
+    }
+
+    /// Transform OTLP traces. See [`transform_traces`](crate::transform_traces).
+    pub fn transform_traces(
+        &mut self,
+        bytes: &[u8],
+        format: InputFormat,
+    ) -> Result<PipelineOutput> {
+        if self.options.native() {
+            let schema = self.schema(traces_schema())?;
+            let decoded = decode::decode_trace_parts(bytes, format)?;
+            let nanos = self.options.nanos;
+            return self.native_output(native::transform_traces(decoded, &schema, nanos)?);
+        }
+        let schema = self.schema(traces_schema())?;
+        let decoded = decode::decode_trace_records(bytes, format, self.options.lenient)?;
+        self.run(decoded, &OTLP_TRACES_PROGRAM, apply_trace_programs, &schema)
+    }
+
+    /// Transform OTLP profiles. See
+    /// [`transform_profiles`](crate::transform_profiles).
+    pub fn transform_profiles(
+        &mut self,
+        bytes: &[u8],
+        format: InputFormat,
+    ) -> Result<PipelineOutput> {
+        let schema = self.schema(profiles_schema())?;
+        let decoded = decode::decode_profile_records(bytes, format, self.options.lenient)?;
+        self.run(
+            decoded,
+            &OTLP_PROFILES_PROGRAM,
+            apply_profile_programs,
+            &schema,
+        )
+    }
+
+    /// Transform OTLP metrics. See [`transform_metrics`](crate::transform_metrics).
+    pub fn transform_metrics(
+        &mut self,
+        bytes: &[u8],
+        format: InputFormat,
+    ) -> Result<PipelineMetricOutput> {
+        // Validate the promoted columns before doing any work
+        self.schema(gauge_schema())?;
+        let layers = self.options.layers();
+
+        let LenientMetricBatches { batches, errors } = if self.options.native() {
+            let decoded = decode::decode_metric_parts(bytes, format)?;
+            LenientMetricBatches {
+                batches: native::transform_metrics(decoded, self.options.nanos)?,
+                errors: Vec::new(),
+            }
+        } else if self.options.lenient {
+            let decoded = decode::decode_metric_records(bytes, format, true)?;
+            lenient::transform_metrics(&mut self.transformer, decoded, layers)?
+        } else {
+            let decoded = decode::decode_metric_records(bytes, format, false)?;
+            let DecodedMetrics { records, skipped } = decoded;
+            let mut stats = TransformStats::decoded(&records);
+            stats.add_skipped(&skipped);
+            let metric_values =
+                apply_metric_programs(&mut self.transformer, records.values, 0, layers)?;
+            let batches = metric_values_to_batches(metric_values, skipped, stats, layers)?;
+            LenientMetricBatches {
+                batches,
+                errors: Vec::new(),
+            }
+        };
+
+        let MetricBatches {
+            gauge,
+            sum,
+            histogram,
+            exp_histogram,
+            summary,
+            stats,
+            ..
+        } = batches;
+        Ok(PipelineMetricOutput {
+            gauge: self.split(gauge)?,
+            sum: self.split(sum)?,
+            histogram: self.split(histogram)?,
+            exp_histogram: self.split(exp_histogram)?,
+            summary: self.split(summary)?,
+            errors,
+            stats,
+        })
+    }
+
+    /// The output schema of a signal, before the timestamp unit is applied.
+    fn schema(&self, base: Schema) -> Result<Schema> {
+        self.options.layers().schema(base)
+    }
+
+    fn run(
+        &mut self,
+        decoded: Decoded,
+        program: &Program,
+        apply: ApplyPrograms,
+        schema: &Schema,
+    ) -> Result<PipelineOutput> {
+        let layers = self.options.layers();
+
+        let LenientBatch {
+            batch,
+            errors,
+            stats,
+        } = if self.options.lenient {
+            lenient::transform(&mut self.transformer, decoded, program, layers, schema)?
+        } else {
+            let mut stats = TransformStats::decoded(&decoded);
+            let transformed = apply(&mut self.transformer, decoded.values, 0, layers)?;
+            let batch = finish_batch(&transformed, schema, &mut stats)?;
+            LenientBatch {
+                batch,
+                errors: Vec::new(),
+                stats,
+            }
+        };
+
+        Ok(PipelineOutput {
+            batches: self.split(Some(batch))?,
+            errors,
+            stats,
+        })
+    }
+
+    fn native_output(&self, transformed: TransformedBatch) -> Result<PipelineOutput> {
+        Ok(PipelineOutput {
+            batches: self.split(Some(transformed.batch))?,
+            errors: Vec::new(),
+            stats: transformed.stats,
+        })
+    }
+
+    /// Apply the timestamp unit and split a batch into `batch_size` rows.
+    fn split(&self, batch: Option<RecordBatch>) -> Result<Vec<RecordBatch>> {
+        let Some(batch) = batch.filter(|batch| batch.num_rows() > 0) else {
+            return Ok(Vec::new());
+        };
+        let batch = match self.options.timestamp_unit {
+            Some(unit) if !self.options.nanos => cast_timestamps(&batch, unit)?,
+            _ => batch,
+        };
+
+        let rows = batch.num_rows();
+        let size = self.options.batch_size.unwrap_or(rows);
+        Ok((0..rows)
+            .step_by(size)
+            .map(|offset| batch.slice(offset, size.min(rows - offset)))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{nanosecond_schema, TIME_UNIX_NANO};
+    use crate::{transform_logs, transform_metrics, PromotedAttribute};
+    use ::arrow::array::AsArray;
+    use ::arrow::compute::concat_batches;
+    use ::arrow::datatypes::{
+        DataType, DurationNanosecondType, Int64Type, TimeUnit, TimestampNanosecondType,
+    };
+
+    const LOGS: &[u8] = include_bytes!("../testdata/logs_large.pb");
+    const METRICS: &[u8] = include_bytes!("../testdata/metrics_mixed.pb");
+
+    #[test]
+    fn test_pipeline_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Pipeline>();
+    }
+
+    #[test]
+    fn test_default_pipeline_matches_transform_logs() {
+        let mut pipeline = Pipeline::builder().build().unwrap();
+        let expected = transform_logs(LOGS, InputFormat::Protobuf).unwrap();
+
+        // The runtime is reused across payloads
+        for _ in 0..2 {
+            let output = pipeline
+                .transform_logs(LOGS, InputFormat::Protobuf)
+                .unwrap();
+            assert_eq!(output.batches, vec![expected.batch.clone()]);
+            assert_eq!(output.stats, expected.stats);
+            assert!(output.errors.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_pipeline_batch_size() {
+        let mut pipeline = Pipeline::builder().with_batch_size(7).build().unwrap();
+        let expected = transform_logs(LOGS, InputFormat::Protobuf).unwrap().batch;
+
+        let output = pipeline
+            .transform_logs(LOGS, InputFormat::Protobuf)
+            .unwrap();
+
+        assert_eq!(output.batches.len(), expected.num_rows().div_ceil(7));
+        assert!(output.batches.iter().all(|batch| batch.num_rows() <= 7));
+        let combined = concat_batches(&expected.schema(), &output.batches).unwrap();
+        assert_eq!(combined, expected);
+    }
+
+    #[test]
+    fn test_pipeline_zero_batch_size() {
+        let result = Pipeline::builder().with_batch_size(0).build();
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_pipeline_timestamp_unit() {
+        let mut pipeline = Pipeline::builder()
+            .with_timestamp_unit(TimestampUnit::Millisecond)
+            .build()
+            .unwrap();
+
+        let output = pipeline
+            .transform_logs(LOGS, InputFormat::Protobuf)
+            .unwrap();
+
+        let schema = output.batches[0].schema();
+        for name in ["timestamp", "observed_timestamp"] {
+            assert_eq!(
+                schema.field_with_name(name).unwrap().data_type(),
+                &DataType::Timestamp(TimeUnit::Millisecond, None)
+            );
+        }
+    }
+
+    /// Nanosecond timestamp column `name` of `batch`
+    fn nanos_column(batch: &RecordBatch, name: &str) -> Vec<i64> {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_primitive::<TimestampNanosecondType>()
+            .values()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_pipeline_nanosecond_logs() {
+        let json = br#"{"resourceLogs": [{"scopeLogs": [{"logRecords": [
+            {"timeUnixNano": "1700000000123456789", "observedTimeUnixNano": "1700000000123456999",
+             "body": {"stringValue": "a"}},
+            {"timeUnixNano": "1700000000123456001", "body": {"stringValue": "b"}}
+        ]}]}]}"#;
+        let mut native = Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .build()
+            .unwrap();
+        // Lenient mode takes the VRL path
+        let mut vrl = Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .with_lenient(true)
+            .build()
+            .unwrap();
+
+        let output = native.transform_logs(json, InputFormat::Json).unwrap();
+        let batch = &output.batches[0];
+
+        assert_eq!(batch.schema().as_ref(), &nanosecond_schema(&logs_schema()));
+        assert_eq!(
+            nanos_column(batch, "timestamp"),
+            [1_700_000_000_123_456_789, 1_700_000_000_123_456_001]
+        );
+        assert_eq!(
+            nanos_column(batch, "observed_timestamp"),
+            [1_700_000_000_123_456_999, 0]
+        );
+        assert_eq!(
+            batch
+                .column_by_name(TIME_UNIX_NANO)
+                .unwrap()
+                .as_primitive::<Int64Type>()
+                .values()
+                .to_vec(),
+            [1_700_000_000_123_456_789, 1_700_000_000_123_456_001]
+        );
+        let vrl_output = vrl.transform_logs(json, InputFormat::Json).unwrap();
+        assert_eq!(vrl_output.batches, output.batches);
+    }
+
+    #[test]
+    fn test_pipeline_nanosecond_traces_match_vrl() {
+        let traces = include_bytes!("../testdata/traces_large.pb");
+        let mut native = Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .build()
+            .unwrap();
+        let mut vrl = Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .with_lenient(true)
+            .build()
+            .unwrap();
+
+        let output = native
+            .transform_traces(traces, InputFormat::Protobuf)
+            .unwrap();
+        let batch = &output.batches[0];
+
+        let start = nanos_column(batch, "timestamp");
+        let end = nanos_column(batch, "end_timestamp");
+        let duration = batch
+            .column_by_name("duration")
+            .unwrap()
+            .as_primitive::<DurationNanosecondType>()
+            .values();
+        for row in 0..batch.num_rows() {
+            assert_eq!(duration[row], (end[row] - start[row]).max(0));
+        }
+        assert_eq!(
+            vrl.transform_traces(traces, InputFormat::Protobuf)
+                .unwrap()
+                .batches,
+            output.batches
+        );
+    }
+
+    #[test]
+    fn test_pipeline_nanosecond_metrics_match_vrl() {
+        let mut native = Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .build()
+            .unwrap();
+        let mut vrl = Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .with_lenient(true)
+            .build()
+            .unwrap();
+
+        let output = native
+            .transform_metrics(METRICS, InputFormat::Protobuf)
+            .unwrap();
+        let vrl_output = vrl
+            .transform_metrics(METRICS, InputFormat::Protobuf)
+            .unwrap();
+
+        assert_eq!(
+            output.gauge[0].schema().as_ref(),
+            &nanosecond_schema(&gauge_schema())
+        );
+        assert_eq!(vrl_output.gauge, output.gauge);
+        assert_eq!(vrl_output.sum, output.sum);
+        assert_eq!(vrl_output.histogram, output.histogram);
+        assert_eq!(vrl_output.stats, output.stats);
+    }
+
+    #[test]
+    fn test_pipeline_nanosecond_metrics_and_custom_program() {
+        let custom =
+            CustomProgram::compile(".metric_description = to_string!(.start_timestamp)").unwrap();
+        let mut pipeline = Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .with_custom_program(custom)
+            .build()
+            .unwrap();
+
+        let output = pipeline
+            .transform_metrics(METRICS, InputFormat::Protobuf)
+            .unwrap();
+        let batch = &output.gauge[0];
+
+        assert_eq!(batch.schema().as_ref(), &nanosecond_schema(&gauge_schema()));
+        // The custom program sees the nanosecond value
+        let start = nanos_column(batch, "start_timestamp");
+        let descriptions = batch
+            .column_by_name("metric_description")
+            .unwrap()
+            .as_string::<i32>();
+        assert_eq!(descriptions.value(0), start[0].to_string());
+    }
+
+    #[test]
+    fn test_pipeline_nanosecond_rejects_past_2262() {
+        let json = br#"{"resourceLogs": [{"scopeLogs": [{"logRecords": [
+            {"timeUnixNano": "18446744073709551615"}
+        ]}]}]}"#;
+        let mut pipeline = Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .build()
+            .unwrap();
+
+        let err = pipeline
+            .transform_logs(json, InputFormat::Json)
+            .unwrap_err();
+        assert!(err.to_string().contains("year 2262"), "{err}");
+    }
+
+    #[test]
+    fn test_pipeline_timestamp_unit_covers_every_timestamp_column() {
+        let mut pipeline = Pipeline::builder()
+            .with_timestamp_unit(TimestampUnit::Nanosecond)
+            .build()
+            .unwrap();
+        let nanos = DataType::Timestamp(TimeUnit::Nanosecond, None);
+
+        let traces = include_bytes!("../testdata/traces_large.pb");
+        let output = pipeline
+            .transform_traces(traces, InputFormat::Protobuf)
+            .unwrap();
+        let schema = output.batches[0].schema();
+        for name in ["timestamp", "end_timestamp"] {
+            assert_eq!(schema.field_with_name(name).unwrap().data_type(), &nanos);
+        }
+        assert_eq!(
+            schema.field_with_name("duration").unwrap().data_type(),
+            &DataType::Duration(TimeUnit::Nanosecond)
+        );
+
+        let output = pipeline
+            .transform_metrics(METRICS, InputFormat::Protobuf)
+            .unwrap();
+        let schema = output.gauge[0].schema();
+        for name in ["timestamp", "start_timestamp"] {
+            assert_eq!(schema.field_with_name(name).unwrap().data_type(), &nanos);
+        }
+    }
+
+    #[test]
+    fn test_pipeline_nanosecond_unit_keeps_nanoseconds() {
+        let traces = include_bytes!("../testdata/traces_large.pb");
+        let mut unit = Pipeline::builder()
+            .with_timestamp_unit(TimestampUnit::Nanosecond)
+            .build()
+            .unwrap();
+        let mut nanos = Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .build()
+            .unwrap();
+
+        let output = unit
+            .transform_traces(traces, InputFormat::Protobuf)
+            .unwrap();
+
+        assert_eq!(
+            output.batches,
+            nanos
+                .transform_traces(traces, InputFormat::Protobuf)
+                .unwrap()
+                .batches
+        );
+    }
+
+    #[test]
+    fn test_pipeline_nanosecond_rejects_coarser_unit() {
+        for unit in [TimestampUnit::Millisecond, TimestampUnit::Microsecond] {
+            let result = Pipeline::builder()
+                .with_nanosecond_timestamps(true)
+                .with_timestamp_unit(unit)
+                .build();
+            assert!(matches!(result, Err(Error::InvalidInput(_))));
+        }
+        // Nanosecond mode already uses nanoseconds
+        Pipeline::builder()
+            .with_nanosecond_timestamps(true)
+            .with_timestamp_unit(TimestampUnit::Nanosecond)
+            .build()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_pipeline_custom_and_promoted() {
+        let custom = CustomProgram::compile(".severity_text = \"REDACTED\"").unwrap();
+        let promoted = PromotedAttributes::new(vec![
+            PromotedAttribute::resource("service.name").with_column("svc")
+        ]);
+        let mut pipeline = Pipeline::builder()
+            .with_custom_program(custom)
+            .with_promoted_attributes(promoted)
+            .build()
+            .unwrap();
+
+        let output = pipeline
+            .transform_logs(LOGS, InputFormat::Protobuf)
+            .unwrap();
+
+        let batch = &output.batches[0];
+        assert!(batch.schema().field_with_name("svc").is_ok());
+        let severity = ::arrow::compute::cast(
+            batch.column_by_name("severity_text").unwrap(),
+            &::arrow::datatypes::DataType::Utf8,
+        )
+        .unwrap();
+        assert!(severity
+            .as_string::<i32>()
+            .iter()
+            .all(|s| s == Some("REDACTED")));
+    }
+
+    #[test]
+    fn test_pipeline_metrics() {
+        let mut pipeline = Pipeline::builder().with_lenient(true).build().unwrap();
+        let expected = transform_metrics(METRICS, InputFormat::Protobuf).unwrap();
+
+        let output = pipeline
+            .transform_metrics(METRICS, InputFormat::Protobuf)
+            .unwrap();
+
+        assert_eq!(output.gauge, expected.gauge.into_iter().collect::<Vec<_>>());
+        assert_eq!(output.sum, expected.sum.into_iter().collect::<Vec<_>>());
+        assert_eq!(output.stats, expected.stats);
+        assert!(output.errors.is_empty());
+    }
+
+    #[test]
+    fn test_pipeline_lenient_invalid_payload() {
+        let mut pipeline = Pipeline::builder().with_lenient(true).build().unwrap();
+        assert!(pipeline
+            .transform_traces(b"not valid protobuf", InputFormat::Protobuf)
+            .is_err());
+    }
+}
@@ -1,23 +1,73 @@
 //! Schema definitions parsed from VRL @schema annotations.
+//!
+//! The built-in schemas are parsed at build time. [`parse_schema`] exposes the
+//! same parser at runtime so custom VRL programs can declare their own output
+//! columns.
 
+pub(crate) mod annotation;
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use arrow::datatypes::{Field, Schema};
 use once_cell::sync::Lazy;
 
+use crate::error::{Error, Result};
+use annotation::AnnotationType;
+
 // Include compiled VRL schemas and schema definitions from build.rs.
 include!(concat!(env!("OUT_DIR"), "/compiled_vrl.rs"));
 
 /// A single schema field definition.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SchemaField {
-    pub name: &'static str,
-    pub field_type: &'static str,
+    pub name: Cow<'static, str>,
+    pub field_type: Cow<'static, str>,
     pub required: bool,
 }
 
 /// A schema definition parsed from VRL annotations.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SchemaDef {
-    pub name: &'static str,
-    pub fields: &'static [SchemaField],
+    pub name: Cow<'static, str>,
+    pub fields: Cow<'static, [SchemaField]>,
+}
+
+impl SchemaDef {
+    /// Build the Arrow schema for this definition.
+    ///
+    /// Uses the same type mapping as the built-in schemas: `timestamp` is
+    /// `Timestamp(Microsecond)`, `duration` is `Duration(Microsecond)`,
+    /// `dict_string` is `Dictionary(Int32, Utf8)`,
+    /// `json` is stored as `Utf8`, and fields not marked `required` are
+    /// nullable.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SchemaMismatch` if a field has an unknown type.
+    pub fn to_arrow_schema(&self) -> Result<Schema> {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                let data_type = AnnotationType::parse(&field.field_type)
+                    .map(AnnotationType::data_type)
+                    .ok_or_else(|| {
+                        Error::SchemaMismatch(format!(
+                            "unknown type '{}' for field '{}' in schema '{}'",
+                            field.field_type, field.name, self.name
+                        ))
+                    })?;
+                Ok(Field::new(field.name.as_ref(), data_type, !field.required))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Schema::new(fields))
+    }
+
+    /// Build the Arrow schema for this definition, wrapped in an `Arc`.
+    pub fn to_arrow_schema_ref(&self) -> Result<Arc<Schema>> {
+        self.to_arrow_schema().map(Arc::new)
+    }
 }
 
 /// Return all schema definitions parsed from VRL.
@@ -29,3 +79,186 @@ pub fn schema_defs() -> &'static [SchemaDef] {
 pub fn schema_def(name: &str) -> Option<&'static SchemaDef> {
     ALL_SCHEMA_DEFS.iter().find(|schema| schema.name == name)
 }
+
+/// Parse the `# @schema` block from VRL source at runtime.
+///
+/// Accepts the same annotation format as the built-in VRL scripts:
+///
+/// ```text
+/// # @schema my_table
+/// # @description Optional, ignored
+/// #
+/// # timestamp: timestamp, required, "Event time"
+/// # service_name: string
+/// # @end
+/// ```
+///
+/// # Arguments
+///
+/// * `source` - VRL source containing a `# @schema` block
+///
+/// # Returns
+///
+/// The parsed schema definition. Call [`SchemaDef::to_arrow_schema`] to get
+/// the Arrow schema for `values_to_arrow`.
+///
+/// # Errors
+///
+/// Returns `Error::SchemaMismatch` if there is no `@schema` block, a field
+/// line is malformed, a field has an unknown type, or a field name repeats.
+///
+/// # Example
+///
+/// ```ignore
+/// let def = parse_schema(source)?;
+/// let program = CustomProgram::compile(source)?;
+/// let values = apply_log_transform_with(values, &program)?;
+/// let batch = values_to_arrow(&values, &def.to_arrow_schema()?)?;
+/// ```
+pub fn parse_schema(source: &str) -> Result<SchemaDef> {
+    let parsed = annotation::parse_schema_block(source)
+        .ok_or_else(|| Error::SchemaMismatch("no @schema annotation found".to_string()))?;
+
+    if let Some(line) = parsed.invalid_lines.first() {
+        return Err(Error::SchemaMismatch(format!(
+            "failed to parse schema field in '{}': {line}",
+            parsed.name
+        )));
+    }
+
+    let mut fields: Vec<SchemaField> = Vec::with_capacity(parsed.fields.len());
+    for field in parsed.fields {
+        if AnnotationType::parse(&field.field_type).is_none() {
+            return Err(Error::SchemaMismatch(format!(
+                "unknown type '{}' for field '{}' in schema '{}'",
+                field.field_type, field.name, parsed.name
+            )));
+        }
+        if fields.iter().any(|existing| existing.name == field.name) {
+            return Err(Error::SchemaMismatch(format!(
+                "duplicate field '{}' in schema '{}'",
+                field.name, parsed.name
+            )));
+        }
+        fields.push(SchemaField {
+            name: Cow::Owned(field.name),
+            field_type: Cow::Owned(field.field_type),
+            required: field.required,
+        });
+    }
+
+    Ok(SchemaDef {
+        name: Cow::Owned(parsed.name),
+        fields: Cow::Owned(fields),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{DataType, TimeUnit};
+
+    const CUSTOM_VRL: &str = r#"
+# @schema custom
+# @description Custom output table
+#
+# timestamp: timestamp, required, "Event time"
+# service_name: string, "Service name"
+# region: dict_string
+# count: int64, required
+# ratio: float64
+# ok: bool
+# attributes: json
+# @end
+
+.service_name = "x"
+"#;
+
+    #[test]
+    fn test_parse_schema() {
+        let def = parse_schema(CUSTOM_VRL).unwrap();
+        assert_eq!(def.name, "custom");
+        assert_eq!(def.fields.len(), 7);
+        assert_eq!(def.fields[0].name, "timestamp");
+        assert_eq!(def.fields[0].field_type, "timestamp");
+        assert!(def.fields[0].required);
+        assert!(!def.fields[1].required);
+    }
+
+    #[test]
+    fn test_parse_schema_to_arrow() {
+        let schema = parse_schema(CUSTOM_VRL).unwrap().to_arrow_schema().unwrap();
+        assert_eq!(
+            schema.field_with_name("timestamp").unwrap().data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        assert!(!schema.field_with_name("timestamp").unwrap().is_nullable());
+        assert_eq!(
+            schema.field_with_name("count").unwrap().data_type(),
+            &DataType::Int64
+        );
+        assert_eq!(
+            schema.field_with_name("ok").unwrap().data_type(),
+            &DataType::Boolean
+        );
+        assert_eq!(
+            schema.field_with_name("attributes").unwrap().data_type(),
+            &DataType::Utf8
+        );
+        assert_eq!(
+            schema.field_with_name("region").unwrap().data_type(),
+            &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+        );
+        assert!(schema.field_with_name("ratio").unwrap().is_nullable());
+    }
+
+    #[test]
+    fn test_parse_schema_matches_builtin() {
+        for def in schema_defs() {
+            let source = match def.name.as_ref() {
+                "logs" => OTLP_LOGS_SOURCE,
+                "spans" => OTLP_TRACES_SOURCE,
+                "gauge" => OTLP_GAUGE_SOURCE,
+                "sum" => OTLP_SUM_SOURCE,
+                "histogram" => OTLP_HISTOGRAM_SOURCE,
+                "exp_histogram" => OTLP_EXP_HISTOGRAM_SOURCE,
+                "summary" => OTLP_SUMMARY_SOURCE,
+                "profiles" => OTLP_PROFILES_SOURCE,
+                other => panic!("unexpected schema {other}"),
+            };
+            let parsed = parse_schema(source).unwrap();
+            assert_eq!(&parsed, def);
+        }
+        assert_eq!(
+            parse_schema(OTLP_LOGS_SOURCE)
+                .unwrap()
+                .to_arrow_schema()
+                .unwrap(),
+            *OTLP_LOGS_SCHEMA
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_missing_block() {
+        let err = parse_schema(".foo = 1").unwrap_err();
+        assert!(matches!(err, Error::SchemaMismatch(_)));
+    }
+
+    #[test]
+    fn test_parse_schema_unknown_type() {
+        let err = parse_schema("# @schema t\n# a: uuid\n# @end\n").unwrap_err();
+        assert!(err.to_string().contains("unknown type 'uuid'"));
+    }
+
+    #[test]
+    fn test_parse_schema_malformed_field() {
+        let err = parse_schema("# @schema t\n# a: required\n# @end\n").unwrap_err();
+        assert!(err.to_string().contains("failed to parse schema field"));
+    }
+
+    #[test]
+    fn test_parse_schema_duplicate_field() {
+        let err = parse_schema("# @schema t\n# a: string\n# a: int64\n# @end\n").unwrap_err();
+        assert!(err.to_string().contains("duplicate field 'a'"));
+    }
+}
@@ -0,0 +1,178 @@
+//! Parser for `# @schema` annotation blocks in VRL sources.
+//!
+//! This file is shared with `build.rs` (included there via `#[path]`), so it
+//! must only depend on `std` and `arrow-schema`.
+//!
+//! Format:
+//!
+//! ```text
+//! # @schema NAME
+//! # @description free text (ignored)
+//! #
+//! # field_name: type, required, "description"
+//! # other_field: type
+//! # @end
+//! ```
+
+use arrow_schema::{DataType, TimeUnit};
+
+/// Column types accepted in `@schema` field lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnnotationType {
+    Timestamp,
+    /// Elapsed time, in the unit of the timestamps
+    Duration,
+    Int64,
+    Int32,
+    Float64,
+    Bool,
+    String,
+    /// Dictionary-encoded string, for low-cardinality columns
+    DictString,
+    Json,
+}
+
+impl AnnotationType {
+    /// Parse a type name as written in an annotation.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "timestamp" => Some(Self::Timestamp),
+            "duration" => Some(Self::Duration),
+            "int64" => Some(Self::Int64),
+            "int32" => Some(Self::Int32),
+            "float64" => Some(Self::Float64),
+            "bool" => Some(Self::Bool),
+            "string" => Some(Self::String),
+            "dict_string" => Some(Self::DictString),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// Arrow data type columns of this type are stored as.
+    pub fn data_type(self) -> DataType {
+        match self {
+            Self::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+            Self::Duration => DataType::Duration(TimeUnit::Microsecond),
+            Self::Int64 => DataType::Int64,
+            Self::Int32 => DataType::Int32,
+            Self::Float64 => DataType::Float64,
+            Self::Bool => DataType::Boolean,
+            Self::String => DataType::Utf8,
+            Self::DictString => {
+                DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+            }
+            // JSON stored as string
+            Self::Json => DataType::Utf8,
+        }
+    }
+}
+
+/// A field line parsed from an `@schema` block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnnotatedField {
+    pub name: String,
+    pub field_type: String,
+    pub required: bool,
+}
+
+/// An `@schema` block parsed from VRL source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnnotatedSchema {
+    pub name: String,
+    pub fields: Vec<AnnotatedField>,
+    /// Field lines that could not be parsed (without the leading `# `)
+    pub invalid_lines: Vec<String>,
+}
+
+/// Parse the first `# @schema` block in `source`.
+///
+/// Returns `None` if the source has no `@schema` line.
+pub fn parse_schema_block(source: &str) -> Option<AnnotatedSchema> {
+    let mut in_schema_block = false;
+    let mut schema_name = None;
+    let mut fields = Vec::new();
+    let mut invalid_lines = Vec::new();
+
+    for line in source.lines() {
+        let line = line.trim();
+
+        // Start of schema block
+        if let Some(name) = line.strip_prefix("# @schema ") {
+            in_schema_block = true;
+            schema_name = Some(name.trim().to_string());
+            continue;
+        }
+
+        // End of schema block
+        if line == "# @end" {
+            break;
+        }
+
+        if !in_schema_block {
+            continue;
+        }
+
+        // Skip @description and empty lines
+        if line.starts_with("# @") || line == "#" || line.is_empty() {
+            continue;
+        }
+
+        // Field definition: # field_name: type, required?, "description"?
+        if let Some(body) = line.strip_prefix("# ") {
+            if !body.contains(':') {
+                continue;
+            }
+            match parse_field_line(body) {
+                Some(field) => fields.push(field),
+                None => invalid_lines.push(body.to_string()),
+            }
+        }
+    }
+
+    schema_name.map(|name| AnnotatedSchema {
+        name,
+        fields,
+        invalid_lines,
+    })
+}
+
+/// Parse a single field line: `field_name: type, required?, "description"?`
+pub fn parse_field_line(line: &str) -> Option<AnnotatedField> {
+    let mut parts = line.splitn(2, ':');
+    let name = parts.next()?.trim().to_string();
+    let rest = parts.next()?.trim();
+
+    if name.is_empty() {
+        return None;
+    }
+
+    // Strip description in quotes if present
+    let rest = if let Some(quote_start) = rest.find('"') {
+        rest[..quote_start].trim()
+    } else {
+        rest
+    };
+
+    // Parse type and required flag
+    let mut field_type = String::new();
+    let mut required = false;
+    for part in rest.split(',') {
+        let part = part.trim();
+        if part == "required" {
+            required = true;
+        } else if !part.is_empty() && field_type.is_empty() {
+            field_type = part.to_string();
+        }
+    }
+
+    if field_type.is_empty() {
+        return None;
+    }
+
+    Some(AnnotatedField {
+        name,
+        field_type,
+        required,
+    })
+}
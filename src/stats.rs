@@ -0,0 +1,150 @@
+//! Record accounting for a transform.
+//!
+//! [`TransformStats`] is returned next to the output of every high-level
+//! transform so callers can see how many records came in, how many made it
+//! into the batches, and what happened to the rest.
+
+use serde::Serialize;
+use vrl::value::{KeyString, Value};
+
+use crate::decode::{DecodeMetricsResult, Decoded, SkippedMetrics};
+
+/// Counts of records and values for one transform.
+///
+/// `records_in` always equals `records_out + dropped.total()`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TransformStats {
+    /// Records in the payload (log records, spans, data points or samples)
+    pub records_in: usize,
+    /// Rows written to the output batches
+    pub records_out: usize,
+    /// Records left out of the output, by reason
+    pub dropped: DroppedRecords,
+    /// Values stored as null although the transformed value was not null
+    /// (e.g. an empty string in a nested column)
+    pub null_coerced_values: usize,
+    /// Trace, span and parent span IDs that are not 32/16 hex characters.
+    /// The IDs are kept as they are.
+    pub invalid_ids: usize,
+    /// Timestamps past the year 2262. Strict transforms fail on the first
+    /// one; lenient transforms drop the record.
+    pub timestamp_overflows: usize,
+}
+
+/// Records dropped from a transform, by reason.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DroppedRecords {
+    /// Metric data points with a NaN value
+    pub nan_values: usize,
+    /// Metric data points with an infinite value
+    pub infinity_values: usize,
+    /// Metric data points without a value
+    pub missing_values: usize,
+    /// Records that failed to decode (lenient mode only)
+    pub decode_errors: usize,
+    /// Records whose VRL program failed (lenient mode only)
+    pub vrl_errors: usize,
+    /// Records that didn't fit the Arrow schema (lenient mode only)
+    pub arrow_errors: usize,
+}
+
+impl DroppedRecords {
+    /// Total number of dropped records
+    pub fn total(&self) -> usize {
+        self.nan_values
+            + self.infinity_values
+            + self.missing_values
+            + self.decode_errors
+            + self.vrl_errors
+            + self.arrow_errors
+    }
+}
+
+impl TransformStats {
+    /// Add the counts of another transform, e.g. to total the requests of a
+    /// stream.
+    pub fn merge(&mut self, other: &TransformStats) {
+        self.records_in += other.records_in;
+        self.records_out += other.records_out;
+        self.dropped.nan_values += other.dropped.nan_values;
+        self.dropped.infinity_values += other.dropped.infinity_values;
+        self.dropped.missing_values += other.dropped.missing_values;
+        self.dropped.decode_errors += other.dropped.decode_errors;
+        self.dropped.vrl_errors += other.dropped.vrl_errors;
+        self.dropped.arrow_errors += other.dropped.arrow_errors;
+        self.null_coerced_values += other.null_coerced_values;
+        self.invalid_ids += other.invalid_ids;
+        self.timestamp_overflows += other.timestamp_overflows;
+    }
+
+    /// Start the accounting for `records_in` decoded records.
+    pub(crate) fn received(records_in: usize) -> Self {
+        Self {
+            records_in,
+            ..Self::default()
+        }
+    }
+
+    /// Start the accounting for decoded metrics, counting skipped data
+    /// points as dropped.
+    pub(crate) fn received_metrics(decoded: &DecodeMetricsResult) -> Self {
+        let mut stats = Self::received(decoded.values.len());
+        stats.add_skipped(&decoded.skipped);
+        stats
+    }
+
+    /// Start the accounting from the decoder's output.
+    pub(crate) fn decoded<T>(decoded: &Decoded<T>) -> Self {
+        Self {
+            records_in: decoded.records(),
+            dropped: DroppedRecords {
+                decode_errors: decoded.errors.len(),
+                ..DroppedRecords::default()
+            },
+            timestamp_overflows: decoded.timestamp_overflows,
+            ..Self::default()
+        }
+    }
+
+    /// Add metric data points the decoder skipped for invalid values.
+    pub(crate) fn add_skipped(&mut self, skipped: &SkippedMetrics) {
+        self.records_in += skipped.total();
+        self.dropped.nan_values += skipped.nan_values;
+        self.dropped.infinity_values += skipped.infinity_values;
+        self.dropped.missing_values += skipped.missing_values;
+    }
+
+    /// Count the invalid IDs in transformed records.
+    pub(crate) fn count_ids(&mut self, values: &[Value]) {
+        let fields: [(KeyString, usize); 3] = [
+            ("trace_id".into(), 32),
+            ("span_id".into(), 16),
+            ("parent_span_id".into(), 16),
+        ];
+
+        for value in values {
+            let Value::Object(map) = value else {
+                continue;
+            };
+            for (field, len) in &fields {
+                if let Some(id) = map.get(field) {
+                    self.count_id(id, *len);
+                }
+            }
+        }
+    }
+
+    /// Count `id` if it is a non-empty string that isn't `len` hex
+    /// characters.
+    pub(crate) fn count_id(&mut self, id: &Value, len: usize) {
+        if let Value::Bytes(id) = id {
+            if !id.is_empty() && !is_hex_id(id, len) {
+                self.invalid_ids += 1;
+            }
+        }
+    }
+}
+
+fn is_hex_id(id: &[u8], len: usize) -> bool {
+    id.len() == len && id.iter().all(u8::is_ascii_hexdigit)
+}
@@ -0,0 +1,470 @@
+//! Protobuf vs JSON equivalence checks.
+//!
+//! The protobuf and OTLP JSON decoders of each signal are separate code
+//! paths. These helpers re-encode a protobuf export request as OTLP JSON,
+//! transform both payloads and report every value that differs between the
+//! two outputs, down to the field inside JSON columns such as `events_json`
+//! or `exemplars_json`:
+//!
+//! ```ignore
+//! use otlp2records::testing::golden_cases;
+//!
+//! for case in golden_cases("testdata")? {
+//!     let diffs = case.diffs()?;
+//!     assert!(diffs.is_empty(), "{}: {diffs:#?}", case.name);
+//! }
+//! ```
+//!
+//! Profiles are not covered: the OTLP JSON mapping of profiles is still in
+//! development.
+
+use std::fmt;
+use std::path::Path;
+
+use ::arrow::array::Array;
+use ::arrow::record_batch::RecordBatch;
+use ::arrow::util::display::{ArrayFormatter, FormatOptions};
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use prost::Message;
+use serde_json::Value as JsonValue;
+
+use crate::encode::encode_request;
+use crate::error::{Error, Result};
+use crate::{transform_logs, transform_metrics, transform_traces, EncodeFormat, InputFormat};
+
+/// Signal of an OTLP export request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Signal {
+    /// `ExportLogsServiceRequest`
+    Logs,
+    /// `ExportTraceServiceRequest`
+    Traces,
+    /// `ExportMetricsServiceRequest`
+    Metrics,
+}
+
+impl Signal {
+    /// Infer the signal from a file name starting with `logs`, `traces` or
+    /// `metrics` (e.g. `metrics_gauge.pb`).
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        [
+            ("logs", Signal::Logs),
+            ("traces", Signal::Traces),
+            ("metrics", Signal::Metrics),
+        ]
+        .into_iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, signal)| signal)
+    }
+}
+
+/// A protobuf payload and its OTLP JSON re-encoding.
+#[derive(Debug, Clone)]
+pub struct GoldenCase {
+    /// File name of the protobuf payload
+    pub name: String,
+    /// Signal inferred from the file name
+    pub signal: Signal,
+    /// The protobuf payload
+    pub protobuf: Vec<u8>,
+    /// The same request as OTLP JSON
+    pub json: Vec<u8>,
+}
+
+impl GoldenCase {
+    /// Transform both payloads and return the differences between the
+    /// outputs. See [`compare_protobuf_json`].
+    pub fn diffs(&self) -> Result<Vec<FieldDiff>> {
+        compare(self.signal, &self.protobuf, &self.json)
+    }
+}
+
+/// A value that differs between the protobuf and JSON outputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// Output table (`logs`, `spans`, `gauge`, `sum`, ...)
+    pub table: String,
+    /// Column name, or empty for a difference in the row count
+    pub column: String,
+    /// Row index, or `None` for a difference in the schema or row count
+    pub row: Option<usize>,
+    /// Path of the differing field inside a JSON column (e.g.
+    /// `[0].attributes.key`), or empty
+    pub path: String,
+    /// Value from the protobuf payload
+    pub protobuf: String,
+    /// Value from the JSON payload
+    pub json: String,
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.table)?;
+        if !self.column.is_empty() {
+            write!(f, ".{}", self.column)?;
+        }
+        if let Some(row) = self.row {
+            write!(f, "[{row}]")?;
+        }
+        write!(
+            f,
+            "{}: protobuf {} != json {}",
+            self.path, self.protobuf, self.json
+        )
+    }
+}
+
+/// Read every `*.pb` file in `dir` (sorted by name) and re-encode it as OTLP
+/// JSON. The signal comes from the file name (see
+/// [`Signal::from_file_name`]).
+pub fn golden_cases(dir: impl AsRef<Path>) -> Result<Vec<GoldenCase>> {
+    let dir = dir.as_ref();
+    let io_error = |e: std::io::Error| Error::InvalidInput(format!("{}: {e}", dir.display()));
+
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.extension().is_some_and(|ext| ext == "pb") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let signal = Signal::from_file_name(&name).ok_or_else(|| {
+                Error::InvalidInput(format!(
+                    "{name}: expected a name starting with logs, traces or metrics"
+                ))
+            })?;
+            let protobuf = std::fs::read(&path).map_err(io_error)?;
+            let json = protobuf_to_json(signal, &protobuf)?;
+            Ok(GoldenCase {
+                name,
+                signal,
+                protobuf,
+                json,
+            })
+        })
+        .collect()
+}
+
+/// Re-encode a protobuf export request as OTLP JSON.
+pub fn protobuf_to_json(signal: Signal, protobuf: &[u8]) -> Result<Vec<u8>> {
+    match signal {
+        Signal::Logs => encode_request(
+            &ExportLogsServiceRequest::decode(protobuf)?,
+            EncodeFormat::Json,
+        ),
+        Signal::Traces => encode_request(
+            &ExportTraceServiceRequest::decode(protobuf)?,
+            EncodeFormat::Json,
+        ),
+        Signal::Metrics => encode_request(
+            &ExportMetricsServiceRequest::decode(protobuf)?,
+            EncodeFormat::Json,
+        ),
+    }
+}
+
+/// Transform a protobuf payload and its JSON re-encoding, and return the
+/// differences between the outputs. An empty result means the two decoders
+/// agree.
+pub fn compare_protobuf_json(signal: Signal, protobuf: &[u8]) -> Result<Vec<FieldDiff>> {
+    compare(signal, protobuf, &protobuf_to_json(signal, protobuf)?)
+}
+
+fn compare(signal: Signal, protobuf: &[u8], json: &[u8]) -> Result<Vec<FieldDiff>> {
+    match signal {
+        Signal::Logs => Ok(diff_batches(
+            "logs",
+            &transform_logs(protobuf, InputFormat::Protobuf)?.batch,
+            &transform_logs(json, InputFormat::Json)?.batch,
+        )),
+        Signal::Traces => Ok(diff_batches(
+            "spans",
+            &transform_traces(protobuf, InputFormat::Protobuf)?.batch,
+            &transform_traces(json, InputFormat::Json)?.batch,
+        )),
+        Signal::Metrics => {
+            let from_protobuf = transform_metrics(protobuf, InputFormat::Protobuf)?;
+            let from_json = transform_metrics(json, InputFormat::Json)?;
+            let tables = [
+                ("gauge", from_protobuf.gauge, from_json.gauge),
+                ("sum", from_protobuf.sum, from_json.sum),
+                ("histogram", from_protobuf.histogram, from_json.histogram),
+                (
+                    "exp_histogram",
+                    from_protobuf.exp_histogram,
+                    from_json.exp_histogram,
+                ),
+                ("summary", from_protobuf.summary, from_json.summary),
+            ];
+
+            let mut diffs = Vec::new();
+            for (table, protobuf, json) in tables {
+                match (protobuf, json) {
+                    (Some(protobuf), Some(json)) => {
+                        diffs.extend(diff_batches(table, &protobuf, &json))
+                    }
+                    (None, None) => {}
+                    (protobuf, json) => diffs.push(FieldDiff {
+                        table: table.to_string(),
+                        column: String::new(),
+                        row: None,
+                        path: String::new(),
+                        protobuf: describe_rows(protobuf.as_ref()),
+                        json: describe_rows(json.as_ref()),
+                    }),
+                }
+            }
+            Ok(diffs)
+        }
+    }
+}
+
+/// Compare two batches value by value.
+///
+/// Columns are matched by name. Differing JSON strings are compared field by
+/// field, reporting each differing leaf with its path.
+pub fn diff_batches(table: &str, protobuf: &RecordBatch, json: &RecordBatch) -> Vec<FieldDiff> {
+    let mut diffs = Vec::new();
+    let diff = |column: &str, row, path: String, protobuf: String, json: String| FieldDiff {
+        table: table.to_string(),
+        column: column.to_string(),
+        row,
+        path,
+        protobuf,
+        json,
+    };
+
+    if protobuf.num_rows() != json.num_rows() {
+        diffs.push(diff(
+            "",
+            None,
+            String::new(),
+            describe_rows(Some(protobuf)),
+            describe_rows(Some(json)),
+        ));
+        return diffs;
+    }
+
+    let json_schema = json.schema();
+    for field in protobuf.schema().fields() {
+        let name = field.name();
+        let Some((_, json_field)) = json_schema.column_with_name(name) else {
+            diffs.push(diff(
+                name,
+                None,
+                String::new(),
+                field.data_type().to_string(),
+                "missing".to_string(),
+            ));
+            continue;
+        };
+        if field.data_type() != json_field.data_type() {
+            diffs.push(diff(
+                name,
+                None,
+                String::new(),
+                field.data_type().to_string(),
+                json_field.data_type().to_string(),
+            ));
+            continue;
+        }
+
+        let protobuf_column = protobuf.column_by_name(name).expect("field is in schema");
+        let json_column = json.column_by_name(name).expect("field is in schema");
+        if protobuf_column == json_column {
+            continue;
+        }
+
+        let options = FormatOptions::default().with_null("null");
+        let (Ok(protobuf_values), Ok(json_values)) = (
+            ArrayFormatter::try_new(protobuf_column.as_ref(), &options),
+            ArrayFormatter::try_new(json_column.as_ref(), &options),
+        ) else {
+            diffs.push(diff(
+                name,
+                None,
+                String::new(),
+                "unformattable column".to_string(),
+                "unformattable column".to_string(),
+            ));
+            continue;
+        };
+
+        for row in 0..protobuf.num_rows() {
+            let protobuf_value = protobuf_values.value(row).to_string();
+            let json_value = json_values.value(row).to_string();
+            if protobuf_value == json_value
+                && protobuf_column.is_null(row) == json_column.is_null(row)
+            {
+                continue;
+            }
+
+            match (
+                serde_json::from_str::<JsonValue>(&protobuf_value),
+                serde_json::from_str::<JsonValue>(&json_value),
+            ) {
+                (Ok(p @ (JsonValue::Object(_) | JsonValue::Array(_))), Ok(j)) => {
+                    let mut leaves = Vec::new();
+                    json_diffs(&p, &j, String::new(), &mut leaves);
+                    diffs.extend(
+                        leaves
+                            .into_iter()
+                            .map(|(path, p, j)| diff(name, Some(row), path, p, j)),
+                    );
+                }
+                _ => diffs.push(diff(
+                    name,
+                    Some(row),
+                    String::new(),
+                    protobuf_value,
+                    json_value,
+                )),
+            }
+        }
+    }
+
+    for field in json_schema.fields() {
+        if protobuf.schema().column_with_name(field.name()).is_none() {
+            diffs.push(diff(
+                field.name(),
+                None,
+                String::new(),
+                "missing".to_string(),
+                field.data_type().to_string(),
+            ));
+        }
+    }
+
+    diffs
+}
+
+/// Collect the differing leaves of two JSON values as (path, protobuf, json).
+fn json_diffs(
+    protobuf: &JsonValue,
+    json: &JsonValue,
+    path: String,
+    out: &mut Vec<(String, String, String)>,
+) {
+    match (protobuf, json) {
+        (JsonValue::Object(p), JsonValue::Object(j)) => {
+            for (key, p_value) in p {
+                let path = format!("{path}.{key}");
+                match j.get(key) {
+                    Some(j_value) => json_diffs(p_value, j_value, path, out),
+                    None => out.push((path, p_value.to_string(), "missing".to_string())),
+                }
+            }
+            for (key, j_value) in j {
+                if !p.contains_key(key) {
+                    out.push((
+                        format!("{path}.{key}"),
+                        "missing".to_string(),
+                        j_value.to_string(),
+                    ));
+                }
+            }
+        }
+        (JsonValue::Array(p), JsonValue::Array(j)) if p.len() == j.len() => {
+            for (index, (p_value, j_value)) in p.iter().zip(j).enumerate() {
+                json_diffs(p_value, j_value, format!("{path}[{index}]"), out);
+            }
+        }
+        (p, j) if p != j => out.push((path, p.to_string(), j.to_string())),
+        _ => {}
+    }
+}
+
+fn describe_rows(batch: Option<&RecordBatch>) -> String {
+    match batch {
+        Some(batch) => format!("{} rows", batch.num_rows()),
+        None => "no batch".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::arrow::array::StringArray;
+    use ::arrow::datatypes::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_signal_from_file_name() {
+        assert_eq!(Signal::from_file_name("logs_large.pb"), Some(Signal::Logs));
+        assert_eq!(
+            Signal::from_file_name("metrics_gauge.pb"),
+            Some(Signal::Metrics)
+        );
+        assert_eq!(Signal::from_file_name("profiles.pb"), None);
+    }
+
+    fn events_batch(events: &[Option<&str>]) -> RecordBatch {
+        let schema = Schema::new(vec![Field::new("events_json", DataType::Utf8, true)]);
+        let column = StringArray::from(events.to_vec());
+        RecordBatch::try_new(Arc::new(schema), vec![Arc::new(column)]).unwrap()
+    }
+
+    #[test]
+    fn test_diff_batches_reports_json_field_paths() {
+        let protobuf = events_batch(&[None, Some(r#"[{"name":"retry","attributes":{"n":2}}]"#)]);
+        let json = events_batch(&[
+            None,
+            Some(r#"[{"name":"retry","attributes":{"n":"2","m":1}}]"#),
+        ]);
+        assert!(diff_batches("spans", &protobuf, &protobuf).is_empty());
+
+        let diffs = diff_batches("spans", &protobuf, &json);
+
+        assert_eq!(diffs.len(), 2, "{diffs:#?}");
+        assert_eq!(diffs[0].row, Some(1));
+        assert_eq!(diffs[0].path, "[0].attributes.n");
+        assert_eq!(
+            (diffs[0].protobuf.as_str(), diffs[0].json.as_str()),
+            ("2", "\"2\"")
+        );
+        assert_eq!(diffs[1].path, "[0].attributes.m");
+        assert_eq!(diffs[1].protobuf, "missing");
+        assert_eq!(
+            diffs[0].to_string(),
+            r#"spans.events_json[1][0].attributes.n: protobuf 2 != json "2""#
+        );
+    }
+
+    #[test]
+    fn test_diff_batches_null_vs_value() {
+        let diffs = diff_batches(
+            "spans",
+            &events_batch(&[None]),
+            &events_batch(&[Some("[]")]),
+        );
+
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(
+            (diffs[0].protobuf.as_str(), diffs[0].json.as_str()),
+            ("null", "[]")
+        );
+    }
+
+    #[test]
+    fn test_diff_batches_row_count() {
+        let pb = include_bytes!("../testdata/logs_large.pb");
+        let batch = transform_logs(pb, InputFormat::Protobuf).unwrap().batch;
+
+        let diffs = diff_batches("logs", &batch, &batch.slice(0, 1));
+
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].row, None);
+        assert_eq!(diffs[0].json, "1 rows");
+    }
+}
@@ -178,8 +178,7 @@ struct EncodeJsonFn {
 impl FunctionExpression for EncodeJsonFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        let json = crate::convert::vrl_value_to_json_lossy(&value);
-        Ok(Value::Bytes(json.to_string().into()))
+        Ok(encode_json(&value))
     }
 
     fn type_def(&self, _state: &TypeState) -> TypeDef {
@@ -187,6 +186,12 @@ impl FunctionExpression for EncodeJsonFn {
     }
 }
 
+/// `encode_json` on a plain value, shared with the native fast path.
+pub(crate) fn encode_json(value: &Value) -> Value {
+    let json = crate::convert::vrl_value_to_json_lossy(value);
+    Value::Bytes(json.to_string().into())
+}
+
 // --- get ---
 /// Object path lookup
 #[derive(Clone, Copy, Debug)]
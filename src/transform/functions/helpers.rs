@@ -45,25 +45,7 @@ struct StringOrNullFn {
 impl FunctionExpression for StringOrNullFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        let s = match value {
-            Value::Null => return Ok(Value::Null),
-            Value::Bytes(b) => {
-                if b.is_empty() {
-                    return Ok(Value::Null);
-                }
-                String::from_utf8_lossy(&b).to_string()
-            }
-            Value::Integer(i) => i.to_string(),
-            Value::Float(f) => f.to_string(),
-            Value::Boolean(b) => b.to_string(),
-            // Objects and arrays: return null (not string-convertible)
-            _ => return Ok(Value::Null),
-        };
-        if s.is_empty() {
-            Ok(Value::Null)
-        } else {
-            Ok(Value::Bytes(s.into()))
-        }
+        Ok(string_or_null(value))
     }
 
     fn type_def(&self, _state: &TypeState) -> TypeDef {
@@ -71,6 +53,29 @@ impl FunctionExpression for StringOrNullFn {
     }
 }
 
+/// `string_or_null` on a plain value, shared with the native fast path.
+pub(crate) fn string_or_null(value: Value) -> Value {
+    let s = match value {
+        Value::Null => return Value::Null,
+        Value::Bytes(b) => {
+            if b.is_empty() {
+                return Value::Null;
+            }
+            String::from_utf8_lossy(&b).to_string()
+        }
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Boolean(b) => b.to_string(),
+        // Objects and arrays: return null (not string-convertible)
+        _ => return Value::Null,
+    };
+    if s.is_empty() {
+        Value::Null
+    } else {
+        Value::Bytes(s.into())
+    }
+}
+
 // --- nanos_to_millis ---
 /// Safely converts nanoseconds to milliseconds (integer division)
 #[derive(Clone, Copy, Debug)]
@@ -249,19 +254,7 @@ struct JsonOrNullFn {
 impl FunctionExpression for JsonOrNullFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
-        let is_empty = match &value {
-            Value::Null => true,
-            Value::Bytes(b) => b.is_empty(),
-            Value::Array(arr) => arr.is_empty(),
-            Value::Object(map) => map.is_empty(),
-            _ => false,
-        };
-        if is_empty {
-            Ok(Value::Null)
-        } else {
-            let json = crate::convert::vrl_value_to_json_lossy(&value);
-            Ok(Value::Bytes(json.to_string().into()))
-        }
+        Ok(json_or_null(&value))
     }
 
     fn type_def(&self, _state: &TypeState) -> TypeDef {
@@ -269,6 +262,23 @@ impl FunctionExpression for JsonOrNullFn {
     }
 }
 
+/// `json_or_null` on a plain value, shared with the native fast path.
+pub(crate) fn json_or_null(value: &Value) -> Value {
+    let is_empty = match value {
+        Value::Null => true,
+        Value::Bytes(b) => b.is_empty(),
+        Value::Array(arr) => arr.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    };
+    if is_empty {
+        Value::Null
+    } else {
+        let json = crate::convert::vrl_value_to_json_lossy(value);
+        Value::Bytes(json.to_string().into())
+    }
+}
+
 // --- int_or_default ---
 /// Returns the integer value if present, or the default if null
 #[derive(Clone, Copy, Debug)]
@@ -406,33 +416,38 @@ impl FunctionExpression for GetAttrFn {
             _ => return Ok(default_value),
         };
 
-        match object {
-            Value::Object(map) => {
-                let key_string: KeyString = key_str.as_str().into();
-                match map.get(&key_string) {
-                    Some(v) if !matches!(v, Value::Null) => {
-                        // Convert to string if it's bytes, otherwise use as-is
-                        match v {
-                            Value::Bytes(b) => {
-                                if b.is_empty() {
-                                    Ok(default_value)
-                                } else {
-                                    Ok(v.clone())
-                                }
+        Ok(get_attr(&object, &key_str, default_value))
+    }
+
+    fn type_def(&self, _state: &TypeState) -> TypeDef {
+        TypeDef::bytes().add_null().infallible()
+    }
+}
+
+/// `get_attr` on a plain value, shared with the native fast path.
+pub(crate) fn get_attr(object: &Value, key: &str, default_value: Value) -> Value {
+    match object {
+        Value::Object(map) => {
+            let key_string: KeyString = key.into();
+            match map.get(&key_string) {
+                Some(v) if !matches!(v, Value::Null) => {
+                    // Convert to string if it's bytes, otherwise use as-is
+                    match v {
+                        Value::Bytes(b) => {
+                            if b.is_empty() {
+                                default_value
+                            } else {
+                                v.clone()
                             }
-                            _ => Ok(v.clone()),
                         }
+                        _ => v.clone(),
                     }
-                    _ => Ok(default_value),
                 }
+                _ => default_value,
             }
-            Value::Null => Ok(default_value),
-            _ => Ok(default_value),
         }
-    }
-
-    fn type_def(&self, _state: &TypeState) -> TypeDef {
-        TypeDef::bytes().add_null().infallible()
+        Value::Null => default_value,
+        _ => default_value,
     }
 }
 
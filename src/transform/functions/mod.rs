@@ -9,6 +9,9 @@ use vrl::compiler::Function;
 pub use self::core::{EncodeJson, Floor, Get, IsArray, IsEmpty, IsObject, ToInt, ToString_};
 pub use helpers::{GetAttr, IntOrDefault, JsonOrNull, NanosToMicros, NanosToMillis, StringOrNull};
 
+pub(crate) use self::core::encode_json;
+pub(crate) use helpers::{get_attr, json_or_null, string_or_null};
+
 /// Get all custom functions for VRL compilation
 pub fn all() -> Vec<Box<dyn Function>> {
     vec![
@@ -5,11 +5,15 @@
 //! OTLP data into the target schema.
 
 pub mod functions;
+pub(crate) mod nanos;
+pub mod promote;
 pub mod runtime;
 
+pub use promote::{AttributeScope, PromotedAttribute, PromotedAttributes, PromotedType};
 pub use runtime::{
-    VrlError, VrlTransformer, OTLP_EXP_HISTOGRAM_PROGRAM, OTLP_GAUGE_PROGRAM,
-    OTLP_HISTOGRAM_PROGRAM, OTLP_LOGS_PROGRAM, OTLP_SUM_PROGRAM, OTLP_TRACES_PROGRAM,
+    CustomProgram, VrlError, VrlTransformer, OTLP_EXP_HISTOGRAM_PROGRAM, OTLP_GAUGE_PROGRAM,
+    OTLP_HISTOGRAM_PROGRAM, OTLP_LOGS_PROGRAM, OTLP_PROFILES_PROGRAM, OTLP_SUMMARY_PROGRAM,
+    OTLP_SUM_PROGRAM, OTLP_TRACES_PROGRAM,
 };
 
 // Only export init_programs for WASM target (used in worker startup)
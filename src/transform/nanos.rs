@@ -0,0 +1,114 @@
+// src/transform/nanos.rs
+//! Full-nanosecond timestamps
+//!
+//! The built-in VRL programs truncate OTLP nanosecond timestamps to
+//! microseconds. In nanosecond mode the original values are read from the
+//! decoded record before the VRL mapping runs, and written over the
+//! timestamp-like columns of the built-in output before any custom program
+//! sees it. The record's own time is also kept as
+//! [`TIME_UNIX_NANO`](crate::TIME_UNIX_NANO).
+
+use vrl::value::{KeyString, Value};
+
+use crate::arrow::TIME_UNIX_NANO;
+
+/// Timestamp-like output columns and the decoded fields holding their
+/// nanoseconds, in order of preference. The first entry is the record time.
+const SOURCES: [(&str, &[&str]); 5] = [
+    ("timestamp", &["time_unix_nano", "start_time_unix_nano"]),
+    ("observed_timestamp", &["observed_time_unix_nano"]),
+    ("end_timestamp", &["end_time_unix_nano"]),
+    ("start_timestamp", &["start_time_unix_nano"]),
+    ("duration", &["duration_ns", "duration_nano"]),
+];
+
+/// Nanosecond values of a decoded record, one per [`SOURCES`] entry.
+pub(crate) struct Nanos([Option<Value>; SOURCES.len()]);
+
+impl Nanos {
+    /// Read the nanosecond fields of a decoded record.
+    pub(crate) fn extract(record: &Value) -> Self {
+        let Value::Object(map) = record else {
+            return Self(Default::default());
+        };
+        Self(SOURCES.map(|(_, fields)| {
+            fields
+                .iter()
+                .find_map(|field| map.get(*field))
+                .filter(|value| !matches!(value, Value::Null))
+                .cloned()
+        }))
+    }
+
+    /// Overwrite the timestamp-like columns the built-in program produced,
+    /// and add the record time.
+    pub(crate) fn insert(self, record: &mut Value) {
+        let Value::Object(map) = record else {
+            return;
+        };
+        let [time, ..] = &self.0;
+        if let Some(time) = time {
+            map.insert(TIME_UNIX_NANO.into(), time.clone());
+        }
+        for ((column, _), value) in SOURCES.iter().zip(self.0) {
+            let column = KeyString::from(*column);
+            if let (Some(value), true) = (value, map.contains_key(&column)) {
+                map.insert(column, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vrl::value::ObjectMap;
+
+    fn object(fields: &[(&str, i64)]) -> Value {
+        let map: ObjectMap = fields
+            .iter()
+            .map(|(key, value)| (KeyString::from(*key), Value::Integer(*value)))
+            .collect();
+        Value::Object(map)
+    }
+
+    #[test]
+    fn test_nanos_restore_span_columns() {
+        let decoded = object(&[
+            ("start_time_unix_nano", 1_000_000_123),
+            ("end_time_unix_nano", 3_000_000_456),
+            ("duration_ns", 2_000_000_333),
+        ]);
+        let mut transformed = object(&[
+            ("timestamp", 1_000_000),
+            ("end_timestamp", 3_000_000),
+            ("duration", 2_000_000),
+        ]);
+
+        Nanos::extract(&decoded).insert(&mut transformed);
+
+        assert_eq!(
+            transformed,
+            object(&[
+                ("timestamp", 1_000_000_123),
+                ("end_timestamp", 3_000_000_456),
+                ("duration", 2_000_000_333),
+                ("time_unix_nano", 1_000_000_123),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_nanos_only_replace_columns_the_program_set() {
+        // Metric points have both times; spans have no start_timestamp column
+        let decoded = object(&[("time_unix_nano", 20), ("start_time_unix_nano", 10)]);
+        let mut transformed = object(&[("timestamp", 0)]);
+
+        Nanos::extract(&decoded).insert(&mut transformed);
+
+        assert_eq!(
+            transformed,
+            object(&[("timestamp", 20), ("time_unix_nano", 20)])
+        );
+    }
+}
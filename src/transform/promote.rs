@@ -0,0 +1,400 @@
+// src/transform/promote.rs
+//! Promotion of selected attributes to typed columns
+//!
+//! Attributes normally end up as JSON blobs (`resource_attributes`,
+//! `log_attributes`, ...). A [`PromotedAttributes`] configuration names keys
+//! to copy into extra columns so they can be filtered without parsing JSON.
+//! Values are read from the decoded record before the VRL mapping runs and
+//! added to the transformed record afterwards, so promoted columns never
+//! interfere with the built-in programs.
+
+use arrow::datatypes::{DataType, Field, Schema};
+use bytes::Bytes;
+use ordered_float::NotNan;
+use vrl::value::{KeyString, Value};
+
+use crate::convert::vrl_value_to_json;
+use crate::error::{Error, Result};
+
+/// Where a promoted attribute is read from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AttributeScope {
+    /// `resource.attributes`
+    Resource,
+    /// `scope.attributes`
+    Scope,
+    /// The log record, span or data point attributes
+    Record,
+}
+
+/// Column type of a promoted attribute.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PromotedType {
+    #[default]
+    String,
+    Int64,
+    Float64,
+    Bool,
+}
+
+impl PromotedType {
+    /// Arrow data type of the promoted column.
+    pub fn data_type(self) -> DataType {
+        match self {
+            PromotedType::String => DataType::Utf8,
+            PromotedType::Int64 => DataType::Int64,
+            PromotedType::Float64 => DataType::Float64,
+            PromotedType::Bool => DataType::Boolean,
+        }
+    }
+}
+
+/// A single attribute key promoted to its own column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PromotedAttribute {
+    pub scope: AttributeScope,
+    pub key: String,
+    pub column: String,
+    pub data_type: PromotedType,
+}
+
+impl PromotedAttribute {
+    /// Promote `key` from `scope` as a string column.
+    ///
+    /// The column name defaults to the key with `.` replaced by `_`
+    /// (`http.route` becomes `http_route`).
+    pub fn new(scope: AttributeScope, key: impl Into<String>) -> Self {
+        let key = key.into();
+        Self {
+            scope,
+            column: key.replace('.', "_"),
+            key,
+            data_type: PromotedType::String,
+        }
+    }
+
+    /// Promote a resource attribute.
+    pub fn resource(key: impl Into<String>) -> Self {
+        Self::new(AttributeScope::Resource, key)
+    }
+
+    /// Promote an instrumentation scope attribute.
+    pub fn scope(key: impl Into<String>) -> Self {
+        Self::new(AttributeScope::Scope, key)
+    }
+
+    /// Promote a log record, span or data point attribute.
+    pub fn record(key: impl Into<String>) -> Self {
+        Self::new(AttributeScope::Record, key)
+    }
+
+    /// Set the column type (defaults to string).
+    pub fn with_type(mut self, data_type: PromotedType) -> Self {
+        self.data_type = data_type;
+        self
+    }
+
+    /// Set the column name.
+    pub fn with_column(mut self, column: impl Into<String>) -> Self {
+        self.column = column.into();
+        self
+    }
+}
+
+/// Set of attributes to promote to typed columns.
+///
+/// Promoted columns are nullable and appended to the signal schema in
+/// configuration order. Values that cannot be represented in the requested
+/// type (e.g. `"abc"` for an int64 column) become null.
+///
+/// # Example
+///
+/// ```ignore
+/// use otlp2records::{PromotedAttribute, PromotedAttributes, PromotedType};
+///
+/// let promoted = PromotedAttributes::new(vec![
+///     PromotedAttribute::record("http.route"),
+///     PromotedAttribute::resource("k8s.pod.name"),
+///     PromotedAttribute::record("http.status_code").with_type(PromotedType::Int64),
+/// ]);
+/// let batch = transform_traces_promoted(bytes, InputFormat::Protobuf, &promoted)?.batch;
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PromotedAttributes {
+    attributes: Vec<PromotedAttribute>,
+}
+
+impl PromotedAttributes {
+    /// Create a configuration from a list of promoted attributes.
+    pub fn new(attributes: Vec<PromotedAttribute>) -> Self {
+        Self { attributes }
+    }
+
+    /// Add a promoted attribute.
+    pub fn push(&mut self, attribute: PromotedAttribute) {
+        self.attributes.push(attribute);
+    }
+
+    /// The configured attributes, in column order.
+    pub fn attributes(&self) -> &[PromotedAttribute] {
+        &self.attributes
+    }
+
+    /// Returns true if no attributes are promoted.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+
+    /// Append the promoted columns to a signal schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SchemaMismatch` if a column name is empty, repeats, or
+    /// collides with a column of `base`.
+    pub fn extend_schema(&self, base: &Schema) -> Result<Schema> {
+        let mut fields: Vec<Field> = base.fields().iter().map(|f| f.as_ref().clone()).collect();
+
+        for attribute in &self.attributes {
+            if attribute.column.is_empty() {
+                return Err(Error::SchemaMismatch(format!(
+                    "promoted attribute '{}' has an empty column name",
+                    attribute.key
+                )));
+            }
+            if fields.iter().any(|f| f.name() == &attribute.column) {
+                return Err(Error::SchemaMismatch(format!(
+                    "promoted column '{}' already exists in schema",
+                    attribute.column
+                )));
+            }
+            fields.push(Field::new(
+                &attribute.column,
+                attribute.data_type.data_type(),
+                true,
+            ));
+        }
+
+        Ok(Schema::new_with_metadata(fields, base.metadata().clone()))
+    }
+
+    /// Read the promoted values from a decoded (pre-VRL) record.
+    pub(crate) fn extract(&self, record: &Value) -> Vec<Value> {
+        self.attributes
+            .iter()
+            .map(|attribute| {
+                lookup(record, attribute)
+                    .map(|value| coerce(value, attribute.data_type))
+                    .unwrap_or(Value::Null)
+            })
+            .collect()
+    }
+
+    /// Insert values returned by [`Self::extract`] into a transformed record.
+    pub(crate) fn insert(&self, record: &mut Value, values: Vec<Value>) {
+        if let Value::Object(map) = record {
+            for (attribute, value) in self.attributes.iter().zip(values) {
+                map.insert(attribute.column.as_str().into(), value);
+            }
+        }
+    }
+}
+
+/// Find the raw attribute value for a promoted attribute in a decoded record.
+fn lookup<'a>(record: &'a Value, attribute: &PromotedAttribute) -> Option<&'a Value> {
+    let Value::Object(map) = record else {
+        return None;
+    };
+    let attributes_key: KeyString = "attributes".into();
+    let attributes = match attribute.scope {
+        AttributeScope::Resource => match map.get(&KeyString::from("resource"))? {
+            Value::Object(resource) => resource.get(&attributes_key)?,
+            _ => return None,
+        },
+        AttributeScope::Scope => match map.get(&KeyString::from("scope"))? {
+            Value::Object(scope) => scope.get(&attributes_key)?,
+            _ => return None,
+        },
+        AttributeScope::Record => map.get(&attributes_key)?,
+    };
+    match attributes {
+        Value::Object(attrs) => attrs.get(&KeyString::from(attribute.key.as_str())),
+        _ => None,
+    }
+}
+
+/// Convert an attribute value to the promoted column type, or null.
+fn coerce(value: &Value, data_type: PromotedType) -> Value {
+    match data_type {
+        PromotedType::String => match value {
+            Value::Bytes(_) => value.clone(),
+            Value::Integer(i) => Value::Bytes(Bytes::from(i.to_string())),
+            Value::Float(f) => Value::Bytes(Bytes::from(f.to_string())),
+            Value::Boolean(b) => Value::Bytes(Bytes::from(b.to_string())),
+            Value::Array(_) | Value::Object(_) => vrl_value_to_json(value)
+                .map(|json| Value::Bytes(Bytes::from(json.to_string())))
+                .unwrap_or(Value::Null),
+            _ => Value::Null,
+        },
+        PromotedType::Int64 => match value {
+            Value::Integer(_) => value.clone(),
+            Value::Float(f) => {
+                let f = f.into_inner();
+                if f.fract() == 0.0 && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+                    Value::Integer(f as i64)
+                } else {
+                    Value::Null
+                }
+            }
+            Value::Bytes(b) => std::str::from_utf8(b)
+                .ok()
+                .and_then(|s| s.trim().parse::<i64>().ok())
+                .map(Value::Integer)
+                .unwrap_or(Value::Null),
+            _ => Value::Null,
+        },
+        PromotedType::Float64 => {
+            let f = match value {
+                Value::Float(f) => Some(f.into_inner()),
+                Value::Integer(i) => Some(*i as f64),
+                Value::Bytes(b) => std::str::from_utf8(b)
+                    .ok()
+                    .and_then(|s| s.trim().parse::<f64>().ok()),
+                _ => None,
+            };
+            f.and_then(|f| NotNan::new(f).ok())
+                .filter(|f| f.is_finite())
+                .map(Value::Float)
+                .unwrap_or(Value::Null)
+        }
+        PromotedType::Bool => match value {
+            Value::Boolean(_) => value.clone(),
+            Value::Bytes(b) => match b.as_ref() {
+                b"true" => Value::Boolean(true),
+                b"false" => Value::Boolean(false),
+                _ => Value::Null,
+            },
+            _ => Value::Null,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vrl::value::ObjectMap;
+
+    fn record() -> Value {
+        let mut resource_attrs = ObjectMap::new();
+        resource_attrs.insert("k8s.pod.name".into(), Value::Bytes("pod-1".into()));
+        let mut resource = ObjectMap::new();
+        resource.insert("attributes".into(), Value::Object(resource_attrs));
+
+        let mut scope_attrs = ObjectMap::new();
+        scope_attrs.insert("lib.enabled".into(), Value::Boolean(true));
+        let mut scope = ObjectMap::new();
+        scope.insert("attributes".into(), Value::Object(scope_attrs));
+
+        let mut attrs = ObjectMap::new();
+        attrs.insert("http.route".into(), Value::Bytes("/users/:id".into()));
+        attrs.insert("http.status_code".into(), Value::Bytes("404".into()));
+        attrs.insert("latency".into(), Value::Integer(12));
+
+        let mut map = ObjectMap::new();
+        map.insert("resource".into(), Value::Object(resource));
+        map.insert("scope".into(), Value::Object(scope));
+        map.insert("attributes".into(), Value::Object(attrs));
+        Value::Object(map)
+    }
+
+    #[test]
+    fn test_default_column_name() {
+        let attribute = PromotedAttribute::record("http.route");
+        assert_eq!(attribute.column, "http_route");
+        assert_eq!(attribute.data_type, PromotedType::String);
+    }
+
+    #[test]
+    fn test_extract_all_scopes() {
+        let promoted = PromotedAttributes::new(vec![
+            PromotedAttribute::resource("k8s.pod.name"),
+            PromotedAttribute::scope("lib.enabled").with_type(PromotedType::Bool),
+            PromotedAttribute::record("http.route"),
+            PromotedAttribute::record("http.status_code").with_type(PromotedType::Int64),
+            PromotedAttribute::record("latency").with_type(PromotedType::Float64),
+            PromotedAttribute::record("missing"),
+        ]);
+        let values = promoted.extract(&record());
+        assert_eq!(
+            values,
+            vec![
+                Value::Bytes("pod-1".into()),
+                Value::Boolean(true),
+                Value::Bytes("/users/:id".into()),
+                Value::Integer(404),
+                Value::Float(NotNan::new(12.0).unwrap()),
+                Value::Null,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insert_columns() {
+        let promoted = PromotedAttributes::new(vec![
+            PromotedAttribute::record("http.route").with_column("route")
+        ]);
+        let values = promoted.extract(&record());
+        let mut out = Value::Object(ObjectMap::new());
+        promoted.insert(&mut out, values);
+        let Value::Object(map) = out else {
+            panic!("expected object");
+        };
+        assert_eq!(map.get("route"), Some(&Value::Bytes("/users/:id".into())));
+    }
+
+    #[test]
+    fn test_coerce_mismatch_is_null() {
+        assert_eq!(
+            coerce(&Value::Bytes("abc".into()), PromotedType::Int64),
+            Value::Null
+        );
+        assert_eq!(coerce(&Value::Integer(1), PromotedType::Bool), Value::Null);
+        assert_eq!(
+            coerce(
+                &Value::Float(NotNan::new(1.5).unwrap()),
+                PromotedType::Int64
+            ),
+            Value::Null
+        );
+        assert_eq!(
+            coerce(&Value::Integer(7), PromotedType::String),
+            Value::Bytes("7".into())
+        );
+    }
+
+    #[test]
+    fn test_extend_schema() {
+        let base = Schema::new(vec![Field::new("service_name", DataType::Utf8, false)]);
+        let promoted = PromotedAttributes::new(vec![
+            PromotedAttribute::record("http.status_code").with_type(PromotedType::Int64)
+        ]);
+        let schema = promoted.extend_schema(&base).unwrap();
+        assert_eq!(schema.fields().len(), 2);
+        let field = schema.field_with_name("http_status_code").unwrap();
+        assert_eq!(field.data_type(), &DataType::Int64);
+        assert!(field.is_nullable());
+    }
+
+    #[test]
+    fn test_extend_schema_rejects_collision() {
+        let base = Schema::new(vec![Field::new("service_name", DataType::Utf8, false)]);
+        let promoted = PromotedAttributes::new(vec![PromotedAttribute::resource("service.name")]);
+        let err = promoted.extend_schema(&base).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        let promoted = PromotedAttributes::new(vec![
+            PromotedAttribute::record("a.b"),
+            PromotedAttribute::resource("a.b"),
+        ]);
+        assert!(promoted.extend_schema(&Schema::empty()).is_err());
+    }
+}
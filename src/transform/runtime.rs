@@ -3,13 +3,16 @@
 
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
+use std::sync::Arc;
 use vrl::compiler::runtime::Runtime;
 use vrl::compiler::{compile, Program, TargetValue, TimeZone};
+use vrl::diagnostic::DiagnosticList;
 use vrl::value::{KeyString, Value};
 
 static UTC_TIMEZONE: Lazy<TimeZone> = Lazy::new(|| TimeZone::Named(chrono_tz::UTC));
 
 use super::functions;
+use crate::error::Error;
 
 // Include compiled VRL sources from build.rs
 include!(concat!(env!("OUT_DIR"), "/compiled_vrl.rs"));
@@ -57,6 +60,100 @@ pub static OTLP_EXP_HISTOGRAM_PROGRAM: Lazy<Program> = Lazy::new(|| {
         .program
 });
 
+pub static OTLP_SUMMARY_PROGRAM: Lazy<Program> = Lazy::new(|| {
+    let fns = functions::all();
+    compile(OTLP_SUMMARY_SOURCE, &fns)
+        .expect("OTLP_SUMMARY VRL should compile")
+        .program
+});
+
+pub static OTLP_PROFILES_PROGRAM: Lazy<Program> = Lazy::new(|| {
+    let fns = functions::all();
+    compile(OTLP_PROFILES_SOURCE, &fns)
+        .expect("OTLP_PROFILES VRL should compile")
+        .program
+});
+
+/// A user-supplied VRL program, run after the built-in OTLP mapping.
+///
+/// The program is compiled against the same function set as the built-in
+/// programs ([`functions::all()`]); the VRL stdlib is not available. It sees
+/// the flattened record produced by the built-in program, with `._table`
+/// set to the destination table (e.g. `"logs"`, `"gauge"`). Any `._table`
+/// assignment is discarded, and fields not present in the output schema are
+/// ignored when building Arrow batches.
+///
+/// Cloning is cheap: the compiled program is shared.
+#[derive(Debug, Clone)]
+pub struct CustomProgram {
+    program: Arc<Program>,
+}
+
+impl CustomProgram {
+    /// Compile VRL source into a custom program.
+    ///
+    /// Compile failures are returned as [`Error::VrlCompilation`], with each
+    /// diagnostic's line and column in the source.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use otlp2records::CustomProgram;
+    ///
+    /// let program = CustomProgram::compile(r#"
+    ///     if .service_name == "unknown" { .service_name = "legacy" }
+    ///     .log_attributes = null
+    /// "#)?;
+    /// ```
+    pub fn compile(source: &str) -> Result<Self, Error> {
+        let fns = functions::all();
+        let result = compile(source, &fns).map_err(|diagnostics| {
+            Error::VrlCompilation(format_diagnostics(source, diagnostics))
+        })?;
+        Ok(Self {
+            program: Arc::new(result.program),
+        })
+    }
+
+    /// The compiled VRL program.
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+}
+
+/// Render compile diagnostics as `error[E<code>] at line L, column C: message`,
+/// one entry per error, joined with `; `.
+fn format_diagnostics(source: &str, diagnostics: DiagnosticList) -> String {
+    diagnostics
+        .iter()
+        .filter(|d| d.is_problem())
+        .map(|d| {
+            let position = d
+                .labels()
+                .iter()
+                .find(|label| label.primary)
+                .or_else(|| d.labels().first())
+                .map(|label| {
+                    let (line, column) = line_column(source, label.span.start());
+                    format!(" at line {line}, column {column}")
+                })
+                .unwrap_or_default();
+            format!("error[E{}]{}: {}", d.code, position, d.message())
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Convert a byte offset into a 1-based (line, column) pair.
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
 /// VRL transformation error
 #[derive(Debug)]
 pub struct VrlError(pub String);
@@ -127,6 +224,46 @@ impl VrlTransformer {
         Ok((table, target.value))
     }
 
+    /// Transform a single record with a built-in program, then an optional custom program.
+    ///
+    /// Returns the table name set by the built-in program. The custom program
+    /// receives the built-in output with `._table` restored so it can branch
+    /// on the destination table.
+    pub fn transform_layered(
+        &mut self,
+        program: &Program,
+        custom: Option<&CustomProgram>,
+        input: Value,
+    ) -> Result<(String, Value), VrlError> {
+        self.transform_layered_with(program, custom, input, |_| {})
+    }
+
+    /// Like [`Self::transform_layered`], calling `between` on the built-in
+    /// output before the custom program runs.
+    pub(crate) fn transform_layered_with(
+        &mut self,
+        program: &Program,
+        custom: Option<&CustomProgram>,
+        input: Value,
+        between: impl FnOnce(&mut Value),
+    ) -> Result<(String, Value), VrlError> {
+        let (table, mut value) = self.transform(program, input)?;
+        between(&mut value);
+
+        let Some(custom) = custom else {
+            return Ok((table, value));
+        };
+
+        if let Value::Object(ref mut map) = value {
+            map.insert("_table".into(), Value::Bytes(table.clone().into()));
+        }
+        // Custom programs may use local variables; don't let them leak between records
+        self.runtime.clear();
+        let (_custom_table, value) = self.transform(custom.program(), value)?;
+
+        Ok((table, value))
+    }
+
     /// Transform a batch of records using the given program.
     /// Returns a HashMap grouping transformed values by their _table routing key.
     pub fn transform_batch(
@@ -149,7 +286,6 @@ impl VrlTransformer {
 
 /// Force initialization of all VRL programs.
 /// Call during worker startup to avoid cold-start latency.
-#[cfg(target_arch = "wasm32")]
 pub fn init_programs() {
     // Access each Lazy to force initialization
     let _ = &*OTLP_LOGS_PROGRAM;
@@ -158,6 +294,8 @@ pub fn init_programs() {
     let _ = &*OTLP_SUM_PROGRAM;
     let _ = &*OTLP_HISTOGRAM_PROGRAM;
     let _ = &*OTLP_EXP_HISTOGRAM_PROGRAM;
+    let _ = &*OTLP_SUMMARY_PROGRAM;
+    let _ = &*OTLP_PROFILES_PROGRAM;
 }
 
 impl Default for VrlTransformer {
@@ -277,4 +415,53 @@ mod tests {
         let err = VrlError("test error".to_string());
         assert_eq!(format!("{err}"), "VRL error: test error");
     }
+
+    #[test]
+    fn test_custom_program_compiles() {
+        let program = CustomProgram::compile(r#".service_name = "renamed""#);
+        assert!(program.is_ok());
+    }
+
+    #[test]
+    fn test_custom_program_compile_error_has_position() {
+        let source = ".a = 1\n.b = nonexistent_fn(.a)";
+        let err = CustomProgram::compile(source).unwrap_err();
+        match err {
+            Error::VrlCompilation(msg) => {
+                assert!(msg.contains("line 2, column"), "unexpected message: {msg}");
+                assert!(msg.contains("error[E"), "unexpected message: {msg}");
+            }
+            other => panic!("expected VrlCompilation, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_transform_layered_runs_custom_after_builtin() {
+        let builtin = compile(r#"._table = "logs"; .body = "mapped""#, &functions::all())
+            .unwrap()
+            .program;
+        let custom = CustomProgram::compile(
+            r#"if ._table == "logs" { .body = "custom" }; ._table = "ignored""#,
+        )
+        .unwrap();
+
+        let mut transformer = VrlTransformer::new();
+        let (table, output) = transformer
+            .transform_layered(&builtin, Some(&custom), Value::Object(ObjectMap::new()))
+            .unwrap();
+
+        assert_eq!(table, "logs");
+        if let Value::Object(map) = output {
+            assert_eq!(map.get("body"), Some(&Value::Bytes(Bytes::from("custom"))));
+            assert!(!map.contains_key("_table"));
+        } else {
+            panic!("Expected Object output");
+        }
+    }
+
+    #[test]
+    fn test_line_column() {
+        assert_eq!(line_column("abc", 0), (1, 1));
+        assert_eq!(line_column("abc\ndef", 5), (2, 2));
+    }
 }
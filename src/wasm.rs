@@ -26,17 +26,36 @@
 //! - `"protobuf"` or `"proto"` for Protocol Buffers binary format
 //! - `"json"` for JSON format
 //! - `"auto"` for auto-detection (JSON vs protobuf)
+//!
+//! # Compressed Input
+//!
+//! Bodies sent with `Content-Encoding: gzip` or `zstd` can be inflated with
+//! `decompress_wasm` first (requires the `compression` feature):
+//!
+//! ```javascript
+//! const body = decompress_wasm(gzipBytes, "gzip", 0); // 0 = default limit
+//! const arrowIpc = transform_logs_wasm(body, "protobuf");
+//! ```
+//!
+//! # Partitioned Output
+//!
+//! `transform_partitioned_wasm` returns one Arrow IPC payload per service,
+//! with its `service_name`, Hive-style `path` and record count.
 
 // This module is only compiled when targeting wasm32 with the wasm feature enabled.
 // The cfg gate is in lib.rs: #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
 
 use wasm_bindgen::prelude::*;
 
-use crate::arrow::{gauge_schema, sum_schema};
-use crate::decode::InputFormat;
+use crate::arrow::{gauge_schema, sum_schema, summary_schema};
+use crate::decode::{decompress, ContentEncoding, InputFormat, DEFAULT_MAX_DECOMPRESSED_SIZE};
 use crate::output::to_ipc;
 use crate::transform::init_programs;
-use crate::{transform_logs, transform_metrics, transform_traces};
+use crate::{
+    transform_logs, transform_logs_partitioned, transform_metrics, transform_metrics_partitioned,
+    transform_profiles, transform_profiles_partitioned, transform_traces,
+    transform_traces_partitioned, ServiceGroupedBatches,
+};
 
 /// Parse format string to InputFormat enum.
 ///
@@ -60,17 +79,62 @@ fn parse_format(format: &str) -> Result<InputFormat, String> {
     }
 }
 
+/// Parse content encoding string to ContentEncoding enum.
+///
+/// # Arguments
+///
+/// * `encoding` - Content-Encoding value: "identity", "gzip", or "zstd"
+///
+/// # Returns
+///
+/// * `Ok(ContentEncoding)` - The parsed encoding
+/// * `Err(String)` - If the encoding is not supported
+fn parse_content_encoding(encoding: &str) -> Result<ContentEncoding, String> {
+    ContentEncoding::from_content_encoding(Some(encoding)).ok_or_else(|| {
+        format!(
+            "Invalid encoding '{}': expected 'identity', 'gzip', or 'zstd'",
+            encoding
+        )
+    })
+}
+
+/// Decompress a request body (internal implementation).
+fn decompress_impl(bytes: &[u8], encoding: &str, max_size: usize) -> Result<Vec<u8>, String> {
+    let encoding = parse_content_encoding(encoding)?;
+    let max_size = if max_size == 0 {
+        DEFAULT_MAX_DECOMPRESSED_SIZE
+    } else {
+        max_size
+    };
+    decompress(bytes, encoding, max_size)
+        .map(|body| body.into_owned())
+        .map_err(|e| e.to_string())
+}
+
 /// Transform OTLP logs to Arrow IPC bytes (internal implementation).
 fn transform_logs_impl(bytes: &[u8], format: &str) -> Result<Vec<u8>, String> {
     let input_format = parse_format(format)?;
-    let batch = transform_logs(bytes, input_format).map_err(|e| e.to_string())?;
+    let batch = transform_logs(bytes, input_format)
+        .map_err(|e| e.to_string())?
+        .batch;
     to_ipc(&batch).map_err(|e| e.to_string())
 }
 
 /// Transform OTLP traces to Arrow IPC bytes (internal implementation).
 fn transform_traces_impl(bytes: &[u8], format: &str) -> Result<Vec<u8>, String> {
     let input_format = parse_format(format)?;
-    let batch = transform_traces(bytes, input_format).map_err(|e| e.to_string())?;
+    let batch = transform_traces(bytes, input_format)
+        .map_err(|e| e.to_string())?
+        .batch;
+    to_ipc(&batch).map_err(|e| e.to_string())
+}
+
+/// Transform OTLP profiles to Arrow IPC bytes (internal implementation).
+fn transform_profiles_impl(bytes: &[u8], format: &str) -> Result<Vec<u8>, String> {
+    let input_format = parse_format(format)?;
+    let batch = transform_profiles(bytes, input_format)
+        .map_err(|e| e.to_string())?
+        .batch;
     to_ipc(&batch).map_err(|e| e.to_string())
 }
 
@@ -108,6 +172,100 @@ fn transform_metrics_sum_impl(bytes: &[u8], format: &str) -> Result<Vec<u8>, Str
     }
 }
 
+/// Transform OTLP summary metrics to Arrow IPC bytes (internal implementation).
+fn transform_metrics_summary_impl(bytes: &[u8], format: &str) -> Result<Vec<u8>, String> {
+    use arrow::array::RecordBatch;
+
+    let input_format = parse_format(format)?;
+    let batches = transform_metrics(bytes, input_format).map_err(|e| e.to_string())?;
+
+    match batches.summary {
+        Some(batch) => to_ipc(&batch).map_err(|e| e.to_string()),
+        None => {
+            // Return empty IPC with correct schema for consistency
+            let empty_batch = RecordBatch::new_empty(summary_schema().into());
+            to_ipc(&empty_batch).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Transform a payload and serialize its stats to JSON (internal implementation).
+fn transform_stats_impl(bytes: &[u8], signal: &str, format: &str) -> Result<String, String> {
+    let input_format = parse_format(format)?;
+    let stats = match signal.to_lowercase().as_str() {
+        "logs" => transform_logs(bytes, input_format).map(|t| t.stats),
+        "traces" => transform_traces(bytes, input_format).map(|t| t.stats),
+        "metrics" => transform_metrics(bytes, input_format).map(|b| b.stats),
+        "profiles" => transform_profiles(bytes, input_format).map(|t| t.stats),
+        _ => {
+            return Err(format!(
+                "Invalid signal '{signal}'. Expected 'logs', 'traces', 'metrics', or 'profiles'"
+            ))
+        }
+    }
+    .map_err(|e| e.to_string())?;
+    serde_json::to_string(&stats).map_err(|e| e.to_string())
+}
+
+/// Transform a payload, grouped by service, to Arrow IPC bytes per service
+/// (internal implementation).
+fn transform_partitioned_impl(
+    bytes: &[u8],
+    signal: &str,
+    format: &str,
+) -> Result<Vec<PartitionedIpc>, String> {
+    let input_format = parse_format(format)?;
+    let metrics = || transform_metrics_partitioned(bytes, input_format);
+    let grouped: ServiceGroupedBatches = match signal.to_lowercase().as_str() {
+        "logs" => transform_logs_partitioned(bytes, input_format),
+        "traces" => transform_traces_partitioned(bytes, input_format),
+        "profiles" => transform_profiles_partitioned(bytes, input_format),
+        "gauge" => metrics().map(|m| m.gauge),
+        "sum" => metrics().map(|m| m.sum),
+        "histogram" => metrics().map(|m| m.histogram),
+        "exp_histogram" => metrics().map(|m| m.exp_histogram),
+        "summary" => metrics().map(|m| m.summary),
+        _ => {
+            return Err(format!(
+                "Invalid signal '{signal}'. Expected 'logs', 'traces', 'profiles', 'gauge', \
+                 'sum', 'histogram', 'exp_histogram', or 'summary'"
+            ))
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    grouped
+        .into_iter()
+        .map(|partition| {
+            Ok(PartitionedIpc {
+                ipc: to_ipc(&partition.batch).map_err(|e| e.to_string())?,
+                service_name: partition.service_name.to_string(),
+                path: partition.path,
+                record_count: partition.record_count,
+                min_timestamp_micros: partition.min_timestamp_micros,
+                max_timestamp_micros: partition.max_timestamp_micros,
+            })
+        })
+        .collect()
+}
+
+/// The records of one service, returned by `transform_partitioned_wasm`.
+#[wasm_bindgen(getter_with_clone)]
+pub struct PartitionedIpc {
+    /// Service name of every record in the partition
+    pub service_name: String,
+    /// Hive-style partition path, e.g. `service_name=api`
+    pub path: String,
+    /// Number of records in the partition
+    pub record_count: usize,
+    /// Minimum timestamp in microseconds
+    pub min_timestamp_micros: i64,
+    /// Maximum timestamp in microseconds
+    pub max_timestamp_micros: i64,
+    /// Arrow IPC bytes of the partition
+    pub ipc: Vec<u8>,
+}
+
 /// Initialize VRL programs for faster cold starts.
 ///
 /// This function is automatically called when the WASM module is loaded.
@@ -180,6 +338,33 @@ pub fn transform_traces_wasm(bytes: &[u8], format: &str) -> Result<Vec<u8>, JsEr
     transform_traces_impl(bytes, format).map_err(|e| JsError::new(&e))
 }
 
+/// Transform OTLP profiles to Arrow IPC bytes.
+///
+/// Decodes OTLP profile data, flattens each sample with its resolved stack
+/// frames, applies VRL transformation, and serializes to Arrow IPC format.
+///
+/// # Arguments
+///
+/// * `bytes` - Raw OTLP profile data (protobuf or JSON bytes)
+/// * `format` - Input format: "protobuf", "proto", "json", or "auto"
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - Arrow IPC bytes that can be read by arrow-js or DuckDB-WASM
+/// * `Err(JsError)` - If decoding, transformation, or serialization fails
+///
+/// # Example
+///
+/// ```javascript
+/// const profileBytes = new Uint8Array([...]); // OTLP protobuf
+/// const arrowIpc = transform_profiles_wasm(profileBytes, "protobuf");
+/// const table = arrow.tableFromIPC(arrowIpc);
+/// ```
+#[wasm_bindgen]
+pub fn transform_profiles_wasm(bytes: &[u8], format: &str) -> Result<Vec<u8>, JsError> {
+    transform_profiles_impl(bytes, format).map_err(|e| JsError::new(&e))
+}
+
 /// Transform OTLP gauge metrics to Arrow IPC bytes.
 ///
 /// Decodes OTLP metric data, filters for gauge metrics, applies VRL transformation,
@@ -238,6 +423,120 @@ pub fn transform_metrics_sum_wasm(bytes: &[u8], format: &str) -> Result<Vec<u8>,
     transform_metrics_sum_impl(bytes, format).map_err(|e| JsError::new(&e))
 }
 
+/// Transform OTLP summary metrics to Arrow IPC bytes.
+///
+/// Decodes OTLP metric data, filters for summary metrics, applies VRL transformation,
+/// and serializes to Arrow IPC format.
+///
+/// # Arguments
+///
+/// * `bytes` - Raw OTLP metric data (protobuf or JSON bytes)
+/// * `format` - Input format: "protobuf", "proto", "json", or "auto"
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - Arrow IPC bytes containing summary metrics (empty if no summaries)
+/// * `Err(JsError)` - If decoding, transformation, or serialization fails
+///
+/// # Example
+///
+/// ```javascript
+/// const metricBytes = new Uint8Array([...]); // OTLP protobuf
+/// const arrowIpc = transform_metrics_summary_wasm(metricBytes, "protobuf");
+/// const table = arrow.tableFromIPC(arrowIpc);
+/// ```
+#[wasm_bindgen]
+pub fn transform_metrics_summary_wasm(bytes: &[u8], format: &str) -> Result<Vec<u8>, JsError> {
+    transform_metrics_summary_impl(bytes, format).map_err(|e| JsError::new(&e))
+}
+
+/// Decompress a gzip or zstd request body.
+///
+/// # Arguments
+///
+/// * `bytes` - Compressed request body
+/// * `encoding` - Content-Encoding value: "identity", "gzip", or "zstd"
+/// * `max_size` - Maximum decompressed size in bytes, or 0 for the default (64 MiB)
+///
+/// # Returns
+///
+/// * `Ok(Vec<u8>)` - Decompressed bytes, ready for the `transform_*_wasm` functions
+/// * `Err(JsError)` - If the encoding is unsupported, the body is corrupt, or
+///   it exceeds `max_size` once decompressed
+///
+/// # Example
+///
+/// ```javascript
+/// const body = decompress_wasm(gzipBytes, "gzip", 0);
+/// const arrowIpc = transform_logs_wasm(body, "protobuf");
+/// ```
+#[wasm_bindgen]
+pub fn decompress_wasm(bytes: &[u8], encoding: &str, max_size: usize) -> Result<Vec<u8>, JsError> {
+    decompress_impl(bytes, encoding, max_size).map_err(|e| JsError::new(&e))
+}
+
+/// Transform an OTLP payload and return its record accounting as JSON.
+///
+/// Runs the same transform as the `transform_*_wasm` functions but returns
+/// the [`TransformStats`](crate::TransformStats) instead of the batch.
+///
+/// # Arguments
+///
+/// * `bytes` - Raw OTLP data (protobuf or JSON bytes)
+/// * `signal` - Signal type: "logs", "traces", "metrics", or "profiles"
+/// * `format` - Input format: "protobuf", "proto", "json", or "auto"
+///
+/// # Returns
+///
+/// * `Ok(String)` - JSON object with `records_in`, `records_out`, `dropped`,
+///   `null_coerced_values`, `invalid_ids` and `timestamp_overflows`
+/// * `Err(JsError)` - If the signal or format is invalid or the transform fails
+///
+/// # Example
+///
+/// ```javascript
+/// const stats = JSON.parse(transform_stats_wasm(logBytes, "logs", "protobuf"));
+/// console.log(`${stats.records_out} of ${stats.records_in} records`);
+/// ```
+#[wasm_bindgen]
+pub fn transform_stats_wasm(bytes: &[u8], signal: &str, format: &str) -> Result<String, JsError> {
+    transform_stats_impl(bytes, signal, format).map_err(|e| JsError::new(&e))
+}
+
+/// Transform an OTLP payload to Arrow IPC bytes, one payload per service.
+///
+/// Records are grouped by service name (null names are grouped as
+/// `unknown`), in the order each service first appears in the input.
+///
+/// # Arguments
+///
+/// * `bytes` - Raw OTLP data (protobuf or JSON bytes)
+/// * `signal` - "logs", "traces", "profiles", or a metric type: "gauge",
+///   "sum", "histogram", "exp_histogram", or "summary"
+/// * `format` - Input format: "protobuf", "proto", "json", or "auto"
+///
+/// # Returns
+///
+/// * `Ok(Vec<PartitionedIpc>)` - One partition per service (empty if the
+///   input had no data of this type)
+/// * `Err(JsError)` - If the signal or format is invalid or the transform fails
+///
+/// # Example
+///
+/// ```javascript
+/// for (const partition of transform_partitioned_wasm(logBytes, "logs", "protobuf")) {
+///     await bucket.put(`${partition.path}/logs.arrow`, partition.ipc);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn transform_partitioned_wasm(
+    bytes: &[u8],
+    signal: &str,
+    format: &str,
+) -> Result<Vec<PartitionedIpc>, JsError> {
+    transform_partitioned_impl(bytes, signal, format).map_err(|e| JsError::new(&e))
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -297,6 +596,25 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_content_encoding() {
+        assert!(matches!(
+            parse_content_encoding("GZIP"),
+            Ok(ContentEncoding::Gzip)
+        ));
+        assert!(matches!(
+            parse_content_encoding("zstd"),
+            Ok(ContentEncoding::Zstd)
+        ));
+        assert!(parse_content_encoding("br").is_err());
+    }
+
+    #[test]
+    fn test_decompress_impl_identity() {
+        assert_eq!(decompress_impl(b"abc", "identity", 0).unwrap(), b"abc");
+        assert!(decompress_impl(b"abc", "identity", 2).is_err());
+    }
+
     #[test]
     fn test_transform_logs_impl_invalid_format() {
         let result = transform_logs_impl(b"test", "invalid");
@@ -309,6 +627,12 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_transform_profiles_impl_invalid_format() {
+        let result = transform_profiles_impl(b"test", "invalid");
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_transform_metrics_gauge_impl_invalid_format() {
         let result = transform_metrics_gauge_impl(b"test", "invalid");
@@ -321,6 +645,12 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_transform_metrics_summary_impl_invalid_format() {
+        let result = transform_metrics_summary_impl(b"test", "invalid");
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_transform_logs_impl_empty_protobuf() {
         use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
@@ -488,4 +818,31 @@ mod tests {
         assert_eq!(batches.len(), 1);
         assert_eq!(batches[0].num_rows(), 1);
     }
+
+    #[test]
+    fn test_transform_partitioned_impl() {
+        let json = r#"{
+            "resourceLogs": [
+                { "resource": { "attributes": [{ "key": "service.name", "value": { "stringValue": "api" } }]},
+                  "scopeLogs": [{ "logRecords": [{ "timeUnixNano": "1700000000000000000" }] }] },
+                { "resource": { "attributes": [{ "key": "service.name", "value": { "stringValue": "db" } }]},
+                  "scopeLogs": [{ "logRecords": [{ "timeUnixNano": "1700000000000000000" }] }] }
+            ]
+        }"#;
+
+        let partitions = transform_partitioned_impl(json.as_bytes(), "logs", "json").unwrap();
+        let paths: Vec<&str> = partitions.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(paths, ["service_name=api", "service_name=db"]);
+        assert!(!partitions[0].ipc.is_empty());
+
+        let gauges = transform_partitioned_impl(b"", "gauge", "protobuf").unwrap();
+        assert!(gauges.is_empty());
+        assert!(transform_partitioned_impl(b"", "events", "json").is_err());
+    }
+
+    #[test]
+    fn test_transform_stats_impl_invalid_signal() {
+        let result = transform_stats_impl(b"test", "events", "json");
+        assert!(result.unwrap_err().contains("Invalid signal"));
+    }
 }
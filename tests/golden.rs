@@ -0,0 +1,258 @@
+//! Golden tests: the protobuf and OTLP JSON decoders must produce the same
+//! batches for the same request.
+
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{
+    exemplar, exponential_histogram_data_point::Buckets, metric, number_data_point, Exemplar,
+    ExponentialHistogram, ExponentialHistogramDataPoint, Gauge, Histogram, HistogramDataPoint,
+    Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use opentelemetry_proto::tonic::trace::v1::{span, ResourceSpans, ScopeSpans, Span, Status};
+use otlp2records::testing::{compare_protobuf_json, golden_cases, FieldDiff, Signal};
+use prost::Message;
+
+const TIME: u64 = 1_700_000_000_123_456_789;
+const TRACE_ID: [u8; 16] = [
+    0x0a, 0xf7, 0x65, 0x19, 0x16, 0xcd, 0x43, 0xdd, 0x84, 0x48, 0xeb, 0x21, 0x1c, 0x80, 0x31, 0x9c,
+];
+const SPAN_ID: [u8; 8] = [0xb7, 0xad, 0x6b, 0x71, 0x69, 0x20, 0x33, 0x31];
+
+fn assert_no_diffs(name: &str, diffs: &[FieldDiff]) {
+    let lines: Vec<String> = diffs.iter().map(FieldDiff::to_string).collect();
+    assert!(
+        diffs.is_empty(),
+        "{name}: protobuf and JSON outputs differ:\n{}",
+        lines.join("\n")
+    );
+}
+
+fn attribute(key: &str, value: any_value::Value) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        value: Some(AnyValue { value: Some(value) }),
+    }
+}
+
+fn attributes() -> Vec<KeyValue> {
+    vec![
+        attribute("str", any_value::Value::StringValue("v".to_string())),
+        attribute("int", any_value::Value::IntValue(-42)),
+        attribute("double", any_value::Value::DoubleValue(0.25)),
+        attribute("bool", any_value::Value::BoolValue(true)),
+    ]
+}
+
+fn resource() -> Option<Resource> {
+    Some(Resource {
+        attributes: vec![attribute(
+            "service.name",
+            any_value::Value::StringValue("golden".to_string()),
+        )],
+        ..Default::default()
+    })
+}
+
+fn scope() -> Option<InstrumentationScope> {
+    Some(InstrumentationScope {
+        name: "golden-scope".to_string(),
+        version: "1.2.3".to_string(),
+        attributes: attributes(),
+        ..Default::default()
+    })
+}
+
+fn exemplars() -> Vec<Exemplar> {
+    vec![
+        Exemplar {
+            filtered_attributes: attributes(),
+            time_unix_nano: TIME,
+            span_id: SPAN_ID.to_vec(),
+            trace_id: TRACE_ID.to_vec(),
+            value: Some(exemplar::Value::AsDouble(1.5)),
+        },
+        Exemplar {
+            time_unix_nano: TIME + 1,
+            value: Some(exemplar::Value::AsInt(7)),
+            ..Default::default()
+        },
+    ]
+}
+
+fn metric(name: &str, data: metric::Data) -> Metric {
+    Metric {
+        name: name.to_string(),
+        description: format!("{name} description"),
+        unit: "ms".to_string(),
+        data: Some(data),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_testdata_protobuf_matches_json() {
+    let cases = golden_cases(concat!(env!("CARGO_MANIFEST_DIR"), "/testdata")).unwrap();
+    assert!(cases.len() >= 8, "found {} testdata files", cases.len());
+
+    for case in &cases {
+        assert_no_diffs(&case.name, &case.diffs().unwrap());
+    }
+}
+
+#[test]
+fn test_span_events_and_links_protobuf_matches_json() {
+    let span = Span {
+        trace_id: TRACE_ID.to_vec(),
+        span_id: SPAN_ID.to_vec(),
+        parent_span_id: vec![1, 2, 3, 4, 5, 6, 7, 8],
+        trace_state: "vendor=value".to_string(),
+        name: "golden-span".to_string(),
+        kind: span::SpanKind::Client as i32,
+        start_time_unix_nano: TIME,
+        end_time_unix_nano: TIME + 250_000_000,
+        attributes: attributes(),
+        dropped_attributes_count: 1,
+        events: vec![
+            span::Event {
+                time_unix_nano: TIME + 1_000,
+                name: "retry".to_string(),
+                attributes: attributes(),
+                dropped_attributes_count: 0,
+            },
+            span::Event {
+                time_unix_nano: TIME + 2_000,
+                name: "done".to_string(),
+                ..Default::default()
+            },
+        ],
+        dropped_events_count: 2,
+        links: vec![
+            span::Link {
+                trace_id: TRACE_ID.to_vec(),
+                span_id: vec![8, 7, 6, 5, 4, 3, 2, 1],
+                trace_state: "k=v".to_string(),
+                attributes: attributes(),
+                ..Default::default()
+            },
+            span::Link {
+                trace_id: TRACE_ID.to_vec(),
+                span_id: SPAN_ID.to_vec(),
+                ..Default::default()
+            },
+        ],
+        dropped_links_count: 3,
+        status: Some(Status {
+            message: "boom".to_string(),
+            code: 2,
+        }),
+        flags: 0x101,
+    };
+    let request = ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: resource(),
+            scope_spans: vec![ScopeSpans {
+                scope: scope(),
+                spans: vec![span],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+
+    let diffs = compare_protobuf_json(Signal::Traces, &request.encode_to_vec()).unwrap();
+    assert_no_diffs("events and links", &diffs);
+}
+
+#[test]
+fn test_metric_exemplars_and_buckets_protobuf_matches_json() {
+    let number_point = |value| NumberDataPoint {
+        attributes: attributes(),
+        start_time_unix_nano: TIME - 1_000_000_000,
+        time_unix_nano: TIME,
+        exemplars: exemplars(),
+        value: Some(value),
+        ..Default::default()
+    };
+    let metrics = vec![
+        metric(
+            "golden.gauge",
+            metric::Data::Gauge(Gauge {
+                data_points: vec![
+                    number_point(number_data_point::Value::AsDouble(3.25)),
+                    number_point(number_data_point::Value::AsInt(-9)),
+                ],
+            }),
+        ),
+        metric(
+            "golden.sum",
+            metric::Data::Sum(Sum {
+                data_points: vec![number_point(number_data_point::Value::AsInt(12))],
+                aggregation_temporality: 2,
+                is_monotonic: true,
+            }),
+        ),
+        metric(
+            "golden.histogram",
+            metric::Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes: attributes(),
+                    start_time_unix_nano: TIME - 1_000_000_000,
+                    time_unix_nano: TIME,
+                    count: 6,
+                    sum: Some(21.5),
+                    bucket_counts: vec![1, 2, 3],
+                    explicit_bounds: vec![1.0, 5.5],
+                    exemplars: exemplars(),
+                    min: Some(0.5),
+                    max: Some(9.0),
+                    ..Default::default()
+                }],
+                aggregation_temporality: 1,
+            }),
+        ),
+        metric(
+            "golden.exp_histogram",
+            metric::Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: vec![ExponentialHistogramDataPoint {
+                    attributes: attributes(),
+                    start_time_unix_nano: TIME - 1_000_000_000,
+                    time_unix_nano: TIME,
+                    count: 10,
+                    sum: Some(-3.5),
+                    scale: -2,
+                    zero_count: 1,
+                    positive: Some(Buckets {
+                        offset: -3,
+                        bucket_counts: vec![2, 0, 4],
+                    }),
+                    negative: Some(Buckets {
+                        offset: 5,
+                        bucket_counts: vec![3],
+                    }),
+                    exemplars: exemplars(),
+                    min: Some(-8.0),
+                    max: Some(6.0),
+                    zero_threshold: 0.001,
+                    ..Default::default()
+                }],
+                aggregation_temporality: 2,
+            }),
+        ),
+    ];
+    let request = ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            resource: resource(),
+            scope_metrics: vec![ScopeMetrics {
+                scope: scope(),
+                metrics,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    };
+
+    let diffs = compare_protobuf_json(Signal::Metrics, &request.encode_to_vec()).unwrap();
+    assert_no_diffs("exemplars and buckets", &diffs);
+}
@@ -2,9 +2,15 @@
 //!
 //! These tests use real OTLP fixtures to verify the complete transformation pipeline.
 
-use arrow::array::{Array, TimestampMicrosecondArray};
+use arrow::array::{Array, ListArray, MapArray, TimestampMicrosecondArray};
 use otlp2records::{
-    to_ipc, to_json, transform_logs, transform_metrics, transform_traces, InputFormat,
+    apply_log_transform_with, apply_metric_transform, apply_trace_transform, decode_logs,
+    decode_metrics, decode_traces, dict_string_type, gauge_schema, native_nested_schema,
+    parse_schema, profiles_schema, records_to_otlp_logs, records_to_otlp_metrics,
+    records_to_otlp_traces, to_ipc, to_json, traces_schema, transform_logs, transform_logs_lenient,
+    transform_metrics, transform_profiles, transform_traces, try_group_batch_by_service,
+    values_to_arrow, CustomProgram, EncodeFormat, ErrorStage, InputFormat, Pipeline,
+    RecordLocation,
 };
 
 // ============================================================================
@@ -14,7 +20,7 @@ use otlp2records::{
 #[test]
 fn test_full_pipeline_logs_json() {
     let json = include_bytes!("fixtures/sample_otlp.json");
-    let batch = transform_logs(json, InputFormat::Json).unwrap();
+    let batch = transform_logs(json, InputFormat::Json).unwrap().batch;
 
     assert!(batch.num_rows() > 0, "Expected at least one log record");
 
@@ -40,7 +46,7 @@ fn test_full_pipeline_logs_json() {
 #[test]
 fn test_logs_to_ipc() {
     let json = include_bytes!("fixtures/sample_otlp.json");
-    let batch = transform_logs(json, InputFormat::Json).unwrap();
+    let batch = transform_logs(json, InputFormat::Json).unwrap().batch;
 
     let ipc = to_ipc(&batch).unwrap();
     assert!(!ipc.is_empty(), "Expected non-empty IPC output");
@@ -49,6 +55,118 @@ fn test_logs_to_ipc() {
     assert!(ipc.len() > 6, "IPC output too short");
 }
 
+#[cfg(feature = "compression")]
+#[test]
+fn test_gzip_logs_pipeline() {
+    use otlp2records::{decompress, ContentEncoding, DEFAULT_MAX_DECOMPRESSED_SIZE};
+    use std::io::Write;
+
+    let pb = include_bytes!("../testdata/logs_large.pb");
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(pb).unwrap();
+    let gzipped = encoder.finish().unwrap();
+
+    let encoding = ContentEncoding::from_content_encoding(Some("gzip")).unwrap();
+    let body = decompress(&gzipped, encoding, DEFAULT_MAX_DECOMPRESSED_SIZE).unwrap();
+    let batch = transform_logs(&body, InputFormat::Protobuf).unwrap().batch;
+    let expected = transform_logs(pb, InputFormat::Protobuf).unwrap().batch;
+
+    assert_eq!(batch, expected);
+}
+
+#[test]
+fn test_protobuf_delimited_stream() {
+    let pb = include_bytes!("../testdata/logs_large.pb");
+    let mut stream = Vec::new();
+    for _ in 0..3 {
+        prost::encode_length_delimiter(pb.len(), &mut stream).unwrap();
+        stream.extend_from_slice(pb);
+    }
+
+    let single = transform_logs(pb, InputFormat::Protobuf).unwrap().batch;
+    let batch = transform_logs(&stream, InputFormat::ProtobufDelimited)
+        .unwrap()
+        .batch;
+    assert_eq!(batch.num_rows(), single.num_rows() * 3);
+    assert_eq!(batch.slice(single.num_rows(), single.num_rows()), single);
+}
+
+#[test]
+fn test_lenient_logs_skip_overflowing_timestamp() {
+    let json = include_bytes!("fixtures/sample_otlp.json");
+    let mut request: serde_json::Value = serde_json::from_slice(json).unwrap();
+    let resource_logs = request["resourceLogs"].as_array_mut().unwrap();
+    let mut bad = resource_logs[0].clone();
+    bad["scopeLogs"][0]["logRecords"][0]["timeUnixNano"] = "18446744073709551615".into();
+    resource_logs.push(bad);
+    let body = serde_json::to_vec(&request).unwrap();
+
+    assert!(transform_logs(&body, InputFormat::Json).is_err());
+
+    let result = transform_logs_lenient(&body, InputFormat::Json).unwrap();
+    assert_eq!(
+        result.batch,
+        transform_logs(json, InputFormat::Json).unwrap().batch
+    );
+    assert_eq!(result.errors.len(), 1);
+    let error = &result.errors[0];
+    assert_eq!(error.index, 1);
+    assert_eq!(
+        error.location,
+        RecordLocation {
+            resource_index: 1,
+            scope_index: 0
+        }
+    );
+    assert_eq!(error.stage, ErrorStage::Decode);
+    assert!(error.message.contains("time_unix_nano"), "{error}");
+}
+
+#[test]
+fn test_pipeline_per_worker_thread() {
+    let pb = include_bytes!("../testdata/logs_large.pb");
+    let expected = transform_logs(pb, InputFormat::Protobuf).unwrap().batch;
+
+    let workers: Vec<_> = (0..2)
+        .map(|_| {
+            let mut pipeline = Pipeline::builder().with_lenient(true).build().unwrap();
+            std::thread::spawn(move || {
+                let output = pipeline.transform_logs(pb, InputFormat::Protobuf).unwrap();
+                assert!(output.errors.is_empty());
+                output.batches
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        assert_eq!(worker.join().unwrap(), vec![expected.clone()]);
+    }
+}
+
+#[test]
+fn test_custom_program_with_runtime_schema() {
+    let source = r#"
+# @schema custom_logs
+# timestamp: timestamp, required
+# service_name: string, required
+# body_length: int64
+# @end
+
+.body_length = if .body == null { 0 } else { 1 }
+"#;
+    let json = include_bytes!("fixtures/sample_otlp.json");
+    let program = CustomProgram::compile(source).unwrap();
+    let schema = parse_schema(source).unwrap().to_arrow_schema().unwrap();
+
+    let values = decode_logs(json, InputFormat::Json).unwrap();
+    let values = apply_log_transform_with(values, &program).unwrap();
+    let batch = values_to_arrow(&values, &schema).unwrap();
+
+    assert!(batch.num_rows() > 0);
+    assert_eq!(batch.num_columns(), 3);
+    assert_eq!(batch.column(2).null_count(), 0);
+}
+
 // ============================================================================
 // Traces Integration Tests
 // ============================================================================
@@ -56,7 +174,7 @@ fn test_logs_to_ipc() {
 #[test]
 fn test_full_pipeline_traces_json() {
     let json = include_bytes!("fixtures/sample_otlp_traces.json");
-    let batch = transform_traces(json, InputFormat::Json).unwrap();
+    let batch = transform_traces(json, InputFormat::Json).unwrap().batch;
 
     assert!(batch.num_rows() > 0, "Expected at least one span");
 
@@ -83,12 +201,406 @@ fn test_full_pipeline_traces_json() {
 #[test]
 fn test_traces_to_ipc() {
     let json = include_bytes!("fixtures/sample_otlp_traces.json");
-    let batch = transform_traces(json, InputFormat::Json).unwrap();
+    let batch = transform_traces(json, InputFormat::Json).unwrap().batch;
 
     let ipc = to_ipc(&batch).unwrap();
     assert!(!ipc.is_empty(), "Expected non-empty IPC output");
 }
 
+#[test]
+fn test_traces_native_nested_columns() {
+    let pb = include_bytes!("../testdata/traces_large.pb");
+    let values = apply_trace_transform(decode_traces(pb, InputFormat::Protobuf).unwrap()).unwrap();
+
+    let json_batch = values_to_arrow(&values, &traces_schema()).unwrap();
+    let schema = native_nested_schema(&traces_schema());
+    let batch = values_to_arrow(&values, &schema).unwrap();
+
+    assert_eq!(batch.num_rows(), json_batch.num_rows());
+    let attributes = batch
+        .column_by_name("span_attributes")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<MapArray>()
+        .unwrap();
+    let json_attributes = json_batch.column_by_name("span_attributes").unwrap();
+    assert_eq!(attributes.null_count(), json_attributes.null_count());
+
+    // Native nested columns survive IPC and NDJSON output
+    assert!(!to_ipc(&batch).unwrap().is_empty());
+    assert!(!to_json(&batch).unwrap().is_empty());
+
+    #[cfg(feature = "parquet")]
+    {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let parquet = otlp2records::to_parquet(&batch).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(parquet))
+            .unwrap()
+            .build()
+            .unwrap();
+        let rows: usize = reader.map(|b| b.unwrap().num_rows()).sum();
+        assert_eq!(rows, batch.num_rows());
+    }
+}
+
+#[test]
+fn test_traces_dictionary_string_columns() {
+    use arrow::array::AsArray;
+    use arrow::compute::cast;
+    use arrow::datatypes::{DataType, Int32Type};
+    use arrow::record_batch::RecordBatch;
+
+    let pb = include_bytes!("../testdata/traces_large.pb");
+    let batch = transform_traces(pb, InputFormat::Protobuf).unwrap().batch;
+
+    let span_names = batch.column_by_name("span_name").unwrap();
+    assert_eq!(span_names.data_type(), &dict_string_type());
+    let distinct = span_names.as_dictionary::<Int32Type>().values().len();
+    assert!(
+        distinct < batch.num_rows(),
+        "{distinct} distinct span names"
+    );
+
+    // Dictionary encoding makes the IPC output smaller than plain strings
+    let plain_columns = batch
+        .columns()
+        .iter()
+        .map(|column| match column.data_type() {
+            DataType::Dictionary(_, _) => cast(column, &DataType::Utf8).unwrap(),
+            _ => column.clone(),
+        })
+        .collect::<Vec<_>>();
+    let plain = RecordBatch::try_from_iter(
+        batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .zip(plain_columns),
+    )
+    .unwrap();
+    assert!(to_ipc(&batch).unwrap().len() < to_ipc(&plain).unwrap().len());
+
+    // Partitioning reads dictionary-encoded service names
+    let grouped = try_group_batch_by_service(batch.clone()).unwrap();
+    assert_eq!(grouped.total_records, batch.num_rows());
+    assert!(grouped.iter().all(|(service, _)| service != "unknown"));
+
+    #[cfg(feature = "parquet")]
+    {
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let parquet = otlp2records::to_parquet(&batch).unwrap();
+        let read: Vec<RecordBatch> =
+            ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(parquet))
+                .unwrap()
+                .build()
+                .unwrap()
+                .map(|b| b.unwrap())
+                .collect();
+        let read = arrow::compute::concat_batches(&read[0].schema(), &read).unwrap();
+        // Dictionary columns come back dictionary-encoded, with the same values
+        assert_eq!(read.columns(), batch.columns());
+    }
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_parquet_sink_compacts_sorted_traces() {
+    use arrow::array::AsArray;
+    use arrow::compute::cast;
+    use arrow::datatypes::{DataType, TimestampMicrosecondType};
+    use otlp2records::{ParquetSinkBuilder, ParquetStatValue};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let pb = include_bytes!("../testdata/traces_large.pb");
+    let batch = transform_traces(pb, InputFormat::Protobuf).unwrap().batch;
+    let rows_per_group = batch.num_rows();
+
+    let mut buffer = Vec::new();
+    let mut sink = ParquetSinkBuilder::new()
+        .with_max_row_group_rows(rows_per_group)
+        .with_sort_columns(["service_name", "timestamp"])
+        .build(&traces_schema(), &mut buffer)
+        .unwrap();
+    for _ in 0..3 {
+        sink.write(&batch).unwrap();
+    }
+    let metadata = sink.close().unwrap();
+    assert_eq!(metadata.num_rows, 3 * batch.num_rows() as u64);
+    assert_eq!(metadata.num_row_groups, 3);
+    assert_eq!(metadata.file_size, buffer.len() as u64);
+
+    let timestamps = batch.column_by_name("timestamp").unwrap();
+    let timestamps = timestamps.as_primitive::<TimestampMicrosecondType>();
+    let timestamp_stats = metadata
+        .columns
+        .iter()
+        .find(|c| c.path == "timestamp")
+        .unwrap();
+    assert_eq!(
+        timestamp_stats.min,
+        arrow::compute::min(timestamps).map(ParquetStatValue::Int)
+    );
+    assert_eq!(
+        timestamp_stats.max,
+        arrow::compute::max(timestamps).map(ParquetStatValue::Int)
+    );
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(buffer))
+        .unwrap()
+        .with_batch_size(rows_per_group)
+        .build()
+        .unwrap();
+    for group in reader {
+        let group = group.unwrap();
+        assert_eq!(group.num_rows(), rows_per_group);
+        let services = cast(
+            group.column_by_name("service_name").unwrap(),
+            &DataType::Utf8,
+        )
+        .unwrap();
+        let services = services.as_string::<i32>();
+        let timestamps = group.column_by_name("timestamp").unwrap();
+        let timestamps = timestamps.as_primitive::<TimestampMicrosecondType>();
+        for row in 1..group.num_rows() {
+            let previous = (services.value(row - 1), timestamps.value(row - 1));
+            let current = (services.value(row), timestamps.value(row));
+            assert!(previous <= current, "row {row} out of order");
+        }
+    }
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_to_parquet_uses_signal_preset() {
+    use arrow::array::AsArray;
+    use arrow::compute::cast;
+    use arrow::datatypes::{DataType, TimestampMicrosecondType};
+    use otlp2records::output::write_parquet;
+    use otlp2records::{parquet_properties_for, SignalType};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use parquet::basic::Compression;
+
+    let pb = include_bytes!("../testdata/traces_large.pb");
+    let batch = transform_traces(pb, InputFormat::Protobuf).unwrap().batch;
+    let parquet = otlp2records::to_parquet(&batch).unwrap();
+
+    // The preset column settings, without sorting
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(parquet)).unwrap();
+    let row_group = &builder.metadata().row_groups()[0];
+    let schema = traces_schema();
+    assert!(row_group.sorting_columns().is_none());
+    for (index, column) in row_group.columns().iter().enumerate() {
+        assert_eq!(column.compression(), Compression::SNAPPY);
+        let name = schema.field(index).name();
+        let bloom = column.bloom_filter_offset().is_some();
+        assert_eq!(bloom, name == "trace_id" || name == "span_id", "{name}");
+    }
+    let read: Vec<_> = builder.build().unwrap().map(|b| b.unwrap()).collect();
+    let read = arrow::compute::concat_batches(&read[0].schema(), &read).unwrap();
+    assert_eq!(read.columns(), batch.columns());
+
+    // Passing the preset sorts the rows
+    let mut parquet = Vec::new();
+    write_parquet(
+        &batch,
+        &mut parquet,
+        Some(parquet_properties_for(SignalType::Traces)),
+    )
+    .unwrap();
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(parquet)).unwrap();
+    let sorted: Vec<&str> = builder.metadata().row_groups()[0]
+        .sorting_columns()
+        .unwrap()
+        .iter()
+        .map(|c| schema.field(c.column_idx as usize).name().as_str())
+        .collect();
+    assert_eq!(sorted, vec!["service_name", "timestamp"]);
+
+    let read: Vec<_> = builder.build().unwrap().map(|b| b.unwrap()).collect();
+    let read = arrow::compute::concat_batches(&read[0].schema(), &read).unwrap();
+    assert_eq!(read.num_rows(), batch.num_rows());
+    let services = cast(
+        read.column_by_name("service_name").unwrap(),
+        &DataType::Utf8,
+    )
+    .unwrap();
+    let services = services.as_string::<i32>();
+    let timestamps = read.column_by_name("timestamp").unwrap();
+    let timestamps = timestamps.as_primitive::<TimestampMicrosecondType>();
+    for row in 1..read.num_rows() {
+        let previous = (services.value(row - 1), timestamps.value(row - 1));
+        assert!(previous <= (services.value(row), timestamps.value(row)));
+    }
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_iceberg_metadata_matches_native_nested_parquet() {
+    use arrow::array::AsArray;
+    use arrow::datatypes::TimestampMicrosecondType;
+    use otlp2records::{
+        group_batch_by, iceberg_partition_keys, iceberg_partition_spec, iceberg_schema,
+        IcebergDataFile, ParquetSink, SignalType,
+    };
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use serde_json::Value;
+
+    fn field_ids(value: &Value, ids: &mut Vec<i64>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    if matches!(key.as_str(), "id" | "element-id" | "key-id" | "value-id") {
+                        ids.push(value.as_i64().unwrap());
+                    }
+                    field_ids(value, ids);
+                }
+            }
+            Value::Array(values) => values.iter().for_each(|v| field_ids(v, ids)),
+            _ => {}
+        }
+    }
+
+    let pb = include_bytes!("../testdata/traces_large.pb");
+    let values = apply_trace_transform(decode_traces(pb, InputFormat::Protobuf).unwrap()).unwrap();
+    let schema = native_nested_schema(&traces_schema());
+    let batch = values_to_arrow(&values, &schema).unwrap();
+
+    let iceberg = iceberg_schema(&schema).unwrap();
+    let mut ids = Vec::new();
+    field_ids(&iceberg, &mut ids);
+    let mut unique = ids.clone();
+    unique.sort_unstable();
+    unique.dedup();
+    assert_eq!(unique.len(), ids.len(), "field IDs are unique");
+
+    let keys = iceberg_partition_keys(SignalType::Traces);
+    let spec = iceberg_partition_spec(&schema, &keys).unwrap();
+    assert_eq!(spec["fields"].as_array().unwrap().len(), 2);
+
+    let partition = group_batch_by(batch, &keys).batches.remove(0);
+    let mut buffer = Vec::new();
+    let mut sink = ParquetSink::try_new(&schema, &mut buffer).unwrap();
+    sink.write(&partition.batch).unwrap();
+    let metadata = sink.close().unwrap();
+
+    // Every Parquet leaf, nested ones included, carries an Iceberg field ID
+    for column in &metadata.columns {
+        let id = column
+            .field_id
+            .unwrap_or_else(|| panic!("{} has no ID", column.path));
+        assert!(ids.contains(&i64::from(id)), "{}: {id}", column.path);
+    }
+
+    let file = IcebergDataFile::try_new("traces/a.parquet", &schema, &metadata)
+        .unwrap()
+        .with_partition(&keys, &partition)
+        .unwrap();
+    assert_eq!(file.record_count, partition.record_count as u64);
+    assert_eq!(file.file_size_in_bytes, buffer.len() as u64);
+    assert_eq!(
+        file.partition["service_name"],
+        Value::from(partition.service_name.as_ref())
+    );
+    assert_eq!(
+        file.partition["timestamp_day"],
+        Value::from(partition.min_timestamp_micros.div_euclid(86_400_000_000))
+    );
+    assert_eq!(file.value_counts.len(), metadata.columns.len());
+
+    let timestamps = partition.batch.column_by_name("timestamp").unwrap();
+    let timestamps = timestamps.as_primitive::<TimestampMicrosecondType>();
+    let min = arrow::compute::min(timestamps).unwrap();
+    assert_eq!(file.lower_bounds[&1], min.to_le_bytes().to_vec());
+    // No bounds for fields inside lists and maps
+    let span_attributes_id = iceberg["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["name"] == "span_attributes")
+        .unwrap()["type"]["key-id"]
+        .as_i64()
+        .unwrap() as i32;
+    assert!(file.value_counts.contains_key(&span_attributes_id));
+    assert!(!file.lower_bounds.contains_key(&span_attributes_id));
+
+    // The nested field IDs do not get in the way of reading the file back
+    let read: usize = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::from(buffer))
+        .unwrap()
+        .build()
+        .unwrap()
+        .map(|b| b.unwrap().num_rows())
+        .sum();
+    assert_eq!(read, partition.record_count);
+}
+
+#[test]
+fn test_histogram_native_bucket_lists() {
+    use arrow::array::{Int64Array, UInt64Array};
+
+    let pb = include_bytes!("../testdata/metrics_histogram.pb");
+    let decoded = decode_metrics(pb, InputFormat::Protobuf).unwrap();
+    let values = apply_metric_transform(decoded.values).unwrap();
+
+    let schema = native_nested_schema(&otlp2records::histogram_schema());
+    let batch = values_to_arrow(&values.histogram, &schema).unwrap();
+    assert!(batch.num_rows() > 0);
+
+    let counts = batch
+        .column_by_name("bucket_counts")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .unwrap();
+    let bounds = batch
+        .column_by_name("explicit_bounds")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .unwrap();
+    let count = batch
+        .column_by_name("count")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+
+    for row in 0..batch.num_rows() {
+        let buckets = counts.value(row);
+        let buckets = buckets.as_any().downcast_ref::<UInt64Array>().unwrap();
+        let total: u64 = buckets.values().iter().sum();
+        assert_eq!(total as i64, count.value(row));
+        if !buckets.is_empty() {
+            assert_eq!(bounds.value(row).len() + 1, buckets.len());
+        }
+    }
+}
+
+#[test]
+fn test_metrics_native_nested_columns() {
+    let pb = include_bytes!("../testdata/metrics_gauge.pb");
+    let decoded = decode_metrics(pb, InputFormat::Protobuf).unwrap();
+    let values = apply_metric_transform(decoded.values).unwrap();
+
+    let batch = values_to_arrow(&values.gauge, &native_nested_schema(&gauge_schema())).unwrap();
+
+    assert!(batch.num_rows() > 0);
+    assert!(batch
+        .column_by_name("metric_attributes")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<MapArray>()
+        .is_some());
+    assert!(batch
+        .column_by_name("exemplars_json")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .is_some());
+}
+
 // ============================================================================
 // Metrics Integration Tests (JSON)
 // ============================================================================
@@ -209,6 +721,81 @@ fn test_full_pipeline_metrics_mixed_protobuf() {
     }
 }
 
+#[test]
+fn test_full_pipeline_metrics_summary_protobuf() {
+    let pb = include_bytes!("../testdata/metrics_summary.pb");
+    let batches = transform_metrics(pb, InputFormat::Protobuf).unwrap();
+
+    assert!(!batches.skipped.has_skipped(), "Expected nothing skipped");
+
+    let summary = batches.summary.expect("Expected summary metrics");
+    assert!(
+        summary.num_rows() > 0,
+        "Expected at least one summary data point"
+    );
+
+    // Verify schema has summary-specific fields
+    let schema = summary.schema();
+    assert!(schema.field_with_name("count").is_ok());
+    assert!(schema.field_with_name("sum").is_ok());
+    assert!(schema.field_with_name("quantile_values").is_ok());
+
+    // Verify output
+    let ndjson = to_json(&summary).unwrap();
+    assert!(!ndjson.is_empty());
+}
+
+// ============================================================================
+// Profiles Integration Tests
+// ============================================================================
+
+#[test]
+fn test_full_pipeline_profiles_json() {
+    let json = include_bytes!("fixtures/sample_otlp_profiles.json");
+    let batch = transform_profiles(json, InputFormat::Json).unwrap().batch;
+
+    assert_eq!(batch.num_rows(), 2);
+    assert_eq!(batch.schema(), profiles_schema().into());
+
+    let rows: Vec<serde_json::Value> = String::from_utf8(to_json(&batch).unwrap())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(rows[0]["service_name"], "checkout");
+    assert_eq!(rows[0]["sample_type"], "cpu");
+    assert_eq!(rows[0]["sample_unit"], "nanoseconds");
+    assert_eq!(rows[0]["value"], 20_000_000);
+    assert_eq!(rows[0]["function_name"], "handleRequest");
+    assert_eq!(rows[0]["trace_id"], "5b8efff798038103d269b633813fc60c");
+    assert_eq!(rows[0]["duration"], 10_000_000);
+
+    let frames: serde_json::Value =
+        serde_json::from_str(rows[0]["frames_json"].as_str().unwrap()).unwrap();
+    assert_eq!(
+        frames,
+        serde_json::json!([
+            {"function": "handleRequest", "file": "server.go", "line": 88,
+             "address": "0x2000", "mapping": "/usr/bin/checkout"},
+            {"function": "main", "file": "main.go", "line": 12,
+             "address": "0x1000", "mapping": "/usr/bin/checkout"}
+        ])
+    );
+
+    // Second sample has no timestamps or link: falls back to profile time
+    assert_eq!(rows[1]["function_name"], "main");
+    assert!(rows[1]["trace_id"].is_null());
+    let timestamps = batch
+        .column_by_name("timestamp")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<TimestampMicrosecondArray>()
+        .unwrap();
+    assert_eq!(timestamps.value(0), 1_700_000_001_000_000);
+    assert_eq!(timestamps.value(1), 1_700_000_000_000_000);
+}
+
 // ============================================================================
 // Cross-format tests
 // ============================================================================
@@ -220,7 +807,8 @@ fn test_logs_schema_consistency() {
         include_bytes!("fixtures/sample_otlp.json"),
         InputFormat::Json,
     )
-    .unwrap();
+    .unwrap()
+    .batch;
 
     // The schema should have consistent fields regardless of input format
     let schema = json_batch.schema();
@@ -252,7 +840,8 @@ fn test_traces_schema_consistency() {
         include_bytes!("fixtures/sample_otlp_traces.json"),
         InputFormat::Json,
     )
-    .unwrap();
+    .unwrap()
+    .batch;
 
     let schema = json_batch.schema();
 
@@ -327,7 +916,8 @@ fn test_logs_to_json_is_valid_ndjson() {
         include_bytes!("fixtures/sample_otlp.json"),
         InputFormat::Json,
     )
-    .unwrap();
+    .unwrap()
+    .batch;
 
     let ndjson = to_json(&batch).unwrap();
     let ndjson_str = String::from_utf8(ndjson).unwrap();
@@ -347,7 +937,8 @@ fn test_traces_to_json_is_valid_ndjson() {
         include_bytes!("fixtures/sample_otlp_traces.json"),
         InputFormat::Json,
     )
-    .unwrap();
+    .unwrap()
+    .batch;
 
     let ndjson = to_json(&batch).unwrap();
     let ndjson_str = String::from_utf8(ndjson).unwrap();
@@ -361,6 +952,102 @@ fn test_traces_to_json_is_valid_ndjson() {
     }
 }
 
+// ============================================================================
+// Reverse transform round-trip tests
+// ============================================================================
+
+/// Rows as sorted NDJSON lines. Regrouping by resource and scope reorders
+/// rows when the input interleaves resources, so round trips compare rows
+/// as a multiset.
+fn sorted_rows(batch: &arrow::record_batch::RecordBatch) -> Vec<String> {
+    let ndjson = String::from_utf8(to_json(batch).unwrap()).unwrap();
+    let mut rows: Vec<String> = ndjson.lines().map(str::to_string).collect();
+    rows.sort();
+    rows
+}
+
+#[test]
+fn test_logs_round_trip_through_otlp() {
+    let batch = transform_logs(
+        include_bytes!("../testdata/logs_large.pb"),
+        InputFormat::Protobuf,
+    )
+    .unwrap()
+    .batch;
+
+    for (encode, decode) in [
+        (EncodeFormat::Protobuf, InputFormat::Protobuf),
+        (EncodeFormat::Json, InputFormat::Json),
+    ] {
+        let body = records_to_otlp_logs(std::slice::from_ref(&batch), encode).unwrap();
+        let round_trip = transform_logs(&body, decode).unwrap().batch;
+        assert_eq!(round_trip.schema(), batch.schema());
+        assert_eq!(
+            sorted_rows(&round_trip),
+            sorted_rows(&batch),
+            "{encode:?} round trip changed logs"
+        );
+    }
+}
+
+#[test]
+fn test_traces_round_trip_through_otlp() {
+    let batch = transform_traces(
+        include_bytes!("../testdata/traces_large.pb"),
+        InputFormat::Protobuf,
+    )
+    .unwrap()
+    .batch;
+
+    for (encode, decode) in [
+        (EncodeFormat::Protobuf, InputFormat::Protobuf),
+        (EncodeFormat::Json, InputFormat::Json),
+    ] {
+        let body = records_to_otlp_traces(std::slice::from_ref(&batch), encode).unwrap();
+        let round_trip = transform_traces(&body, decode).unwrap().batch;
+        assert_eq!(round_trip.schema(), batch.schema());
+        assert_eq!(
+            sorted_rows(&round_trip),
+            sorted_rows(&batch),
+            "{encode:?} round trip changed spans"
+        );
+    }
+}
+
+#[test]
+fn test_metrics_round_trip_through_otlp() {
+    let fixtures: [&[u8]; 6] = [
+        include_bytes!("../testdata/metrics_gauge.pb"),
+        include_bytes!("../testdata/metrics_sum.pb"),
+        include_bytes!("../testdata/metrics_histogram.pb"),
+        include_bytes!("../testdata/metrics_exponential_histogram.pb"),
+        include_bytes!("../testdata/metrics_summary.pb"),
+        include_bytes!("../testdata/metrics_mixed.pb"),
+    ];
+
+    for fixture in fixtures {
+        let batches = transform_metrics(fixture, InputFormat::Protobuf).unwrap();
+        for (encode, decode) in [
+            (EncodeFormat::Protobuf, InputFormat::Protobuf),
+            (EncodeFormat::Json, InputFormat::Json),
+        ] {
+            let body = records_to_otlp_metrics(&batches, encode).unwrap();
+            let round_trip = transform_metrics(&body, decode).unwrap();
+            assert_eq!(round_trip.gauge, batches.gauge, "{encode:?} gauge");
+            assert_eq!(round_trip.sum, batches.sum, "{encode:?} sum");
+            assert_eq!(
+                round_trip.histogram, batches.histogram,
+                "{encode:?} histogram"
+            );
+            assert_eq!(
+                round_trip.exp_histogram, batches.exp_histogram,
+                "{encode:?} exp_histogram"
+            );
+            assert_eq!(round_trip.summary, batches.summary, "{encode:?} summary");
+        }
+    }
+}
+
 // ============================================================================
 // Timestamp validation tests - verify timestamps from real .pb files are not 1970
 // ============================================================================
@@ -372,7 +1059,7 @@ const MIN_VALID_TIMESTAMP_MICROS: i64 = 1_577_836_800_000_000; // Jan 1, 2020
 #[test]
 fn test_traces_large_pb_timestamps_not_epoch() {
     let pb = include_bytes!("../testdata/traces_large.pb");
-    let batch = transform_traces(pb, InputFormat::Protobuf).unwrap();
+    let batch = transform_traces(pb, InputFormat::Protobuf).unwrap().batch;
 
     assert!(batch.num_rows() > 0, "Expected at least one span");
 
@@ -408,7 +1095,7 @@ fn test_traces_large_pb_timestamps_not_epoch() {
 #[test]
 fn test_logs_large_pb_timestamps_not_epoch() {
     let pb = include_bytes!("../testdata/logs_large.pb");
-    let batch = transform_logs(pb, InputFormat::Protobuf).unwrap();
+    let batch = transform_logs(pb, InputFormat::Protobuf).unwrap().batch;
 
     assert!(batch.num_rows() > 0, "Expected at least one log record");
 
@@ -456,6 +1143,7 @@ fn test_metrics_pb_timestamps_not_epoch() {
             "exp_histogram",
         ),
         (include_bytes!("../testdata/metrics_mixed.pb"), "mixed"),
+        (include_bytes!("../testdata/metrics_summary.pb"), "summary"),
     ];
 
     for (pb, name) in metric_files {
@@ -480,6 +1168,11 @@ fn test_metrics_pb_timestamps_not_epoch() {
         if let Some(exp_histogram) = &batches.exp_histogram {
             verify_metric_timestamps(exp_histogram, &format!("{name}/exp_histogram"));
         }
+
+        // Check summary timestamps
+        if let Some(summary) = &batches.summary {
+            verify_metric_timestamps(summary, &format!("{name}/summary"));
+        }
     }
 }
 